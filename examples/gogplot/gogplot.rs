@@ -6,9 +6,10 @@ use std::collections::HashMap;
 use std::error::Error;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 use gogplot::aesthetics::{Aesthetic, AestheticDomain};
-use gogplot::layer::LayerBuilder;
+use gogplot::layer::{LayerBuilder, LayerBuilderExt};
 use gogplot::position::{dodge::Dodge, stack::Stack, Position};
 use gogplot::prelude::*;
 use gogplot::stat::bin::Bin;
@@ -206,6 +207,15 @@ struct LayerSpec {
     params: LayerParams,
     #[serde(default)]
     position: Option<String>,
+    /// Layer data; the plot input is used when absent
+    #[serde(default)]
+    data: Option<InputSpec>,
+    #[serde(default = "default_inherit_aes")]
+    inherit_aes: bool,
+}
+
+fn default_inherit_aes() -> bool {
+    true
 }
 
 #[derive(Debug, Deserialize)]
@@ -272,7 +282,7 @@ fn process_spec(path: &Path, spec_value: Value) -> Result<(), Box<dyn Error>> {
 
     let spec_dir = path.parent().unwrap_or_else(|| Path::new("."));
     let data = load_data(&spec.input, spec_dir)?;
-    let plot = build_plot(&spec.plot, &data)?;
+    let plot = build_plot(&spec.plot, &data, spec_dir)?;
     render_plot(&plot, &spec.output)?;
 
     Ok(())
@@ -295,6 +305,7 @@ fn load_data(input: &InputSpec, spec_dir: &Path) -> Result<Box<dyn DataSource>,
 fn build_plot<'a>(
     plot_spec: &'a PlotSpec,
     data: &'a Box<dyn DataSource>,
+    spec_dir: &Path,
 ) -> Result<gogplot::plot::Plot<'a>, Box<dyn Error>> {
     let mut builder = plot(data);
     builder = apply_global_mapping(builder, &plot_spec.mapping);
@@ -315,10 +326,10 @@ fn build_plot<'a>(
                     geom = geom.alpha(alpha);
                 }
                 if let Some(color) = layer.params.color.as_ref() {
-                    geom = geom.color(parse_color(color)?);
+                    geom = geom.color(Color::from_str(color)?);
                 }
 
-                let geom = finalize_layer(geom, &layer.stat, &layer.position, None, None)?;
+                let geom = finalize_layer(geom, layer, spec_dir, None, None)?;
                 builder = builder + geom;
             }
             GeomKind::Line => {
@@ -333,13 +344,13 @@ fn build_plot<'a>(
                     geom = geom.alpha(alpha);
                 }
                 if let Some(color) = layer.params.color.as_ref() {
-                    geom = geom.color(parse_color(color)?);
+                    geom = geom.color(Color::from_str(color)?);
                 }
                 if let Some(linetype) = layer.params.linetype.as_ref() {
                     geom = geom.linestyle(LineStyle::from(linetype.as_str()));
                 }
 
-                let geom = finalize_layer(geom, &layer.stat, &layer.position, None, None)?;
+                let geom = finalize_layer(geom, layer, spec_dir, None, None)?;
                 builder = builder + geom;
             }
             GeomKind::Hline => {
@@ -357,13 +368,13 @@ fn build_plot<'a>(
                     geom = geom.alpha(alpha);
                 }
                 if let Some(color) = layer.params.color.as_ref() {
-                    geom = geom.color(parse_color(color)?);
+                    geom = geom.color(Color::from_str(color)?);
                 }
                 if let Some(linetype) = layer.params.linetype.as_ref() {
                     geom = geom.linestyle(LineStyle::from(linetype.as_str()));
                 }
 
-                let geom = finalize_layer(geom, &layer.stat, &layer.position, Some("y"), None)?;
+                let geom = finalize_layer(geom, layer, spec_dir, Some("y"), None)?;
                 builder = builder + geom;
             }
             GeomKind::Vline => {
@@ -381,13 +392,13 @@ fn build_plot<'a>(
                     geom = geom.alpha(alpha);
                 }
                 if let Some(color) = layer.params.color.as_ref() {
-                    geom = geom.color(parse_color(color)?);
+                    geom = geom.color(Color::from_str(color)?);
                 }
                 if let Some(linetype) = layer.params.linetype.as_ref() {
                     geom = geom.linestyle(LineStyle::from(linetype.as_str()));
                 }
 
-                let geom = finalize_layer(geom, &layer.stat, &layer.position, Some("x"), None)?;
+                let geom = finalize_layer(geom, layer, spec_dir, Some("x"), None)?;
                 builder = builder + geom;
             }
             GeomKind::Boxplot => {
@@ -396,10 +407,10 @@ fn build_plot<'a>(
                 geom = apply_boxplot_layer_mapping(geom, &layer.mapping);
 
                 if let Some(fill) = layer.params.fill.as_ref() {
-                    geom = geom.fill(parse_color(fill)?);
+                    geom = geom.fill(Color::from_str(fill)?);
                 }
                 if let Some(color) = layer.params.color.as_ref() {
-                    geom = geom.color(parse_color(color)?);
+                    geom = geom.color(Color::from_str(color)?);
                 }
                 if let Some(alpha) = layer.params.alpha {
                     geom = geom.alpha(alpha);
@@ -411,7 +422,7 @@ fn build_plot<'a>(
                     geom = geom.linestyle(LineStyle::from(linetype.as_str()));
                 }
 
-                let geom = finalize_layer(geom, &layer.stat, &layer.position, None, None)?;
+                let geom = finalize_layer(geom, layer, spec_dir, None, None)?;
                 builder = builder + geom;
             }
             GeomKind::Density => {
@@ -420,10 +431,10 @@ fn build_plot<'a>(
                 geom = apply_density_layer_mapping(geom, &layer.mapping);
 
                 if let Some(fill) = layer.params.fill.as_ref() {
-                    geom = geom.fill(parse_color(fill)?);
+                    geom = geom.fill(Color::from_str(fill)?);
                 }
                 if let Some(color) = layer.params.color.as_ref() {
-                    geom = geom.color(parse_color(color)?);
+                    geom = geom.color(Color::from_str(color)?);
                 }
                 if let Some(alpha) = layer.params.alpha {
                     geom = geom.alpha(alpha);
@@ -435,7 +446,7 @@ fn build_plot<'a>(
                     geom = geom.linestyle(LineStyle::from(linetype.as_str()));
                 }
 
-                let geom = finalize_layer(geom, &layer.stat, &layer.position, None, None)?;
+                let geom = finalize_layer(geom, layer, spec_dir, None, None)?;
                 builder = builder + geom;
             }
             GeomKind::Bar => {
@@ -444,10 +455,10 @@ fn build_plot<'a>(
                 geom = apply_bar_layer_mapping(geom, &layer.mapping);
 
                 if let Some(fill) = layer.params.fill.as_ref() {
-                    geom = geom.fill(parse_color(fill)?);
+                    geom = geom.fill(Color::from_str(fill)?);
                 }
                 if let Some(color) = layer.params.color.as_ref() {
-                    geom = geom.color(parse_color(color)?);
+                    geom = geom.color(Color::from_str(color)?);
                 }
                 if let Some(alpha) = layer.params.alpha {
                     geom = geom.alpha(alpha);
@@ -465,7 +476,7 @@ fn build_plot<'a>(
                     None
                 };
 
-                let geom = finalize_layer(geom, &layer.stat, &layer.position, None, default_stat)?;
+                let geom = finalize_layer(geom, layer, spec_dir, None, default_stat)?;
                 builder = builder + geom;
             }
             GeomKind::Histogram => {
@@ -474,10 +485,10 @@ fn build_plot<'a>(
                 geom = apply_histogram_layer_mapping(geom, &layer.mapping);
 
                 if let Some(fill) = layer.params.fill.as_ref() {
-                    geom = geom.fill(parse_color(fill)?);
+                    geom = geom.fill(Color::from_str(fill)?);
                 }
                 if let Some(color) = layer.params.color.as_ref() {
-                    geom = geom.color(parse_color(color)?);
+                    geom = geom.color(Color::from_str(color)?);
                 }
                 if let Some(alpha) = layer.params.alpha {
                     geom = geom.alpha(alpha);
@@ -499,7 +510,7 @@ fn build_plot<'a>(
                     None
                 };
 
-                let geom = finalize_layer(geom, &layer.stat, &layer.position, None, default_stat)?;
+                let geom = finalize_layer(geom, layer, spec_dir, None, default_stat)?;
                 builder = builder + geom;
             }
             GeomKind::Rect => {
@@ -508,16 +519,16 @@ fn build_plot<'a>(
                 geom = apply_rect_layer_mapping(geom, &layer.mapping);
 
                 if let Some(fill) = layer.params.fill.as_ref() {
-                    geom = geom.fill(parse_color(fill)?);
+                    geom = geom.fill(Color::from_str(fill)?);
                 }
                 if let Some(color) = layer.params.color.as_ref() {
-                    geom = geom.color(parse_color(color)?);
+                    geom = geom.color(Color::from_str(color)?);
                 }
                 if let Some(alpha) = layer.params.alpha {
                     geom = geom.alpha(alpha);
                 }
 
-                let geom = finalize_layer(geom, &layer.stat, &layer.position, None, None)?;
+                let geom = finalize_layer(geom, layer, spec_dir, None, None)?;
                 builder = builder + geom;
             }
             GeomKind::Segment => {
@@ -526,7 +537,7 @@ fn build_plot<'a>(
                 geom = apply_segment_layer_mapping(geom, &layer.mapping);
 
                 if let Some(color) = layer.params.color.as_ref() {
-                    geom = geom.color(parse_color(color)?);
+                    geom = geom.color(Color::from_str(color)?);
                 }
                 if let Some(alpha) = layer.params.alpha {
                     geom = geom.alpha(alpha);
@@ -538,7 +549,7 @@ fn build_plot<'a>(
                     geom = geom.linestyle(LineStyle::from(linetype.as_str()));
                 }
 
-                let geom = finalize_layer(geom, &layer.stat, &layer.position, None, None)?;
+                let geom = finalize_layer(geom, layer, spec_dir, None, None)?;
                 builder = builder + geom;
             }
             GeomKind::Text => {
@@ -547,7 +558,7 @@ fn build_plot<'a>(
                 geom = apply_text_layer_mapping(geom, &layer.mapping);
 
                 if let Some(color) = layer.params.color.as_ref() {
-                    geom = geom.color(parse_color(color)?);
+                    geom = geom.color(Color::from_str(color)?);
                 }
                 if let Some(alpha) = layer.params.alpha {
                     geom = geom.alpha(alpha);
@@ -559,7 +570,7 @@ fn build_plot<'a>(
                     geom = geom.angle(angle);
                 }
 
-                let geom = finalize_layer(geom, &layer.stat, &layer.position, None, None)?;
+                let geom = finalize_layer(geom, layer, spec_dir, None, None)?;
                 builder = builder + geom;
             }
             GeomKind::Label => {
@@ -568,10 +579,10 @@ fn build_plot<'a>(
                 geom = apply_label_layer_mapping(geom, &layer.mapping);
 
                 if let Some(color) = layer.params.color.as_ref() {
-                    geom = geom.color(parse_color(color)?);
+                    geom = geom.color(Color::from_str(color)?);
                 }
                 if let Some(fill) = layer.params.fill.as_ref() {
-                    geom = geom.fill(parse_color(fill)?);
+                    geom = geom.fill(Color::from_str(fill)?);
                 }
                 if let Some(alpha) = layer.params.alpha {
                     geom = geom.alpha(alpha);
@@ -589,7 +600,7 @@ fn build_plot<'a>(
                     geom = geom.radius(radius);
                 }
 
-                let geom = finalize_layer(geom, &layer.stat, &layer.position, None, None)?;
+                let geom = finalize_layer(geom, layer, spec_dir, None, None)?;
                 builder = builder + geom;
             }
             GeomKind::Errorbar => {
@@ -598,7 +609,7 @@ fn build_plot<'a>(
                 geom = apply_errorbar_layer_mapping(geom, &layer.mapping);
 
                 if let Some(color) = layer.params.color.as_ref() {
-                    geom = geom.color(parse_color(color)?);
+                    geom = geom.color(Color::from_str(color)?);
                 }
                 if let Some(alpha) = layer.params.alpha {
                     geom = geom.alpha(alpha);
@@ -613,7 +624,7 @@ fn build_plot<'a>(
                     geom = geom.width(width);
                 }
 
-                let geom = finalize_layer(geom, &layer.stat, &layer.position, None, None)?;
+                let geom = finalize_layer(geom, layer, spec_dir, None, None)?;
                 builder = builder + geom;
             }
             GeomKind::Smooth => {
@@ -627,10 +638,10 @@ fn build_plot<'a>(
                 geom = apply_smooth_layer_mapping(geom, &layer.mapping);
 
                 if let Some(color) = layer.params.color.as_ref() {
-                    geom = geom.color(parse_color(color)?);
+                    geom = geom.color(Color::from_str(color)?);
                 }
                 if let Some(fill) = layer.params.fill.as_ref() {
-                    geom = geom.fill(parse_color(fill)?);
+                    geom = geom.fill(Color::from_str(fill)?);
                 }
                 if let Some(alpha) = layer.params.alpha {
                     geom = geom.alpha(alpha);
@@ -642,7 +653,7 @@ fn build_plot<'a>(
                     geom = geom.linestyle(LineStyle::from(linetype.as_str()));
                 }
 
-                let geom = finalize_layer(geom, &layer.stat, &layer.position, None, None)?;
+                let geom = finalize_layer(geom, layer, spec_dir, None, None)?;
                 builder = builder + geom;
             }
         }
@@ -785,16 +796,28 @@ fn apply_position<B: LayerBuilder>(
     Ok(builder)
 }
 
+fn apply_layer_data<B: LayerBuilder>(
+    builder: B,
+    layer: &LayerSpec,
+    spec_dir: &Path,
+) -> Result<B, Box<dyn Error>> {
+    let mut builder = builder.inherit_aes(layer.inherit_aes);
+    if let Some(input) = layer.data.as_ref() {
+        builder = builder.data(load_data(input, spec_dir)?);
+    }
+    Ok(builder)
+}
+
 fn finalize_layer<B: LayerBuilder>(
     builder: B,
-    stat: &Option<StatSpec>,
-    position: &Option<String>,
+    layer: &LayerSpec,
+    spec_dir: &Path,
     default_axis: Option<&str>,
     default_stat: Option<Box<dyn Stat>>,
 ) -> Result<B, Box<dyn Error>> {
-    let builder = apply_stat(builder, stat, default_axis, default_stat)?;
-    let builder = apply_position(builder, position)?;
-    Ok(builder)
+    let builder = apply_stat(builder, &layer.stat, default_axis, default_stat)?;
+    let builder = apply_position(builder, &layer.position)?;
+    apply_layer_data(builder, layer, spec_dir)
 }
 
 fn apply_global_mapping<'a>(builder: gogplot::plot::PlotBuilder<'a>, mapping: &MappingSpec) -> gogplot::plot::PlotBuilder<'a> {
@@ -1550,19 +1573,6 @@ fn apply_smooth_layer_mapping(mut geom: gogplot::geom::smooth::GeomSmoothBuilder
     geom
 }
 
-fn parse_color(name: &str) -> Result<Color, Box<dyn Error>> {
-    let map: HashMap<String, Color> = color::color_map()
-        .iter()
-        .map(|(k, v)| (k.to_ascii_lowercase(), v.clone()))
-        .collect();
-    let key = name.to_ascii_lowercase();
-    if let Some(c) = map.get(&key) {
-        Ok(*c)
-    } else {
-        Err(format!("unknown color: {}", name).into())
-    }
-}
-
 fn load_csv_to_dataframe(path: &Path) -> Result<DataFrame, Box<dyn Error>> {
     let mut reader = csv::Reader::from_path(path)?;
     let headers = reader
@@ -42,6 +42,10 @@ pub enum AestheticProperty {
     Lower,
     Middle,
     Upper,
+    NotchLower,
+    NotchUpper,
+    RelVarWidth,
+    Z,
     XOffset,
     YOffset,
     Width,
@@ -73,6 +77,10 @@ impl AestheticProperty {
             AestheticProperty::Lower => AestheticPropertyType::Float,
             AestheticProperty::Middle => AestheticPropertyType::Float,
             AestheticProperty::Upper => AestheticPropertyType::Float,
+            AestheticProperty::NotchLower => AestheticPropertyType::Float,
+            AestheticProperty::NotchUpper => AestheticPropertyType::Float,
+            AestheticProperty::RelVarWidth => AestheticPropertyType::Float,
+            AestheticProperty::Z => AestheticPropertyType::Float,
             AestheticProperty::XOffset => AestheticPropertyType::Float,
             AestheticProperty::YOffset => AestheticPropertyType::Float,
             AestheticProperty::Width => AestheticPropertyType::Float,
@@ -105,6 +113,10 @@ impl AestheticProperty {
             AestheticProperty::Lower => &[Aesthetic::Lower],
             AestheticProperty::Middle => &[Aesthetic::Middle],
             AestheticProperty::Upper => &[Aesthetic::Upper],
+            AestheticProperty::NotchLower => &[Aesthetic::NotchLower],
+            AestheticProperty::NotchUpper => &[Aesthetic::NotchUpper],
+            AestheticProperty::RelVarWidth => &[Aesthetic::RelVarWidth],
+            AestheticProperty::Z => &[Aesthetic::Z],
             AestheticProperty::XOffset => &[Aesthetic::XOffset],
             AestheticProperty::YOffset => &[Aesthetic::YOffset],
             AestheticProperty::Width => &[Aesthetic::Width],
@@ -136,6 +148,10 @@ impl AestheticProperty {
             AestheticProperty::Lower => "lower",
             AestheticProperty::Middle => "middle",
             AestheticProperty::Upper => "upper",
+            AestheticProperty::NotchLower => "notchlower",
+            AestheticProperty::NotchUpper => "notchupper",
+            AestheticProperty::RelVarWidth => "relvarwidth",
+            AestheticProperty::Z => "z",
             AestheticProperty::XOffset => "xoffset",
             AestheticProperty::YOffset => "yoffset",
             AestheticProperty::Width => "width",
@@ -169,6 +185,10 @@ pub enum Aesthetic {
     Lower,  // Q1 (first quartile) for boxplots
     Middle, // Median for boxplots
     Upper,  // Q3 (third quartile) for boxplots
+    NotchLower,  // Lower notch bound for notched boxplots
+    NotchUpper,  // Upper notch bound for notched boxplots
+    RelVarWidth, // Relative box width for variable-width boxplots
+    Z,           // Third variable aggregated by summary stats
     Color(AestheticDomain),
     Fill(AestheticDomain),
     Alpha(AestheticDomain),
@@ -232,6 +252,8 @@ impl Aesthetic {
                 | Aesthetic::Lower
                 | Aesthetic::Middle
                 | Aesthetic::Upper
+                | Aesthetic::NotchLower
+                | Aesthetic::NotchUpper
         )
     }
 
@@ -263,6 +285,10 @@ impl Aesthetic {
             | Aesthetic::Lower
             | Aesthetic::Middle
             | Aesthetic::Upper
+            | Aesthetic::NotchLower
+            | Aesthetic::NotchUpper
+            | Aesthetic::RelVarWidth
+            | Aesthetic::Z
             | Aesthetic::Label => AestheticDomain::Continuous,
         }
     }
@@ -279,6 +305,10 @@ impl Aesthetic {
             Aesthetic::Lower => "lower",
             Aesthetic::Middle => "middle",
             Aesthetic::Upper => "upper",
+            Aesthetic::NotchLower => "notchlower",
+            Aesthetic::NotchUpper => "notchupper",
+            Aesthetic::RelVarWidth => "relvarwidth",
+            Aesthetic::Z => "z",
             Aesthetic::Color(_) => "color",
             Aesthetic::Fill(_) => "fill",
             Aesthetic::Alpha(_) => "alpha",
@@ -315,6 +345,10 @@ impl Aesthetic {
             Aesthetic::Lower => Some(AestheticProperty::Lower),
             Aesthetic::Middle => Some(AestheticProperty::Middle),
             Aesthetic::Upper => Some(AestheticProperty::Upper),
+            Aesthetic::NotchLower => Some(AestheticProperty::NotchLower),
+            Aesthetic::NotchUpper => Some(AestheticProperty::NotchUpper),
+            Aesthetic::RelVarWidth => Some(AestheticProperty::RelVarWidth),
+            Aesthetic::Z => Some(AestheticProperty::Z),
             Aesthetic::Xmin(_) => Some(AestheticProperty::XMin),
             Aesthetic::Xmax(_) => Some(AestheticProperty::XMax),
             Aesthetic::Ymin(_) => Some(AestheticProperty::YMin),
@@ -459,8 +493,12 @@ impl AesValue {
         }
     }
 
-    /// Get the discrete iterator for a resolved value.
+    /// Get the discrete iterator for a resolved value. Missing values of a
+    /// vector read as [`DiscreteValue::Na`].
     pub fn iter_discrete_resolved(&self, len: usize) -> Option<Box<dyn Iterator<Item = DiscreteValue> + '_>> {
+        if let AesValue::Vector { values, .. } = self {
+            return values.iter_discrete();
+        }
         match self.iter_resolved(len)? {
             VectorIter::Int(iter) => Some(Box::new(iter.map(DiscreteValue::Int))),
             VectorIter::Str(iter) => Some(Box::new(iter.map(|s| DiscreteValue::from(s)))),
@@ -501,6 +539,11 @@ impl AesValue {
         &'a self,
         data: &'a dyn DataSource,
     ) -> Option<Box<dyn Iterator<Item = DiscreteValue> + 'a>> {
+        match self {
+            AesValue::Column { name, .. } => return data.get(name.as_str())?.iter_discrete(),
+            AesValue::Vector { values, .. } => return values.iter_discrete(),
+            AesValue::Constant { .. } => {}
+        }
         match self.as_vector_iter(data)? {
             VectorIter::Int(iter) => Some(Box::new(iter.map(DiscreteValue::Int))),
             VectorIter::Str(iter) => {
@@ -611,6 +654,16 @@ impl AesMap {
         self.get(aes)?.as_vector_iter(data)
     }
 
+    /// Copy the values of an aesthetic, keeping the levels and missing
+    /// values of a categorical
+    pub fn get_vector(&self, aes: &Aesthetic, data: &dyn DataSource) -> Option<VectorValue> {
+        match self.get(aes)? {
+            AesValue::Column { name } => Some(data.get(name)?.to_vector()),
+            AesValue::Vector { values, .. } => Some(values.to_vector()),
+            AesValue::Constant { .. } => Some(self.get_vector_iter(aes, data)?.to_vector()),
+        }
+    }
+
     pub fn get_iter_discrete<'a>(
         &'a self,
         aes: &Aesthetic,
@@ -666,7 +719,7 @@ impl AesMap {
                 let column = data.get(name).ok_or_else(|| PlotError::MissingColumn {
                     column: name.clone(),
                 })?;
-                let vector_value = column.iter().to_vector();
+                let vector_value = column.to_vector();
                 self.map.insert(aes, AesValue::vector(vector_value, Some(name.clone())));
             }
         }
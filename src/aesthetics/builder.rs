@@ -279,6 +279,17 @@ pub trait LabelAesBuilder: AesMapBuilderTrait {
     }
 }
 
+pub trait ZContinuousAesBuilder: AesMapBuilderTrait {
+    fn z_continuous(&mut self, column: &str) {
+        self.aes().set(
+            Aesthetic::Z,
+            AesValue::Column {
+                name: column.to_string(),
+            },
+        );
+    }
+}
+
 pub trait GroupAesBuilder: AesMapBuilderTrait {
     fn group(&mut self, column: &str) {
         self.aes().set(
@@ -345,5 +356,6 @@ impl SizeDiscreteAesBuilder for AesMapBuilder {}
 impl ShapeAesBuilder for AesMapBuilder {}
 impl LineStyleAesBuilder for AesMapBuilder {}
 impl LabelAesBuilder for AesMapBuilder {}
+impl ZContinuousAesBuilder for AesMapBuilder {}
 impl GroupAesBuilder for AesMapBuilder {}
 
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use crate::{aesthetics::AesValue, data::VectorValue, utils::dataframe::DataFrame};
+use crate::{aesthetics::AesValue, data::VectorValue, error::PlotError, utils::dataframe::DataFrame};
 
 pub struct AesValueBuilder {
     value: AesValue,
@@ -35,7 +35,7 @@ impl AesValueBuilder {
         }
     }
 
-    pub fn append(&mut self, data: &mut DataFrame, extra: impl Into<VectorValue>) {
+    pub fn append(&mut self, data: &mut DataFrame, extra: impl Into<VectorValue>) -> Result<(), PlotError> {
         match &mut self.value {
             AesValue::Column { name, .. } => {
                 if data.has_column(name) {
@@ -47,9 +47,10 @@ impl AesValueBuilder {
             AesValue::Constant { value: _, .. } => todo!(),
             AesValue::Vector { values, .. } => {
                 let vec = Arc::get_mut(values).expect("Cannot get mutable reference to vector");
-                vec.append(&mut extra.into());
+                vec.append(&extra.into())?;
             }
         }
+        Ok(())
     }
 
     pub fn build(self) -> AesValue {
@@ -0,0 +1,195 @@
+// Coordinate systems
+
+use crate::error::PlotError;
+use crate::scale::traits::ContinuousDomainScale;
+use crate::scale::ScaleSet;
+
+/// Cartesian coordinates with an optional zoom
+///
+/// Unlike scale limits, `xlim` and `ylim` don't remove any data: stats see
+/// the full data and the view is cropped to the requested range afterwards.
+#[derive(Debug, Clone)]
+pub struct CoordCartesian {
+    xlim: Option<(f64, f64)>,
+    ylim: Option<(f64, f64)>,
+    expand: bool,
+}
+
+impl CoordCartesian {
+    pub fn new() -> Self {
+        Self {
+            xlim: None,
+            ylim: None,
+            expand: true,
+        }
+    }
+
+    /// Zoom the x axis to this range, in data space
+    pub fn xlim(mut self, min: f64, max: f64) -> Self {
+        self.xlim = Some((min, max));
+        self
+    }
+
+    /// Zoom the y axis to this range, in data space
+    pub fn ylim(mut self, min: f64, max: f64) -> Self {
+        self.ylim = Some((min, max));
+        self
+    }
+
+    /// Whether to pad the zoomed range with the scale's expansion (default true)
+    pub fn expand(mut self, expand: bool) -> Self {
+        self.expand = expand;
+        self
+    }
+
+    /// Override the trained x/y domains with the zoom ranges. Data outside
+    /// them is kept and clipped to the panel rather than dropped.
+    pub(crate) fn apply(&self, scales: &mut ScaleSet) -> Result<(), PlotError> {
+        if let Some(xlim) = self.xlim {
+            self.zoom(&mut scales.x_continuous, xlim)?;
+        }
+        if let Some(ylim) = self.ylim {
+            self.zoom(&mut scales.y_continuous, ylim)?;
+        }
+        Ok(())
+    }
+
+    fn zoom<S: ContinuousDomainScale>(&self, scale: &mut S, (min, max): (f64, f64)) -> Result<(), PlotError> {
+        let lo = scale.transform().transform(min);
+        let hi = scale.transform().transform(max);
+        if !lo.is_finite() || !hi.is_finite() || min >= max {
+            return Err(PlotError::InvalidLimits { min, max });
+        }
+        let range = (lo.min(hi), lo.max(hi));
+        let domain = if self.expand {
+            scale.expansion().expand(range)
+        } else {
+            range
+        };
+        scale.zoom(domain);
+        Ok(())
+    }
+}
+
+impl Default for CoordCartesian {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Create cartesian coordinates; use `xlim`/`ylim` to zoom without dropping data
+pub fn coord_cartesian() -> CoordCartesian {
+    CoordCartesian::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aesthetics::builder::{XContinuousAesBuilder, YContinuousAesBuilder};
+    use crate::error::to_io_error;
+    use crate::geom::point::geom_point;
+    use crate::geom::smooth::geom_smooth;
+    use crate::layer::LayerBuilderExt;
+    use crate::plot::plot;
+    use crate::scale::traits::ContinuousRangeScale;
+    use crate::scale::{scale_x_continuous, OutOfBounds};
+    use crate::stat::smooth::{Method, Smooth};
+    use crate::utils::mtcars::mtcars;
+
+    fn init_test_logging() {
+        let _ = env_logger::builder()
+            .is_test(true)
+            .filter_level(log::LevelFilter::Debug)
+            .try_init();
+    }
+
+    #[test]
+    fn zoom_keeps_data_for_stats() {
+        init_test_logging();
+
+        let data = mtcars();
+        let builder = plot(&data).aes(|a| {
+            a.x_continuous("wt");
+            a.y_continuous("mpg");
+        }) + geom_point()
+            + geom_smooth().stat(Smooth::new().method(Method::Lm))
+            + coord_cartesian().xlim(2.0, 4.0);
+
+        let p = builder
+            .build()
+            .map_err(to_io_error)
+            .expect("Failed to build plot");
+
+        // The fit sees every car, only the view is cropped
+        let fits = p.smooth_fits();
+        assert_eq!(fits[0].n, 32);
+        let (lo, hi) = p.scales.x_continuous.domain().unwrap();
+        assert!((lo - 1.9).abs() < 1e-9 && (hi - 4.1).abs() < 1e-9);
+
+        p.save("tests/images/coord_cartesian_1.png", 800, 600)
+            .map_err(to_io_error)
+            .expect("Failed to save plot image");
+    }
+
+    #[test]
+    fn limits_filter_data_for_stats() {
+        init_test_logging();
+
+        let data = mtcars();
+        let builder = plot(&data).aes(|a| {
+            a.x_continuous("wt");
+            a.y_continuous("mpg");
+        }) + geom_point()
+            + geom_smooth().stat(Smooth::new().method(Method::Lm))
+            + scale_x_continuous().limits(2.0, 4.0);
+
+        let p = builder
+            .build()
+            .map_err(to_io_error)
+            .expect("Failed to build plot");
+
+        // Only cars with 2 <= wt <= 4 reach the stat
+        let fits = p.smooth_fits();
+        assert_eq!(fits[0].n, 24);
+
+        p.save("tests/images/coord_cartesian_2.png", 800, 600)
+            .map_err(to_io_error)
+            .expect("Failed to save plot image");
+    }
+
+    #[test]
+    fn squish_keeps_rows_for_stats() {
+        let data = mtcars();
+        let builder = plot(&data).aes(|a| {
+            a.x_continuous("wt");
+            a.y_continuous("mpg");
+        }) + geom_smooth().stat(Smooth::new().method(Method::Lm))
+            + scale_x_continuous()
+                .limits(2.0, 4.0)
+                .oob(OutOfBounds::Squish);
+
+        let p = builder.build().expect("Failed to build plot");
+        assert_eq!(p.smooth_fits()[0].n, 32);
+    }
+
+    #[test]
+    fn zoom_maps_outside_values_past_the_panel() {
+        let mut scales = ScaleSet::default();
+        scales.x_continuous.set_domain((0.0, 10.0));
+
+        // Outside the trained domain, values are dropped
+        assert_eq!(scales.x_continuous.map_value(&12.0), None);
+
+        // A zoom keeps them, to be clipped when drawn
+        coord_cartesian().xlim(0.0, 5.0).expand(false).apply(&mut scales).unwrap();
+        assert_eq!(scales.x_continuous.map_value(&2.5), Some(0.5));
+        assert_eq!(scales.x_continuous.map_value(&7.5), Some(1.5));
+    }
+
+    #[test]
+    fn zoom_rejects_invalid_range() {
+        let mut scales = ScaleSet::default();
+        let result = coord_cartesian().ylim(3.0, 3.0).apply(&mut scales);
+        assert!(matches!(result, Err(PlotError::InvalidLimits { .. })));
+    }
+}
@@ -1,7 +1,10 @@
 use internment::Intern;
 use ordered_float::OrderedFloat;
+use std::collections::HashMap;
 use std::hash::Hash;
 
+use crate::error::PlotError;
+
 /// Interned string type for efficient cloning and comparison of categorical values.
 /// Uses pointer comparison for equality and hashing.
 pub type IStr = Intern<String>;
@@ -14,6 +17,16 @@ pub trait PrimitiveType: PartialEq + PartialOrd + Clone + Sized + Send + Sync +
     fn to_sortable(&self) -> Self::Sortable;
 
     fn from_sortable(sortable: Self::Sortable) -> Self;
+
+    /// The value as a discrete level; floats have none
+    fn to_discrete(&self) -> Option<DiscreteValue> {
+        match self.to_primitive() {
+            PrimitiveValue::Int(i) => Some(DiscreteValue::Int(i)),
+            PrimitiveValue::Str(s) => Some(DiscreteValue::Str(s)),
+            PrimitiveValue::Bool(b) => Some(DiscreteValue::Bool(b)),
+            PrimitiveValue::Float(_) => None,
+        }
+    }
 }
 
 impl PrimitiveType for i64 {
@@ -169,38 +182,86 @@ impl From<IStr> for PrimitiveValue {
     }
 }
 
+/// Label of the level for missing discrete values, [`DiscreteValue::Na`]
+pub const NA_LEVEL: &str = "NA";
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum DiscreteValue {
     Str(IStr),
     Int(i64),
     Bool(bool),
+    /// A missing value. It sorts after the other levels, is labelled
+    /// [`NA_LEVEL`] and takes the scale's `na_value`.
+    Na,
 }
 
 impl DiscreteValue {
     pub fn as_str(&self) -> &str {
         match self {
             DiscreteValue::Str(s) => s.as_ref(),
+            DiscreteValue::Na => NA_LEVEL,
             _ => panic!("DiscreteValue is not a string"),
         }
     }
 
+    /// The level standing for missing values
+    pub fn na() -> Self {
+        DiscreteValue::Na
+    }
+
+    /// Whether this is the level standing for missing values
+    pub fn is_na(&self) -> bool {
+        matches!(self, DiscreteValue::Na)
+    }
+
     pub fn to_string(&self) -> String {
         match self {
             DiscreteValue::Int(i) => i.to_string(),
             DiscreteValue::Str(s) => s.to_string(),
             DiscreteValue::Bool(b) => b.to_string(),
+            DiscreteValue::Na => NA_LEVEL.to_string(),
         }
     }
 
+    /// The value as an interned string; the missing level reads as [`NA_LEVEL`]
     pub fn to_istr(&self) -> IStr {
         match self {
             DiscreteValue::Int(i) => IStr::new(i.to_string()),
             DiscreteValue::Str(s) => s.clone(),
             DiscreteValue::Bool(b) => IStr::new(b.to_string()),
+            DiscreteValue::Na => IStr::new(NA_LEVEL.to_string()),
+        }
+    }
+}
+
+/// Missing values read as the string [`NA_LEVEL`] but keep their own level
+impl PrimitiveType for DiscreteValue {
+    type Sortable = DiscreteValue;
+
+    fn to_primitive(&self) -> PrimitiveValue {
+        match self {
+            DiscreteValue::Int(i) => PrimitiveValue::Int(*i),
+            DiscreteValue::Str(s) => PrimitiveValue::Str(*s),
+            DiscreteValue::Bool(b) => PrimitiveValue::Bool(*b),
+            DiscreteValue::Na => PrimitiveValue::from(NA_LEVEL),
         }
     }
+
+    fn to_sortable(&self) -> Self::Sortable {
+        self.clone()
+    }
+
+    fn from_sortable(sortable: Self::Sortable) -> Self {
+        sortable
+    }
+
+    fn to_discrete(&self) -> Option<DiscreteValue> {
+        Some(self.clone())
+    }
 }
 
+impl DiscreteType for DiscreteValue {}
+
 impl From<PrimitiveValue> for DiscreteValue {
     fn from(pv: PrimitiveValue) -> Self {
         match pv {
@@ -230,6 +291,18 @@ impl From<IStr> for DiscreteValue {
     }
 }
 
+impl From<i64> for DiscreteValue {
+    fn from(i: i64) -> Self {
+        DiscreteValue::Int(i)
+    }
+}
+
+impl From<bool> for DiscreteValue {
+    fn from(b: bool) -> Self {
+        DiscreteValue::Bool(b)
+    }
+}
+
 /// Simplified data type classification for determining scale types.
 /// This distinguishes numeric types (which can use continuous scales)
 /// from string types (which require categorical scales).
@@ -241,12 +314,147 @@ pub enum ColumnDataType {
     String,
 }
 
+/// A categorical (factor) column: codes into an ordered list of levels
+///
+/// The level order is the order discrete scales, legends and dodged groups
+/// use, and levels need not occur in the data. Values read as their
+/// level's label. Missing values have no code: they are not a level, read
+/// as [`NA_LEVEL`] and come back as [`DiscreteValue::Na`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Categorical {
+    levels: Vec<String>,
+    codes: Vec<Option<u32>>,
+}
+
+impl Categorical {
+    /// Encode values with the given levels, in order. Values that aren't
+    /// listed become levels after the listed ones, in order of appearance.
+    pub fn new<S: Into<String>>(
+        values: impl IntoIterator<Item = impl AsRef<str>>,
+        levels: impl IntoIterator<Item = S>,
+    ) -> Self {
+        Self::with_missing(values.into_iter().map(Some), levels)
+    }
+
+    /// Encode values with the given levels, like [`Categorical::new`],
+    /// where `None` is a missing value
+    pub fn with_missing<S: Into<String>>(
+        values: impl IntoIterator<Item = Option<impl AsRef<str>>>,
+        levels: impl IntoIterator<Item = S>,
+    ) -> Self {
+        let mut categorical = Self {
+            levels: Vec::new(),
+            codes: Vec::new(),
+        };
+        let mut index = HashMap::new();
+        for level in levels {
+            categorical.level_code(level.into(), &mut index);
+        }
+        for value in values {
+            let code = value.map(|value| categorical.level_code(value.as_ref().to_string(), &mut index));
+            categorical.codes.push(code);
+        }
+        categorical
+    }
+
+    /// Encode values with levels in order of first appearance
+    pub fn from_values(values: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+        Self::new(values, Vec::<String>::new())
+    }
+
+    // Code of a level, adding it if it's new
+    fn level_code(&mut self, level: String, index: &mut HashMap<String, u32>) -> u32 {
+        if let Some(&code) = index.get(&level) {
+            return code;
+        }
+        let code = self.levels.len() as u32;
+        index.insert(level.clone(), code);
+        self.levels.push(level);
+        code
+    }
+
+    /// The levels, in order
+    pub fn levels(&self) -> &[String] {
+        &self.levels
+    }
+
+    /// The code of each value, indexing into the levels; `None` when missing
+    pub fn codes(&self) -> &[Option<u32>] {
+        &self.codes
+    }
+
+    pub fn len(&self) -> usize {
+        self.codes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.codes.is_empty()
+    }
+
+    /// Whether the value at `i` is missing
+    pub fn is_missing(&self, i: usize) -> bool {
+        self.codes[i].is_none()
+    }
+
+    /// Number of missing values
+    pub fn missing_count(&self) -> usize {
+        self.codes.iter().filter(|code| code.is_none()).count()
+    }
+
+    /// The label of the value at `i`, [`NA_LEVEL`] when it is missing
+    pub fn label(&self, i: usize) -> &str {
+        self.code_label(self.codes[i])
+    }
+
+    /// Iterate over the values' labels
+    pub fn labels(&self) -> impl Iterator<Item = &str> + '_ {
+        self.codes.iter().map(|&code| self.code_label(code))
+    }
+
+    fn code_label(&self, code: Option<u32>) -> &str {
+        match code {
+            Some(code) => &self.levels[code as usize],
+            None => NA_LEVEL,
+        }
+    }
+
+    /// The value at `i` as a discrete level
+    pub fn value(&self, i: usize) -> DiscreteValue {
+        match self.codes[i] {
+            Some(code) => DiscreteValue::from(self.levels[code as usize].as_str()),
+            None => DiscreteValue::Na,
+        }
+    }
+
+    /// The values at `indices`, with the same levels
+    pub fn subset(&self, indices: &[usize]) -> Self {
+        Self {
+            levels: self.levels.clone(),
+            codes: indices.iter().map(|&i| self.codes[i]).collect(),
+        }
+    }
+
+    /// Append values, adding any of their levels that are new
+    pub fn append(&mut self, other: &Categorical) {
+        let mut index: HashMap<String, u32> = self.levels.iter().cloned().zip(0..).collect();
+        for &code in &other.codes {
+            let code = code.map(|code| self.level_code(other.levels[code as usize].clone(), &mut index));
+            self.codes.push(code);
+        }
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub enum VectorValue {
     Int(Vec<i64>),
     Float(Vec<f64>),
     Str(Vec<String>),
     Bool(Vec<bool>),
+    Categorical(Categorical),
+    /// Days since 1970-01-01
+    Date(Vec<i32>),
+    /// Seconds since the Unix epoch, and the time zone they are shown in
+    DateTime(Vec<f64>, Option<String>),
 }
 
 impl VectorValue {
@@ -256,6 +464,9 @@ impl VectorValue {
             VectorValue::Float(v) => v.len(),
             VectorValue::Str(v) => v.len(),
             VectorValue::Bool(v) => v.len(),
+            VectorValue::Categorical(v) => v.len(),
+            VectorValue::Date(v) => v.len(),
+            VectorValue::DateTime(v, _) => v.len(),
         }
     }
 
@@ -265,15 +476,25 @@ impl VectorValue {
             VectorValue::Float(_) => VectorType::Float,
             VectorValue::Str(_) => VectorType::Str,
             VectorValue::Bool(_) => VectorType::Bool,
+            VectorValue::Categorical(_) => VectorType::Str,
+            VectorValue::Date(_) => VectorType::Date,
+            VectorValue::DateTime(..) => VectorType::DateTime,
         }
     }
 
     pub fn cmp_at_index(&self, i: usize, j: usize) -> std::cmp::Ordering {
         match self {
             VectorValue::Int(v) => v[i].cmp(&v[j]),
-            VectorValue::Float(v) => v[i].partial_cmp(&v[j]).unwrap(),
+            VectorValue::Float(v) => v[i].total_cmp(&v[j]),
             VectorValue::Str(v) => v[i].cmp(&v[j]),
             VectorValue::Bool(v) => v[i].cmp(&v[j]),
+            // Missing values sort last
+            VectorValue::Categorical(v) => match (v.codes[i], v.codes[j]) {
+                (Some(a), Some(b)) => a.cmp(&b),
+                (a, b) => a.is_none().cmp(&b.is_none()),
+            },
+            VectorValue::Date(v) => v[i].cmp(&v[j]),
+            VectorValue::DateTime(v, _) => v[i].total_cmp(&v[j]),
         }
     }
 
@@ -295,6 +516,31 @@ impl VectorValue {
                 let iter = indices.iter().map(move |&i| v[i]);
                 VectorIter::Bool(Box::new(iter))
             }
+            VectorValue::Categorical(v) => {
+                let iter = indices.iter().map(move |&i| v.label(i));
+                VectorIter::Str(Box::new(iter))
+            }
+            VectorValue::Date(v) => {
+                let iter = indices.iter().map(move |&i| v[i] as i64);
+                VectorIter::Int(Box::new(iter))
+            }
+            VectorValue::DateTime(v, _) => {
+                let iter = indices.iter().map(move |&i| v[i]);
+                VectorIter::Float(Box::new(iter))
+            }
+        }
+    }
+
+    /// The values at `indices`, keeping the levels of a categorical and
+    /// the type of dates and date-times
+    pub fn subset(&self, indices: &[usize]) -> VectorValue {
+        match self {
+            VectorValue::Categorical(v) => VectorValue::Categorical(v.subset(indices)),
+            VectorValue::Date(v) => VectorValue::Date(indices.iter().map(|&i| v[i]).collect()),
+            VectorValue::DateTime(v, tz) => {
+                VectorValue::DateTime(indices.iter().map(|&i| v[i]).collect(), tz.clone())
+            }
+            _ => self.subset_iter(indices).to_vector(),
         }
     }
 
@@ -304,17 +550,36 @@ impl VectorValue {
             VectorValue::Float(_) => VectorValue::Float(Vec::new()),
             VectorValue::Str(_) => VectorValue::Str(Vec::new()),
             VectorValue::Bool(_) => VectorValue::Bool(Vec::new()),
+            VectorValue::Categorical(v) => VectorValue::Categorical(v.subset(&[])),
+            VectorValue::Date(_) => VectorValue::Date(Vec::new()),
+            VectorValue::DateTime(_, tz) => VectorValue::DateTime(Vec::new(), tz.clone()),
         }
     }
 
-    pub fn append(&mut self, other: &VectorValue) {
+    /// Append values of the same type, and for date-times the same time zone
+    pub fn append(&mut self, other: &VectorValue) -> Result<(), PlotError> {
         match (self, other) {
             (VectorValue::Int(v1), VectorValue::Int(v2)) => v1.extend_from_slice(v2),
             (VectorValue::Float(v1), VectorValue::Float(v2)) => v1.extend_from_slice(v2),
             (VectorValue::Str(v1), VectorValue::Str(v2)) => v1.extend_from_slice(v2),
             (VectorValue::Bool(v1), VectorValue::Bool(v2)) => v1.extend_from_slice(v2),
-            _ => panic!("Cannot append VectorValues of different types"),
+            (VectorValue::Categorical(v1), VectorValue::Categorical(v2)) => v1.append(v2),
+            (VectorValue::Date(v1), VectorValue::Date(v2)) => v1.extend_from_slice(v2),
+            (VectorValue::DateTime(v1, tz1), VectorValue::DateTime(v2, tz2)) => {
+                if tz1 != tz2 {
+                    return Err(PlotError::IncompatibleVectors {
+                        details: format!("date-times in time zones {:?} and {:?}", tz1, tz2),
+                    });
+                }
+                v1.extend_from_slice(v2)
+            }
+            (v1, v2) => {
+                return Err(PlotError::IncompatibleVectors {
+                    details: format!("{:?} and {:?} values", v1.vtype(), v2.vtype()),
+                });
+            }
         }
+        Ok(())
     }
 }
 
@@ -348,6 +613,39 @@ impl From<Vec<bool>> for VectorValue {
     }
 }
 
+impl From<Categorical> for VectorValue {
+    fn from(v: Categorical) -> Self {
+        VectorValue::Categorical(v)
+    }
+}
+
+impl From<Vec<jiff::civil::Date>> for VectorValue {
+    fn from(v: Vec<jiff::civil::Date>) -> Self {
+        let epoch = jiff::civil::Date::constant(1970, 1, 1);
+        let days = v
+            .iter()
+            .map(|date| (date.duration_since(epoch).as_secs() / 86_400) as i32)
+            .collect();
+        VectorValue::Date(days)
+    }
+}
+
+impl From<Vec<jiff::Timestamp>> for VectorValue {
+    fn from(v: Vec<jiff::Timestamp>) -> Self {
+        let seconds = v.iter().map(|ts| ts.as_duration().as_secs_f64()).collect();
+        VectorValue::DateTime(seconds, None)
+    }
+}
+
+/// Date-times take the time zone of the first value
+impl From<Vec<jiff::Zoned>> for VectorValue {
+    fn from(v: Vec<jiff::Zoned>) -> Self {
+        let tz = v.first().and_then(|z| z.time_zone().iana_name()).map(str::to_string);
+        let seconds = v.iter().map(|z| z.timestamp().as_duration().as_secs_f64()).collect();
+        VectorValue::DateTime(seconds, tz)
+    }
+}
+
 impl GenericVector for VectorValue {
     fn len(&self) -> usize {
         self.len()
@@ -375,6 +673,44 @@ impl GenericVector for VectorValue {
                 let iter = v.iter().cloned();
                 VectorIter::Bool(Box::new(iter))
             }
+            VectorValue::Categorical(v) => VectorIter::Str(Box::new(v.labels())),
+            VectorValue::Date(v) => {
+                let iter = v.iter().map(|&d| d as i64);
+                VectorIter::Int(Box::new(iter))
+            }
+            VectorValue::DateTime(v, _) => {
+                let iter = v.iter().cloned();
+                VectorIter::Float(Box::new(iter))
+            }
+        }
+    }
+
+    fn temporal(&self) -> Option<TemporalType> {
+        match self {
+            VectorValue::Date(_) => Some(TemporalType::Date),
+            VectorValue::DateTime(_, tz) => Some(TemporalType::DateTime { tz: tz.clone() }),
+            _ => None,
+        }
+    }
+
+    fn is_null(&self, i: usize) -> bool {
+        match self {
+            VectorValue::Categorical(v) => v.is_missing(i),
+            _ => false,
+        }
+    }
+
+    fn null_count(&self) -> usize {
+        match self {
+            VectorValue::Categorical(v) => v.missing_count(),
+            _ => 0,
+        }
+    }
+
+    fn levels(&self) -> Option<Vec<String>> {
+        match self {
+            VectorValue::Categorical(v) => Some(v.levels.clone()),
+            _ => None,
         }
     }
 }
@@ -410,7 +746,7 @@ impl<'a> VectorIter<'a> {
     pub fn to_discrete_iter(self) -> Box<dyn Iterator<Item = DiscreteValue> + 'a> {
         match self {
             VectorIter::Int(iter) => Box::new(iter.map(DiscreteValue::Int)),
-            VectorIter::Str(iter) => Box::new(iter.map(|s| DiscreteValue::Str(IStr::new(s.to_string())))),
+            VectorIter::Str(iter) => Box::new(iter.map(DiscreteValue::from)),
             VectorIter::Bool(iter) => Box::new(iter.map(DiscreteValue::Bool)),
             VectorIter::Float(iter) => Box::new(iter.map(|f| DiscreteValue::Int(f as i64))),
         }
@@ -440,23 +776,44 @@ impl<'a> Iterator for VectorIter<'a> {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum VectorType {
     Int,
     Float,
     Str,
     Bool,
+    /// Days since 1970-01-01, read as integers
+    Date,
+    /// Seconds since the Unix epoch, read as floats
+    DateTime,
 }
 
 impl VectorType {
     /// Convert VectorType to simplified ColumnDataType classification
     pub fn to_column_data_type(self) -> ColumnDataType {
         match self {
-            VectorType::Int | VectorType::Float | VectorType::Bool => ColumnDataType::Numeric,
+            VectorType::Int
+            | VectorType::Float
+            | VectorType::Bool
+            | VectorType::Date
+            | VectorType::DateTime => ColumnDataType::Numeric,
             VectorType::Str => ColumnDataType::String,
         }
     }
 }
 
+/// Calendar meaning of a numeric column
+///
+/// Dates hold days since 1970-01-01, date-times hold seconds since the Unix
+/// epoch and durations hold seconds; columns reporting one of these get a
+/// date, datetime or duration scale.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemporalType {
+    Date,
+    DateTime { tz: Option<String> },
+    Duration,
+}
+
 impl std::fmt::Display for VectorType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -464,6 +821,8 @@ impl std::fmt::Display for VectorType {
             VectorType::Float => write!(f, "float"),
             VectorType::Str => write!(f, "string"),
             VectorType::Bool => write!(f, "boolean"),
+            VectorType::Date => write!(f, "date"),
+            VectorType::DateTime => write!(f, "datetime"),
         }
     }
 }
@@ -509,6 +868,109 @@ pub trait GenericVector: Send + Sync {
             None
         }
     }
+
+    /// Days since 1970-01-01 of a date vector
+    fn iter_date(&self) -> Option<Box<dyn Iterator<Item = i32> + '_>> {
+        if self.vtype() != VectorType::Date {
+            return None;
+        }
+        Some(Box::new(self.iter_int()?.map(|d| d as i32)))
+    }
+
+    /// Seconds since the Unix epoch of a date-time vector
+    fn iter_datetime(&self) -> Option<Box<dyn Iterator<Item = f64> + '_>> {
+        if self.vtype() != VectorType::DateTime {
+            return None;
+        }
+        self.iter_float()
+    }
+
+    /// Whether the values are dates, date-times or durations (None for plain numbers)
+    ///
+    /// Date-time vectors carrying a time zone should override this.
+    fn temporal(&self) -> Option<TemporalType> {
+        match self.vtype() {
+            VectorType::Date => Some(TemporalType::Date),
+            VectorType::DateTime => Some(TemporalType::DateTime { tz: None }),
+            _ => None,
+        }
+    }
+
+    /// Whether the value at `i` is missing (null). Missing values still
+    /// appear in the iterators, as NaN for floats, [`NA_LEVEL`] for
+    /// categoricals and a placeholder otherwise, so rows stay aligned;
+    /// [`GenericVector::iter_discrete`]
+    /// reads them as [`DiscreteValue::Na`].
+    fn is_null(&self, _i: usize) -> bool {
+        false
+    }
+
+    /// Number of missing values
+    fn null_count(&self) -> usize {
+        0
+    }
+
+    /// The ordered levels of a categorical (factor) column, including any
+    /// that don't occur. Values read as their level's label.
+    fn levels(&self) -> Option<Vec<String>> {
+        None
+    }
+
+    /// The values as discrete levels, with missing rows as
+    /// [`DiscreteValue::Na`]. Floats have none.
+    fn iter_discrete(&self) -> Option<Box<dyn Iterator<Item = DiscreteValue> + '_>> {
+        let values: Box<dyn Iterator<Item = DiscreteValue> + '_> = match self.iter() {
+            VectorIter::Int(iter) => Box::new(iter.map(DiscreteValue::Int)),
+            VectorIter::Str(iter) => Box::new(iter.map(DiscreteValue::from)),
+            VectorIter::Bool(iter) => Box::new(iter.map(DiscreteValue::Bool)),
+            VectorIter::Float(_) => return None,
+        };
+        if self.null_count() == 0 {
+            return Some(values);
+        }
+        Some(Box::new(values.enumerate().map(
+            |(i, value)| if self.is_null(i) { DiscreteValue::Na } else { value },
+        )))
+    }
+
+    /// Copy the values, keeping the levels of a categorical column and the
+    /// type of dates and date-times. Null rows of a categorical column stay
+    /// missing.
+    fn to_vector(&self) -> VectorValue {
+        if let Some(days) = self.iter_date() {
+            return VectorValue::Date(days.collect());
+        }
+        if let Some(seconds) = self.iter_datetime() {
+            let tz = match self.temporal() {
+                Some(TemporalType::DateTime { tz }) => tz,
+                _ => None,
+            };
+            return VectorValue::DateTime(seconds.collect(), tz);
+        }
+        match (self.levels(), self.iter_str()) {
+            (Some(levels), Some(labels)) => {
+                let labels = labels.enumerate().map(|(i, label)| (!self.is_null(i)).then_some(label));
+                VectorValue::Categorical(Categorical::with_missing(labels, levels))
+            }
+            _ => self.iter().to_vector(),
+        }
+    }
+
+    /// Which rows are missing: nulls, and NaN for floats. `None` when no
+    /// row is.
+    fn missing(&self) -> Option<Vec<bool>> {
+        let mut missing: Vec<bool> = if self.null_count() > 0 {
+            (0..self.len()).map(|i| self.is_null(i)).collect()
+        } else {
+            vec![false; self.len()]
+        };
+        if let Some(values) = self.iter_float() {
+            for (missing, value) in missing.iter_mut().zip(values) {
+                *missing |= value.is_nan();
+            }
+        }
+        missing.contains(&true).then_some(missing)
+    }
 }
 
 pub trait StrVector: GenericVector + Send + Sync {
@@ -117,6 +117,21 @@ pub enum PlotError {
         max: f64,
     },
 
+    /// Scale parameters are invalid (e.g., non-finite expansion)
+    InvalidScaleParameters {
+        details: String,
+    },
+
+    /// Facet configuration error (e.g., a grid too small for its panels)
+    InvalidFacetParameters {
+        details: String,
+    },
+
+    /// Vectors can't be appended (e.g., different types or time zones)
+    IncompatibleVectors {
+        details: String,
+    },
+
     /// A required stat input is missing
     MissingStatInput {
         stat: String,
@@ -261,6 +276,15 @@ impl Display for PlotError {
             PlotError::InvalidLimits { min, max } => {
                 write!(f, "Invalid scale limits: min={}, max={}", min, max)
             }
+            PlotError::InvalidScaleParameters { details } => {
+                write!(f, "Invalid scale parameters: {}", details)
+            }
+            PlotError::InvalidFacetParameters { details } => {
+                write!(f, "Invalid facet parameters: {}", details)
+            }
+            PlotError::IncompatibleVectors { details } => {
+                write!(f, "Incompatible vectors: {}", details)
+            }
             PlotError::MissingStatInput { stat, aesthetic } => {
                 write!(f, "{} stat requires {:?} aesthetic", stat, aesthetic)
             }
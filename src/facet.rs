@@ -0,0 +1,839 @@
+// Faceting: small multiples of one plot, a panel per combination of the
+// values of one or more discrete columns
+
+use crate::data::{DataSource, GenericVector, VectorIter, VectorType, NA_LEVEL};
+use crate::error::{PlotError, Result};
+use crate::layer::Layer;
+use crate::scale::positional::{ContinuousPositionalScale, DiscretePositionalScale};
+use crate::scale::traits::{ContinuousDomainScale, DiscreteDomainScale};
+use crate::scale::ScaleSet;
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
+use std::ops::Range;
+use std::sync::Arc;
+
+/// Strip label of the margin panels, which show every value of a variable
+pub const MARGIN_LABEL: &str = "(all)";
+
+/// Which positional scales each panel trains on its own data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FacetScales {
+    /// All panels share the x and y scales
+    #[default]
+    Fixed,
+    FreeX,
+    FreeY,
+    Free,
+}
+
+impl FacetScales {
+    pub fn free_x(self) -> bool {
+        matches!(self, FacetScales::FreeX | FacetScales::Free)
+    }
+
+    pub fn free_y(self) -> bool {
+        matches!(self, FacetScales::FreeY | FacetScales::Free)
+    }
+}
+
+/// A function turning a facet variable name and one of its values into a label
+pub type LabelClosure = Arc<dyn Fn(&str, &str) -> String + Send + Sync>;
+
+/// Turns a facet variable and one of its values into a strip label
+#[derive(Clone, Default)]
+pub enum Labeller {
+    /// The value alone
+    #[default]
+    Value,
+    /// The variable and the value, as "var: value"
+    Both,
+    /// A function of the variable name and the value
+    Custom(LabelClosure),
+}
+
+impl Labeller {
+    pub fn custom(labeller: impl Fn(&str, &str) -> String + Send + Sync + 'static) -> Self {
+        Labeller::Custom(Arc::new(labeller))
+    }
+
+    pub fn label(&self, var: &str, value: &str) -> String {
+        match self {
+            Labeller::Value => value.to_string(),
+            Labeller::Both => format!("{}: {}", var, value),
+            Labeller::Custom(labeller) => labeller(var, value),
+        }
+    }
+}
+
+impl fmt::Debug for Labeller {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Labeller::Value => write!(f, "Value"),
+            Labeller::Both => write!(f, "Both"),
+            Labeller::Custom(_) => write!(f, "Custom(..)"),
+        }
+    }
+}
+
+/// Panels for each combination of the facet variables that occurs in the
+/// data, wrapped row by row into a grid
+#[derive(Debug, Clone)]
+pub struct FacetWrap {
+    vars: Vec<String>,
+    ncol: Option<usize>,
+    nrow: Option<usize>,
+    scales: FacetScales,
+    labeller: Labeller,
+}
+
+impl FacetWrap {
+    pub fn new<S: Into<String>>(vars: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            vars: vars.into_iter().map(Into::into).collect(),
+            ncol: None,
+            nrow: None,
+            scales: FacetScales::Fixed,
+            labeller: Labeller::Value,
+        }
+    }
+
+    /// Number of panel columns (by default close to square)
+    pub fn ncol(mut self, ncol: usize) -> Self {
+        self.ncol = Some(ncol);
+        self
+    }
+
+    /// Number of panel rows
+    pub fn nrow(mut self, nrow: usize) -> Self {
+        self.nrow = Some(nrow);
+        self
+    }
+
+    /// Share the x and y scales between panels or train them per panel
+    pub fn scales(mut self, scales: FacetScales) -> Self {
+        self.scales = scales;
+        self
+    }
+
+    /// How strips label each variable's value
+    pub fn labeller(mut self, labeller: Labeller) -> Self {
+        self.labeller = labeller;
+        self
+    }
+
+    /// Rows and columns of a grid holding `n` panels
+    fn dims(&self, n: usize) -> Result<(usize, usize)> {
+        let (nrow, ncol) = match (self.nrow, self.ncol) {
+            (Some(0), _) | (_, Some(0)) => {
+                return Err(PlotError::InvalidFacetParameters {
+                    details: "nrow and ncol must be at least 1".to_string(),
+                });
+            }
+            (Some(nrow), Some(ncol)) => (nrow, ncol),
+            (None, Some(ncol)) => (n.div_ceil(ncol), ncol),
+            (Some(nrow), None) => (nrow, n.div_ceil(nrow)),
+            (None, None) => {
+                let ncol = (n as f64).sqrt().ceil() as usize;
+                (n.div_ceil(ncol), ncol)
+            }
+        };
+        if nrow * ncol < n {
+            return Err(PlotError::InvalidFacetParameters {
+                details: format!("{} panels don't fit in {} rows and {} columns", n, nrow, ncol),
+            });
+        }
+        Ok((nrow, ncol))
+    }
+}
+
+/// Split the plot into panels by the values of `vars`
+pub fn facet_wrap<S: Into<String>>(vars: impl IntoIterator<Item = S>) -> FacetWrap {
+    FacetWrap::new(vars)
+}
+
+/// A grid with a row per value of the row variables and a column per value
+/// of the column variables. Strips sit above the top row and right of the
+/// last column.
+#[derive(Debug, Clone)]
+pub struct FacetGrid {
+    rows: Vec<String>,
+    cols: Vec<String>,
+    scales: FacetScales,
+    space: FacetScales,
+    margins: bool,
+    labeller: Labeller,
+}
+
+impl FacetGrid {
+    pub fn new<R: Into<String>, C: Into<String>>(
+        rows: impl IntoIterator<Item = R>,
+        cols: impl IntoIterator<Item = C>,
+    ) -> Self {
+        Self {
+            rows: rows.into_iter().map(Into::into).collect(),
+            cols: cols.into_iter().map(Into::into).collect(),
+            scales: FacetScales::Fixed,
+            space: FacetScales::Fixed,
+            margins: false,
+            labeller: Labeller::Value,
+        }
+    }
+
+    /// Share the x and y scales between panels, or let each column train its
+    /// own x scale and each row its own y scale
+    pub fn scales(mut self, scales: FacetScales) -> Self {
+        self.scales = scales;
+        self
+    }
+
+    /// Size columns (free x) and rows (free y) in proportion to the range of
+    /// their scales, so a unit has the same length in every panel
+    pub fn space(mut self, space: FacetScales) -> Self {
+        self.space = space;
+        self
+    }
+
+    /// Add a row and a column of margin panels drawing every row's data
+    pub fn margins(mut self, margins: bool) -> Self {
+        self.margins = margins;
+        self
+    }
+
+    /// How strips label each variable's value
+    pub fn labeller(mut self, labeller: Labeller) -> Self {
+        self.labeller = labeller;
+        self
+    }
+}
+
+/// Split the plot into a grid of panels by the values of `rows` and `cols`;
+/// either may be empty
+pub fn facet_grid<R: Into<String>, C: Into<String>>(
+    rows: impl IntoIterator<Item = R>,
+    cols: impl IntoIterator<Item = C>,
+) -> FacetGrid {
+    FacetGrid::new(rows, cols)
+}
+
+/// How a plot is split into panels
+#[derive(Debug, Clone)]
+pub enum Facet {
+    Wrap(FacetWrap),
+    Grid(FacetGrid),
+}
+
+impl From<FacetWrap> for Facet {
+    fn from(facet: FacetWrap) -> Self {
+        Facet::Wrap(facet)
+    }
+}
+
+impl From<FacetGrid> for Facet {
+    fn from(facet: FacetGrid) -> Self {
+        Facet::Grid(facet)
+    }
+}
+
+/// One panel of a faceted plot
+#[derive(Debug, Clone)]
+pub struct Panel {
+    pub row: usize,
+    pub col: usize,
+    /// Strip labels above the panel
+    pub strips: Vec<String>,
+    /// Strip labels right of the panel
+    pub row_strips: Vec<String>,
+    /// The panel's layers within `Plot::layers`
+    pub layers: Range<usize>,
+    /// The scales the panel's layers were mapped with
+    pub scales: ScaleSet,
+}
+
+/// The panels of a faceted plot and the grid they sit in
+#[derive(Debug, Clone)]
+pub struct FacetLayout {
+    pub nrow: usize,
+    pub ncol: usize,
+    pub scales: FacetScales,
+    /// Free x scales are shared down each column and free y scales along
+    /// each row, as in `facet_grid`, rather than trained per panel
+    pub grid: bool,
+    /// Relative width of each column
+    pub widths: Vec<f64>,
+    /// Relative height of each row
+    pub heights: Vec<f64>,
+    pub panels: Vec<Panel>,
+}
+
+impl FacetLayout {
+    /// Whether a panel sits at this row and column
+    pub fn has_panel(&self, row: usize, col: usize) -> bool {
+        self.panels.iter().any(|panel| panel.row == row && panel.col == col)
+    }
+
+    /// The scales of the panel drawing layer `index`
+    pub fn layer_scales(&self, index: usize) -> Option<&ScaleSet> {
+        self.panels
+            .iter()
+            .find(|panel| panel.layers.contains(&index))
+            .map(|panel| &panel.scales)
+    }
+
+    /// Give each panel the plot's trained scales, retrained along free axes
+    /// on the layers of the panels sharing them
+    pub(crate) fn train(
+        &mut self,
+        layers: &mut [Layer],
+        untrained: &ScaleSet,
+        trained: &ScaleSet,
+        parent_data: &dyn DataSource,
+    ) -> Result<()> {
+        let mut panel_scales = Vec::with_capacity(self.panels.len());
+        for (i, panel) in self.panels.iter().enumerate() {
+            let mut scales = trained.clone();
+            if self.scales.free_x() {
+                let local = self.train_shared(layers, untrained, parent_data, |j, other| {
+                    if self.grid { other.col == panel.col } else { j == i }
+                })?;
+                if let Some(local) = local {
+                    scales.x_continuous = local.x_continuous;
+                    scales.x_discrete = local.x_discrete;
+                }
+            }
+            if self.scales.free_y() {
+                let local = self.train_shared(layers, untrained, parent_data, |j, other| {
+                    if self.grid { other.row == panel.row } else { j == i }
+                })?;
+                if let Some(local) = local {
+                    scales.y_continuous = local.y_continuous;
+                    scales.y_discrete = local.y_discrete;
+                }
+            }
+            panel_scales.push(scales);
+        }
+        for (panel, scales) in self.panels.iter_mut().zip(panel_scales) {
+            panel.scales = scales;
+        }
+        Ok(())
+    }
+
+    /// Scales trained on the layers of the panels for which `shared` holds,
+    /// or None if they have no layers
+    fn train_shared(
+        &self,
+        layers: &mut [Layer],
+        untrained: &ScaleSet,
+        parent_data: &dyn DataSource,
+        shared: impl Fn(usize, &Panel) -> bool,
+    ) -> Result<Option<ScaleSet>> {
+        let mut local = untrained.clone();
+        let mut trained = false;
+        for (j, panel) in self.panels.iter().enumerate() {
+            if !shared(j, panel) {
+                continue;
+            }
+            for layer in &mut layers[panel.layers.clone()] {
+                layer.train_scales(&mut local, parent_data)?;
+                layer.geom.train_scales(&mut local);
+                trained = true;
+            }
+        }
+        Ok(trained.then_some(local))
+    }
+
+    /// Size columns and rows in proportion to the ranges of their x and
+    /// y scales along the free axes of `space`
+    pub(crate) fn apply_space(&mut self, space: FacetScales) {
+        for panel in &self.panels {
+            if space.free_x() {
+                self.widths[panel.col] = scale_span(&panel.scales.x_continuous, &panel.scales.x_discrete);
+            }
+            if space.free_y() {
+                self.heights[panel.row] = scale_span(&panel.scales.y_continuous, &panel.scales.y_discrete);
+            }
+        }
+    }
+}
+
+/// The length of a positional scale's domain, in categories for a
+/// discrete scale
+fn scale_span(continuous: &ContinuousPositionalScale, discrete: &DiscretePositionalScale) -> f64 {
+    let categories = discrete.categories().len();
+    if categories > 0 {
+        return categories as f64;
+    }
+    match continuous.domain() {
+        Some((lo, hi)) if hi > lo => hi - lo,
+        _ => 1.0,
+    }
+}
+
+/// Where a panel sits and which rows it draws: a level index per facet
+/// variable, or None to draw all of them in a margin panel
+struct Cell {
+    row: usize,
+    col: usize,
+    key: Vec<Option<usize>>,
+    strips: Vec<String>,
+    row_strips: Vec<String>,
+}
+
+impl Facet {
+    fn vars(&self) -> Vec<&str> {
+        match self {
+            Facet::Wrap(wrap) => wrap.vars.iter().map(String::as_str).collect(),
+            Facet::Grid(grid) => grid.rows.iter().chain(&grid.cols).map(String::as_str).collect(),
+        }
+    }
+
+    /// Axes along which panels draw in proportion to their scales' ranges
+    pub(crate) fn space(&self) -> FacetScales {
+        match self {
+            Facet::Wrap(_) => FacetScales::Fixed,
+            Facet::Grid(grid) => grid.space,
+        }
+    }
+
+    /// Split every layer into one layer per panel holding the panel's rows.
+    /// A layer without a facet variable's column is repeated across all of
+    /// its values.
+    pub(crate) fn split(
+        &self,
+        layers: Vec<Layer>,
+        parent_data: &dyn DataSource,
+    ) -> Result<(Vec<Layer>, FacetLayout)> {
+        let vars = self.vars();
+        if vars.is_empty() {
+            return Err(PlotError::InvalidFacetParameters {
+                details: "no facet variables".to_string(),
+            });
+        }
+
+        // Each layer's label per row for each variable, None without the column
+        let labels: Vec<Vec<Option<Vec<Option<String>>>>> = layers
+            .iter()
+            .map(|layer| {
+                let data = layer.data(parent_data);
+                vars.iter()
+                    .map(|var| data.get(var).map(column_labels))
+                    .collect()
+            })
+            .collect();
+
+        let mut levels = Vec::with_capacity(vars.len());
+        for (v, var) in vars.iter().enumerate() {
+            let columns: Vec<&dyn GenericVector> = layers
+                .iter()
+                .filter_map(|layer| layer.data(parent_data).get(var))
+                .collect();
+            if columns.is_empty() {
+                return Err(PlotError::missing_column(*var));
+            }
+            let present = labels.iter().filter_map(|layer| layer[v].as_ref()).flatten();
+            levels.push(facet_levels(&columns, present));
+        }
+
+        // Rows as level indices, None where the layer lacks the variable
+        let codes: Vec<Vec<Vec<Option<usize>>>> = labels
+            .iter()
+            .zip(&layers)
+            .map(|(layer_labels, layer)| {
+                let n = layer.data(parent_data).len();
+                (0..n)
+                    .map(|row| {
+                        layer_labels
+                            .iter()
+                            .zip(&levels)
+                            .map(|(column, levels)| {
+                                let label = &column.as_ref()?[row];
+                                levels.iter().position(|level| level == label)
+                            })
+                            .collect()
+                    })
+                    .collect()
+            })
+            .collect();
+
+        // Strip labels of the variables in `range` for a key
+        let strip_labels = |labeller: &Labeller, range: Range<usize>, key: &[Option<usize>]| -> Vec<String> {
+            range
+                .zip(key)
+                .map(|(v, level)| {
+                    let value = match level.map(|level| levels[v][level].as_deref()) {
+                        None => MARGIN_LABEL,
+                        Some(None) => NA_LEVEL,
+                        Some(Some(value)) => value,
+                    };
+                    labeller.label(vars[v], value)
+                })
+                .collect()
+        };
+
+        let (nrow, ncol, scales, cells) = match self {
+            Facet::Wrap(wrap) => {
+                let keys = present_keys(&codes, 0..vars.len());
+                if keys.is_empty() {
+                    return Err(PlotError::no_valid_data("no layer has every facet variable"));
+                }
+                let (nrow, ncol) = wrap.dims(keys.len())?;
+                let cells = keys
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, key)| Cell {
+                        row: i / ncol,
+                        col: i % ncol,
+                        strips: strip_labels(&wrap.labeller, 0..vars.len(), &key),
+                        row_strips: Vec::new(),
+                        key,
+                    })
+                    .collect::<Vec<_>>();
+                (nrow, ncol, wrap.scales, cells)
+            }
+            Facet::Grid(grid) => {
+                let split_at = grid.rows.len();
+                let mut row_keys = present_keys(&codes, 0..split_at);
+                let mut col_keys = present_keys(&codes, split_at..vars.len());
+                if row_keys.is_empty() || col_keys.is_empty() {
+                    return Err(PlotError::no_valid_data("no layer has every facet variable"));
+                }
+                if grid.margins && !grid.rows.is_empty() {
+                    row_keys.push(vec![None; grid.rows.len()]);
+                }
+                if grid.margins && !grid.cols.is_empty() {
+                    col_keys.push(vec![None; grid.cols.len()]);
+                }
+                let (nrow, ncol) = (row_keys.len(), col_keys.len());
+                let mut cells = Vec::with_capacity(nrow * ncol);
+                for (row, row_key) in row_keys.iter().enumerate() {
+                    for (col, col_key) in col_keys.iter().enumerate() {
+                        let strips = if row == 0 {
+                            strip_labels(&grid.labeller, split_at..vars.len(), col_key)
+                        } else {
+                            Vec::new()
+                        };
+                        let row_strips = if col == ncol - 1 {
+                            strip_labels(&grid.labeller, 0..split_at, row_key)
+                        } else {
+                            Vec::new()
+                        };
+                        cells.push(Cell {
+                            row,
+                            col,
+                            key: row_key.iter().chain(col_key).copied().collect(),
+                            strips,
+                            row_strips,
+                        });
+                    }
+                }
+                (nrow, ncol, grid.scales, cells)
+            }
+        };
+
+        let mut split = Vec::new();
+        let mut panels = Vec::with_capacity(cells.len());
+        for cell in cells {
+            let start = split.len();
+            for (layer, rows) in layers.iter().zip(&codes) {
+                let indices: Vec<usize> = rows
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, row)| {
+                        row.iter().zip(&cell.key).all(|(code, level)| match (code, level) {
+                            (Some(code), Some(level)) => code == level,
+                            _ => true,
+                        })
+                    })
+                    .map(|(row, _)| row)
+                    .collect();
+                if !indices.is_empty() {
+                    split.push(layer.subset(&indices, parent_data));
+                }
+            }
+            panels.push(Panel {
+                row: cell.row,
+                col: cell.col,
+                strips: cell.strips,
+                row_strips: cell.row_strips,
+                layers: start..split.len(),
+                scales: ScaleSet::default(),
+            });
+        }
+
+        let layout = FacetLayout {
+            nrow,
+            ncol,
+            scales,
+            grid: matches!(self, Facet::Grid(_)),
+            widths: vec![1.0; ncol],
+            heights: vec![1.0; nrow],
+            panels,
+        };
+        Ok((split, layout))
+    }
+}
+
+/// The combinations of levels of the variables in `vars` that occur in rows
+/// having all of them, in level order
+fn present_keys(codes: &[Vec<Vec<Option<usize>>>], vars: Range<usize>) -> Vec<Vec<Option<usize>>> {
+    let keys: BTreeSet<Vec<usize>> = codes
+        .iter()
+        .flatten()
+        .filter_map(|row| row[vars.clone()].iter().copied().collect::<Option<Vec<usize>>>())
+        .collect();
+    keys.into_iter()
+        .map(|key| key.into_iter().map(Some).collect())
+        .collect()
+}
+
+/// Each row's value as a strip label; None where the value is missing
+fn column_labels(column: &dyn GenericVector) -> Vec<Option<String>> {
+    let labels: Vec<String> = match column.vtype() {
+        VectorType::Date => column
+            .iter_date()
+            .map(|days| days.map(format_date).collect())
+            .unwrap_or_default(),
+        _ => match column.iter() {
+            VectorIter::Int(values) => values.map(|v| v.to_string()).collect(),
+            VectorIter::Float(values) => values.map(|v| v.to_string()).collect(),
+            VectorIter::Str(values) => values.map(str::to_string).collect(),
+            VectorIter::Bool(values) => values.map(|v| v.to_string()).collect(),
+        },
+    };
+    labels
+        .into_iter()
+        .enumerate()
+        .map(|(i, label)| (!column.is_null(i) && label != "NaN").then_some(label))
+        .collect()
+}
+
+fn format_date(days: i32) -> String {
+    jiff::civil::Date::constant(1970, 1, 1)
+        .checked_add(jiff::Span::new().days(days))
+        .map(|date| date.to_string())
+        .unwrap_or_else(|_| days.to_string())
+}
+
+/// The panel order of a variable's values: the levels of a categorical
+/// column, otherwise numeric or alphabetical order, with missing values
+/// (None) last
+fn facet_levels<'a>(
+    columns: &[&dyn GenericVector],
+    present: impl Iterator<Item = &'a Option<String>>,
+) -> Vec<Option<String>> {
+    let present: HashSet<&Option<String>> = present.collect();
+    let values = present.iter().filter_map(|level| level.as_ref());
+    let mut levels: Vec<Option<String>> = match columns.iter().find_map(|column| column.levels()) {
+        Some(levels) => {
+            let values: HashSet<&String> = values.collect();
+            levels.into_iter().filter(|level| values.contains(level)).map(Some).collect()
+        }
+        None => {
+            let mut levels: Vec<String> = values.cloned().collect();
+            let numbers: Option<Vec<f64>> = levels.iter().map(|level| level.parse().ok()).collect();
+            match numbers {
+                Some(_) => levels.sort_by(|a, b| {
+                    let a: f64 = a.parse().unwrap_or(f64::NAN);
+                    let b: f64 = b.parse().unwrap_or(f64::NAN);
+                    a.total_cmp(&b)
+                }),
+                None => levels.sort(),
+            }
+            levels.into_iter().map(Some).collect()
+        }
+    };
+    if present.contains(&None) {
+        levels.push(None);
+    }
+    levels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aesthetics::builder::{XContinuousAesBuilder, YContinuousAesBuilder};
+    use crate::error::to_io_error;
+    use crate::geom::point::geom_point;
+    use crate::geom::smooth::geom_smooth;
+    use crate::layer::LayerBuilderExt;
+    use crate::plot::plot;
+    use crate::scale::traits::ContinuousDomainScale;
+    use crate::stat::smooth::{Method, Smooth};
+    use crate::utils::mtcars::mtcars;
+
+    #[test]
+    fn facet_wrap_fixed_scales() {
+        let data = mtcars();
+        let builder = plot(&data).aes(|a| {
+            a.x_continuous("wt");
+            a.y_continuous("mpg");
+        }) + geom_point()
+            + geom_smooth().stat(Smooth::new().method(Method::Lm))
+            + facet_wrap(["cyl"]);
+
+        let p = builder
+            .build()
+            .map_err(to_io_error)
+            .expect("Failed to build plot");
+
+        let layout = p.facet.as_ref().unwrap();
+        assert_eq!((layout.nrow, layout.ncol), (2, 2));
+        let strips: Vec<&str> = layout.panels.iter().map(|panel| panel.strips[0].as_str()).collect();
+        assert_eq!(strips, ["4", "6", "8"]);
+
+        // The stat ran on each panel's rows
+        let n: Vec<usize> = p.smooth_fits().iter().map(|fit| fit.n).collect();
+        assert_eq!(n, [11, 7, 14]);
+        for panel in &layout.panels {
+            assert_eq!(panel.scales.x_continuous.domain(), p.scales.x_continuous.domain());
+        }
+
+        p.save("tests/images/facet_wrap_1.png", 800, 600)
+            .map_err(to_io_error)
+            .expect("Failed to save plot image");
+    }
+
+    #[test]
+    fn facet_wrap_free_scales() {
+        let data = mtcars();
+        let builder = plot(&data).aes(|a| {
+            a.x_continuous("wt");
+            a.y_continuous("mpg");
+        }) + geom_point()
+            + facet_wrap(["am", "vs"]).ncol(4).scales(FacetScales::Free);
+
+        let p = builder
+            .build()
+            .map_err(to_io_error)
+            .expect("Failed to build plot");
+
+        let layout = p.facet.as_ref().unwrap();
+        assert_eq!((layout.nrow, layout.ncol), (1, 4));
+        assert_eq!(layout.panels[3].strips, ["1", "1"]);
+        let domains: Vec<_> = layout.panels.iter().map(|panel| panel.scales.y_continuous.domain()).collect();
+        assert_ne!(domains[0], domains[3]);
+
+        p.save("tests/images/facet_wrap_2.png", 1000, 400)
+            .map_err(to_io_error)
+            .expect("Failed to save plot image");
+    }
+
+    #[test]
+    fn facet_wrap_grid_too_small() {
+        let data = mtcars();
+        let builder = plot(&data).aes(|a| {
+            a.x_continuous("wt");
+            a.y_continuous("mpg");
+        }) + geom_point()
+            + facet_wrap(["gear"]).nrow(1).ncol(2);
+
+        assert!(matches!(builder.build(), Err(PlotError::InvalidFacetParameters { .. })));
+    }
+
+    #[test]
+    fn facet_grid_margins() {
+        let data = mtcars();
+        let builder = plot(&data).aes(|a| {
+            a.x_continuous("wt");
+            a.y_continuous("mpg");
+        }) + geom_point()
+            + facet_grid(["am"], ["cyl"]).margins(true).labeller(Labeller::Both);
+
+        let p = builder
+            .build()
+            .map_err(to_io_error)
+            .expect("Failed to build plot");
+
+        let layout = p.facet.as_ref().unwrap();
+        assert_eq!((layout.nrow, layout.ncol), (3, 4));
+        let strips: Vec<&[String]> = layout.panels[..4].iter().map(|panel| panel.strips.as_slice()).collect();
+        assert_eq!(strips, [["cyl: 4"], ["cyl: 6"], ["cyl: 8"], ["cyl: (all)"]]);
+        let row_strips: Vec<&[String]> = layout
+            .panels
+            .iter()
+            .filter(|panel| panel.col == 3)
+            .map(|panel| panel.row_strips.as_slice())
+            .collect();
+        assert_eq!(row_strips, [["am: 0"], ["am: 1"], ["am: (all)"]]);
+        assert!(layout.panels[5].strips.is_empty() && layout.panels[5].row_strips.is_empty());
+
+        // The corner margin draws every row
+        let corner = &layout.panels[11];
+        assert_eq!(p.layers[corner.layers.start].data(data.as_ref()).len(), 32);
+
+        p.save("tests/images/facet_grid_1.png", 900, 600)
+            .map_err(to_io_error)
+            .expect("Failed to save plot image");
+    }
+
+    #[test]
+    fn facet_grid_free_space() {
+        let data = mtcars();
+        let builder = plot(&data).aes(|a| {
+            a.x_continuous("wt");
+            a.y_continuous("mpg");
+        }) + geom_point()
+            + facet_grid(["cyl"], ["am"])
+                .scales(FacetScales::Free)
+                .space(FacetScales::FreeY)
+                .labeller(Labeller::custom(|var, value| format!("{} = {}", var, value)));
+
+        let p = builder
+            .build()
+            .map_err(to_io_error)
+            .expect("Failed to build plot");
+
+        let layout = p.facet.as_ref().unwrap();
+        assert_eq!((layout.nrow, layout.ncol), (3, 2));
+        assert_eq!(layout.panels[1].row_strips, ["cyl = 4"]);
+
+        // Free y is shared along each row and free x down each column
+        for panel in &layout.panels {
+            let row = &layout.panels[panel.row * 2];
+            let col = &layout.panels[panel.col];
+            assert_eq!(panel.scales.y_continuous.domain(), row.scales.y_continuous.domain());
+            assert_eq!(panel.scales.x_continuous.domain(), col.scales.x_continuous.domain());
+        }
+        assert_ne!(layout.panels[0].scales.y_continuous.domain(), layout.panels[4].scales.y_continuous.domain());
+
+        // Rows are as tall as their y ranges, columns equally wide
+        let (lo, hi) = layout.panels[0].scales.y_continuous.domain().unwrap();
+        assert_eq!(layout.heights[0], hi - lo);
+        assert_eq!(layout.widths, [1.0, 1.0]);
+
+        p.save("tests/images/facet_grid_2.png", 600, 800)
+            .map_err(to_io_error)
+            .expect("Failed to save plot image");
+    }
+
+    #[test]
+    fn facet_wrap_missing_values() {
+        use crate::data::DataSource;
+        use crate::utils::dataframe::DataFrame;
+
+        let mut df = DataFrame::new();
+        df.add_column("x", vec![1.0, 2.0, 3.0, 4.0]);
+        df.add_column("y", vec![2.0, 3.0, 1.0, 4.0]);
+        df.add_optional_column("region", vec![Some("NA"), None, Some("EU"), Some("NA")]);
+        let data: Box<dyn DataSource> = Box::new(df);
+
+        let p = (plot(&data).aes(|a| {
+            a.x_continuous("x");
+            a.y_continuous("y");
+        }) + geom_point()
+            + facet_wrap(["region"]))
+        .build()
+        .map_err(to_io_error)
+        .expect("Failed to build plot");
+
+        // Missing values get their own, last panel apart from the "NA" string
+        let layout = p.facet.as_ref().unwrap();
+        let strips: Vec<&str> = layout.panels.iter().map(|panel| panel.strips[0].as_str()).collect();
+        assert_eq!(strips, ["EU", "NA", "NA"]);
+        let rows: Vec<usize> = layout
+            .panels
+            .iter()
+            .map(|panel| p.layers[panel.layers.start].data(data.as_ref()).len())
+            .collect();
+        assert_eq!(rows, [1, 2, 1]);
+    }
+}
@@ -127,6 +127,7 @@ pub fn geom_bar() -> GeomBarBuilder {
 /// - `Fill`: Bar fill color (can be constant or mapped to data)
 /// - `Color`: Bar border color (can be constant or mapped to data)
 /// - `Alpha`: Bar transparency (0.0 = transparent, 1.0 = opaque)
+#[derive(Clone)]
 pub struct GeomBar {
     area: AreaElement,
 
@@ -1,4 +1,5 @@
 
+use std::any::Any;
 use std::collections::HashMap;
 
 use super::{Geom, RenderContext, AestheticRequirement, DomainConstraint};
@@ -6,12 +7,15 @@ use crate::aesthetics::builder::{
     AesMapBuilder, AlphaContinuousAesBuilder, AlphaDiscreteAesBuilder, FillContinuousAesBuilder,
     FillDiscreteAesBuilder, GroupAesBuilder, XDiscreteAesBuilder, YContinuousAesBuilder,
 };
-use crate::aesthetics::{AesMap, AestheticDomain, AestheticProperty};
+use crate::aesthetics::{AesMap, Aesthetic, AestheticDomain, AestheticProperty};
+use crate::data::{DataSource, VectorIter};
 use crate::error::{PlotError, Result};
 use crate::geom::properties::{Property, PropertyValue, PropertyVector};
 use crate::layer::{Layer, LayerBuilder, LayerBuilderCore};
 use crate::scale::ScaleIdentifier;
 use crate::stat::boxplot::Boxplot;
+use crate::stat::{Stat, StatAestheticRequirements};
+use crate::utils::dataframe::DataFrame;
 use crate::theme::{AreaElement, Color};
 use crate::visuals::LineStyle;
 
@@ -32,6 +36,9 @@ pub struct GeomBoxplotBuilder {
     core: LayerBuilderCore,
     area: AreaElement,
     width: f64,
+    notch: bool,
+    varwidth: bool,
+    outliers: bool,
 }
 
 impl GeomBoxplotBuilder {
@@ -40,6 +47,9 @@ impl GeomBoxplotBuilder {
             core: LayerBuilderCore::default(),
             area: AreaElement::default(),
             width: 0.75,
+            notch: false,
+            varwidth: false,
+            outliers: true,
         }
     }
 
@@ -48,6 +58,25 @@ impl GeomBoxplotBuilder {
         self
     }
 
+    /// Draw notches around the median (median ± 1.58 × IQR / √n)
+    pub fn notch(mut self, notch: bool) -> Self {
+        self.notch = notch;
+        self
+    }
+
+    /// Make box widths proportional to the square root of the group size
+    pub fn varwidth(mut self, varwidth: bool) -> Self {
+        self.varwidth = varwidth;
+        self
+    }
+
+    /// Show outliers (default true); hide them when the same points are
+    /// drawn by another layer, e.g. jittered points
+    pub fn outliers(mut self, outliers: bool) -> Self {
+        self.outliers = outliers;
+        self
+    }
+
     pub fn aes(mut self, closure: impl FnOnce(&mut dyn GeomBoxplotAesBuilderTrait)) -> Self {
         if self.core.stat.is_none() {
             if self.core.aes_builder.is_none() {
@@ -97,11 +126,17 @@ impl LayerBuilder for GeomBoxplotBuilder {
         geom_boxplot.area.overrides(&mut overrides);
 
         geom_boxplot.width = self.width;
-
-        // Make Boxplot the default stat if none specified
-        if self.core.stat.is_none() {
-            self.core.stat = Some(Box::new(Boxplot::default()));
-        }
+        geom_boxplot.notch = self.notch;
+        geom_boxplot.varwidth = self.varwidth;
+        geom_boxplot.outliers = self.outliers;
+
+        // Make Boxplot the default stat if none specified; a supplied stat
+        // has its outlier rows dropped so they don't train the y scale
+        self.core.stat = match self.core.stat.take() {
+            None => Some(Box::new(Boxplot::default().with_outliers(self.outliers))),
+            Some(stat) if !self.outliers => Some(Box::new(WithoutOutliers(stat))),
+            stat => stat,
+        };
 
         LayerBuilderCore::build(
             self.core,
@@ -113,6 +148,47 @@ impl LayerBuilder for GeomBoxplotBuilder {
     }
 }
 
+/// Wraps a boxplot stat, dropping the outlier rows it computes (those
+/// without box statistics)
+struct WithoutOutliers(Box<dyn Stat>);
+
+impl Stat for WithoutOutliers {
+    fn aesthetic_requirements(&self) -> StatAestheticRequirements {
+        self.0.aesthetic_requirements()
+    }
+
+    fn compute_params(
+        &self,
+        data: &dyn DataSource,
+        mapping: &AesMap,
+        aesthetics: &[Aesthetic],
+    ) -> Result<Option<Box<dyn Any>>> {
+        self.0.compute_params(data, mapping, aesthetics)
+    }
+
+    fn compute_group(
+        &self,
+        aesthetics: Vec<Aesthetic>,
+        iters: Vec<VectorIter<'_>>,
+        params: Option<&dyn Any>,
+    ) -> Result<(DataFrame, AesMap)> {
+        self.0.compute_group(aesthetics, iters, params)
+    }
+
+    fn compute(&self, data: &dyn DataSource, mapping: &AesMap) -> Result<(DataFrame, AesMap)> {
+        let (computed, mapping) = self.0.compute(data, mapping)?;
+        let Some(middle) = computed.get("middle").and_then(|middle| middle.iter_float()) else {
+            return Ok((computed, mapping));
+        };
+        let boxes: Vec<usize> = middle
+            .enumerate()
+            .filter(|(_, middle)| !middle.is_nan())
+            .map(|(i, _)| i)
+            .collect();
+        Ok((computed.subset(&boxes), mapping))
+    }
+}
+
 pub fn geom_boxplot() -> GeomBoxplotBuilder {
     GeomBoxplotBuilder::new()
 }
@@ -129,7 +205,10 @@ pub fn geom_boxplot() -> GeomBoxplotBuilder {
 ///
 /// The box spans from Q1 to Q3, with a line at the median.
 /// Whiskers extend to Ymin and Ymax (typically 1.5 * IQR from the box).
-/// Outliers beyond the whiskers are shown as points.
+/// Outliers beyond the whiskers are shown as points unless hidden.
+///
+/// Notched boxes use the NotchLower/NotchUpper aesthetics, and variable
+/// width boxes the RelVarWidth aesthetic, both computed by stat_boxplot.
 ///
 /// # Required Aesthetics
 ///
@@ -150,11 +229,21 @@ pub fn geom_boxplot() -> GeomBoxplotBuilder {
 /// - Color: Box outline and whisker color
 /// - Alpha: Transparency (0.0 = transparent, 1.0 = opaque)
 /// - Size: Line width for box outline and whiskers
+#[derive(Clone)]
 pub struct GeomBoxplot {
     area: AreaElement,
 
     /// Box width (as proportion of spacing between x values)
     pub width: f64,
+
+    /// Whether to draw notches around the median
+    pub notch: bool,
+
+    /// Whether box widths vary with the group size
+    pub varwidth: bool,
+
+    /// Whether to draw outliers
+    pub outliers: bool,
 }
 
 impl GeomBoxplot {
@@ -163,6 +252,9 @@ impl GeomBoxplot {
         Self {
             area: AreaElement::default(),
             width: 0.75,
+            notch: false,
+            varwidth: false,
+            outliers: true,
         }
     }
 }
@@ -332,6 +424,20 @@ impl Geom for GeomBoxplot {
             .remove(&AestheticProperty::Width)
             .map(|v| v.as_floats());
 
+        // Notch and variable width aesthetics are only used when enabled
+        let notch_lower = properties
+            .remove(&AestheticProperty::NotchLower)
+            .map(|v| v.as_floats())
+            .filter(|_| self.notch);
+        let notch_upper = properties
+            .remove(&AestheticProperty::NotchUpper)
+            .map(|v| v.as_floats())
+            .filter(|_| self.notch);
+        let relvarwidth = properties
+            .remove(&AestheticProperty::RelVarWidth)
+            .map(|v| v.as_floats())
+            .filter(|_| self.varwidth);
+
         eprintln!("DEBUG boxplot arrays:");
         eprintln!("  x_values.len() = {}", x_values.len());
         eprintln!("  ymin_values.len() = {}", ymin_values.len());
@@ -353,6 +459,8 @@ impl Geom for GeomBoxplot {
             &linestyle_values,
             x_offset.as_deref(),
             width_factor.as_deref(),
+            notch_lower.as_deref().zip(notch_upper.as_deref()),
+            relvarwidth.as_deref(),
         )
     }
 }
@@ -374,6 +482,8 @@ impl GeomBoxplot {
         linestyle_values: &[LineStyle],
         x_offset: Option<&[f64]>,
         width_factor: Option<&[f64]>,
+        notches: Option<(&[f64], &[f64])>,
+        relvarwidth: Option<&[f64]>,
     ) -> Result<()> {
         if x_values.is_empty() {
             return Ok(());
@@ -403,6 +513,9 @@ impl GeomBoxplot {
             if ymin_values[i].is_nan() || lower_values[i].is_nan() || middle_values[i].is_nan() 
                 || upper_values[i].is_nan() || ymax_values[i].is_nan() {
                 // This is an outlier point - draw it if Y is present
+                if !self.outliers {
+                    continue;
+                }
                 if let Some(y_vals) = y_values {
                     if !y_vals[i].is_nan() {
                         self.draw_outlier(
@@ -424,7 +537,16 @@ impl GeomBoxplot {
             let x_center = x_values[i] + x_offset.map(|offsets| offsets[i] * spacing).unwrap_or(0.0);
             
             // Apply optional width scaling factor from position adjustment
-            let box_width = base_box_width * width_factor.map(|factors| factors[i]).unwrap_or(1.0);
+            let box_width = base_box_width
+                * width_factor.map(|factors| factors[i]).unwrap_or(1.0)
+                * relvarwidth
+                    .map(|widths| widths[i])
+                    .filter(|w| w.is_finite())
+                    .unwrap_or(1.0);
+
+            let notch = notches
+                .map(|(lower, upper)| (lower[i], upper[i]))
+                .filter(|(lower, upper)| lower.is_finite() && upper.is_finite());
 
             self.draw_box(
                 ctx,
@@ -435,6 +557,7 @@ impl GeomBoxplot {
                 middle_values[i],
                 upper_values[i],
                 ymax_values[i],
+                notch,
                 &color_values[i],
                 &fill_values[i],
                 alpha_values[i],
@@ -455,6 +578,7 @@ impl GeomBoxplot {
         middle: f64,
         upper: f64,
         ymax: f64,
+        notch: Option<(f64, f64)>,
         color: &Color,
         fill: &Color,
         alpha: f64,
@@ -477,6 +601,9 @@ impl GeomBoxplot {
         let width = x_right_px - x_left_px;
         let box_height = lower_px - upper_px;  // Y inverted in screen coords
 
+        // Notches narrow the box to half its width at the median
+        let notch_inset = if notch.is_some() { width * 0.25 } else { 0.0 };
+
         // Draw the box (Q1 to Q3)
         let Color(r, g, b, a) = fill;
         ctx.cairo.set_source_rgba(
@@ -485,7 +612,23 @@ impl GeomBoxplot {
             *b as f64 / 255.0,
             *a as f64 / 255.0 * alpha,
         );
-        ctx.cairo.rectangle(x_left_px, upper_px, width, box_height);
+        if let Some((notch_lower, notch_upper)) = notch {
+            let notch_lower_px = ctx.map_y(notch_lower);
+            let notch_upper_px = ctx.map_y(notch_upper);
+            ctx.cairo.move_to(x_left_px, lower_px);
+            ctx.cairo.line_to(x_left_px, notch_lower_px);
+            ctx.cairo.line_to(x_left_px + notch_inset, middle_px);
+            ctx.cairo.line_to(x_left_px, notch_upper_px);
+            ctx.cairo.line_to(x_left_px, upper_px);
+            ctx.cairo.line_to(x_right_px, upper_px);
+            ctx.cairo.line_to(x_right_px, notch_upper_px);
+            ctx.cairo.line_to(x_right_px - notch_inset, middle_px);
+            ctx.cairo.line_to(x_right_px, notch_lower_px);
+            ctx.cairo.line_to(x_right_px, lower_px);
+            ctx.cairo.close_path();
+        } else {
+            ctx.cairo.rectangle(x_left_px, upper_px, width, box_height);
+        }
         ctx.cairo.fill_preserve().map_err(|e| PlotError::RenderError { 
             operation: "fill_preserve".to_string(), 
             message: e.to_string() 
@@ -510,8 +653,8 @@ impl GeomBoxplot {
         })?;
 
         // Draw median line
-        ctx.cairo.move_to(x_left_px, middle_px);
-        ctx.cairo.line_to(x_right_px, middle_px);
+        ctx.cairo.move_to(x_left_px + notch_inset, middle_px);
+        ctx.cairo.line_to(x_right_px - notch_inset, middle_px);
         ctx.cairo.stroke().map_err(|e| PlotError::RenderError { 
             operation: "stroke".to_string(), 
             message: e.to_string() 
@@ -598,6 +741,8 @@ mod tests {
     use crate::plot::plot;
     use crate::position::dodge::Dodge;
     use crate::prelude::AreaElement;
+    use crate::scale::traits::ContinuousDomainScale;
+    use crate::stat::boxplot::Whiskers;
     use crate::theme::color;
     use crate::utils::dataframe::DataFrame;
     use crate::utils::mtcars::mtcars;
@@ -725,4 +870,57 @@ mod tests {
             .map_err(to_io_error)
             .expect("Failed to save plot image");
     }
+
+    #[test]
+    fn hidden_outliers_with_supplied_stat() {
+        let groups = vec!["A", "A", "A", "A", "A", "B", "B", "B", "B", "B"];
+        let values = vec![5.0, 6.0, 7.0, 8.0, 40.0, 10.0, 11.0, 12.0, 13.0, 14.0];
+        let data: Box<dyn DataSource> = Box::new(DataFrame::from_columns(vec![
+            ("group", VectorValue::from(groups)),
+            ("value", VectorValue::from(values)),
+        ]));
+
+        // The supplied stat emits the outlier at 40; it must not reach the y scale
+        let p = (plot(&data).aes(|a| {
+            a.x_discrete("group");
+            a.y_continuous("value");
+        }) + geom_boxplot().outliers(false).stat(Boxplot::default()))
+        .build()
+        .map_err(to_io_error)
+        .expect("Failed to build plot");
+
+        let layer_data = p.layers[0].data(p.data.as_ref());
+        assert_eq!(layer_data.len(), 2);
+        let (_, y_max) = p.scales.y_continuous.domain().unwrap();
+        assert!(y_max < 40.0);
+    }
+
+    #[test]
+    fn basic_boxplot_4() {
+        init_test_logging();
+
+        let data = mtcars();
+
+        // Notched, variable width boxes with percentile whiskers; outliers hidden
+        let builder = plot(&data).aes(|a| {
+            a.x_discrete("cyl");
+            a.y_continuous("mpg");
+        }) + geom_boxplot()
+            .notch(true)
+            .varwidth(true)
+            .outliers(false)
+            .stat(Boxplot::default().with_whiskers(Whiskers::Percentile {
+                lower: 0.05,
+                upper: 0.95,
+            }))
+            .fill(color::LIGHTBLUE);
+
+        let p = builder
+            .build()
+            .map_err(to_io_error)
+            .expect("Failed to build plot");
+        p.save("tests/images/basic_boxplot_4.png", 800, 600)
+            .map_err(to_io_error)
+            .expect("Failed to save plot image");
+    }
 }
@@ -115,6 +115,7 @@ pub fn geom_density() -> GeomDensityBuilder {
 ///
 /// This geom automatically computes the density using the Density stat
 /// and renders it as a line plot.
+#[derive(Clone)]
 pub struct GeomDensity {
     area: AreaElement,
 }
@@ -108,6 +108,7 @@ pub fn geom_errorbar() -> GeomErrorbarBuilder {
 }
 
 /// GeomErrorbar renders vertical error bars with horizontal caps at ymin and ymax
+#[derive(Clone)]
 pub struct GeomErrorbar {
     line: LineElement,
     width: f64,
@@ -356,8 +357,11 @@ impl Geom for GeomErrorbar {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::aesthetics::builder::YContinuousAesBuilder;
+    use crate::layer::LayerBuilderExt;
+    use crate::stat::summary::GroupSummary;
     use crate::theme::color;
-    use crate::theme::traits::LineElement;
+    use crate::theme::traits::{LineElement, PointElement};
     use crate::utils::mtcars::mtcars;
     use crate::{error::to_io_error, plot::plot};
 
@@ -438,4 +442,30 @@ mod tests {
             .map_err(to_io_error)
             .expect("Failed to save plot image");
     }
+
+    #[test]
+    fn basic_errorbar_4() {
+        init_test_logging();
+
+        let data = mtcars();
+
+        // Mean and 95% t confidence interval of mpg for each cylinder count
+        let builder = plot(&data).aes(|a| {
+            a.x_continuous("cyl");
+            a.y_continuous("mpg");
+        }) + crate::geom::point::geom_point().alpha(0.3)
+            + geom_errorbar().width(0.5).stat(GroupSummary::mean_cl_normal())
+            + crate::geom::point::geom_point()
+                .size(4.0)
+                .color(color::RED)
+                .stat(GroupSummary::mean_se());
+
+        let p = builder
+            .build()
+            .map_err(to_io_error)
+            .expect("Failed to build plot");
+        p.save("tests/images/basic_errorbar_4.png", 800, 600)
+            .map_err(to_io_error)
+            .expect("Failed to save plot image");
+    }
 }
@@ -124,6 +124,7 @@ pub fn geom_histogram() -> GeomHistogramBuilder {
 /// - `Fill`: Bar fill color (can be constant or mapped to data)
 /// - `Color`: Bar border color (can be constant or mapped to data)
 /// - `Alpha`: Bar transparency (0.0 = transparent, 1.0 = opaque)
+#[derive(Clone)]
 pub struct GeomHistogram {
     area: AreaElement,
 }
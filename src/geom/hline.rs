@@ -130,6 +130,7 @@ pub fn geom_hline() -> GeomHLineBuilder {
 ///
 /// Y-intercept is provided via the Y aesthetic (mapped or constant).
 /// Draws horizontal lines across the full width of the plot at each y value.
+#[derive(Clone)]
 pub struct GeomHLine {
     /// Fixed y-intercept value(s) for the horizontal line(s)
     pub y_intercept: Option<f64>,
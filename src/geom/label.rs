@@ -148,6 +148,7 @@ pub fn geom_label() -> GeomLabelBuilder {
 }
 
 /// GeomLabel renders text labels with a background box at specified positions
+#[derive(Clone)]
 pub struct GeomLabel {
     /// Text element properties (color, size, alpha, font family/weight/style, hjust, vjust)
     pub text: TextElement,
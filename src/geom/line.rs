@@ -5,6 +5,7 @@ use crate::aesthetics::builder::{
     AesMapBuilder, AlphaContinuousAesBuilder, AlphaDiscreteAesBuilder, ColorContinuousAesBuilder,
     ColorDiscreteAesBuilder, LineStyleAesBuilder, SizeContinuousAesBuilder, SizeDiscreteAesBuilder,
     XContinuousAesBuilder, XDiscreteAesBuilder, YContinuousAesBuilder, YDiscreteAesBuilder,
+    ZContinuousAesBuilder,
 };
 use crate::aesthetics::{AesMap, AestheticDomain, AestheticProperty};
 use crate::error::Result;
@@ -27,6 +28,7 @@ pub trait GeomLineAesBuilderTrait:
     + SizeContinuousAesBuilder
     + SizeDiscreteAesBuilder
     + LineStyleAesBuilder
+    + ZContinuousAesBuilder
 {
 }
 
@@ -105,6 +107,7 @@ pub fn geom_line() -> GeomLineBuilder {
 }
 
 /// GeomLine renders points/scatterplot
+#[derive(Clone)]
 pub struct GeomLine {
     line: LineElement,
 }
@@ -146,6 +149,11 @@ impl GeomLine {
             let (((((x1_norm, y1_norm), color1), size1), alpha1), linestyle1) = &points[i];
             let (((((x2_norm, y2_norm), _), _), _), _) = &points[i + 1];
 
+            // A missing value on either end leaves a gap
+            if ![x1_norm, y1_norm, x2_norm, y2_norm].iter().all(|v| v.is_finite()) {
+                continue;
+            }
+
             // Set color and alpha for this segment
             let Color(r, g, b, a) = *color1;
             ctx.cairo.set_source_rgba(
@@ -239,6 +247,10 @@ impl Geom for GeomLine {
 
     fn apply_scales(&mut self, _scales: &crate::scale::ScaleSet) {}
 
+    fn breaks_at_missing(&self) -> bool {
+        true
+    }
+
     fn render(
         &self,
         ctx: &mut RenderContext,
@@ -271,7 +283,7 @@ impl Geom for GeomLine {
 
         // Create a permutation iterator to sort points by x-value
         let mut indices: Vec<usize> = (0..x_values.len()).collect();
-        indices.sort_by(|&i, &j| x_values[i].partial_cmp(&x_values[j]).unwrap());
+        indices.sort_by(|&i, &j| x_values[i].total_cmp(&x_values[j]));
 
         let x_values = indices.iter().map(|&i| x_values[i]);
         let y_values = indices.iter().map(|&i| y_values[i]);
@@ -296,10 +308,13 @@ impl Geom for GeomLine {
 mod tests {
     use super::*;
     use crate::{
+        data::DataSource,
         error::to_io_error,
+        layer::LayerBuilderExt,
         plot::plot,
+        stat::summary_bin::{Aggregate, SummaryBin},
         theme::{color, traits::LineElement},
-        utils::mtcars::mtcars,
+        utils::{dataframe::DataFrame, mtcars::mtcars},
     };
 
     fn init_test_logging() {
@@ -417,4 +432,52 @@ mod tests {
             .map_err(to_io_error)
             .expect("Failed to save plot image");
     }
+
+    #[test]
+    fn basic_lines_6() {
+        init_test_logging();
+
+        let data = mtcars();
+
+        let builder = plot(&data).aes(|a| {
+            a.x_continuous("wt");
+            a.z_continuous("mpg");
+        }) + geom_line().stat(SummaryBin::with_count(8)).color(color::STEELBLUE)
+            + geom_line()
+                .stat(SummaryBin::with_count(8).aggregate(Aggregate::Median))
+                .color(color::FIREBRICK);
+
+        let p = builder
+            .build()
+            .map_err(to_io_error)
+            .expect("Failed to build plot");
+        p.save("tests/images/basic_lines_6.png", 800, 600)
+            .map_err(to_io_error)
+            .expect("Failed to save plot image");
+    }
+
+    #[test]
+    fn line_gaps_1() {
+        init_test_logging();
+
+        let mut df = DataFrame::new();
+        df.add_column("x", vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0]);
+        df.add_optional_column("y", vec![Some(2.0), Some(3.0), None, Some(4.0), Some(3.5), None, Some(5.0)]);
+        let data: Box<dyn DataSource> = Box::new(df);
+
+        // Missing y values leave gaps instead of joining their neighbours
+        let builder = plot(&data).aes(|a| {
+            a.x_continuous("x");
+            a.y_continuous("y");
+        }) + geom_line().size(2.0);
+
+        let p = builder
+            .build()
+            .map_err(to_io_error)
+            .expect("Failed to build plot");
+        assert_eq!(p.layers[0].data(p.data.as_ref()).len(), 7);
+        p.save("tests/images/line_gaps_1.png", 800, 600)
+            .map_err(to_io_error)
+            .expect("Failed to save plot image");
+    }
 }
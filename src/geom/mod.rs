@@ -18,6 +18,7 @@ pub mod hline;
 pub mod label;
 pub mod line;
 pub mod point;
+pub mod polygon;
 pub mod rect;
 pub mod segment;
 pub mod smooth;
@@ -34,9 +35,10 @@ pub use hline::{GeomHLine, GeomHLineBuilder, geom_hline};
 pub use label::GeomLabel;
 pub use line::GeomLine;
 pub use point::GeomPoint;
+pub use polygon::{GeomPolygon, GeomPolygonBuilder, geom_polygon};
 pub use rect::{GeomRect, GeomRectBuilder, geom_rect};
 pub use segment::{GeomSegment, GeomSegmentBuilder, geom_segment};
-pub use smooth::{GeomSmooth, GeomSmoothBuilder, geom_smooth};
+pub use smooth::{GeomSmooth, GeomSmoothBuilder, geom_smooth, geom_smooth_label};
 pub use text::GeomText;
 pub use vline::{GeomVLine, GeomVLineBuilder, geom_vline};
 
@@ -56,7 +58,19 @@ pub trait GeomBuilder {
     fn build(self) -> Box<dyn Geom>;
 }
 
-pub trait Geom: Send + Sync {
+/// Clones a boxed geom, so layers holding one can be cloned. Implemented
+/// for every geom that is `Clone`.
+pub trait GeomClone {
+    fn box_clone(&self) -> Box<dyn Geom>;
+}
+
+impl<T: Geom + Clone + 'static> GeomClone for T {
+    fn box_clone(&self) -> Box<dyn Geom> {
+        Box::new(self.clone())
+    }
+}
+
+pub trait Geom: GeomClone + Send + Sync {
     /// Get the list of aesthetic requirements for this geom
     fn aesthetic_requirements(&self) -> &'static [AestheticRequirement];
 
@@ -73,6 +87,12 @@ pub trait Geom: Send + Sync {
     /// Apply the provided scales to the geom's aesthetic constants where necessary
     fn apply_scales(&mut self, scales: &ScaleSet);
 
+    /// Whether rows with a missing y are kept, leaving a gap in the geom,
+    /// instead of being dropped before stats run
+    fn breaks_at_missing(&self) -> bool {
+        false
+    }
+
     /// Render the geom with the provided context, data, and aesthetic properties for a single group.
     fn render<'a>(
         &self,
@@ -80,3 +100,9 @@ pub trait Geom: Send + Sync {
         data: HashMap<AestheticProperty, PropertyVector>,
     ) -> Result<(), PlotError>;
 }
+
+impl Clone for Box<dyn Geom> {
+    fn clone(&self) -> Box<dyn Geom> {
+        self.box_clone()
+    }
+}
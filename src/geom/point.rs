@@ -104,6 +104,7 @@ pub fn geom_point() -> GeomPointBuilder {
 }
 
 /// GeomPoint renders points/scatterplot
+#[derive(Clone)]
 pub struct GeomPoint {
     point: PointElement,
 }
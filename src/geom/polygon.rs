@@ -0,0 +1,395 @@
+use std::collections::HashMap;
+
+use super::{Geom, RenderContext, AestheticRequirement, DomainConstraint};
+use crate::aesthetics::builder::{
+    AesMapBuilder, AlphaContinuousAesBuilder, AlphaDiscreteAesBuilder, ColorContinuousAesBuilder,
+    ColorDiscreteAesBuilder, FillContinuousAesBuilder, FillDiscreteAesBuilder, GroupAesBuilder,
+    LineStyleAesBuilder, XContinuousAesBuilder, YContinuousAesBuilder,
+};
+use crate::aesthetics::{AesMap, AestheticDomain, AestheticProperty};
+use crate::error::{PlotError, Result};
+use crate::geom::properties::{Property, PropertyValue, PropertyVector};
+use crate::layer::{Layer, LayerBuilder, LayerBuilderCore};
+use crate::scale::ScaleIdentifier;
+use crate::theme::{AreaElement, Color};
+
+pub trait GeomPolygonAesBuilderTrait:
+    XContinuousAesBuilder
+    + YContinuousAesBuilder
+    + ColorContinuousAesBuilder
+    + ColorDiscreteAesBuilder
+    + FillContinuousAesBuilder
+    + FillDiscreteAesBuilder
+    + AlphaContinuousAesBuilder
+    + AlphaDiscreteAesBuilder
+    + GroupAesBuilder
+    + LineStyleAesBuilder
+{
+}
+
+impl GeomPolygonAesBuilderTrait for AesMapBuilder {}
+
+pub struct GeomPolygonBuilder {
+    core: LayerBuilderCore,
+    area: AreaElement,
+}
+
+impl GeomPolygonBuilder {
+    pub fn new() -> Self {
+        Self {
+            core: LayerBuilderCore::default(),
+            area: AreaElement::default(),
+        }
+    }
+
+    pub fn aes(mut self, closure: impl FnOnce(&mut dyn GeomPolygonAesBuilderTrait)) -> Self {
+        if self.core.stat.is_none() {
+            if self.core.aes_builder.is_none() {
+                self.core.aes_builder = Some(AesMapBuilder::new());
+            }
+            if let Some(ref mut builder) = self.core.aes_builder {
+                closure(builder);
+            }
+        } else {
+            if self.core.after_aes_builder.is_none() {
+                self.core.after_aes_builder = Some(AesMapBuilder::new());
+            }
+            if let Some(ref mut builder) = self.core.after_aes_builder {
+                closure(builder);
+            }
+        }
+        self
+    }
+}
+
+impl crate::theme::traits::AreaElement for GeomPolygonBuilder {
+    fn this(&self) -> &AreaElement {
+        &self.area
+    }
+
+    fn this_mut(&mut self) -> &mut AreaElement {
+        &mut self.area
+    }
+}
+
+impl LayerBuilder for GeomPolygonBuilder {
+    fn this(&self) -> &LayerBuilderCore {
+        &self.core
+    }
+
+    fn this_mut(&mut self) -> &mut LayerBuilderCore {
+        &mut self.core
+    }
+
+    fn build(self: Box<Self>, parent_mapping: &AesMap) -> Result<Layer> {
+        let mut geom_polygon = GeomPolygon::new();
+        geom_polygon.area = self.area;
+
+        let mut overrides = Vec::new();
+        geom_polygon.area.overrides(&mut overrides);
+
+        LayerBuilderCore::build(
+            self.core,
+            parent_mapping,
+            Box::new(geom_polygon),
+            HashMap::new(),
+            &overrides,
+        )
+    }
+}
+
+impl Default for GeomPolygonBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn geom_polygon() -> GeomPolygonBuilder {
+    GeomPolygonBuilder::new()
+}
+
+/// GeomPolygon renders closed polygons
+///
+/// Vertices are joined in data order (not sorted by x) and each group is
+/// drawn as one polygon, filled when the fill colour is not transparent.
+/// Combined with the `Ellipse` stat it draws confidence ellipses.
+#[derive(Clone)]
+pub struct GeomPolygon {
+    area: AreaElement,
+}
+
+impl GeomPolygon {
+    /// Create a new polygon geom with default settings
+    pub fn new() -> Self {
+        Self {
+            area: AreaElement::default(),
+        }
+    }
+}
+
+impl Default for GeomPolygon {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+const AESTHETIC_REQUIREMENTS: [AestheticRequirement; 7] = [
+    AestheticRequirement {
+        property: AestheticProperty::X,
+        required: true,
+        constraint: DomainConstraint::MustBe(AestheticDomain::Continuous),
+    },
+    AestheticRequirement {
+        property: AestheticProperty::Y,
+        required: true,
+        constraint: DomainConstraint::MustBe(AestheticDomain::Continuous),
+    },
+    AestheticRequirement {
+        property: AestheticProperty::Color,
+        required: false,
+        constraint: DomainConstraint::Any,
+    },
+    AestheticRequirement {
+        property: AestheticProperty::Fill,
+        required: false,
+        constraint: DomainConstraint::Any,
+    },
+    AestheticRequirement {
+        property: AestheticProperty::Alpha,
+        required: false,
+        constraint: DomainConstraint::Any,
+    },
+    AestheticRequirement {
+        property: AestheticProperty::Size,
+        required: false,
+        constraint: DomainConstraint::Any,
+    },
+    AestheticRequirement {
+        property: AestheticProperty::Linetype,
+        required: false,
+        constraint: DomainConstraint::MustBe(AestheticDomain::Discrete),
+    },
+];
+
+impl Geom for GeomPolygon {
+    fn aesthetic_requirements(&self) -> &'static [AestheticRequirement] {
+        &AESTHETIC_REQUIREMENTS
+    }
+
+    fn properties(&self) -> HashMap<AestheticProperty, Property> {
+        let mut props = HashMap::new();
+        self.area.properties(&mut props);
+        props
+    }
+
+    fn property_defaults(&self, theme: &crate::theme::Theme) -> HashMap<AestheticProperty, PropertyValue> {
+        let mut defaults = HashMap::new();
+        self.area.defaults("polygon", "area", theme, &mut defaults);
+        defaults
+    }
+
+    fn required_scales(&self) -> Vec<ScaleIdentifier> {
+        vec![ScaleIdentifier::XContinuous, ScaleIdentifier::YContinuous]
+    }
+
+    fn train_scales(&self, _scales: &mut crate::scale::ScaleSet) {
+        // Polygon doesn't need additional scale training
+    }
+
+    fn apply_scales(&mut self, _scales: &crate::scale::ScaleSet) {
+        // No scale-dependent properties to update
+    }
+
+    fn render(
+        &self,
+        ctx: &mut RenderContext,
+        mut properties: HashMap<AestheticProperty, PropertyVector>,
+    ) -> Result<()> {
+        let x_values = properties
+            .remove(&AestheticProperty::X)
+            .ok_or(PlotError::MissingAestheticProperty {
+                aesthetic_property: AestheticProperty::X,
+            })?
+            .as_floats();
+
+        let y_values = properties
+            .remove(&AestheticProperty::Y)
+            .ok_or(PlotError::MissingAestheticProperty {
+                aesthetic_property: AestheticProperty::Y,
+            })?
+            .as_floats();
+
+        let color_values = properties
+            .remove(&AestheticProperty::Color)
+            .ok_or(PlotError::MissingAestheticProperty {
+                aesthetic_property: AestheticProperty::Color,
+            })?
+            .to_color()
+            .as_colors();
+
+        let fill_values = properties
+            .remove(&AestheticProperty::Fill)
+            .ok_or(PlotError::MissingAestheticProperty {
+                aesthetic_property: AestheticProperty::Fill,
+            })?
+            .to_color()
+            .as_colors();
+
+        let alpha_values = properties
+            .remove(&AestheticProperty::Alpha)
+            .ok_or(PlotError::MissingAestheticProperty {
+                aesthetic_property: AestheticProperty::Alpha,
+            })?
+            .as_floats();
+
+        let size_values = properties
+            .remove(&AestheticProperty::Size)
+            .ok_or(PlotError::MissingAestheticProperty {
+                aesthetic_property: AestheticProperty::Size,
+            })?
+            .as_floats();
+
+        let linestyles = properties
+            .remove(&AestheticProperty::Linetype)
+            .ok_or(PlotError::MissingAestheticProperty {
+                aesthetic_property: AestheticProperty::Linetype,
+            })?
+            .as_linestyles();
+
+        let vertices: Vec<(f64, f64)> = x_values
+            .iter()
+            .zip(y_values.iter())
+            .filter(|(x, y)| x.is_finite() && y.is_finite())
+            .map(|(&x, &y)| (ctx.map_x(x), ctx.map_y(y)))
+            .collect();
+
+        if vertices.len() < 2 {
+            return Ok(());
+        }
+
+        // Use first vertex's aesthetics (a polygon is drawn in a single style)
+        let line_color = color_values[0];
+        let fill_color = fill_values[0];
+        let alpha = alpha_values[0];
+        let line_width = size_values[0];
+        let linestyle = &linestyles[0];
+
+        let trace = |ctx: &mut RenderContext| {
+            ctx.cairo.move_to(vertices[0].0, vertices[0].1);
+            for &(x, y) in &vertices[1..] {
+                ctx.cairo.line_to(x, y);
+            }
+            ctx.cairo.close_path();
+        };
+
+        // Fill the interior if fill has alpha > 0
+        let Color(fr, fg, fb, fa) = fill_color;
+        if fa > 0 {
+            ctx.cairo.set_source_rgba(
+                fr as f64 / 255.0,
+                fg as f64 / 255.0,
+                fb as f64 / 255.0,
+                fa as f64 / 255.0 * alpha,
+            );
+            trace(ctx);
+            ctx.cairo.fill().map_err(|e| PlotError::RenderError {
+                operation: "fill polygon".to_string(),
+                message: e.to_string(),
+            })?;
+        }
+
+        // Stroke the outline
+        let Color(r, g, b, a) = line_color;
+        ctx.cairo.set_source_rgba(
+            r as f64 / 255.0,
+            g as f64 / 255.0,
+            b as f64 / 255.0,
+            a as f64 / 255.0 * alpha,
+        );
+        ctx.cairo.set_line_width(line_width);
+        linestyle.apply(ctx.cairo);
+        trace(ctx);
+        ctx.cairo.stroke().map_err(|e| PlotError::RenderError {
+            operation: "stroke polygon".to_string(),
+            message: e.to_string(),
+        })?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{DataSource, VectorValue};
+    use crate::error::to_io_error;
+    use crate::geom::point::geom_point;
+    use crate::layer::LayerBuilderExt;
+    use crate::plot::plot;
+    use crate::stat::ellipse::{Ellipse, EllipseType};
+    use crate::theme::color;
+    use crate::theme::traits::AreaElement;
+    use crate::utils::dataframe::DataFrame;
+
+    fn init_test_logging() {
+        let _ = env_logger::builder()
+            .is_test(true)
+            .filter_level(log::LevelFilter::Debug)
+            .try_init();
+    }
+
+    #[test]
+    fn basic_polygon_1() {
+        init_test_logging();
+
+        let data: Box<dyn DataSource> = Box::new(DataFrame::from_columns(vec![
+            ("x", VectorValue::from(vec![1.0, 3.0, 4.0, 2.5, 0.5])),
+            ("y", VectorValue::from(vec![1.0, 0.5, 2.5, 4.0, 3.0])),
+        ]));
+
+        let builder = plot(&data).aes(|a| {
+            a.x_continuous("x");
+            a.y_continuous("y");
+        }) + geom_polygon()
+            .color(color::STEELBLUE)
+            .fill(color::LIGHTBLUE);
+
+        let p = builder
+            .build()
+            .map_err(to_io_error)
+            .expect("Failed to build plot");
+        p.save("tests/images/basic_polygon_1.png", 800, 600)
+            .map_err(to_io_error)
+            .expect("Failed to save plot image");
+    }
+
+    #[test]
+    fn basic_polygon_2() {
+        init_test_logging();
+
+        let data = crate::utils::mtcars::mtcars();
+
+        let builder = plot(&data).aes(|a| {
+            a.x_continuous("wt");
+            a.y_continuous("mpg");
+            a.color_discrete("cyl");
+        }) + geom_point()
+            + geom_polygon()
+                .stat(Ellipse::default())
+                .aes(|a| {
+                    a.fill_discrete("cyl");
+                })
+                .alpha(0.3)
+            + geom_polygon()
+                .stat(Ellipse::default().kind(EllipseType::Norm))
+                .fill(crate::theme::Color(0, 0, 0, 0));
+
+        let p = builder
+            .build()
+            .map_err(to_io_error)
+            .expect("Failed to build plot");
+        p.save("tests/images/basic_polygon_2.png", 800, 600)
+            .map_err(to_io_error)
+            .expect("Failed to save plot image");
+    }
+}
@@ -4,7 +4,7 @@ use super::{Geom, RenderContext};
 use crate::aesthetics::builder::{
     AesMapBuilder, AlphaContinuousAesBuilder, AlphaDiscreteAesBuilder, FillContinuousAesBuilder,
     FillDiscreteAesBuilder, GroupAesBuilder, XMaxContinuousAesBuilder, XMinContinuousAesBuilder,
-    YMaxContinuousAesBuilder, YMinContinuousAesBuilder,
+    YMaxContinuousAesBuilder, YMinContinuousAesBuilder, ZContinuousAesBuilder,
 };
 use crate::aesthetics::{AesMap, AestheticProperty};
 use crate::error::Result;
@@ -25,6 +25,7 @@ pub trait GeomRectAesBuilderTrait:
     + AlphaContinuousAesBuilder
     + AlphaDiscreteAesBuilder
     + GroupAesBuilder
+    + ZContinuousAesBuilder
 {
 }
 
@@ -110,6 +111,7 @@ pub fn geom_rect() -> GeomRectBuilder {
 ///
 /// Rectangles are defined by their bounding boxes, which must come from data.
 /// Useful for heatmaps, tile plots, and annotating regions.
+#[derive(Clone)]
 pub struct GeomRect {
     area: AreaElement,
 }
@@ -315,11 +317,13 @@ impl Geom for GeomRect {
 mod tests {
     use super::*;
     use crate::{
+        aesthetics::builder::{XContinuousAesBuilder, YContinuousAesBuilder},
         aesthetics::{Aesthetic, AestheticDomain},
         data::DataSource,
         error::to_io_error,
         plot::plot,
         stat::summary::Summary,
+        stat::summary_bin::Summary2d,
         theme::{color, traits::AreaElement},
         utils::{dataframe::DataFrame, mtcars::mtcars},
     };
@@ -400,4 +404,25 @@ mod tests {
             .map_err(to_io_error)
             .expect("Failed to save plot image");
     }
+
+    #[test]
+    fn basic_rects_3() {
+        init_test_logging();
+
+        let data = mtcars();
+
+        let builder = plot(&data).aes(|a| {
+            a.x_continuous("wt");
+            a.y_continuous("hp");
+            a.z_continuous("mpg");
+        }) + geom_rect().stat(Summary2d::with_count(8, 8));
+
+        let p = builder
+            .build()
+            .map_err(to_io_error)
+            .expect("Failed to build plot");
+        p.save("tests/images/basic_rects_3.png", 800, 600)
+            .map_err(to_io_error)
+            .expect("Failed to save plot image");
+    }
 }
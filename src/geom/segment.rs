@@ -121,6 +121,7 @@ pub fn geom_segment() -> GeomSegmentBuilder {
 /// - `Color`: Line color (can be constant or mapped to data)
 /// - `Alpha`: Line transparency (0.0 = transparent, 1.0 = opaque)
 /// - `Size`: Line width in pixels
+#[derive(Clone)]
 pub struct GeomSegment {
     line: LineElement,
 }
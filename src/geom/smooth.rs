@@ -10,9 +10,10 @@ use crate::aesthetics::builder::{
 use crate::aesthetics::{AesMap, AestheticDomain, AestheticProperty};
 use crate::error::{PlotError, Result};
 use crate::geom::properties::{Property, PropertyValue, PropertyVector};
-use crate::layer::{Layer, LayerBuilder, LayerBuilderCore};
+use crate::geom::text::{GeomTextBuilder, geom_text};
+use crate::layer::{Layer, LayerBuilder, LayerBuilderCore, LayerBuilderExt};
 use crate::scale::ScaleIdentifier;
-use crate::stat::smooth::Smooth;
+use crate::stat::smooth::{Smooth, SmoothLabel};
 use crate::theme::{AreaElement, Color};
 use crate::visuals::LineStyle;
 
@@ -113,6 +114,15 @@ pub fn geom_smooth() -> GeomSmoothBuilder {
     GeomSmoothBuilder::new()
 }
 
+/// Text layer labelling each group with its fitted equation and R²
+///
+/// Uses the [`SmoothLabel`] stat; pass a configured one with `.stat(...)`
+/// to match the method of an accompanying `geom_smooth()` layer.
+pub fn geom_smooth_label() -> GeomTextBuilder {
+    use crate::theme::traits::TextElement;
+    geom_text().stat(SmoothLabel::default()).hjust(0.0)
+}
+
 /// GeomSmooth renders fitted curves with confidence intervals
 pub struct GeomSmooth {
     area: AreaElement,
@@ -400,6 +410,7 @@ impl Clone for GeomSmooth {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::stat::smooth::{Interval, Method};
     use crate::theme::color;
     use crate::theme::traits::AreaElement;
     use crate::utils::mtcars::mtcars;
@@ -525,4 +536,40 @@ mod tests {
             .map_err(to_io_error)
             .expect("Failed to save plot image");
     }
+
+    #[test]
+    fn basic_smooth_6() {
+        init_test_logging();
+
+        let data = mtcars();
+
+        // Linear fit per group with prediction intervals and equation labels
+        let smooth = Smooth::new()
+            .method(Method::Lm)
+            .interval(Interval::Prediction);
+        let builder = plot(&data).aes(|a| {
+            a.x_continuous("wt");
+            a.y_continuous("mpg");
+            a.color_discrete("cyl");
+        }) + crate::geom::point::geom_point()
+            + geom_smooth().stat(smooth.clone())
+            + geom_smooth_label().stat(SmoothLabel::new().smooth(smooth));
+
+        let p = builder
+            .build()
+            .map_err(to_io_error)
+            .expect("Failed to build plot");
+
+        let fits = p.smooth_fits();
+        assert_eq!(fits.len(), 3);
+        for fit in &fits {
+            assert_eq!(fit.coefficients.len(), 2);
+            assert!(fit.coefficients[1] < 0.0, "mpg should decrease with wt");
+        }
+        assert_eq!(fits.iter().map(|f| f.n).sum::<usize>(), 32);
+
+        p.save("tests/images/basic_smooth_6.png", 800, 600)
+            .map_err(to_io_error)
+            .expect("Failed to save plot image");
+    }
 }
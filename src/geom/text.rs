@@ -119,6 +119,7 @@ pub fn geom_text() -> GeomTextBuilder {
 }
 
 /// GeomText renders text labels at specified positions
+#[derive(Clone)]
 pub struct GeomText {
     text: TextElement,
     /// Text rotation angle in degrees
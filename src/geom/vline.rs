@@ -125,6 +125,7 @@ pub fn geom_vline() -> GeomVLineBuilder {
 ///
 /// X-intercept is provided via the X aesthetic (mapped or constant).
 /// Draws vertical lines across the full height of the plot at each x value.
+#[derive(Clone)]
 pub struct GeomVLine {
     /// Fixed x-intercept value(s) for the vertical line(s)
     pub x_intercept: Option<f64>,
@@ -1,7 +1,8 @@
 // Guide system for displaying legends and other visual aids
 
+use crate::scale::labels::LabelFormatter;
 use crate::theme::Color;
-use crate::visuals::Shape;
+use crate::visuals::{LineStyle, Shape};
 
 /// Position for a legend
 #[derive(Clone, Debug, PartialEq)]
@@ -75,6 +76,8 @@ pub struct LegendEntry {
     pub size: Option<f64>,
     /// Alpha/transparency (if applicable)
     pub alpha: Option<f64>,
+    /// Line style (if applicable); drawn as a line segment instead of a symbol
+    pub linetype: Option<LineStyle>,
 }
 
 
@@ -115,6 +118,11 @@ impl LegendEntry {
         self.alpha = Some(alpha);
         self
     }
+
+    pub fn linetype(mut self, linetype: LineStyle) -> Self {
+        self.linetype = Some(linetype);
+        self
+    }
 }
 
 /// Type of legend guide
@@ -133,6 +141,15 @@ pub enum LegendType {
         /// Labels for the breaks
         labels: Vec<String>,
     },
+    /// Stepped color bar for a binned scale, one equal block per bin
+    ColorSteps {
+        /// Color of each bin, low to high
+        colors: Vec<Color>,
+        /// Break positions along the bar, from 0 (bottom) to 1 (top)
+        breaks: Vec<f64>,
+        /// Labels for the breaks
+        labels: Vec<String>,
+    },
 }
 
 /// Configuration for a legend guide
@@ -151,6 +168,8 @@ pub struct LegendGuide {
     pub entries: Vec<LegendEntry>,
     /// Type of legend (discrete or continuous color bar)
     pub legend_type: LegendType,
+    /// Formatter for the labels of a legend built from a continuous scale
+    pub label_formatter: Option<Box<dyn LabelFormatter>>,
 }
 
 impl Default for LegendGuide {
@@ -163,6 +182,7 @@ impl Default for LegendGuide {
             nrow: None,
             entries: Vec::new(),
             legend_type: LegendType::Discrete,
+            label_formatter: None,
         }
     }
 }
@@ -201,6 +221,13 @@ impl LegendGuide {
         self.entries.push(entry);
         self
     }
+
+    /// Label a legend built from a continuous scale with this formatter.
+    /// The legend's entries or color bar are still taken from the scale.
+    pub fn label_formatter(mut self, formatter: impl LabelFormatter + 'static) -> Self {
+        self.label_formatter = Some(Box::new(formatter));
+        self
+    }
 }
 
 /// Axis position (used internally to determine X or Y axis)
@@ -286,6 +313,8 @@ pub struct Guides {
     pub size: Option<LegendGuide>,
     /// Alpha legend
     pub alpha: Option<LegendGuide>,
+    /// Linetype legend
+    pub linetype: Option<LegendGuide>,
 }
 
 
@@ -329,6 +358,11 @@ impl Guides {
         self
     }
 
+    pub fn linetype(mut self, guide: LegendGuide) -> Self {
+        self.linetype = Some(guide);
+        self
+    }
+
     /// Suppress the color legend
     pub fn no_color_legend(mut self) -> Self {
         self.color = Some(LegendGuide {
@@ -364,4 +398,13 @@ impl Guides {
         });
         self
     }
+
+    /// Suppress the linetype legend
+    pub fn no_linetype_legend(mut self) -> Self {
+        self.linetype = Some(LegendGuide {
+            position: LegendPosition::None,
+            ..Default::default()
+        });
+        self
+    }
 }
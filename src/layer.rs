@@ -2,22 +2,32 @@
 
 use crate::aesthetics::builder::AesMapBuilder;
 use crate::aesthetics::{AesMap, AesValue, Aesthetic, AestheticDomain, AestheticProperty};
-use crate::data::{DataSource, DiscreteValue, VectorIter};
-use crate::error::Result;
+use crate::data::{
+    Categorical, DataSource, DiscreteValue, GenericVector, TemporalType, VectorIter, VectorValue,
+};
+use crate::error::{DataType, PlotError, Result};
 use crate::geom::properties::{Property, PropertyValue, PropertyVector};
 use crate::geom::{AestheticRequirement, DomainConstraint, Geom};
+use crate::plot::PlotData;
 use crate::position::Position;
-use crate::scale::ScaleSet;
+use crate::scale::discrete::DiscreteAesthetic;
+use crate::scale::traits::ContinuousDomainScale;
+use crate::scale::{OutOfBounds, ScaleSet};
+use crate::utils::dataframe::DataFrame;
 use crate::stat::Stat;
+use crate::stat::smooth::SmoothFit;
 use core::panic;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 pub struct LayerBuilderCore {
     pub stat: Option<Box<dyn Stat>>,
     pub position: Option<Box<dyn Position>>,
-    pub data: Option<Box<dyn DataSource>>,
+    pub data: Option<Arc<dyn DataSource>>,
     pub aes_builder: Option<AesMapBuilder>,
     pub after_aes_builder: Option<AesMapBuilder>,
+    pub inherit_aes: bool,
+    pub na_rm: bool,
 }
 
 impl Default for LayerBuilderCore {
@@ -28,6 +38,8 @@ impl Default for LayerBuilderCore {
             data: None,
             aes_builder: None,
             after_aes_builder: None,
+            inherit_aes: true,
+            na_rm: false,
         }
     }
 }
@@ -41,12 +53,17 @@ impl LayerBuilderCore {
         overrides: &[Aesthetic],
     ) -> Result<Layer> {
         // Mapping is always initialized - either from layer's aes_builder or inherited from parent
+        let empty_mapping = AesMap::new();
+        let parent_mapping = if self.inherit_aes {
+            parent_mapping
+        } else {
+            &empty_mapping
+        };
         let mapping = self
             .aes_builder
             .map(|builder| builder.build(parent_mapping, overrides))
             .unwrap_or_else(|| parent_mapping.clone());
         // after_mapping should NOT inherit from parent - it works with stat output data
-        let empty_mapping = AesMap::new();
         let after_mapping = self
             .after_aes_builder
             .map(|builder| builder.build(&empty_mapping, overrides));
@@ -57,14 +74,15 @@ impl LayerBuilderCore {
             determine_aesthetic_domains(&mapping, &requirements, initial_domains, has_stat)?;
 
         Ok(Layer {
-            stat: self.stat,
-            position: self.position,
+            stat: self.stat.map(Arc::from),
+            position: self.position.map(Arc::from),
             geom,
             data: self.data,
             mapping,
             after_mapping,
             aesthetic_domains,
             aesthetic_group_sentinals: None,
+            na_rm: self.na_rm,
         })
     }
 }
@@ -98,21 +116,45 @@ pub trait LayerBuilderExt: LayerBuilder + Sized {
         self.set_position(Box::new(position));
         self
     }
+
+    /// Draw this layer from its own data instead of the plot's; borrowed
+    /// data is copied
+    fn data<'a>(mut self, data: impl Into<PlotData<'a>>) -> Self {
+        self.this_mut().data = Some(data.into().into_shared());
+        self
+    }
+
+    /// Whether the layer starts from the plot's mapping (default true)
+    fn inherit_aes(mut self, inherit: bool) -> Self {
+        self.this_mut().inherit_aes = inherit;
+        self
+    }
+
+    /// Drop every row with a missing value without a warning, including
+    /// missing levels and line gaps that are otherwise kept
+    fn na_rm(mut self, na_rm: bool) -> Self {
+        self.this_mut().na_rm = na_rm;
+        self
+    }
 }
 
 impl<T: LayerBuilder + Sized> LayerBuilderExt for T {}
 
 /// Layer struct - represents one layer in a plot
 /// Each layer has its own geom, optional data, aesthetic mappings, stat, and position
+///
+/// Stat, position and data are shared between clones; the geom is copied.
+#[derive(Clone)]
 pub struct Layer {
-    pub stat: Option<Box<dyn Stat>>,
-    pub position: Option<Box<dyn Position>>,
+    pub(crate) stat: Option<Arc<dyn Stat>>,
+    pub(crate) position: Option<Arc<dyn Position>>,
     pub geom: Box<dyn Geom>,
-    pub data: Option<Box<dyn DataSource>>,
+    pub(crate) data: Option<Arc<dyn DataSource>>,
     pub mapping: AesMap,
     pub after_mapping: Option<AesMap>,
     pub aesthetic_domains: HashMap<AestheticProperty, AestheticDomain>,
     pub aesthetic_group_sentinals: Option<Vec<(Aesthetic, Vec<DiscreteValue>)>>,
+    pub na_rm: bool,
 }
 
 impl Layer {
@@ -127,24 +169,48 @@ impl Layer {
             after_mapping: None,
             aesthetic_domains: HashMap::new(),
             aesthetic_group_sentinals: None,
+            na_rm: false,
         }
     }
 
     pub fn with_stat(mut self, stat: Box<dyn Stat>) -> Self {
-        self.stat = Some(stat);
+        self.stat = Some(Arc::from(stat));
         self
     }
 
     pub fn with_position(mut self, position: Box<dyn Position>) -> Self {
-        self.position = Some(position);
+        self.position = Some(Arc::from(position));
         self
     }
 
     pub fn with_data(mut self, data: Box<dyn DataSource>) -> Self {
-        self.data = Some(data);
+        self.data = Some(Arc::from(data));
         self
     }
 
+    /// The layer's stat, if it has one
+    pub fn stat(&self) -> Option<&dyn Stat> {
+        self.stat.as_deref()
+    }
+
+    /// The layer's position adjustment, if it has one
+    pub fn position(&self) -> Option<&dyn Position> {
+        self.position.as_deref()
+    }
+
+    /// The layer's own data, if it doesn't use the plot's
+    pub fn own_data(&self) -> Option<&dyn DataSource> {
+        self.data.as_deref()
+    }
+
+    /// Fitted model summaries, if this layer's stat produced smooth diagnostics
+    pub fn smooth_fits(&self) -> Vec<SmoothFit> {
+        self.data
+            .as_ref()
+            .map(|data| SmoothFit::from_data(data.as_ref()))
+            .unwrap_or_default()
+    }
+
     pub fn with_mapping(mut self, mapping: AesMap) -> Self {
         self.mapping = mapping;
         self
@@ -297,23 +363,394 @@ impl Layer {
         subset
     }
 
-    pub fn apply_stat(&mut self, data: &Box<dyn DataSource>) -> Result<()> {
-        // Establish grouping before stat application
-        // Use layer data if available, otherwise use plot data
-        if self.data.is_some() {
-            // We need to work around the borrow checker here
-            // Extract data pointer, then call establish_grouping
-            let data_ptr = self.data.as_ref().unwrap().as_ref() as *const dyn DataSource;
-            unsafe {
-                self.establish_grouping(&*data_ptr);
+    /// Whether the column mapped to `aes` holds dates, date-times or durations
+    pub fn temporal_type(&self, aes: &Aesthetic, parent_data: &dyn DataSource) -> Option<TemporalType> {
+        let Some(AesValue::Column { name }) = self.mapping.get(aes) else {
+            return None;
+        };
+        self.data(parent_data).get(name)?.temporal()
+    }
+
+    /// The ordered levels of a categorical column mapped to `aes`, if any
+    pub fn column_levels(&self, aes: &Aesthetic, parent_data: &dyn DataSource) -> Option<Vec<String>> {
+        let Some(AesValue::Column { name }) = self.mapping.get(aes) else {
+            return None;
+        };
+        self.data(parent_data).get(name)?.levels()
+    }
+
+    /// Parse string x/y columns into numbers on date, datetime and duration scales
+    pub fn apply_temporal(&mut self, scales: &ScaleSet, parent_data: &dyn DataSource) -> Result<()> {
+        let data = self.data(parent_data);
+        let mut parsed: Vec<(String, Vec<f64>)> = Vec::new();
+
+        let positional = [
+            (Aesthetic::X(AestheticDomain::Continuous), &scales.x_continuous),
+            (Aesthetic::Y(AestheticDomain::Continuous), &scales.y_continuous),
+        ];
+        for (aes, scale) in positional {
+            let Some(AesValue::Column { name }) = self.mapping.get(&aes) else {
+                continue;
+            };
+            let Some(column) = data.get(name) else {
+                continue;
+            };
+            let VectorIter::Str(iter) = column.iter() else {
+                continue;
+            };
+            let transform = scale.transform();
+            let values: Vec<Option<f64>> = iter.map(|v| transform.parse(v.trim())).collect();
+            // Leave columns alone when the scale can't read them at all
+            let failed = values.iter().filter(|v| v.is_none()).count();
+            if failed == values.len() {
+                continue;
             }
-        } else {
-            self.establish_grouping(data.as_ref());
+            if failed > 0 {
+                log::warn!("Could not parse {} values in column '{}' as {}", failed, name, transform.name());
+            }
+            let values = values.into_iter().map(|v| v.unwrap_or(f64::NAN)).collect();
+            parsed.push((name.clone(), values));
+        }
+
+        if parsed.is_empty() {
+            return Ok(());
+        }
+
+        let mut converted = DataFrame::new();
+        for name in data.column_names() {
+            let Some(column) = data.get(&name) else {
+                continue;
+            };
+            match parsed.iter().find(|(col, _)| *col == name) {
+                Some((_, values)) => converted.add_column(&name, crate::data::VectorValue::Float(values.clone())),
+                None => converted.add_column(&name, column.to_vector()),
+            }
+        }
+        self.data = Some(Arc::new(converted));
+        Ok(())
+    }
+
+    /// Pair each row's level of a discrete aesthetic with a numeric column
+    ///
+    /// Returns nothing when the aesthetic isn't mapped to a column of this
+    /// layer's data.
+    pub fn levels_with(
+        &self,
+        aes: &Aesthetic,
+        by: &str,
+        parent_data: &dyn DataSource,
+    ) -> Result<Vec<(DiscreteValue, f64)>> {
+        let data = self.data(parent_data);
+        let Some(AesValue::Column { name }) = self.mapping.get(aes) else {
+            return Ok(Vec::new());
+        };
+        let Some(column) = data.get(name) else {
+            return Ok(Vec::new());
+        };
+        let by_column = data.get(by).ok_or_else(|| PlotError::MissingColumn {
+            column: by.to_string(),
+        })?;
+        let values: Vec<f64> = match by_column.iter() {
+            VectorIter::Int(iter) => iter.map(|v| v as f64).collect(),
+            VectorIter::Float(iter) => iter.collect(),
+            _ => {
+                return Err(PlotError::InvalidColumnType {
+                    column: by.to_string(),
+                    expected: DataType::Numeric,
+                });
+            }
+        };
+        Ok(discrete_levels(column)
+            .into_iter()
+            .zip(values)
+            .filter_map(|(level, value)| Some((level?, value)))
+            .collect())
+    }
+
+    /// Handle missing values in the mapped columns before stats see them
+    ///
+    /// Rows with a missing value are dropped, with a warning counting them,
+    /// except that a discrete column keeps them as missing values, which
+    /// take the NA level ([`DiscreteValue::Na`]), and, for geoms that break
+    /// at missing values, rows with a missing y are kept to leave a gap.
+    /// With `na_rm` set every such row is dropped, silently.
+    ///
+    /// Gaps are only left by layers without a stat, whose rows reach the
+    /// geom as they are; stats summarise their rows and drop missing ones.
+    /// A missing x is always dropped, as the geom orders its rows by x and
+    /// such a row has no place in that order.
+    pub fn apply_na(&mut self, parent_data: &dyn DataSource) -> Result<()> {
+        let data = self.data(parent_data);
+        let n = data.len();
+        let mut keep = vec![true; n];
+        let mut filled: Vec<(String, VectorValue)> = Vec::new();
+
+        for (aes, value) in self.mapping.iter() {
+            let AesValue::Column { name } = value else {
+                continue;
+            };
+            let Some(column) = data.get(name) else {
+                continue;
+            };
+            let Some(missing) = column.missing() else {
+                continue;
+            };
+            if filled.iter().any(|(col, _)| col == name) {
+                continue;
+            }
+            let kept = if self.na_rm {
+                None
+            } else if aes.domain() == AestheticDomain::Discrete {
+                discrete_na_levels(column, &missing)
+            } else if *aes == Aesthetic::Y(AestheticDomain::Continuous)
+                && self.stat.is_none()
+                && self.geom.breaks_at_missing()
+            {
+                let values = crate::utils::data::numeric_values(column.iter());
+                (values.len() == n).then(|| {
+                    let gaps = values
+                        .into_iter()
+                        .zip(&missing)
+                        .map(|(value, &missing)| if missing { f64::NAN } else { value })
+                        .collect();
+                    VectorValue::Float(gaps)
+                })
+            } else {
+                None
+            };
+            match kept {
+                Some(values) => filled.push((name.clone(), values)),
+                None => {
+                    for (row, missing) in keep.iter_mut().zip(missing) {
+                        *row &= !missing;
+                    }
+                }
+            }
+        }
+
+        let indices: Vec<usize> = (0..n).filter(|&i| keep[i]).collect();
+        if indices.len() == n && filled.is_empty() {
+            return Ok(());
+        }
+        if indices.len() < n && !self.na_rm {
+            log::warn!("Removed {} rows containing missing values", n - indices.len());
+        }
+
+        let mut filtered = DataFrame::new();
+        for name in data.column_names() {
+            let Some(column) = data.get(&name) else {
+                continue;
+            };
+            if let Some((_, values)) = filled.iter().find(|(col, _)| *col == name) {
+                filtered.add_column(&name, values.subset(&indices));
+                continue;
+            }
+            // Other columns keep track of their missing values
+            let values = column.to_vector().subset(&indices);
+            if column.null_count() > 0 {
+                let validity = indices.iter().map(|&i| !column.is_null(i)).collect();
+                filtered.add_column_with_validity(&name, values, validity);
+            } else {
+                filtered.add_column(&name, values);
+            }
+        }
+        self.data = Some(Arc::new(filtered));
+        Ok(())
+    }
+
+    /// A copy of the layer drawing only the given rows of its data
+    pub fn subset(&self, rows: &[usize], parent_data: &dyn DataSource) -> Layer {
+        let data = self.data(parent_data);
+        let mut subset = DataFrame::new();
+        for name in data.column_names() {
+            let Some(column) = data.get(&name) else {
+                continue;
+            };
+            let values = column.to_vector().subset(rows);
+            if column.null_count() > 0 {
+                let validity = rows.iter().map(|&i| !column.is_null(i)).collect();
+                subset.add_column_with_validity(&name, values, validity);
+            } else {
+                subset.add_column(&name, values);
+            }
+        }
+        let mut layer = self.clone();
+        layer.data = Some(Arc::new(subset));
+        layer
+    }
+
+    /// Apply the x and y scales' out-of-bounds policy to the layer's input
+    /// data, so stats only see the data within the scale limits. Censored
+    /// rows are dropped and squished values are clamped to the limits.
+    pub fn apply_oob(&mut self, scales: &ScaleSet, parent_data: &dyn DataSource) -> Result<()> {
+        let data = self.data(parent_data);
+        let n = data.len();
+        let mut keep = vec![true; n];
+        let mut squished: Vec<(String, Vec<f64>)> = Vec::new();
+
+        let positional = [
+            (Aesthetic::X(AestheticDomain::Continuous), &scales.x_continuous),
+            (Aesthetic::Y(AestheticDomain::Continuous), &scales.y_continuous),
+        ];
+        for (aes, scale) in positional {
+            let (Some(min), Some(max)) = scale.limits() else {
+                continue;
+            };
+            if scale.oob() == OutOfBounds::Keep {
+                continue;
+            }
+            let Some(AesValue::Column { name }) = self.mapping.get(&aes) else {
+                continue;
+            };
+            let Some(column) = data.get(name) else {
+                continue;
+            };
+            let values: Vec<f64> = match column.iter() {
+                VectorIter::Int(iter) => iter.map(|v| v as f64).collect(),
+                VectorIter::Float(iter) => iter.collect(),
+                _ => continue,
+            };
+
+            let transform = scale.transform();
+            let limits = (transform.transform(min), transform.transform(max));
+            let mut clamped = values.clone();
+            let mut changed = false;
+            for (i, &v) in values.iter().enumerate() {
+                let t = transform.transform(v);
+                if !t.is_finite() {
+                    continue;
+                }
+                match scale.oob().apply(t, limits) {
+                    None => keep[i] = false,
+                    Some(s) if s != t => {
+                        clamped[i] = transform.inverse(s);
+                        changed = true;
+                    }
+                    Some(_) => {}
+                }
+            }
+            if changed {
+                squished.push((name.clone(), clamped));
+            }
+        }
+
+        // Rows whose level is outside a discrete scale's limits are dropped
+        for scale_aes in DiscreteAesthetic::ALL {
+            let categories = scale_aes.categories(scales);
+            if categories.limits().is_none() {
+                continue;
+            }
+            let Some(AesValue::Column { name }) = self.mapping.get(&scale_aes.aesthetic()) else {
+                continue;
+            };
+            let Some(column) = data.get(name) else {
+                continue;
+            };
+            for (i, level) in discrete_levels(column).into_iter().enumerate() {
+                if level.is_some_and(|level| !categories.contains_value(&level)) {
+                    keep[i] = false;
+                }
+            }
+        }
+
+        let indices: Vec<usize> = (0..n).filter(|&i| keep[i]).collect();
+        if indices.len() == n && squished.is_empty() {
+            return Ok(());
+        }
+        if indices.len() < n {
+            log::warn!("Removed {} rows outside the scale limits", n - indices.len());
         }
 
+        let mut filtered = DataFrame::new();
+        for name in data.column_names() {
+            let Some(column) = data.get(&name) else {
+                continue;
+            };
+            let values = match squished.iter().find(|(col, _)| *col == name) {
+                Some((_, clamped)) => crate::data::VectorValue::Float(clamped.clone()),
+                None => column.to_vector(),
+            };
+            filtered.add_column(&name, values.subset(&indices));
+        }
+        self.data = Some(Arc::new(filtered));
+        Ok(())
+    }
+
+    /// The numeric values of the column mapped to a continuous x or y
+    fn positional_values(&self, aes: &Aesthetic, parent_data: &dyn DataSource) -> Option<(String, Vec<f64>)> {
+        let Some(AesValue::Column { name }) = self.mapping.get(aes) else {
+            return None;
+        };
+        let column = self.data(parent_data).get(name)?;
+        let values = crate::utils::data::numeric_values(column.iter());
+        if values.is_empty() {
+            return None;
+        }
+        Some((name.clone(), values))
+    }
+
+    /// Record x and y values on binned scales, which may place their bins
+    /// from the data
+    pub fn observe_bins(&self, scales: &mut ScaleSet, parent_data: &dyn DataSource) {
+        let positional = [
+            (Aesthetic::X(AestheticDomain::Continuous), &mut scales.x_continuous),
+            (Aesthetic::Y(AestheticDomain::Continuous), &mut scales.y_continuous),
+        ];
+        for (aes, scale) in positional {
+            if scale.bins().is_none() {
+                continue;
+            }
+            if let Some((_, values)) = self.positional_values(&aes, parent_data) {
+                scale.observe_bins(&values);
+            }
+        }
+    }
+
+    /// Move x and y values on binned scales to the middle of their bin, so
+    /// stats see one value per bin
+    pub fn apply_bins(&mut self, scales: &ScaleSet, parent_data: &dyn DataSource) -> Result<()> {
+        let mut binned: Vec<(String, Vec<f64>)> = Vec::new();
+        let positional = [
+            (Aesthetic::X(AestheticDomain::Continuous), &scales.x_continuous),
+            (Aesthetic::Y(AestheticDomain::Continuous), &scales.y_continuous),
+        ];
+        for (aes, scale) in positional {
+            if scale.bins().is_none_or(|bins| bins.is_empty()) {
+                continue;
+            }
+            let Some((name, values)) = self.positional_values(&aes, parent_data) else {
+                continue;
+            };
+            let centers = values.iter().map(|&v| scale.bin_center(v).unwrap_or(v)).collect();
+            binned.push((name, centers));
+        }
+        if binned.is_empty() {
+            return Ok(());
+        }
+
+        let data = self.data(parent_data);
+        let mut replaced = DataFrame::new();
+        for name in data.column_names() {
+            let Some(column) = data.get(&name) else {
+                continue;
+            };
+            match binned.iter().find(|(col, _)| *col == name) {
+                Some((_, centers)) => replaced.add_column(&name, centers.clone()),
+                None => replaced.add_column(&name, column.to_vector()),
+            }
+        }
+        self.data = Some(Arc::new(replaced));
+        Ok(())
+    }
+
+    pub fn apply_stat(&mut self, parent_data: &dyn DataSource) -> Result<()> {
+        // Establish grouping before stat application
+        // Use layer data if available, otherwise use plot data
+        self.establish_grouping(parent_data);
+
         if let Some(stat) = &self.stat {
             // Stat transforms data and produces a mapping
-            let (new_data, stat_mapping) = stat.compute(data.as_ref(), &self.mapping)?;
+            let (new_data, stat_mapping) = stat.compute(self.data(parent_data), &self.mapping)?;
 
             // DEBUG: Check row count after stat
             if let Some(x_col) = new_data.get("x") {
@@ -340,25 +777,16 @@ impl Layer {
                 }
             }
 
-            self.data = Some(Box::new(new_data));
+            self.data = Some(Arc::new(new_data));
             self.mapping = final_mapping;
         }
         Ok(())
     }
 
-    pub fn apply_position(&mut self, data: &Box<dyn DataSource>) -> Result<()> {
+    pub fn apply_position(&mut self, parent_data: &dyn DataSource) -> Result<()> {
         // Establish grouping before position application (in case stat changed the mapping)
         // Use layer data if available, otherwise use plot data
-        if self.data.is_some() {
-            // We need to work around the borrow checker here
-            // Extract data pointer, then call establish_grouping
-            let data_ptr = self.data.as_ref().unwrap().as_ref() as *const dyn DataSource;
-            unsafe {
-                self.establish_grouping(&*data_ptr);
-            }
-        } else {
-            self.establish_grouping(data.as_ref());
-        }
+        self.establish_grouping(parent_data);
 
         log::debug!(
             "apply_position - effective_mapping contains: {:?}",
@@ -467,6 +895,10 @@ impl Layer {
                 AestheticProperty::Lower => Aesthetic::Lower,
                 AestheticProperty::Middle => Aesthetic::Middle,
                 AestheticProperty::Upper => Aesthetic::Upper,
+                AestheticProperty::NotchLower => Aesthetic::NotchLower,
+                AestheticProperty::NotchUpper => Aesthetic::NotchUpper,
+                AestheticProperty::RelVarWidth => Aesthetic::RelVarWidth,
+                AestheticProperty::Z => Aesthetic::Z,
                 AestheticProperty::XBegin => Aesthetic::XBegin,
                 AestheticProperty::XEnd => Aesthetic::XEnd,
                 AestheticProperty::YBegin => Aesthetic::YBegin,
@@ -527,6 +959,17 @@ impl Layer {
         None
     }
 
+    // Position of each level of a categorical column mapped to `aes`, so
+    // groups follow the column's level order rather than sorted order
+    fn level_ranks(&self, aes: &Aesthetic, parent_data: &dyn DataSource) -> HashMap<DiscreteValue, usize> {
+        self.column_levels(aes, parent_data)
+            .unwrap_or_default()
+            .into_iter()
+            .enumerate()
+            .map(|(i, level)| (DiscreteValue::from(level), i))
+            .collect()
+    }
+
     fn establish_grouping(&mut self, parent_data: &dyn DataSource) {
         let data = self.data(parent_data);
 
@@ -552,8 +995,9 @@ impl Layer {
                 .get_iter_discrete(aes, data)
                 .unwrap()
                 .collect::<Vec<_>>();
+            let ranks = self.level_ranks(aes, parent_data);
             let mut permutation: Vec<usize> = (0..group_values.len()).collect();
-            permutation.sort_by_key(|&i| &group_values[i]);
+            permutation.sort_by_key(|&i| (level_rank(&ranks, &group_values[i]), &group_values[i]));
 
             let mut group_index = 0;
             let mut group_sentinals: Vec<(Aesthetic, Vec<DiscreteValue>)> = grouping_aesthetics
@@ -599,8 +1043,19 @@ impl Layer {
             }
         }
 
+        let ranks: Vec<_> = grouping_aesthetics
+            .iter()
+            .map(|aes| self.level_ranks(aes, parent_data))
+            .collect();
+        let sort_key = |i: usize| {
+            group_values[i]
+                .iter()
+                .zip(&ranks)
+                .map(|(v, r)| (level_rank(r, v), v))
+                .collect::<Vec<_>>()
+        };
         let mut permutation: Vec<usize> = (0..group_values.len()).collect();
-        permutation.sort_by_key(|&i| &group_values[i]);
+        permutation.sort_by_cached_key(|&i| sort_key(i));
 
         let mut group_index = 0;
         let mut group_sentinals: Vec<(Aesthetic, Vec<DiscreteValue>)> = grouping_aesthetics
@@ -707,3 +1162,44 @@ pub fn determine_aesthetic_domains(
 
     Ok(domains)
 }
+
+/// The discrete level of each value in a column, with missing values as
+/// [`DiscreteValue::Na`]; floats have none
+fn discrete_levels(column: &dyn GenericVector) -> Vec<Option<DiscreteValue>> {
+    match column.iter_discrete() {
+        Some(values) => values.map(Some).collect(),
+        None => vec![None; column.len()],
+    }
+}
+
+/// A discrete column as a categorical whose missing rows have no level,
+/// so they read as [`DiscreteValue::Na`]. A categorical column keeps its
+/// levels; other columns take their values, sorted, as levels. Float
+/// columns have no discrete values and give None.
+fn discrete_na_levels(column: &dyn GenericVector, missing: &[bool]) -> Option<VectorValue> {
+    let values: Vec<DiscreteValue> = column.iter_discrete()?.collect();
+    let levels = match column.levels() {
+        Some(levels) => levels,
+        None => {
+            let mut levels: Vec<&DiscreteValue> = values
+                .iter()
+                .zip(missing)
+                .filter(|(_, missing)| !**missing)
+                .map(|(value, _)| value)
+                .collect();
+            levels.sort();
+            levels.dedup();
+            levels.into_iter().map(DiscreteValue::to_string).collect()
+        }
+    };
+    let labels = values
+        .iter()
+        .zip(missing)
+        .map(|(value, &missing)| (!missing).then(|| value.to_string()));
+    Some(VectorValue::Categorical(Categorical::with_missing(labels, levels)))
+}
+
+/// Sort rank of a group value; values that are not levels sort after levels
+fn level_rank(ranks: &HashMap<DiscreteValue, usize>, value: &DiscreteValue) -> usize {
+    ranks.get(value).copied().unwrap_or(usize::MAX)
+}
@@ -1,6 +1,8 @@
 pub mod aesthetics;
+pub mod coord;
 pub mod data;
 pub mod error;
+pub mod facet;
 pub mod geom;
 pub mod guide;
 pub mod layer;
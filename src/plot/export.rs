@@ -2,6 +2,7 @@
 
 use crate::{data::DataSource, scale::ScaleSet};
 use crate::error::PlotError;
+use crate::facet::FacetLayout;
 use crate::guide::Guides;
 use crate::layer::Layer;
 use crate::plot::render;
@@ -31,6 +32,7 @@ pub fn save(
     path: impl AsRef<Path>,
     layers: &[Layer],
     scales: &ScaleSet,
+    facet: Option<&FacetLayout>,
     theme: &Theme,
     guides: &Guides,
     title: Option<&String>,
@@ -47,8 +49,9 @@ pub fn save(
 
     match extension.to_lowercase().as_str() {
         "png" => {
-            let surface =
-                render::render(layers, scales, theme, guides, title, data, plot_mapping, width, height)?;
+            let surface = render::render(
+                layers, scales, facet, theme, guides, title, data, plot_mapping, width, height,
+            )?;
             let mut file = std::fs::File::create(path)
                 .map_err(|e| PlotError::io_error("create file", e))?;
             surface
@@ -64,7 +67,7 @@ pub fn save(
             let mut ctx = Context::new(&surface)
                 .map_err(|e| PlotError::render_error("create context", format!("{}", e)))?;
             render::render_with_context(
-                &mut ctx, layers, scales, theme, guides, title, data, plot_mapping, width, height,
+                &mut ctx, layers, scales, facet, theme, guides, title, data, plot_mapping, width, height,
             )?;
             surface.finish();
         }
@@ -76,7 +79,7 @@ pub fn save(
             let mut ctx = Context::new(&surface)
                 .map_err(|e| PlotError::render_error("create context", format!("{}", e)))?;
             render::render_with_context(
-                &mut ctx, layers, scales, theme, guides, title, data, plot_mapping, width, height,
+                &mut ctx, layers, scales, facet, theme, guides, title, data, plot_mapping, width, height,
             )?;
             surface.finish();
         }
@@ -3,23 +3,92 @@
 mod export;
 mod render;
 
-use crate::aesthetics::{AesMap, AestheticProperty};
-use crate::data::DataSource;
+use crate::aesthetics::{AesMap, Aesthetic, AestheticDomain, AestheticProperty};
+use crate::coord::CoordCartesian;
+use crate::data::{DataSource, TemporalType, VectorIter};
 use crate::error::PlotError;
+use crate::facet::{Facet, FacetGrid, FacetLayout, FacetWrap};
 use crate::aesthetics::builder::AesMapBuilder;
 use crate::guide::{AxisGuide, Guides};
 use crate::layer::{Layer, LayerBuilder};
-use crate::scale::{ScaleSet, ContinuousScaleBuilder};
+use crate::scale::discrete::{self, DiscreteScaleBuilder};
+use crate::scale::identity::IdentityScaleBuilder;
+use crate::scale::manual::{ManualScale, ManualScaleBuilder};
+use crate::scale::traits::ScaleBase;
+use crate::scale::{
+    ContinuousScaleBuilder, ScaleAesthetic, ScaleSet, scale_x_date, scale_x_datetime,
+    scale_x_duration, scale_y_date, scale_y_datetime, scale_y_duration,
+};
+use crate::stat::smooth::SmoothFit;
 use crate::theme::Theme;
 use cairo::ImageSurface;
 use std::ops::Add;
 use std::path::Path;
+use std::sync::Arc;
+
+/// Default data of a plot, borrowed from the caller or shared
+///
+/// A plot over shared data is `Plot<'static>`, so it can be returned from
+/// functions, stored and sent to a render thread.
+#[derive(Clone)]
+pub enum PlotData<'a> {
+    Borrowed(&'a dyn DataSource),
+    Shared(Arc<dyn DataSource>),
+}
+
+impl PlotData<'_> {
+    /// The data as a shared source, copying it if borrowed
+    pub fn into_shared(self) -> Arc<dyn DataSource> {
+        match self {
+            PlotData::Borrowed(data) => Arc::from(data.clone_box()),
+            PlotData::Shared(data) => data,
+        }
+    }
+}
+
+impl<'a> AsRef<dyn DataSource + 'a> for PlotData<'a> {
+    fn as_ref(&self) -> &(dyn DataSource + 'a) {
+        match self {
+            PlotData::Borrowed(data) => *data,
+            PlotData::Shared(data) => data.as_ref(),
+        }
+    }
+}
+
+impl<'a> From<&'a Box<dyn DataSource>> for PlotData<'a> {
+    fn from(data: &'a Box<dyn DataSource>) -> Self {
+        PlotData::Borrowed(data.as_ref())
+    }
+}
+
+impl From<Box<dyn DataSource>> for PlotData<'static> {
+    fn from(data: Box<dyn DataSource>) -> Self {
+        PlotData::Shared(Arc::from(data))
+    }
+}
+
+impl From<Arc<dyn DataSource>> for PlotData<'static> {
+    fn from(data: Arc<dyn DataSource>) -> Self {
+        PlotData::Shared(data)
+    }
+}
+
+impl<D: DataSource + 'static> From<D> for PlotData<'static> {
+    fn from(data: D) -> Self {
+        PlotData::Shared(Arc::new(data))
+    }
+}
 
 pub struct PlotBuilder<'a> {
-    data: &'a Box<dyn DataSource>,
+    data: PlotData<'a>,
     mapping: AesMap,
     layers: Vec<Box<dyn LayerBuilder>>,
     scales: Vec<ContinuousScaleBuilder>,
+    manual_scales: Vec<ManualScale>,
+    identity_scales: Vec<IdentityScaleBuilder>,
+    discrete_scales: Vec<DiscreteScaleBuilder>,
+    coord: Option<CoordCartesian>,
+    facet: Option<Facet>,
     guides: Guides,
     theme: Theme,
     title: Option<String>,
@@ -35,6 +104,11 @@ impl<'a> PlotBuilder<'a> {
             mapping: builder.build(&empty, &[]),
             layers: self.layers,
             scales: self.scales,
+            manual_scales: self.manual_scales,
+            identity_scales: self.identity_scales,
+            discrete_scales: self.discrete_scales,
+            coord: self.coord,
+            facet: self.facet,
             guides: self.guides,
             theme: self.theme,
             title: self.title,
@@ -65,6 +139,36 @@ impl<'a> PlotBuilder<'a> {
         self
     }
 
+    /// Add a manual discrete scale
+    pub fn add_manual_scale(mut self, scale: impl Into<ManualScale>) -> Self {
+        self.manual_scales.push(scale.into());
+        self
+    }
+
+    /// Add an identity scale
+    pub fn add_identity_scale(mut self, scale: IdentityScaleBuilder) -> Self {
+        self.identity_scales.push(scale);
+        self
+    }
+
+    /// Add a discrete scale configuration
+    pub fn add_discrete_scale(mut self, scale: DiscreteScaleBuilder) -> Self {
+        self.discrete_scales.push(scale);
+        self
+    }
+
+    /// Set the coordinate system (e.g. to zoom without dropping data)
+    pub fn coord(mut self, coord: CoordCartesian) -> Self {
+        self.coord = Some(coord);
+        self
+    }
+
+    /// Split the plot into panels (small multiples)
+    pub fn facet(mut self, facet: impl Into<Facet>) -> Self {
+        self.facet = Some(facet.into());
+        self
+    }
+
     pub fn build(self) -> Result<Plot<'a>, PlotError> {
         let mut layers: Vec<Layer> = self
             .layers
@@ -73,15 +177,106 @@ impl<'a> PlotBuilder<'a> {
             .collect();
 
         let mut scales = ScaleSet::default();
-        
+
+        // Date, date-time and duration columns get a matching scale unless one was configured
+        let mut scale_builders = self.scales;
+        let positional = [
+            (ScaleAesthetic::XContinuous, Aesthetic::X(AestheticDomain::Continuous)),
+            (ScaleAesthetic::YContinuous, Aesthetic::Y(AestheticDomain::Continuous)),
+        ];
+        for (scale_aes, aes) in positional {
+            if scale_builders.iter().any(|builder| builder.aesthetic == scale_aes) {
+                continue;
+            }
+            let temporal = layers
+                .iter()
+                .find_map(|layer| layer.temporal_type(&aes, self.data.as_ref()));
+            let builder = match (temporal, scale_aes) {
+                (Some(TemporalType::Date), ScaleAesthetic::XContinuous) => scale_x_date(),
+                (Some(TemporalType::Date), _) => scale_y_date(),
+                (Some(TemporalType::DateTime { tz }), scale_aes) => {
+                    let builder = if scale_aes == ScaleAesthetic::XContinuous {
+                        scale_x_datetime()
+                    } else {
+                        scale_y_datetime()
+                    };
+                    match tz {
+                        Some(tz) => builder.timezone(tz),
+                        None => builder,
+                    }
+                }
+                (Some(TemporalType::Duration), ScaleAesthetic::XContinuous) => scale_x_duration(),
+                (Some(TemporalType::Duration), _) => scale_y_duration(),
+                (None, _) => continue,
+            };
+            scale_builders.push(builder);
+        }
+
         // Apply scale builders before training
-        for scale_builder in self.scales {
-            scale_builder.apply_to(&mut scales);
+        for scale_builder in scale_builders {
+            scale_builder.apply_to(&mut scales)?;
+        }
+        for manual_scale in self.manual_scales {
+            manual_scale.apply_to(&mut scales)?;
+        }
+        for identity_scale in self.identity_scales {
+            identity_scale.apply_to(&mut scales);
+        }
+
+        // Read string dates and durations on temporal scales
+        for layer in &mut layers {
+            layer.apply_temporal(&scales, self.data.as_ref())?;
+        }
+
+        // Drop rows with missing values, or keep them as levels and gaps
+        for layer in &mut layers {
+            layer.apply_na(self.data.as_ref())?;
+        }
+
+        // Follow the level order of categorical columns, including those
+        // holding missing values, unless a discrete builder overrides it
+        discrete::apply_levels(&self.discrete_scales, &layers, &mut scales, self.data.as_ref());
+        for discrete_scale in &self.discrete_scales {
+            discrete_scale.apply_to(&mut scales)?;
+        }
+
+        // Order discrete levels by a summary of another column, before stats run
+        for discrete_scale in &self.discrete_scales {
+            discrete_scale.apply_reorder(&layers, &mut scales, self.data.as_ref())?;
+        }
+
+        // Censor or squish x/y data outside the scale limits before stats see it
+        for layer in &mut layers {
+            layer.apply_oob(&scales, self.data.as_ref())?;
+        }
+
+        // Cut x and y on binned scales into bins before stats count them
+        for layer in &layers {
+            layer.observe_bins(&mut scales, self.data.as_ref());
+        }
+        for scale in [&mut scales.x_continuous, &mut scales.y_continuous] {
+            scale.compute_bins();
+            // The axis spans whole bins, not just the centers
+            if let Some(bins) = scale.bins().filter(|bins| !bins.is_empty()) {
+                let edges: Vec<f64> = bins.edges().iter().map(|&e| scale.transform.inverse(e)).collect();
+                scale.train(VectorIter::Float(Box::new(edges.into_iter())));
+            }
+        }
+        for layer in &mut layers {
+            layer.apply_bins(&scales, self.data.as_ref())?;
+        }
+
+        // Split each layer into one per panel, so stats and positions run per panel
+        let mut facet_layout: Option<FacetLayout> = None;
+        if let Some(facet) = &self.facet {
+            let (split, layout) = facet.split(layers, self.data.as_ref())?;
+            layers = split;
+            facet_layout = Some(layout);
         }
 
         // Step 1: Apply stat transformations to each layer
         for layer in &mut layers {
-            layer.apply_stat(&self.data)?;
+            layer.apply_stat(self.data.as_ref())?;
         }
 
         // Step 1b: Resolve column references to materialized vectors
@@ -93,10 +288,11 @@ impl<'a> PlotBuilder<'a> {
 
         // Step 2: Apply position adjustments across layers
         for layer in &mut layers {
-            layer.apply_position(&self.data)?;
+            layer.apply_position(self.data.as_ref())?;
         }
 
         // Step 3: Train scales on all layer data
+        let untrained = facet_layout.as_ref().map(|_| scales.clone());
         for layer in &mut layers {
             layer.train_scales(&mut scales, self.data.as_ref())?;
         }
@@ -106,9 +302,35 @@ impl<'a> PlotBuilder<'a> {
             layer.geom.train_scales(&mut scales);
         }
 
+        for discrete_scale in &self.discrete_scales {
+            discrete_scale.check_labels(&scales)?;
+        }
+
+        // Panels share the trained scales, or retrain them along free axes
+        if let (Some(layout), Some(untrained)) = (&mut facet_layout, &untrained) {
+            layout.train(&mut layers, untrained, &scales, self.data.as_ref())?;
+        }
+
+        // Step 3c: Zoom the view; this crops the domain without dropping data
+        if let Some(coord) = &self.coord {
+            coord.apply(&mut scales)?;
+            for panel in facet_layout.iter_mut().flat_map(|layout| &mut layout.panels) {
+                coord.apply(&mut panel.scales)?;
+            }
+        }
+
+        // Size grid rows and columns by the zoomed ranges of their scales
+        if let (Some(layout), Some(facet)) = (&mut facet_layout, &self.facet) {
+            layout.apply_space(facet.space());
+        }
+
         // Step 4: Apply scales to convert data to visual coordinates
-        for layer in &mut layers {
-            layer.apply_scales(&scales, self.data.as_ref())?;
+        for (i, layer) in layers.iter_mut().enumerate() {
+            let layer_scales = facet_layout
+                .as_ref()
+                .and_then(|layout| layout.layer_scales(i))
+                .unwrap_or(&scales);
+            layer.apply_scales(layer_scales, self.data.as_ref())?;
             
             // DEBUG: Check data after apply_scales
             if let Some(data) = &layer.data {
@@ -133,6 +355,11 @@ impl<'a> PlotBuilder<'a> {
 
         scales.x_continuous.compute_breaks(5);
         scales.y_continuous.compute_breaks(5);
+        scales.alpha_continuous.compute_breaks(4);
+        for panel in facet_layout.iter_mut().flat_map(|layout| &mut layout.panels) {
+            panel.scales.x_continuous.compute_breaks(5);
+            panel.scales.y_continuous.compute_breaks(5);
+        }
 
         // Populate default axis and legend labels from aesthetic mappings
         let mut guides = self.guides;
@@ -156,6 +383,7 @@ impl<'a> PlotBuilder<'a> {
             mapping: self.mapping,
             layers,
             scales,
+            facet: facet_layout,
             theme: self.theme,
             guides,
             title: self.title,
@@ -164,11 +392,26 @@ impl<'a> PlotBuilder<'a> {
 }
 
 pub fn plot<'a>(data: &'a Box<dyn DataSource>) -> PlotBuilder<'a> {
+    plot_data(data)
+}
+
+/// Start a plot that owns its data, given as any data source, a
+/// `Box<dyn DataSource>` or an `Arc<dyn DataSource>` shared between plots
+pub fn plot_owned(data: impl Into<PlotData<'static>>) -> PlotBuilder<'static> {
+    plot_data(data)
+}
+
+fn plot_data<'a>(data: impl Into<PlotData<'a>>) -> PlotBuilder<'a> {
     PlotBuilder {
-        data,
+        data: data.into(),
         mapping: AesMap::new(),
         layers: Vec::new(),
         scales: Vec::new(),
+        manual_scales: Vec::new(),
+        identity_scales: Vec::new(),
+        discrete_scales: Vec::new(),
+        coord: None,
+        facet: None,
         guides: Guides::default(),
         theme: Theme::default(),
         title: None,
@@ -201,10 +444,62 @@ impl<'a> Add<ContinuousScaleBuilder> for PlotBuilder<'a> {
     }
 }
 
+impl<'a, T> Add<ManualScaleBuilder<T>> for PlotBuilder<'a>
+where
+    ManualScale: From<ManualScaleBuilder<T>>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: ManualScaleBuilder<T>) -> Self::Output {
+        self.add_manual_scale(rhs)
+    }
+}
+
+impl<'a> Add<IdentityScaleBuilder> for PlotBuilder<'a> {
+    type Output = Self;
+
+    fn add(self, rhs: IdentityScaleBuilder) -> Self::Output {
+        self.add_identity_scale(rhs)
+    }
+}
+
+impl<'a> Add<DiscreteScaleBuilder> for PlotBuilder<'a> {
+    type Output = Self;
+
+    fn add(self, rhs: DiscreteScaleBuilder) -> Self::Output {
+        self.add_discrete_scale(rhs)
+    }
+}
+
+impl<'a> Add<CoordCartesian> for PlotBuilder<'a> {
+    type Output = Self;
+
+    fn add(self, rhs: CoordCartesian) -> Self::Output {
+        self.coord(rhs)
+    }
+}
+
+impl<'a> Add<FacetWrap> for PlotBuilder<'a> {
+    type Output = Self;
+
+    fn add(self, rhs: FacetWrap) -> Self::Output {
+        self.facet(rhs)
+    }
+}
+
+impl<'a> Add<FacetGrid> for PlotBuilder<'a> {
+    type Output = Self;
+
+    fn add(self, rhs: FacetGrid) -> Self::Output {
+        self.facet(rhs)
+    }
+}
+
 /// Main plot structure
+#[derive(Clone)]
 pub struct Plot<'a> {
     /// Default data source for all layers
-    pub data: &'a Box<dyn DataSource>,
+    pub(crate) data: PlotData<'a>,
 
     /// Default aesthetic mappings for all layers
     pub mapping: AesMap,
@@ -215,6 +510,9 @@ pub struct Plot<'a> {
     /// Scales for coordinate and aesthetic mappings
     pub scales: ScaleSet,
 
+    /// Panels of a faceted plot, each drawing a range of `layers`
+    pub facet: Option<FacetLayout>,
+
     /// Visual theme
     pub theme: Theme,
 
@@ -229,16 +527,22 @@ impl<'a> Plot<'a> {
     /// Create a new plot with optional default data
     pub fn new(data: &'a Box<dyn DataSource>) -> Self {
         Self {
-            data,
+            data: data.into(),
             mapping: AesMap::new(),
             layers: Vec::new(),
             scales: ScaleSet::default(),
+            facet: None,
             theme: Theme::default(),
             guides: Guides::default(),
             title: None,
         }
     }
 
+    /// Default data source for all layers
+    pub fn data(&self) -> &dyn DataSource {
+        self.data.as_ref()
+    }
+
     /// Set the plot title (builder style)
     pub fn title(mut self, title: impl Into<String>) -> Self {
         self.title = Some(title.into());
@@ -277,6 +581,16 @@ impl<'a> Plot<'a> {
         self
     }
 
+    /// Fitted model summaries from all smooth layers, in layer order
+    ///
+    /// Only available once the plot has been built.
+    pub fn smooth_fits(&self) -> Vec<SmoothFit> {
+        self.layers
+            .iter()
+            .flat_map(|layer| layer.smooth_fits())
+            .collect()
+    }
+
     /// Render the plot to an ImageSurface
     ///
     /// # Arguments
@@ -291,6 +605,7 @@ impl<'a> Plot<'a> {
         render::render(
             &self.layers,
             &self.scales,
+            self.facet.as_ref(),
             &self.theme,
             &self.guides,
             self.title.as_ref(),
@@ -324,6 +639,7 @@ impl<'a> Plot<'a> {
             path,
             &self.layers,
             &self.scales,
+            self.facet.as_ref(),
             &self.theme,
             &self.guides,
             self.title.as_ref(),
@@ -334,3 +650,77 @@ impl<'a> Plot<'a> {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aesthetics::builder::{
+        ColorDiscreteAesBuilder, XContinuousAesBuilder, YContinuousAesBuilder,
+    };
+    use crate::aesthetics::{Aesthetic, AestheticDomain};
+    use crate::error::to_io_error;
+    use crate::geom::line::geom_line;
+    use crate::geom::point::geom_point;
+    use crate::layer::LayerBuilderExt;
+    use crate::utils::dataframe::DataFrame;
+    use crate::utils::mtcars::mtcars;
+
+    // Owned plots can outlive the function that made their data
+    fn mtcars_plot(data: impl Into<PlotData<'static>>) -> Plot<'static> {
+        let builder = plot_owned(data).aes(|a| {
+            a.x_continuous("wt");
+            a.y_continuous("mpg");
+        }) + geom_point();
+        builder.build().map_err(to_io_error).expect("Failed to build plot")
+    }
+
+    #[test]
+    fn owned_plot_1() {
+        let shared: Arc<dyn DataSource> = Arc::from(mtcars());
+        let p = mtcars_plot(shared.clone());
+        assert_eq!(p.data().len(), 32);
+        assert_eq!(mtcars_plot(mtcars()).layers.len(), 1);
+
+        // Clones render on another thread
+        let copy = p.clone();
+        std::thread::spawn(move || copy.save("tests/images/owned_plot_1.png", 800, 600))
+            .join()
+            .expect("Render thread panicked")
+            .map_err(to_io_error)
+            .expect("Failed to save plot image");
+        assert_eq!(Arc::strong_count(&shared), 2);
+    }
+
+    #[test]
+    fn layer_data_1() {
+        let data = mtcars();
+        let mut predictions = DataFrame::new();
+        predictions.add_column("grid", vec![1.5, 2.5, 3.5, 4.5, 5.5]);
+        predictions.add_column("fit", vec![29.4, 24.1, 18.8, 13.4, 8.1]);
+
+        // Raw points from mtcars, the fitted line from its own table
+        let builder = plot(&data).aes(|a| {
+            a.x_continuous("wt");
+            a.y_continuous("mpg");
+            a.color_discrete("cyl");
+        }) + geom_point()
+            + geom_line()
+                .data(predictions)
+                .inherit_aes(false)
+                .aes(|a| {
+                    a.x_continuous("grid");
+                    a.y_continuous("fit");
+                });
+        let p = builder.build().map_err(to_io_error).expect("Failed to build plot");
+
+        let line = &p.layers[1];
+        assert_eq!(line.own_data().map(|data| data.len()), Some(5));
+        // Scaled aesthetics are stored under their continuous variant
+        assert!(!line.mapping.contains(Aesthetic::Color(AestheticDomain::Continuous)));
+        assert!(p.layers[0].mapping.contains(Aesthetic::Color(AestheticDomain::Continuous)));
+
+        p.save("tests/images/layer_data_1.png", 800, 600)
+            .map_err(to_io_error)
+            .expect("Failed to save plot image");
+    }
+}
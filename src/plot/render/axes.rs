@@ -8,7 +8,7 @@ use crate::scale::traits::{ContinuousDomainScale, ContinuousRangeScale, Discrete
 use crate::theme::Theme;
 use cairo::Context;
 
-use super::cairo_helpers::{apply_line_element, apply_text_element};
+use super::cairo_helpers::{apply_fill_style, apply_line_element, apply_text_element};
 
 /// Draw grid lines in the panel area
 pub fn draw_grid_lines(
@@ -25,32 +25,18 @@ pub fn draw_grid_lines(
     if let Some(ref grid_minor) = theme.panel.grid_minor {
         apply_line_element(ctx, grid_minor);
 
-        // Draw vertical minor grid lines between x scale breaks
-        let x_breaks = x_scale.breaks();
-        for i in 1..x_breaks.len() {
-            // Calculate midpoint in transformed space, then inverse transform
-            let transformed_prev = x_scale.transform.transform(x_breaks[i - 1]);
-            let transformed_next = x_scale.transform.transform(x_breaks[i]);
-            let transformed_mid = (transformed_prev + transformed_next) / 2.0;
-            let midpoint = x_scale.transform.inverse(transformed_mid);
-            
-            if let Some(normalized) = x_scale.map_value(&midpoint) {
+        // Draw vertical minor grid lines at x scale minor breaks
+        for break_val in x_scale.minor_breaks().iter() {
+            if let Some(normalized) = x_scale.map_value(break_val) {
                 let x_pos = plot_x0 + normalized * (plot_x1 - plot_x0);
                 ctx.move_to(x_pos, plot_y0);
                 ctx.line_to(x_pos, plot_y1);
             }
         }
 
-        // Draw horizontal minor grid lines between y scale breaks
-        let y_breaks = y_scale.breaks();
-        for i in 1..y_breaks.len() {
-            // Calculate midpoint in transformed space, then inverse transform
-            let transformed_prev = y_scale.transform.transform(y_breaks[i - 1]);
-            let transformed_next = y_scale.transform.transform(y_breaks[i]);
-            let transformed_mid = (transformed_prev + transformed_next) / 2.0;
-            let midpoint = y_scale.transform.inverse(transformed_mid);
-            
-            if let Some(normalized) = y_scale.map_value(&midpoint) {
+        // Draw horizontal minor grid lines at y scale minor breaks
+        for break_val in y_scale.minor_breaks().iter() {
+            if let Some(normalized) = y_scale.map_value(break_val) {
                 // Note: y is inverted (y1 is bottom, y0 is top)
                 let y_pos = plot_y1 + normalized * (plot_y0 - plot_y1);
                 ctx.move_to(plot_x0, y_pos);
@@ -90,19 +76,97 @@ pub fn draw_grid_lines(
     Ok(())
 }
 
-/// Draw axes with tick marks and labels
-pub fn draw_axes(
+/// Clip drawing to the panel, leaving out the gaps at any axis breaks
+pub fn clip_to_panel(ctx: &mut Context, scales: &ScaleSet, x0: f64, x1: f64, y0: f64, y1: f64) {
+    let x_spans = spans_between(&scales.x_continuous.gaps());
+    let y_spans = spans_between(&scales.y_continuous.gaps());
+    for &(left, right) in &x_spans {
+        for &(bottom, top) in &y_spans {
+            // Note: y is inverted (y1 is bottom, y0 is top)
+            let (left, right) = (x0 + left * (x1 - x0), x0 + right * (x1 - x0));
+            let (bottom, top) = (y1 + bottom * (y0 - y1), y1 + top * (y0 - y1));
+            ctx.rectangle(left, top, right - left, bottom - top);
+        }
+    }
+    ctx.clip();
+}
+
+/// The normalized spans of an axis either side of its gaps
+fn spans_between(gaps: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    let mut spans = Vec::new();
+    let mut start = 0.0;
+    for &(gap_start, gap_end) in gaps {
+        spans.push((start, gap_start));
+        start = gap_end;
+    }
+    spans.push((start, 1.0));
+    spans
+}
+
+/// Mark the gaps at axis breaks: each gap is cleared across the panel and
+/// the axis lines, and a slash is drawn at either edge of it on both sides
+/// of the panel
+pub fn draw_axis_breaks(
+    ctx: &mut Context,
+    theme: &Theme,
+    scales: &ScaleSet,
+    x0: f64,
+    x1: f64,
+    y0: f64,
+    y1: f64,
+) {
+    let slash = 4.0;
+
+    for (start, end) in scales.x_continuous.gaps() {
+        let overhang = theme.axis_x.line.tick_length as f64;
+        let (left, right) = (x0 + start * (x1 - x0), x0 + end * (x1 - x0));
+        apply_fill_style(ctx, &theme.background.fill);
+        ctx.rectangle(left, y0 - overhang, right - left, y1 - y0 + 2.0 * overhang);
+        ctx.fill().ok();
+
+        apply_line_element(ctx, &theme.axis_x.line.line);
+        for y in [y0, y1] {
+            for x in [left, right] {
+                ctx.move_to(x - slash / 2.0, y + slash);
+                ctx.line_to(x + slash / 2.0, y - slash);
+            }
+        }
+        ctx.stroke().ok();
+    }
+
+    for (start, end) in scales.y_continuous.gaps() {
+        let overhang = theme.axis_y.line.tick_length as f64;
+        // Note: y is inverted (y1 is bottom, y0 is top)
+        let (bottom, top) = (y1 + start * (y0 - y1), y1 + end * (y0 - y1));
+        apply_fill_style(ctx, &theme.background.fill);
+        ctx.rectangle(x0 - overhang, top, x1 - x0 + 2.0 * overhang, bottom - top);
+        ctx.fill().ok();
+
+        apply_line_element(ctx, &theme.axis_y.line.line);
+        for x in [x0, x1] {
+            for y in [bottom, top] {
+                ctx.move_to(x - slash, y + slash / 2.0);
+                ctx.line_to(x + slash, y - slash / 2.0);
+            }
+        }
+        ctx.stroke().ok();
+    }
+}
+
+/// Draw the x and/or y axis lines, ticks and labels of one panel
+pub fn draw_panel_axes(
     ctx: &mut Context,
     theme: &Theme,
     x_axis: Option<&AxisGuide>,
     y_axis: Option<&AxisGuide>,
     scales: &ScaleSet,
-    title: Option<&String>,
+    show_x: bool,
+    show_y: bool,
     x0: f64,
     x1: f64,
     y0: f64,
     y1: f64,
-) -> Result<(), PlotError> {
+) {
     // Determine which scales to use for axes
     // Use discrete scale if it has categories, otherwise use continuous
     let use_x_discrete = scales.x_discrete.categories().len() > 0;
@@ -128,149 +192,147 @@ pub fn draw_axes(
         )
     };
 
-    // Draw axis lines based on position
-
-    // X axis line
-    let x_position = x_axis
-        .and_then(|guide| match &guide.position {
-            AxisType::X(pos) => Some(pos.clone()),
-            _ => None,
-        })
-        .unwrap_or(XAxisPosition::Bottom);
-
-    apply_line_element(ctx, &theme.axis_x.line.line);
-    match x_position {
-        XAxisPosition::Bottom => {
-            ctx.move_to(x0, y1);
-            ctx.line_to(x1, y1);
-        }
-        XAxisPosition::Top => {
-            ctx.move_to(x0, y0);
-            ctx.line_to(x1, y0);
-        }
-    }
-    ctx.stroke().ok();
-
-    // Draw X axis ticks and labels
-    let tick_length = theme.axis_x.line.tick_length as f64;
-    apply_line_element(ctx, &theme.axis_x.line.ticks);
-    apply_text_element(ctx, &theme.axis_x.text.text);
-
-        for (break_val, label) in x_breaks.iter().zip(x_labels.iter()) {
-            // Map break value to viewport coordinate
-            // For discrete scales, the breaks are already normalized (0-1)
+    // Map breaks to normalized positions
+    // For discrete scales, the breaks are already normalized (0-1)
+    let x_ticks: Vec<(f64, &str)> = x_breaks
+        .iter()
+        .zip(x_labels.iter())
+        .filter_map(|(break_val, label)| {
             let normalized = if use_x_discrete {
                 Some(*break_val)
             } else {
                 scales.x_continuous.map_value(break_val)
             };
-
-            if let Some(normalized) = normalized {
-                let x_pos = x0 + normalized * (x1 - x0);
-
-                // Draw tick mark
-                match x_position {
-                    XAxisPosition::Bottom => {
-                        ctx.move_to(x_pos, y1);
-                        ctx.line_to(x_pos, y1 + tick_length);
-                    }
-                    XAxisPosition::Top => {
-                        ctx.move_to(x_pos, y0);
-                        ctx.line_to(x_pos, y0 - tick_length);
-                    }
-                }
-                ctx.stroke().ok();
-
-                // Draw label
-                let extents = ctx.text_extents(label).ok();
-                if let Some(ext) = extents {
-                    let label_margin = theme.axis_x.text.text_margin.top as f64;
+            normalized.map(|n| (n, label.as_str()))
+        })
+        .collect();
+    let y_ticks: Vec<(f64, &str)> = y_breaks
+        .iter()
+        .zip(y_labels.iter())
+        .filter_map(|(break_val, label)| {
+            let normalized = if use_y_discrete {
+                Some(*break_val)
+            } else {
+                scales.y_continuous.map_value(break_val)
+            };
+            normalized.map(|n| (n, label.as_str()))
+        })
+        .collect();
+
+    if show_x {
+        // X axis line
+        let x_position = x_axis
+            .and_then(|guide| match &guide.position {
+                AxisType::X(pos) => Some(pos.clone()),
+                _ => None,
+            })
+            .unwrap_or(XAxisPosition::Bottom);
+
+        draw_x_axis_line(ctx, theme, &x_position, x0, x1, y0, y1);
+
+        // Draw X axis minor ticks (continuous scales only)
+        if let (Some(minor_ticks), false) = (&theme.axis_x.line.minor_ticks, use_x_discrete) {
+            let minor_length = theme.axis_x.line.minor_tick_length as f64;
+            apply_line_element(ctx, minor_ticks);
+            for break_val in scales.x_continuous.minor_breaks() {
+                if let Some(normalized) = scales.x_continuous.map_value(break_val) {
+                    let x_pos = x0 + normalized * (x1 - x0);
                     match x_position {
                         XAxisPosition::Bottom => {
-                            let y_label = y1 + tick_length + label_margin + ext.height();
-                            ctx.move_to(x_pos - ext.width() / 2.0, y_label);
+                            ctx.move_to(x_pos, y1);
+                            ctx.line_to(x_pos, y1 + minor_length);
                         }
                         XAxisPosition::Top => {
-                            let y_label = y0 - tick_length - label_margin;
-                            ctx.move_to(x_pos - ext.width() / 2.0, y_label);
+                            ctx.move_to(x_pos, y0);
+                            ctx.line_to(x_pos, y0 - minor_length);
                         }
                     }
-                    ctx.show_text(label).ok();
                 }
             }
+            ctx.stroke().ok();
         }
 
-    // Y axis line
-    let y_position = y_axis
-        .and_then(|guide| match &guide.position {
-            AxisType::Y(pos) => Some(pos.clone()),
-            _ => None,
-        })
-        .unwrap_or(YAxisPosition::Left);
+        draw_x_ticks(ctx, theme, &x_ticks, &x_position, x0, x1, y0, y1);
 
-    apply_line_element(ctx, &theme.axis_y.line.line);
-    match y_position {
-        YAxisPosition::Left => {
-            ctx.move_to(x0, y0);
-            ctx.line_to(x0, y1);
-        }
-        YAxisPosition::Right => {
-            ctx.move_to(x1, y0);
-            ctx.line_to(x1, y1);
+        // Secondary X axis on the opposite side (continuous scales only)
+        if let (Some(secondary), false) = (scales.x_continuous.secondary(), use_x_discrete) {
+            let opposite = match x_position {
+                XAxisPosition::Bottom => XAxisPosition::Top,
+                XAxisPosition::Top => XAxisPosition::Bottom,
+            };
+            let ticks = secondary_ticks(&scales.x_continuous);
+            draw_x_axis_line(ctx, theme, &opposite, x0, x1, y0, y1);
+            draw_x_ticks(ctx, theme, &ticks, &opposite, x0, x1, y0, y1);
+            if let Some(name) = secondary.title() {
+                draw_x_title(ctx, theme, name, &opposite, x0, x1, y0, y1);
+            }
         }
     }
-    ctx.stroke().ok();
-
-    // Draw Y axis ticks and labels
-    let y_tick_length = theme.axis_y.line.tick_length as f64;
-    apply_line_element(ctx, &theme.axis_y.line.ticks);
-    apply_text_element(ctx, &theme.axis_y.text.text);
-
-        for (break_val, label) in y_breaks.iter().zip(y_labels.iter()) {
-            // Map break value to viewport coordinate
-            // For discrete scales, the breaks are already normalized (0-1)
-            let normalized = if use_y_discrete {
-                Some(*break_val)
-            } else {
-                scales.y_continuous.map_value(break_val)
-            };
-
-            if let Some(normalized) = normalized {
-                // Note: y is inverted (y1 is bottom, y0 is top)
-                let y_pos = y1 + normalized * (y0 - y1);
-
-                // Draw tick mark
-                match y_position {
-                    YAxisPosition::Left => {
-                        ctx.move_to(x0, y_pos);
-                        ctx.line_to(x0 - y_tick_length, y_pos);
-                    }
-                    YAxisPosition::Right => {
-                        ctx.move_to(x1, y_pos);
-                        ctx.line_to(x1 + y_tick_length, y_pos);
-                    }
-                }
-                ctx.stroke().ok();
 
-                // Draw label
-                let extents = ctx.text_extents(label).ok();
-                if let Some(ext) = extents {
-                    let label_margin = theme.axis_y.text.text_margin.right as f64;
+    if show_y {
+        // Y axis line
+        let y_position = y_axis
+            .and_then(|guide| match &guide.position {
+                AxisType::Y(pos) => Some(pos.clone()),
+                _ => None,
+            })
+            .unwrap_or(YAxisPosition::Left);
+
+        draw_y_axis_line(ctx, theme, &y_position, x0, x1, y0, y1);
+
+        // Draw Y axis minor ticks (continuous scales only)
+        if let (Some(minor_ticks), false) = (&theme.axis_y.line.minor_ticks, use_y_discrete) {
+            let minor_length = theme.axis_y.line.minor_tick_length as f64;
+            apply_line_element(ctx, minor_ticks);
+            for break_val in scales.y_continuous.minor_breaks() {
+                if let Some(normalized) = scales.y_continuous.map_value(break_val) {
+                    // Note: y is inverted (y1 is bottom, y0 is top)
+                    let y_pos = y1 + normalized * (y0 - y1);
                     match y_position {
                         YAxisPosition::Left => {
-                            let x_label = x0 - y_tick_length - label_margin - ext.width();
-                            ctx.move_to(x_label, y_pos + ext.height() / 2.0);
+                            ctx.move_to(x0, y_pos);
+                            ctx.line_to(x0 - minor_length, y_pos);
                         }
                         YAxisPosition::Right => {
-                            let x_label = x1 + y_tick_length + label_margin;
-                            ctx.move_to(x_label, y_pos + ext.height() / 2.0);
+                            ctx.move_to(x1, y_pos);
+                            ctx.line_to(x1 + minor_length, y_pos);
                         }
                     }
-                    ctx.show_text(label).ok();
                 }
             }
+            ctx.stroke().ok();
         }
 
+        draw_y_ticks(ctx, theme, &y_ticks, &y_position, x0, x1, y0, y1);
+
+        // Secondary Y axis on the opposite side (continuous scales only)
+        if let (Some(secondary), false) = (scales.y_continuous.secondary(), use_y_discrete) {
+            let opposite = match y_position {
+                YAxisPosition::Left => YAxisPosition::Right,
+                YAxisPosition::Right => YAxisPosition::Left,
+            };
+            let ticks = secondary_ticks(&scales.y_continuous);
+            draw_y_axis_line(ctx, theme, &opposite, x0, x1, y0, y1);
+            draw_y_ticks(ctx, theme, &ticks, &opposite, x0, x1, y0, y1);
+            if let Some(name) = secondary.title() {
+                draw_y_title(ctx, theme, name, &opposite, x0, x1, y0, y1);
+            }
+        }
+    }
+}
+
+/// Draw the axis titles and the plot title around the panel area
+pub fn draw_titles(
+    ctx: &mut Context,
+    theme: &Theme,
+    x_axis: Option<&AxisGuide>,
+    y_axis: Option<&AxisGuide>,
+    title: Option<&String>,
+    x0: f64,
+    x1: f64,
+    y0: f64,
+    y1: f64,
+) {
     // Draw X axis title
     if let Some(x_axis) = x_axis {
         if let Some(x_label) = &x_axis.title {
@@ -278,36 +340,7 @@ pub fn draw_axes(
                 AxisType::X(pos) => pos.clone(),
                 _ => XAxisPosition::Bottom,
             };
-
-            apply_text_element(ctx, &theme.axis_x.text.title);
-            let extents = ctx.text_extents(x_label).ok();
-            if let Some(ext) = extents {
-                let x_center = (x0 + x1) / 2.0;
-                let tick_length = theme.axis_x.line.tick_length as f64;
-                let label_margin = theme.axis_x.text.text_margin.top as f64;
-                let typical_label_height = theme.axis_x.text.text.size.unwrap_or(10.0) as f64;
-                let title_margin = theme.axis_x.text.title_margin.top as f64;
-
-                match x_position {
-                    XAxisPosition::Bottom => {
-                        // Position below: axis line + ticks + tick label margin + typical label height + title margin
-                        let y_offset = y1
-                            + tick_length
-                            + label_margin
-                            + typical_label_height
-                            + title_margin
-                            + ext.height();
-                        ctx.move_to(x_center - ext.width() / 2.0, y_offset);
-                    }
-                    XAxisPosition::Top => {
-                        // Position above: axis line + ticks + tick label margin + title margin
-                        let y_offset =
-                            y0 - tick_length - label_margin - typical_label_height - title_margin;
-                        ctx.move_to(x_center - ext.width() / 2.0, y_offset);
-                    }
-                }
-                ctx.show_text(x_label).ok();
-            }
+            draw_x_title(ctx, theme, x_label, &x_position, x0, x1, y0, y1);
         }
     }
 
@@ -318,46 +351,7 @@ pub fn draw_axes(
                 AxisType::Y(pos) => pos.clone(),
                 _ => YAxisPosition::Left,
             };
-
-            ctx.save().ok();
-            apply_text_element(ctx, &theme.axis_y.text.title);
-            let y_center = (y0 + y1) / 2.0;
-            let extents = ctx.text_extents(y_label).ok();
-            if let Some(ext) = extents {
-                let tick_length = theme.axis_y.line.tick_length as f64;
-                let label_margin = theme.axis_y.text.text_margin.right as f64;
-                // Estimate max label width (rough approximation based on font size * typical digits)
-                let typical_label_width = theme.axis_y.text.text.size.unwrap_or(10.0) as f64 * 2.5;
-                let title_margin = theme.axis_y.text.title_margin.right as f64;
-                let title_height = ext.height();
-
-                match y_position {
-                    YAxisPosition::Left => {
-                        // Position to left of: axis line + ticks + tick label margin + typical max label width + title margin
-                        let x_offset = x0
-                            - tick_length
-                            - label_margin
-                            - typical_label_width
-                            - title_margin
-                            - title_height;
-                        ctx.move_to(x_offset, y_center + ext.width() / 2.0);
-                        ctx.rotate(-std::f64::consts::PI / 2.0);
-                    }
-                    YAxisPosition::Right => {
-                        // Position to right of: axis line + ticks + tick label margin + typical max label width + title margin
-                        let x_offset = x1
-                            + tick_length
-                            + label_margin
-                            + typical_label_width
-                            + title_margin
-                            + title_height;
-                        ctx.move_to(x_offset, y_center - ext.width() / 2.0);
-                        ctx.rotate(std::f64::consts::PI / 2.0);
-                    }
-                }
-                ctx.show_text(y_label).ok();
-            }
-            ctx.restore().ok();
+            draw_y_title(ctx, theme, y_label, &y_position, x0, x1, y0, y1);
         }
     }
 
@@ -372,6 +366,241 @@ pub fn draw_axes(
             ctx.show_text(title).ok();
         }
     }
+}
 
-    Ok(())
+/// Normalized positions and labels of a scale's secondary axis breaks
+fn secondary_ticks(scale: &ContinuousPositionalScale) -> Vec<(f64, &str)> {
+    scale
+        .secondary_breaks()
+        .iter()
+        .zip(scale.secondary_labels())
+        .filter_map(|(break_val, label)| Some((scale.map_value(break_val)?, label.as_str())))
+        .collect()
+}
+
+/// Draw an X axis line at the bottom or top of the panel
+fn draw_x_axis_line(ctx: &mut Context, theme: &Theme, position: &XAxisPosition, x0: f64, x1: f64, y0: f64, y1: f64) {
+    apply_line_element(ctx, &theme.axis_x.line.line);
+    match position {
+        XAxisPosition::Bottom => {
+            ctx.move_to(x0, y1);
+            ctx.line_to(x1, y1);
+        }
+        XAxisPosition::Top => {
+            ctx.move_to(x0, y0);
+            ctx.line_to(x1, y0);
+        }
+    }
+    ctx.stroke().ok();
+}
+
+/// Draw a Y axis line at the left or right of the panel
+fn draw_y_axis_line(ctx: &mut Context, theme: &Theme, position: &YAxisPosition, x0: f64, x1: f64, y0: f64, y1: f64) {
+    apply_line_element(ctx, &theme.axis_y.line.line);
+    match position {
+        YAxisPosition::Left => {
+            ctx.move_to(x0, y0);
+            ctx.line_to(x0, y1);
+        }
+        YAxisPosition::Right => {
+            ctx.move_to(x1, y0);
+            ctx.line_to(x1, y1);
+        }
+    }
+    ctx.stroke().ok();
+}
+
+/// Draw X axis tick marks and labels at normalized positions
+fn draw_x_ticks(
+    ctx: &mut Context,
+    theme: &Theme,
+    ticks: &[(f64, &str)],
+    position: &XAxisPosition,
+    x0: f64,
+    x1: f64,
+    y0: f64,
+    y1: f64,
+) {
+    let tick_length = theme.axis_x.line.tick_length as f64;
+    apply_line_element(ctx, &theme.axis_x.line.ticks);
+    apply_text_element(ctx, &theme.axis_x.text.text);
+
+    for &(normalized, label) in ticks {
+        let x_pos = x0 + normalized * (x1 - x0);
+
+        // Draw tick mark
+        match position {
+            XAxisPosition::Bottom => {
+                ctx.move_to(x_pos, y1);
+                ctx.line_to(x_pos, y1 + tick_length);
+            }
+            XAxisPosition::Top => {
+                ctx.move_to(x_pos, y0);
+                ctx.line_to(x_pos, y0 - tick_length);
+            }
+        }
+        ctx.stroke().ok();
+
+        // Draw label
+        let extents = ctx.text_extents(label).ok();
+        if let Some(ext) = extents {
+            let label_margin = theme.axis_x.text.text_margin.top as f64;
+            match position {
+                XAxisPosition::Bottom => {
+                    let y_label = y1 + tick_length + label_margin + ext.height();
+                    ctx.move_to(x_pos - ext.width() / 2.0, y_label);
+                }
+                XAxisPosition::Top => {
+                    let y_label = y0 - tick_length - label_margin;
+                    ctx.move_to(x_pos - ext.width() / 2.0, y_label);
+                }
+            }
+            ctx.show_text(label).ok();
+        }
+    }
+}
+
+/// Draw Y axis tick marks and labels at normalized positions
+fn draw_y_ticks(
+    ctx: &mut Context,
+    theme: &Theme,
+    ticks: &[(f64, &str)],
+    position: &YAxisPosition,
+    x0: f64,
+    x1: f64,
+    y0: f64,
+    y1: f64,
+) {
+    let y_tick_length = theme.axis_y.line.tick_length as f64;
+    apply_line_element(ctx, &theme.axis_y.line.ticks);
+    apply_text_element(ctx, &theme.axis_y.text.text);
+
+    for &(normalized, label) in ticks {
+        // Note: y is inverted (y1 is bottom, y0 is top)
+        let y_pos = y1 + normalized * (y0 - y1);
+
+        // Draw tick mark
+        match position {
+            YAxisPosition::Left => {
+                ctx.move_to(x0, y_pos);
+                ctx.line_to(x0 - y_tick_length, y_pos);
+            }
+            YAxisPosition::Right => {
+                ctx.move_to(x1, y_pos);
+                ctx.line_to(x1 + y_tick_length, y_pos);
+            }
+        }
+        ctx.stroke().ok();
+
+        // Draw label
+        let extents = ctx.text_extents(label).ok();
+        if let Some(ext) = extents {
+            let label_margin = theme.axis_y.text.text_margin.right as f64;
+            match position {
+                YAxisPosition::Left => {
+                    let x_label = x0 - y_tick_length - label_margin - ext.width();
+                    ctx.move_to(x_label, y_pos + ext.height() / 2.0);
+                }
+                YAxisPosition::Right => {
+                    let x_label = x1 + y_tick_length + label_margin;
+                    ctx.move_to(x_label, y_pos + ext.height() / 2.0);
+                }
+            }
+            ctx.show_text(label).ok();
+        }
+    }
+}
+
+/// Draw an X axis title below or above the panel
+fn draw_x_title(
+    ctx: &mut Context,
+    theme: &Theme,
+    x_label: &str,
+    position: &XAxisPosition,
+    x0: f64,
+    x1: f64,
+    y0: f64,
+    y1: f64,
+) {
+    apply_text_element(ctx, &theme.axis_x.text.title);
+    let extents = ctx.text_extents(x_label).ok();
+    if let Some(ext) = extents {
+        let x_center = (x0 + x1) / 2.0;
+        let tick_length = theme.axis_x.line.tick_length as f64;
+        let label_margin = theme.axis_x.text.text_margin.top as f64;
+        let typical_label_height = theme.axis_x.text.text.size.unwrap_or(10.0) as f64;
+        let title_margin = theme.axis_x.text.title_margin.top as f64;
+
+        match position {
+            XAxisPosition::Bottom => {
+                // Position below: axis line + ticks + tick label margin + typical label height + title margin
+                let y_offset = y1
+                    + tick_length
+                    + label_margin
+                    + typical_label_height
+                    + title_margin
+                    + ext.height();
+                ctx.move_to(x_center - ext.width() / 2.0, y_offset);
+            }
+            XAxisPosition::Top => {
+                // Position above: axis line + ticks + tick label margin + title margin
+                let y_offset =
+                    y0 - tick_length - label_margin - typical_label_height - title_margin;
+                ctx.move_to(x_center - ext.width() / 2.0, y_offset);
+            }
+        }
+        ctx.show_text(x_label).ok();
+    }
+}
+
+/// Draw a Y axis title, rotated, to the left or right of the panel
+fn draw_y_title(
+    ctx: &mut Context,
+    theme: &Theme,
+    y_label: &str,
+    position: &YAxisPosition,
+    x0: f64,
+    x1: f64,
+    y0: f64,
+    y1: f64,
+) {
+    ctx.save().ok();
+    apply_text_element(ctx, &theme.axis_y.text.title);
+    let y_center = (y0 + y1) / 2.0;
+    let extents = ctx.text_extents(y_label).ok();
+    if let Some(ext) = extents {
+        let tick_length = theme.axis_y.line.tick_length as f64;
+        let label_margin = theme.axis_y.text.text_margin.right as f64;
+        // Estimate max label width (rough approximation based on font size * typical digits)
+        let typical_label_width = theme.axis_y.text.text.size.unwrap_or(10.0) as f64 * 2.5;
+        let title_margin = theme.axis_y.text.title_margin.right as f64;
+        let title_height = ext.height();
+
+        match position {
+            YAxisPosition::Left => {
+                // Position to left of: axis line + ticks + tick label margin + typical max label width + title margin
+                let x_offset = x0
+                    - tick_length
+                    - label_margin
+                    - typical_label_width
+                    - title_margin
+                    - title_height;
+                ctx.move_to(x_offset, y_center + ext.width() / 2.0);
+                ctx.rotate(-std::f64::consts::PI / 2.0);
+            }
+            YAxisPosition::Right => {
+                // Position to right of: axis line + ticks + tick label margin + typical max label width + title margin
+                let x_offset = x1
+                    + tick_length
+                    + label_margin
+                    + typical_label_width
+                    + title_margin
+                    + title_height;
+                ctx.move_to(x_offset, y_center - ext.width() / 2.0);
+                ctx.rotate(std::f64::consts::PI / 2.0);
+            }
+        }
+        ctx.show_text(y_label).ok();
+    }
+    ctx.restore().ok();
 }
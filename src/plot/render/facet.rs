@@ -0,0 +1,187 @@
+// Faceted plot rendering: a grid of panels, each under its strips and,
+// in a facet grid, the last column beside its row strips
+
+use crate::data::DataSource;
+use crate::error::PlotError;
+use crate::facet::FacetLayout;
+use crate::guide::{Guides, XAxisPosition, YAxisPosition};
+use crate::layer::Layer;
+use crate::theme::Theme;
+use cairo::Context;
+
+use super::cairo_helpers::{apply_fill_style, apply_text_element};
+use super::{PanelBounds, draw_panel};
+
+/// The panels of a faceted plot, the sides their axes go on and the area
+/// between x0..x1 and y0..y1 they share
+pub struct FacetArea<'a> {
+    pub layout: &'a FacetLayout,
+    pub x_position: &'a XAxisPosition,
+    pub y_position: &'a YAxisPosition,
+    pub x0: f64,
+    pub x1: f64,
+    pub y0: f64,
+    pub y1: f64,
+}
+
+/// Draw the panels of a faceted plot in their area.
+/// With fixed scales only the outer panels get axes; free scales in a wrap
+/// draw an axis on every panel and widen the gaps between them to make room,
+/// while a grid shares them along its rows and columns. Columns and rows
+/// take their share of the area from the layout's widths and heights.
+pub fn draw_facets(
+    ctx: &mut Context,
+    theme: &Theme,
+    guides: &Guides,
+    layers: &[Layer],
+    data: Option<&dyn DataSource>,
+    area: &FacetArea,
+) -> Result<(), PlotError> {
+    let FacetArea { layout, x_position, y_position, x0, x1, y0, y1 } = *area;
+    let free_x = layout.scales.free_x() && !layout.grid;
+    let free_y = layout.scales.free_y() && !layout.grid;
+    let right_axis = matches!(y_position, YAxisPosition::Right);
+
+    // Room above each row for its strips, and right of each column for its
+    // row strips (outside a right y axis)
+    let strip_height = strip_height(theme);
+    let mut top_bands = vec![0.0; layout.nrow];
+    let mut right_bands = vec![0.0; layout.ncol];
+    for panel in &layout.panels {
+        let top = panel.strips.len() as f64 * strip_height;
+        top_bands[panel.row] = f64::max(top_bands[panel.row], top);
+        if !panel.row_strips.is_empty() {
+            let axis = if right_axis { y_axis_width(theme) } else { 0.0 };
+            let right = panel.row_strips.len() as f64 * strip_height + axis;
+            right_bands[panel.col] = f64::max(right_bands[panel.col], right);
+        }
+    }
+
+    let spacing = theme.panel.spacing as f64;
+    let col_gap = spacing + if free_y { y_axis_width(theme) } else { 0.0 };
+    let row_gap = spacing + if free_x { x_axis_height(theme) } else { 0.0 };
+    let (nrow, ncol) = (layout.nrow as f64, layout.ncol as f64);
+    let widths = shares(
+        &layout.widths,
+        x1 - x0 - (ncol - 1.0) * col_gap - right_bands.iter().sum::<f64>(),
+    );
+    let heights = shares(
+        &layout.heights,
+        y1 - y0 - (nrow - 1.0) * row_gap - top_bands.iter().sum::<f64>(),
+    );
+
+    let mut lefts = Vec::with_capacity(layout.ncol);
+    let mut left = x0;
+    for (width, band) in widths.iter().zip(&right_bands) {
+        lefts.push(left);
+        left += width + band + col_gap;
+    }
+    let mut tops = Vec::with_capacity(layout.nrow);
+    let mut top = y0;
+    for (height, band) in heights.iter().zip(&top_bands) {
+        tops.push(top + band);
+        top += band + height + row_gap;
+    }
+
+    for panel in &layout.panels {
+        let left = lefts[panel.col];
+        let right = left + widths[panel.col];
+        let top = tops[panel.row];
+        let bottom = top + heights[panel.row];
+
+        // Axes go on the panels at the edge of the grid on the axis' side
+        let show_x = free_x
+            || match x_position {
+                XAxisPosition::Bottom => !layout.has_panel(panel.row + 1, panel.col),
+                XAxisPosition::Top => panel.row == 0 || !layout.has_panel(panel.row - 1, panel.col),
+            };
+        let show_y = free_y
+            || match y_position {
+                YAxisPosition::Left => panel.col == 0 || !layout.has_panel(panel.row, panel.col - 1),
+                YAxisPosition::Right => !layout.has_panel(panel.row, panel.col + 1),
+            };
+
+        let bounds = PanelBounds {
+            x0: left,
+            x1: right,
+            y0: top,
+            y1: bottom,
+            show_x,
+            show_y,
+        };
+        draw_panel(ctx, theme, guides, &layers[panel.layers.clone()], &panel.scales, data, &bounds)?;
+
+        let strips_top = top - panel.strips.len() as f64 * strip_height;
+        for (i, label) in panel.strips.iter().enumerate() {
+            let strip_top = strips_top + i as f64 * strip_height;
+            draw_strip(ctx, theme, label, (left, right), (strip_top, strip_top + strip_height), false);
+        }
+
+        let mut strips_left = right;
+        if right_axis && show_y {
+            strips_left += y_axis_width(theme);
+        }
+        for (i, label) in panel.row_strips.iter().enumerate() {
+            let strip_left = strips_left + i as f64 * strip_height;
+            draw_strip(ctx, theme, label, (strip_left, strip_left + strip_height), (top, bottom), true);
+        }
+    }
+
+    Ok(())
+}
+
+/// Split `length` in proportion to `weights`
+fn shares(weights: &[f64], length: f64) -> Vec<f64> {
+    let total: f64 = weights.iter().sum();
+    weights.iter().map(|weight| length.max(0.0) * weight / total).collect()
+}
+
+/// Height of one strip: its text and the margin around it
+fn strip_height(theme: &Theme) -> f64 {
+    let size = theme.strip.text.size.unwrap_or(10.0);
+    size + (theme.strip.margin.top + theme.strip.margin.bottom) as f64
+}
+
+/// Room for x axis ticks and labels below a panel
+fn x_axis_height(theme: &Theme) -> f64 {
+    let tick_length = theme.axis_x.line.tick_length as f64;
+    let label_margin = theme.axis_x.text.text_margin.top as f64;
+    tick_length + label_margin + theme.axis_x.text.text.size.unwrap_or(10.0)
+}
+
+/// Room for y axis ticks and labels beside a panel, estimated like the
+/// y axis title's offset
+fn y_axis_width(theme: &Theme) -> f64 {
+    let tick_length = theme.axis_y.line.tick_length as f64;
+    let label_margin = theme.axis_y.text.text_margin.right as f64;
+    tick_length + label_margin + theme.axis_y.text.text.size.unwrap_or(10.0) * 2.5
+}
+
+/// Draw a strip's background and its centered label, rotated to read
+/// downwards in a strip right of a panel
+fn draw_strip(
+    ctx: &mut Context,
+    theme: &Theme,
+    label: &str,
+    (x0, x1): (f64, f64),
+    (y0, y1): (f64, f64),
+    rotated: bool,
+) {
+    if let Some(ref background) = theme.strip.background {
+        apply_fill_style(ctx, background);
+        ctx.rectangle(x0, y0, x1 - x0, y1 - y0);
+        ctx.fill().ok();
+    }
+
+    ctx.save().ok();
+    apply_text_element(ctx, &theme.strip.text);
+    if let Ok(ext) = ctx.text_extents(label) {
+        ctx.translate((x0 + x1) / 2.0, (y0 + y1) / 2.0);
+        if rotated {
+            ctx.rotate(std::f64::consts::PI / 2.0);
+        }
+        ctx.move_to(-ext.width() / 2.0 - ext.x_bearing(), -ext.height() / 2.0 - ext.y_bearing());
+        ctx.show_text(label).ok();
+    }
+    ctx.restore().ok();
+}
@@ -4,15 +4,19 @@ use crate::error::PlotError;
 use crate::guide::{Guides, LegendEntry, LegendGuide, LegendPosition, LegendType};
 use crate::layer::Layer;
 use crate::scale::ScaleSet;
+use crate::scale::identity::Identity;
+use crate::scale::labels::LabelFormatter;
 use crate::scale::traits::{
     ColorRangeScale, ContinuousDomainScale, ContinuousRangeScale, DiscreteDomainScale,
 };
-use crate::theme::{Color, Theme};
+use crate::theme::{Color, LineElement, Theme};
 use crate::utils::set::DiscreteSet;
 use crate::visuals::Shape;
 use cairo::Context;
 
-use super::cairo_helpers::{apply_color, apply_fill_style, apply_font, apply_line_style};
+use super::cairo_helpers::{
+    apply_color, apply_fill_style, apply_font, apply_line_element, apply_line_style,
+};
 
 /// Helper to create discrete legend entries from categories
 fn create_discrete_entries<T>(
@@ -23,14 +27,13 @@ fn create_discrete_entries<T>(
     let mut entries = Vec::new();
 
     for i in 0..categories.len() {
-        if let Some(value) = categories.get_at(i) {
-            let label = value.to_string();
-
-            if let Some(mapped) = map_value(&value) {
-                let mut entry = LegendEntry::new(label);
-                apply_value(&mut entry, mapped);
-                entries.push(entry);
-            }
+        let Some(value) = categories.get_at(i) else {
+            continue;
+        };
+        if let (Some(mapped), Some(label)) = (map_value(&value), categories.label_at(i)) {
+            let mut entry = LegendEntry::new(label);
+            apply_value(&mut entry, mapped);
+            entries.push(entry);
         }
     }
 
@@ -46,6 +49,7 @@ fn create_discrete_color_legend(title: String, scales: &ScaleSet) -> LegendGuide
             crate::data::DiscreteValue::Int(x) => scales.color_discrete.map_value(x),
             crate::data::DiscreteValue::Str(x) => scales.color_discrete.map_value(x),
             crate::data::DiscreteValue::Bool(x) => scales.color_discrete.map_value(x),
+            crate::data::DiscreteValue::Na => scales.color_discrete.map_value(value),
         },
         |entry, color| {
             entry.color = Some(color);
@@ -61,33 +65,88 @@ fn create_discrete_color_legend(title: String, scales: &ScaleSet) -> LegendGuide
     }
 }
 
+/// Helper to create a color bar legend from a continuous color scale
+fn create_color_bar_legend(
+    title: String,
+    scale: &crate::scale::color::ContinuousColorScale,
+) -> LegendGuide {
+    let Some(domain) = scale.domain() else {
+        return LegendGuide::default();
+    };
+
+    if let Some(bins) = scale.bins().filter(|bins| !bins.is_empty()) {
+        return create_color_steps_legend(title, scale, bins);
+    }
+
+    // Sample the gradient across the domain; map_value expects data space
+    let samples = 16;
+    let colors = (0..=samples)
+        .filter_map(|i| {
+            let t = domain.0 + (domain.1 - domain.0) * i as f64 / samples as f64;
+            scale.map_value(&scale.transform().inverse(t))
+        })
+        .collect();
+
+    // Breaks are positioned along the bar in transformed space
+    let (breaks, labels) = scale
+        .breaks()
+        .iter()
+        .zip(scale.labels())
+        .map(|(&b, label)| (scale.transform().transform(b), label.clone()))
+        .filter(|(b, _)| b.is_finite())
+        .unzip();
+
+    LegendGuide {
+        title: Some(title),
+        legend_type: LegendType::ColorBar {
+            domain,
+            colors,
+            breaks,
+            labels,
+        },
+        ..Default::default()
+    }
+}
+
+/// Helper to create a stepped color bar from a binned color scale
+fn create_color_steps_legend(
+    title: String,
+    scale: &crate::scale::color::ContinuousColorScale,
+    bins: &crate::scale::binned::Binning,
+) -> LegendGuide {
+    // Bins get equal space on the bar, so a break is placed within its bin
+    let edges = bins.edges();
+    let (breaks, labels) = scale
+        .breaks()
+        .iter()
+        .zip(scale.labels())
+        .filter_map(|(&b, label)| {
+            let t = scale.transform().transform(b);
+            let bin = bins.bin(t)?;
+            let within = (t - edges[bin]) / (edges[bin + 1] - edges[bin]);
+            Some(((bin as f64 + within) / bins.len() as f64, label.clone()))
+        })
+        .unzip();
+
+    LegendGuide {
+        title: Some(title),
+        legend_type: LegendType::ColorSteps {
+            colors: scale.bin_colors(),
+            breaks,
+            labels,
+        },
+        ..Default::default()
+    }
+}
+
 /// Helper to create a continuous color legend
 fn create_continuous_color_legend(title: String, scales: &ScaleSet) -> LegendGuide {
-    if let Some(domain) = scales.color_continuous.domain() {
-        let colors = vec![
-            scales
-                .color_continuous
-                .map_value(&domain.0)
-                .unwrap_or(crate::theme::color::BLACK),
-            scales
-                .color_continuous
-                .map_value(&domain.1)
-                .unwrap_or(crate::theme::color::WHITE),
-        ];
-
-        LegendGuide {
-            title: Some(title),
-            legend_type: LegendType::ColorBar {
-                domain,
-                colors,
-                breaks: scales.color_continuous.breaks().to_vec(),
-                labels: scales.color_continuous.labels().to_vec(),
-            },
-            ..Default::default()
-        }
-    } else {
-        LegendGuide::default()
-    }
+    create_color_bar_legend(title, &scales.color_continuous)
+}
+
+/// Helper to create a continuous fill legend
+fn create_continuous_fill_legend(title: String, scales: &ScaleSet) -> LegendGuide {
+    create_color_bar_legend(title, &scales.fill_continuous)
 }
 
 /// Helper to create a discrete fill legend
@@ -99,6 +158,7 @@ fn create_discrete_fill_legend(title: String, scales: &ScaleSet) -> LegendGuide
             crate::data::DiscreteValue::Int(x) => scales.fill_discrete.map_value(x),
             crate::data::DiscreteValue::Str(x) => scales.fill_discrete.map_value(x),
             crate::data::DiscreteValue::Bool(x) => scales.fill_discrete.map_value(x),
+            crate::data::DiscreteValue::Na => scales.fill_discrete.map_value(value),
         },
         |entry, color| {
             entry.color = Some(color);
@@ -123,6 +183,7 @@ fn create_discrete_size_legend(title: String, scales: &ScaleSet) -> LegendGuide
             crate::data::DiscreteValue::Int(x) => scales.size_discrete.map_value(x),
             crate::data::DiscreteValue::Str(x) => scales.size_discrete.map_value(x),
             crate::data::DiscreteValue::Bool(x) => scales.size_discrete.map_value(x),
+            crate::data::DiscreteValue::Na => scales.size_discrete.map_value(value),
         },
         |entry, size| {
             entry.color = Some(crate::theme::color::BLACK);
@@ -138,40 +199,45 @@ fn create_discrete_size_legend(title: String, scales: &ScaleSet) -> LegendGuide
     }
 }
 
+/// Helper to pick the values listed in a continuous legend: the distinct
+/// values of an identity scale when there are few, otherwise the breaks
+fn legend_values(
+    identity: Option<&Identity>,
+    breaks: &[f64],
+    labels: &[String],
+) -> (Vec<f64>, Vec<String>) {
+    match identity.and_then(Identity::values) {
+        Some(values) => (values.to_vec(), crate::scale::utils::format_breaks(values)),
+        None => (breaks.to_vec(), labels.to_vec()),
+    }
+}
+
 /// Helper to create a continuous size legend
 fn create_continuous_size_legend(title: String, scales: &ScaleSet) -> LegendGuide {
-    if let Some(domain) = scales.size_continuous.domain() {
-        let mut entries = Vec::new();
-        let num_breaks = 4;
-
-        for i in 0..num_breaks {
-            let t = i as f64 / (num_breaks - 1) as f64;
-            let value = domain.0 + t * (domain.1 - domain.0);
-            let size = scales.size_continuous.map_value(&value);
-
-            if let Some(size) = size {
-                let label = if (value - value.round()).abs() < 0.01 {
-                    format!("{}", value.round() as i64)
-                } else {
-                    format!("{:.1}", value)
-                };
+    let scale = &scales.size_continuous;
+    if scale.domain().is_none() {
+        return LegendGuide::default();
+    }
 
-                entries.push(
-                    LegendEntry::new(label)
-                        .color(crate::theme::color::BLACK)
-                        .size(size),
-                );
-            }
-        }
+    let (breaks, labels) = legend_values(scale.identity(), scale.breaks(), scale.labels());
+    let entries = breaks
+        .iter()
+        .zip(&labels)
+        .filter_map(|(value, label)| {
+            let size = scale.map_value(value)?;
+            Some(
+                LegendEntry::new(label.clone())
+                    .color(crate::theme::color::BLACK)
+                    .size(size),
+            )
+        })
+        .collect();
 
-        LegendGuide {
-            title: Some(title),
-            entries,
-            legend_type: LegendType::Discrete,
-            ..Default::default()
-        }
-    } else {
-        LegendGuide::default()
+    LegendGuide {
+        title: Some(title),
+        entries,
+        legend_type: LegendType::Discrete,
+        ..Default::default()
     }
 }
 
@@ -185,6 +251,7 @@ fn create_discrete_shape_legend(title: String, scales: &ScaleSet) -> LegendGuide
             crate::data::DiscreteValue::Int(x) => scales.shape_scale.map_value(x),
             crate::data::DiscreteValue::Str(x) => scales.shape_scale.map_value(x),
             crate::data::DiscreteValue::Bool(x) => scales.shape_scale.map_value(x),
+            crate::data::DiscreteValue::Na => scales.shape_scale.map_value(value),
         },
         |entry, shape| {
             entry.color = Some(crate::theme::color::BLACK);
@@ -201,6 +268,31 @@ fn create_discrete_shape_legend(title: String, scales: &ScaleSet) -> LegendGuide
     }
 }
 
+/// Helper to create a discrete linetype legend
+fn create_discrete_linetype_legend(title: String, scales: &ScaleSet) -> LegendGuide {
+    use crate::scale::traits::LineStyleRangeScale;
+
+    let entries = create_discrete_entries(
+        scales.linestyle_scale.categories(),
+        |value| match value {
+            crate::data::DiscreteValue::Int(x) => scales.linestyle_scale.map_value(x),
+            crate::data::DiscreteValue::Str(x) => scales.linestyle_scale.map_value(x),
+            crate::data::DiscreteValue::Bool(x) => scales.linestyle_scale.map_value(x),
+            crate::data::DiscreteValue::Na => scales.linestyle_scale.map_value(value),
+        },
+        |entry, linetype| {
+            entry.linetype = Some(linetype);
+        },
+    );
+
+    LegendGuide {
+        title: Some(title),
+        entries,
+        legend_type: LegendType::Discrete,
+        ..Default::default()
+    }
+}
+
 /// Helper to create a discrete alpha legend
 fn create_discrete_alpha_legend(title: String, scales: &ScaleSet) -> LegendGuide {
     use crate::scale::traits::ContinuousRangeScale;
@@ -211,6 +303,7 @@ fn create_discrete_alpha_legend(title: String, scales: &ScaleSet) -> LegendGuide
             crate::data::DiscreteValue::Int(x) => scales.alpha_discrete.map_value(x),
             crate::data::DiscreteValue::Str(x) => scales.alpha_discrete.map_value(x),
             crate::data::DiscreteValue::Bool(x) => scales.alpha_discrete.map_value(x),
+            crate::data::DiscreteValue::Na => scales.alpha_discrete.map_value(value),
         },
         |entry, alpha| {
             // Show alpha as gray circles with varying transparency
@@ -233,42 +326,33 @@ fn create_discrete_alpha_legend(title: String, scales: &ScaleSet) -> LegendGuide
 fn create_continuous_alpha_legend(title: String, scales: &ScaleSet) -> LegendGuide {
     use crate::scale::traits::ContinuousRangeScale;
 
-    if let Some(domain) = scales.alpha_continuous.domain() {
-        let mut entries = Vec::new();
-        let num_breaks = 4;
-
-        for i in 0..num_breaks {
-            let t = i as f64 / (num_breaks - 1) as f64;
-            let value = domain.0 + t * (domain.1 - domain.0);
-            let alpha = scales.alpha_continuous.map_value(&value);
-
-            if let Some(alpha) = alpha {
-                let label = if (value - value.round()).abs() < 0.01 {
-                    format!("{}", value.round() as i64)
-                } else {
-                    format!("{:.1}", value)
-                };
-
-                // Show alpha as gray circles with varying transparency
-                let gray = 128u8;
-                let alpha_u8 = (alpha * 255.0) as u8;
+    let scale = &scales.alpha_continuous;
+    if scale.domain().is_none() {
+        return LegendGuide::default();
+    }
 
-                entries.push(
-                    LegendEntry::new(label)
-                        .color(Color(gray, gray, gray, alpha_u8))
-                        .size(5.0),
-                );
-            }
-        }
+    let (breaks, labels) = legend_values(scale.identity(), scale.breaks(), scale.labels());
+    let entries = breaks
+        .iter()
+        .zip(&labels)
+        .filter_map(|(value, label)| {
+            let alpha = scale.map_value(value)?;
+            // Show alpha as gray circles with varying transparency
+            let gray = 128u8;
+            let alpha_u8 = (alpha * 255.0) as u8;
+            Some(
+                LegendEntry::new(label.clone())
+                    .color(Color(gray, gray, gray, alpha_u8))
+                    .size(5.0),
+            )
+        })
+        .collect();
 
-        LegendGuide {
-            title: Some(title),
-            entries,
-            legend_type: LegendType::Discrete,
-            ..Default::default()
-        }
-    } else {
-        LegendGuide::default()
+    LegendGuide {
+        title: Some(title),
+        entries,
+        legend_type: LegendType::Discrete,
+        ..Default::default()
     }
 }
 
@@ -283,40 +367,71 @@ fn create_individual_legend(
     use crate::aesthetics::{AestheticDomain, AestheticProperty};
 
     let check_and_set = |guide_field: &mut Option<LegendGuide>| {
-        if guide_field.is_none() {
-            *guide_field = Some(match domain {
-                AestheticDomain::Discrete => match property {
-                    AestheticProperty::Color => {
-                        create_discrete_color_legend(title.to_string(), scales)
-                    }
-                    AestheticProperty::Fill => {
-                        create_discrete_fill_legend(title.to_string(), scales)
-                    }
-                    AestheticProperty::Size => {
-                        create_discrete_size_legend(title.to_string(), scales)
-                    }
-                    AestheticProperty::Shape => {
-                        create_discrete_shape_legend(title.to_string(), scales)
-                    }
-                    AestheticProperty::Alpha => {
-                        create_discrete_alpha_legend(title.to_string(), scales)
-                    }
-                    _ => LegendGuide::default(),
-                },
-                AestheticDomain::Continuous => match property {
-                    AestheticProperty::Color => {
-                        create_continuous_color_legend(title.to_string(), scales)
-                    }
-                    AestheticProperty::Size => {
-                        create_continuous_size_legend(title.to_string(), scales)
-                    }
-                    AestheticProperty::Alpha => {
-                        create_continuous_alpha_legend(title.to_string(), scales)
-                    }
-                    _ => LegendGuide::default(),
-                },
-            });
+        // A guide with a label formatter but no entries is filled in from
+        // the scale; any other guide set by the user is drawn as given
+        let configured = match guide_field.take() {
+            None => None,
+            Some(guide) if guide.entries.is_empty() && guide.label_formatter.is_some() => Some(guide),
+            Some(guide) => {
+                *guide_field = Some(guide);
+                return;
+            }
+        };
+        let relabelled;
+        let scales = match configured.as_ref().and_then(|guide| guide.label_formatter.clone()) {
+            Some(formatter) if domain == AestheticDomain::Continuous => {
+                relabelled = relabel_continuous_scale(scales, property, formatter);
+                &relabelled
+            }
+            _ => scales,
+        };
+        let mut legend = match domain {
+            AestheticDomain::Discrete => match property {
+                AestheticProperty::Color => {
+                    create_discrete_color_legend(title.to_string(), scales)
+                }
+                AestheticProperty::Fill => {
+                    create_discrete_fill_legend(title.to_string(), scales)
+                }
+                AestheticProperty::Size => {
+                    create_discrete_size_legend(title.to_string(), scales)
+                }
+                AestheticProperty::Shape => {
+                    create_discrete_shape_legend(title.to_string(), scales)
+                }
+                AestheticProperty::Alpha => {
+                    create_discrete_alpha_legend(title.to_string(), scales)
+                }
+                AestheticProperty::Linetype => {
+                    create_discrete_linetype_legend(title.to_string(), scales)
+                }
+                _ => LegendGuide::default(),
+            },
+            AestheticDomain::Continuous => match property {
+                AestheticProperty::Color => {
+                    create_continuous_color_legend(title.to_string(), scales)
+                }
+                AestheticProperty::Fill => {
+                    create_continuous_fill_legend(title.to_string(), scales)
+                }
+                AestheticProperty::Size => {
+                    create_continuous_size_legend(title.to_string(), scales)
+                }
+                AestheticProperty::Alpha => {
+                    create_continuous_alpha_legend(title.to_string(), scales)
+                }
+                _ => LegendGuide::default(),
+            },
+        };
+        if let Some(guide) = configured {
+            legend.title = guide.title.or(legend.title);
+            legend.position = guide.position;
+            legend.direction = guide.direction;
+            legend.ncol = guide.ncol;
+            legend.nrow = guide.nrow;
+            legend.label_formatter = guide.label_formatter;
         }
+        *guide_field = Some(legend);
     };
 
     match property {
@@ -325,10 +440,30 @@ fn create_individual_legend(
         AestheticProperty::Size => check_and_set(&mut guides.size),
         AestheticProperty::Shape => check_and_set(&mut guides.shape),
         AestheticProperty::Alpha => check_and_set(&mut guides.alpha),
+        AestheticProperty::Linetype => check_and_set(&mut guides.linetype),
         _ => {}
     }
 }
 
+/// Helper to label the continuous scale behind a legend with a formatter
+fn relabel_continuous_scale(
+    scales: &ScaleSet,
+    property: crate::aesthetics::AestheticProperty,
+    formatter: Box<dyn LabelFormatter>,
+) -> ScaleSet {
+    use crate::aesthetics::AestheticProperty;
+
+    let mut scales = scales.clone();
+    match property {
+        AestheticProperty::Color => scales.color_continuous.set_label_formatter(formatter),
+        AestheticProperty::Fill => scales.fill_continuous.set_label_formatter(formatter),
+        AestheticProperty::Size => scales.size_continuous.set_label_formatter(formatter),
+        AestheticProperty::Alpha => scales.alpha_continuous.set_label_formatter(formatter),
+        _ => {}
+    }
+    scales
+}
+
 /// Create a merged legend for multiple aesthetics mapping to the same column
 fn create_merged_legend(
     guides: &mut Guides,
@@ -337,7 +472,9 @@ fn create_merged_legend(
     scales: &ScaleSet,
 ) {
     use crate::aesthetics::{AestheticDomain, AestheticProperty};
-    use crate::scale::traits::{ColorRangeScale, ContinuousRangeScale, ShapeRangeScale};
+    use crate::scale::traits::{
+        ColorRangeScale, ContinuousRangeScale, LineStyleRangeScale, ShapeRangeScale,
+    };
 
     let domain = aesthetics[0].domain;
 
@@ -366,6 +503,9 @@ fn create_merged_legend(
     let has_alpha = aesthetics
         .iter()
         .any(|a| a.property == AestheticProperty::Alpha);
+    let has_linetype = aesthetics
+        .iter()
+        .any(|a| a.property == AestheticProperty::Linetype);
 
     // Get categories from the first available scale
     let categories = if has_color {
@@ -378,6 +518,8 @@ fn create_merged_legend(
         scales.shape_scale.categories()
     } else if has_alpha {
         scales.alpha_discrete.categories()
+    } else if has_linetype {
+        scales.linestyle_scale.categories()
     } else {
         return;
     };
@@ -386,9 +528,7 @@ fn create_merged_legend(
     let mut entries = Vec::new();
 
     for i in 0..categories.len() {
-        if let Some(value) = categories.get_at(i) {
-            let label = value.to_string();
-
+        if let (Some(value), Some(label)) = (categories.get_at(i), categories.label_at(i)) {
             let mut entry = LegendEntry::new(label);
 
             // Get values from each scale
@@ -397,6 +537,7 @@ fn create_merged_legend(
                     crate::data::DiscreteValue::Int(x) => scales.color_discrete.map_value(x),
                     crate::data::DiscreteValue::Str(x) => scales.color_discrete.map_value(x),
                     crate::data::DiscreteValue::Bool(x) => scales.color_discrete.map_value(x),
+                    crate::data::DiscreteValue::Na => scales.color_discrete.map_value(&value),
                 } {
                     entry.color = Some(color);
                 }
@@ -407,6 +548,7 @@ fn create_merged_legend(
                     crate::data::DiscreteValue::Int(x) => scales.fill_discrete.map_value(x),
                     crate::data::DiscreteValue::Str(x) => scales.fill_discrete.map_value(x),
                     crate::data::DiscreteValue::Bool(x) => scales.fill_discrete.map_value(x),
+                    crate::data::DiscreteValue::Na => scales.fill_discrete.map_value(&value),
                 } {
                     entry.fill = Some(fill);
                 }
@@ -417,6 +559,7 @@ fn create_merged_legend(
                     crate::data::DiscreteValue::Int(x) => scales.size_discrete.map_value(x),
                     crate::data::DiscreteValue::Str(x) => scales.size_discrete.map_value(x),
                     crate::data::DiscreteValue::Bool(x) => scales.size_discrete.map_value(x),
+                    crate::data::DiscreteValue::Na => scales.size_discrete.map_value(&value),
                 } {
                     entry.size = Some(size);
                 }
@@ -430,6 +573,7 @@ fn create_merged_legend(
                     crate::data::DiscreteValue::Int(x) => scales.shape_scale.map_value(x),
                     crate::data::DiscreteValue::Str(x) => scales.shape_scale.map_value(x),
                     crate::data::DiscreteValue::Bool(x) => scales.shape_scale.map_value(x),
+                    crate::data::DiscreteValue::Na => scales.shape_scale.map_value(&value),
                 } {
                     entry.shape = Some(shape);
                 }
@@ -440,11 +584,21 @@ fn create_merged_legend(
                     crate::data::DiscreteValue::Int(x) => scales.alpha_discrete.map_value(x),
                     crate::data::DiscreteValue::Str(x) => scales.alpha_discrete.map_value(x),
                     crate::data::DiscreteValue::Bool(x) => scales.alpha_discrete.map_value(x),
+                    crate::data::DiscreteValue::Na => scales.alpha_discrete.map_value(&value),
                 } {
                     entry.alpha = Some(alpha);
                 }
             }
 
+            if has_linetype {
+                entry.linetype = match &value {
+                    crate::data::DiscreteValue::Int(x) => scales.linestyle_scale.map_value(x),
+                    crate::data::DiscreteValue::Str(x) => scales.linestyle_scale.map_value(x),
+                    crate::data::DiscreteValue::Bool(x) => scales.linestyle_scale.map_value(x),
+                    crate::data::DiscreteValue::Na => scales.linestyle_scale.map_value(&value),
+                };
+            }
+
             entries.push(entry);
         }
     }
@@ -456,7 +610,7 @@ fn create_merged_legend(
         ..Default::default()
     };
 
-    // Decide which guide field to use - prioritize color > fill > size > shape > alpha
+    // Decide which guide field to use - prioritize color > fill > size > shape > alpha > linetype
     // and suppress others
     if has_color && guides.color.is_none() {
         guides.color = Some(merged_guide);
@@ -485,6 +639,12 @@ fn create_merged_legend(
                 ..Default::default()
             });
         }
+        if has_linetype {
+            guides.linetype = Some(LegendGuide {
+                position: LegendPosition::None,
+                ..Default::default()
+            });
+        }
     } else if has_fill && guides.fill.is_none() {
         guides.fill = Some(merged_guide);
         if has_size {
@@ -505,6 +665,12 @@ fn create_merged_legend(
                 ..Default::default()
             });
         }
+        if has_linetype {
+            guides.linetype = Some(LegendGuide {
+                position: LegendPosition::None,
+                ..Default::default()
+            });
+        }
     } else if has_size && guides.size.is_none() {
         guides.size = Some(merged_guide);
         if has_shape {
@@ -519,6 +685,12 @@ fn create_merged_legend(
                 ..Default::default()
             });
         }
+        if has_linetype {
+            guides.linetype = Some(LegendGuide {
+                position: LegendPosition::None,
+                ..Default::default()
+            });
+        }
     } else if has_shape && guides.shape.is_none() {
         guides.shape = Some(merged_guide);
         if has_alpha {
@@ -527,8 +699,22 @@ fn create_merged_legend(
                 ..Default::default()
             });
         }
+        if has_linetype {
+            guides.linetype = Some(LegendGuide {
+                position: LegendPosition::None,
+                ..Default::default()
+            });
+        }
     } else if has_alpha && guides.alpha.is_none() {
         guides.alpha = Some(merged_guide);
+        if has_linetype {
+            guides.linetype = Some(LegendGuide {
+                position: LegendPosition::None,
+                ..Default::default()
+            });
+        }
+    } else if has_linetype && guides.linetype.is_none() {
+        guides.linetype = Some(merged_guide);
     }
 }
 
@@ -601,6 +787,9 @@ pub fn generate_automatic_legends(
         }
     }
 
+    // Identity scales have no legend unless one was asked for
+    mapped_aesthetics.retain(|m| scales.identity(m.property, m.domain).is_none_or(Identity::legend));
+
     // Group aesthetics by column name
     let mut aesthetic_groups: HashMap<String, Vec<MappedAesthetic>> = HashMap::new();
     for mapped in mapped_aesthetics {
@@ -700,6 +889,14 @@ pub fn calculate_legend_width(
         }
     }
 
+    if guides
+        .linetype
+        .as_ref()
+        .is_some_and(|legend| !matches!(legend.position, LegendPosition::None))
+    {
+        legend_count += 1;
+    }
+
     if legend_count > 0 {
         // Add margin for legend placement (10px before legend, legend width, 10px after)
         total_width = legend_spacing + legend_width + legend_spacing;
@@ -754,6 +951,11 @@ pub fn draw_legends(
         legends.push(alpha_guide);
     }
 
+    // Add linetype legend if present
+    if let Some(ref linetype_guide) = guides.linetype {
+        legends.push(linetype_guide);
+    }
+
     if legends.is_empty() {
         return Ok(());
     }
@@ -787,7 +989,7 @@ pub fn draw_legends(
             LegendType::Discrete => {
                 title_height + (legend.entries.len() as f64 * item_height) + padding * 2.0
             }
-            LegendType::ColorBar { .. } => {
+            LegendType::ColorBar { .. } | LegendType::ColorSteps { .. } => {
                 title_height + 150.0 + padding * 2.0 // Fixed height for color bar
             }
         };
@@ -827,7 +1029,20 @@ pub fn draw_legends(
                     let has_color = entry.color.is_some();
                     let alpha = entry.alpha.unwrap_or(1.0);
 
-                    if has_shape {
+                    if let Some(ref linetype) = entry.linetype {
+                        // Draw a short line segment in the entry's dash pattern
+                        let line = LineElement {
+                            color: Some(entry.color.unwrap_or(theme.legend.text_color)),
+                            size: Some(1.5),
+                            alpha: Some(alpha),
+                            linestyle: Some(linetype.clone()),
+                        };
+                        apply_line_element(ctx, &line);
+                        ctx.move_to(symbol_x, symbol_y);
+                        ctx.line_to(symbol_x + 20.0, symbol_y);
+                        ctx.stroke().ok();
+                        ctx.set_dash(&[], 0.0);
+                    } else if has_shape {
                         // Draw shape with fill and/or color
                         let shape = entry.shape.unwrap();
 
@@ -919,36 +1134,36 @@ pub fn draw_legends(
                 let bar_height = 120.0;
                 let bar_y = item_y;
 
-                // Draw color gradient
-                let n_segments = colors.len().max(2) - 1;
-                let segment_height = bar_height / n_segments as f64;
-
-                for i in 0..n_segments {
-                    let y = bar_y + i as f64 * segment_height;
-
-                    if i < colors.len() - 1 {
-                        // Create gradient pattern for this segment
+                // Draw color gradient (high values at top, like the ticks)
+                if !colors.is_empty() {
+                    let steps = 60;
+                    let step_height = bar_height / steps as f64;
+                    let last = (colors.len() - 1) as f64;
+                    for j in 0..steps {
+                        let t = 1.0 - (j as f64 + 0.5) / steps as f64;
+                        let pos = t * last;
+                        let i = (pos.floor() as usize).min(colors.len() - 1);
                         let color1 = colors[i];
-                        let color2 = colors[i + 1];
-
-                        // For simplicity, just fill with interpolated colors
-                        let steps = 10;
-                        for j in 0..steps {
-                            let t = j as f64 / steps as f64;
-                            let r = (color1.0 as f64 * (1.0 - t) + color2.0 as f64 * t) as u8;
-                            let g = (color1.1 as f64 * (1.0 - t) + color2.1 as f64 * t) as u8;
-                            let b = (color1.2 as f64 * (1.0 - t) + color2.2 as f64 * t) as u8;
-                            let a = (color1.3 as f64 * (1.0 - t) + color2.3 as f64 * t) as u8;
-
-                            apply_color(ctx, &Color(r, g, b, a));
-                            ctx.rectangle(
-                                bar_x,
-                                y + j as f64 * segment_height / steps as f64,
-                                bar_width,
-                                segment_height / steps as f64 + 0.5,
-                            );
-                            ctx.fill().ok();
-                        }
+                        let color2 = colors[(i + 1).min(colors.len() - 1)];
+                        let f = pos - i as f64;
+                        let mix = |a: u8, b: u8| (a as f64 * (1.0 - f) + b as f64 * f) as u8;
+
+                        apply_color(
+                            ctx,
+                            &Color(
+                                mix(color1.0, color2.0),
+                                mix(color1.1, color2.1),
+                                mix(color1.2, color2.2),
+                                mix(color1.3, color2.3),
+                            ),
+                        );
+                        ctx.rectangle(
+                            bar_x,
+                            bar_y + j as f64 * step_height,
+                            bar_width,
+                            step_height + 0.5,
+                        );
+                        ctx.fill().ok();
                     }
                 }
 
@@ -979,6 +1194,48 @@ pub fn draw_legends(
                     ctx.show_text(label).ok();
                 }
             }
+            LegendType::ColorSteps {
+                colors,
+                breaks,
+                labels,
+            } => {
+                let bar_x = legend_x + padding + 10.0;
+                let bar_width = 20.0;
+                let bar_height = 120.0;
+                let bar_y = item_y;
+
+                // One block per bin, high bins at top
+                if !colors.is_empty() {
+                    let step_height = bar_height / colors.len() as f64;
+                    for (i, color) in colors.iter().enumerate() {
+                        apply_color(ctx, color);
+                        let top = bar_y + bar_height - (i + 1) as f64 * step_height;
+                        ctx.rectangle(bar_x, top, bar_width, step_height);
+                        ctx.fill().ok();
+                    }
+                }
+
+                apply_line_style(ctx, &theme.legend.border);
+                ctx.rectangle(bar_x, bar_y, bar_width, bar_height);
+                ctx.stroke().ok();
+
+                // Labels sit at the bin edges
+                let label_x = bar_x + bar_width + 5.0;
+                apply_font(ctx, &theme.legend.text_font);
+                apply_color(ctx, &theme.legend.text_color);
+                ctx.set_font_size(9.0);
+
+                for (&position, label) in breaks.iter().zip(labels.iter()) {
+                    let tick_y = bar_y + bar_height - position * bar_height;
+
+                    ctx.move_to(bar_x + bar_width, tick_y);
+                    ctx.line_to(bar_x + bar_width + 3.0, tick_y);
+                    ctx.stroke().ok();
+
+                    ctx.move_to(label_x + 3.0, tick_y + 3.0);
+                    ctx.show_text(label).ok();
+                }
+            }
         }
 
         legend_y += legend_height + 10.0;
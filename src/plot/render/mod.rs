@@ -2,18 +2,22 @@
 
 pub mod axes;
 pub mod cairo_helpers;
+pub mod facet;
 pub mod legend;
 
 use crate::data::DataSource;
 use crate::error::PlotError;
+use crate::facet::FacetLayout;
 use crate::geom::RenderContext;
 use crate::layer::Layer;
 use crate::scale::ScaleSet;
+use crate::scale::traits::DiscreteDomainScale;
 use crate::theme::Theme;
 use cairo::{Context, Format, ImageSurface};
 
-use self::axes::{draw_axes, draw_grid_lines};
+use self::axes::{clip_to_panel, draw_axis_breaks, draw_grid_lines, draw_panel_axes, draw_titles};
 use self::cairo_helpers::{apply_fill_style, apply_line_style};
+use self::facet::{FacetArea, draw_facets};
 use self::legend::{calculate_legend_width, draw_legends};
 
 /// Render the plot to an ImageSurface
@@ -29,6 +33,7 @@ use self::legend::{calculate_legend_width, draw_legends};
 pub fn render(
     layers: &[Layer],
     scales: &ScaleSet,
+    facet: Option<&FacetLayout>,
     theme: &Theme,
     guides: &crate::guide::Guides,
     title: Option<&String>,
@@ -46,7 +51,7 @@ pub fn render(
 
     // Use the common rendering code
     render_with_context(
-        &mut ctx, layers, scales, theme, guides, title, data, plot_mapping, width, height,
+        &mut ctx, layers, scales, facet, theme, guides, title, data, plot_mapping, width, height,
     )?;
 
     Ok(surface)
@@ -57,6 +62,7 @@ pub fn render_with_context(
     ctx: &mut Context,
     layers: &[Layer],
     scales: &ScaleSet,
+    facet: Option<&FacetLayout>,
     theme: &Theme,
     guides: &crate::guide::Guides,
     title: Option<&String>,
@@ -102,7 +108,7 @@ pub fn render_with_context(
 
     // Adjust margins based on axis positions
     // When axis moves to opposite side, use theme's opposite margin
-    let margin_left = match y_position {
+    let mut margin_left = match y_position {
         YAxisPosition::Left => theme_margin_left,
         YAxisPosition::Right => theme_margin_right,
     };
@@ -112,19 +118,37 @@ pub fn render_with_context(
         YAxisPosition::Right => theme_margin_left,
     };
 
-    let margin_top = match x_position {
+    let mut margin_top = match x_position {
         XAxisPosition::Top => theme_margin_bottom,
         XAxisPosition::Bottom => theme_margin_top,
     };
 
-    let margin_bottom = match x_position {
+    let mut margin_bottom = match x_position {
         XAxisPosition::Bottom => theme_margin_bottom,
         XAxisPosition::Top => theme_margin_top,
     };
 
-    // Increase right margin if legends are present
+    // A secondary axis needs as much room as an axis on the opposite side
+    let x_secondary = scales.x_continuous.secondary().is_some() && scales.x_discrete.categories().len() == 0;
+    let y_secondary = scales.y_continuous.secondary().is_some() && scales.y_discrete.categories().len() == 0;
+    if x_secondary {
+        margin_top = f64::max(margin_top, theme_margin_bottom);
+        margin_bottom = f64::max(margin_bottom, theme_margin_bottom);
+    }
+    if y_secondary {
+        margin_left = f64::max(margin_left, theme_margin_left);
+        margin_right = f64::max(margin_right, theme_margin_left);
+    }
+
+    // Increase right margin if legends are present, placing them beyond
+    // any axis on the right
+    let right_axis_width = if y_secondary || y_position == YAxisPosition::Right {
+        theme_margin_left
+    } else {
+        0.0
+    };
     if legend_width > 0.0 {
-        margin_right = f64::max(margin_right, legend_width);
+        margin_right = f64::max(margin_right, right_axis_width + legend_width);
     }
 
     let plot_x0 = margin_left;
@@ -135,6 +159,86 @@ pub fn render_with_context(
     log::info!("Viewport bounds: x=[{}, {}], y=[{}, {}], width={}, height={}", 
         plot_x0, plot_x1, plot_y0, plot_y1, width, height);
 
+    // Draw the panel, or one per facet, then the titles around them
+    match facet {
+        Some(layout) => {
+            let area = FacetArea {
+                layout,
+                x_position: &x_position,
+                y_position: &y_position,
+                x0: plot_x0,
+                x1: plot_x1,
+                y0: plot_y0,
+                y1: plot_y1,
+            };
+            draw_facets(ctx, theme, guides, layers, data, &area)?
+        }
+        None => {
+            let bounds = PanelBounds {
+                x0: plot_x0,
+                x1: plot_x1,
+                y0: plot_y0,
+                y1: plot_y1,
+                show_x: true,
+                show_y: true,
+            };
+            draw_panel(ctx, theme, guides, layers, scales, data, &bounds)?
+        }
+    }
+    draw_titles(
+        ctx,
+        theme,
+        guides.x_axis.as_ref(),
+        guides.y_axis.as_ref(),
+        title,
+        plot_x0,
+        plot_x1,
+        plot_y0,
+        plot_y1,
+    );
+
+    // Draw legends
+    draw_legends(
+        ctx,
+        theme,
+        layers,
+        scales,
+        guides,
+        plot_mapping,
+        plot_x0,
+        plot_x1 + right_axis_width,
+        plot_y0,
+        plot_y1,
+        width,
+        height,
+    )?;
+
+    Ok(())
+}
+
+/// Where a panel is drawn, between x0..x1 and y0..y1, and which of its
+/// axes get ticks and labels
+struct PanelBounds {
+    x0: f64,
+    x1: f64,
+    y0: f64,
+    y1: f64,
+    show_x: bool,
+    show_y: bool,
+}
+
+/// Draw one panel: its background, grid, border and axes, then its layers
+/// clipped to it
+fn draw_panel(
+    ctx: &mut Context,
+    theme: &Theme,
+    guides: &crate::guide::Guides,
+    layers: &[Layer],
+    scales: &ScaleSet,
+    data: Option<&dyn DataSource>,
+    bounds: &PanelBounds,
+) -> Result<(), PlotError> {
+    let PanelBounds { x0: plot_x0, x1: plot_x1, y0: plot_y0, y1: plot_y1, show_x, show_y } = *bounds;
     // Draw panel background
     if let Some(ref panel_bg) = theme.panel.background {
         apply_fill_style(ctx, panel_bg);
@@ -162,20 +266,25 @@ pub fn render_with_context(
     }
 
     // Draw axes before rendering layers
-    draw_axes(
+    draw_panel_axes(
         ctx,
         theme,
         guides.x_axis.as_ref(),
         guides.y_axis.as_ref(),
         scales,
-        title,
+        show_x,
+        show_y,
         plot_x0,
         plot_x1,
         plot_y0,
         plot_y1,
-    )?;
+    );
+    draw_axis_breaks(ctx, theme, scales, plot_x0, plot_x1, plot_y0, plot_y1);
 
-    // Render each layer
+    // Render each layer, clipped to the panel (zoomed or kept data may fall
+    // outside) and cut at any axis breaks
+    ctx.save().ok();
+    clip_to_panel(ctx, scales, plot_x0, plot_x1, plot_y0, plot_y1);
     for layer in layers {
         let mut render_ctx = RenderContext::new(
             ctx,
@@ -186,11 +295,7 @@ pub fn render_with_context(
 
         layer.render(&mut render_ctx, data.unwrap())?;
     }
-
-    // Draw legends
-    draw_legends(
-        ctx, theme, layers, scales, guides, plot_mapping, plot_x0, plot_x1, plot_y0, plot_y1, width, height,
-    )?;
+    ctx.restore().ok();
 
     Ok(())
 }
@@ -47,7 +47,7 @@ impl Position for Dodge {
 
         // Get the discrete X values - we'll create continuous offsets from these
         let x_discrete_iter = mapping
-            .get_resolved_iter(&x_discrete_aes)
+            .get_resolved_discrete(&x_discrete_aes)
             .unwrap();
 
         let group_values = mapping
@@ -104,13 +104,13 @@ impl GroupDodger {
 
     fn process(
         &mut self,
-        x_iter: crate::data::VectorIter<'_>,
+        x_iter: impl Iterator<Item = crate::data::DiscreteValue>,
         group_iter: crate::data::VectorIter<'_>,
     ) -> Result<(), PlotError> {
         use crate::data::DiscreteValue;
 
         // Collect x and group values into discrete form
-        let x_values: Vec<DiscreteValue> = x_iter.to_discrete_iter().collect();
+        let x_values: Vec<DiscreteValue> = x_iter.collect();
 
         let group_values: Vec<DiscreteValue> = group_iter.to_discrete_iter().collect();
 
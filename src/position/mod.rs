@@ -14,7 +14,7 @@ use crate::error::PlotError;
 ///
 /// Note: Position adjustments run after resolution, so all Column references
 /// in the mapping have been converted to Vector values. No DataSource is needed.
-pub trait Position {
+pub trait Position: Send + Sync {
     /// Apply position adjustment to data
     ///
     /// # Arguments
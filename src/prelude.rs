@@ -1,14 +1,15 @@
 //! Convenience re-exports for building plots.
 
 // Core plot builder
-pub use crate::plot::plot;
+pub use crate::plot::{plot, plot_owned};
 
 // Common geoms (extend as needed)
 pub use crate::geom::point::geom_point;
 pub use crate::geom::line::geom_line;
 pub use crate::geom::bar::geom_bar;
 pub use crate::geom::density::geom_density;
-pub use crate::geom::smooth::geom_smooth;
+pub use crate::geom::polygon::geom_polygon;
+pub use crate::geom::smooth::{geom_smooth, geom_smooth_label};
 pub use crate::geom::histogram::geom_histogram;
 pub use crate::geom::boxplot::geom_boxplot;
 pub use crate::geom::hline::geom_hline;
@@ -18,6 +19,13 @@ pub use crate::geom::segment::geom_segment;
 pub use crate::geom::text::geom_text;
 pub use crate::geom::label::geom_label;
 
+// Layer options shared by every geom (stat/position/data/...)
+pub use crate::layer::LayerBuilderExt;
+
+// Scales, coordinates and facets
+pub use crate::coord::coord_cartesian;
+pub use crate::facet::{facet_grid, facet_wrap, FacetScales, Labeller};
+
 // Aesthetic builder traits (x/y/color/size/etc.)
 pub use crate::aesthetics::builder::*;
 
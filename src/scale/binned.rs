@@ -0,0 +1,292 @@
+//! Binned scales
+//!
+//! A binned scale cuts its continuous domain into classes at a set of edges.
+//! Color and fill scales give each bin one color from their gradient, and
+//! x and y scales move each value to the middle of its bin before stats
+//! run, so a bar chart counts per bin. Edges live in transformed space.
+
+use crate::scale::transform::Transform;
+
+/// How bin edges are placed when no breaks are given
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BinMethod {
+    /// Round numbers spanning the range
+    #[default]
+    Pretty,
+    /// Bins of equal width
+    Equal,
+    /// Bins holding roughly equal numbers of values
+    Quantile,
+    /// Jenks natural breaks, minimizing the variance within each bin
+    Jenks,
+}
+
+// Jenks is quadratic in the number of values, so larger data is summarized
+// by this many evenly spaced order statistics first
+const JENKS_SAMPLE: usize = 500;
+
+/// Bin settings and the edges computed from them
+#[derive(Debug, Clone)]
+pub struct Binning {
+    method: BinMethod,
+    n_bins: usize,
+    // Observed finite values, in transformed space
+    values: Vec<f64>,
+    // Bin edges including both ends, ascending; empty until computed
+    edges: Vec<f64>,
+}
+
+impl Binning {
+    pub fn new(method: BinMethod, n_bins: usize) -> Self {
+        Self {
+            method,
+            n_bins,
+            values: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    pub fn method(&self) -> BinMethod {
+        self.method
+    }
+
+    pub fn n_bins(&self) -> usize {
+        self.n_bins
+    }
+
+    /// Forget observed values and edges, e.g. when the transform changes
+    pub(crate) fn reset(&mut self) {
+        self.values.clear();
+        self.edges.clear();
+    }
+
+    /// Record values, in transformed space, for the data-driven methods
+    pub(crate) fn observe(&mut self, values: impl Iterator<Item = f64>) {
+        self.values.extend(values.filter(|v| v.is_finite()));
+    }
+
+    /// Compute the edges over `range` (transformed space), or over the
+    /// observed values when there is no range. Explicit breaks, in data
+    /// space, become the interior edges.
+    pub(crate) fn compute(
+        &mut self,
+        range: Option<(f64, f64)>,
+        explicit: Option<&[f64]>,
+        transform: &dyn Transform,
+    ) {
+        let observed = self
+            .values
+            .iter()
+            .fold(None, |bounds: Option<(f64, f64)>, &v| match bounds {
+                Some((lo, hi)) => Some((lo.min(v), hi.max(v))),
+                None => Some((v, v)),
+            });
+        let Some((lo, hi)) = range.or(observed) else {
+            self.edges.clear();
+            return;
+        };
+
+        let mut interior = match explicit {
+            Some(breaks) => breaks.iter().map(|&b| transform.transform(b)).collect(),
+            None => self.interior_edges((lo, hi)),
+        };
+        interior.retain(|e| e.is_finite() && *e > lo && *e < hi);
+        interior.sort_by(f64::total_cmp);
+        interior.dedup();
+
+        self.edges = std::iter::once(lo)
+            .chain(interior)
+            .chain(std::iter::once(hi))
+            .collect();
+    }
+
+    fn interior_edges(&self, (lo, hi): (f64, f64)) -> Vec<f64> {
+        let n = self.n_bins.max(1);
+        match self.method {
+            BinMethod::Pretty => super::utils::extended_breaks((lo, hi), n),
+            BinMethod::Equal => (1..n).map(|i| lo + (hi - lo) * i as f64 / n as f64).collect(),
+            BinMethod::Quantile => {
+                let sorted = self.sorted_values(lo, hi);
+                (1..n).filter_map(|i| quantile(&sorted, i as f64 / n as f64)).collect()
+            }
+            BinMethod::Jenks => jenks(&self.sorted_values(lo, hi), n),
+        }
+    }
+
+    fn sorted_values(&self, lo: f64, hi: f64) -> Vec<f64> {
+        let mut sorted: Vec<f64> = self.values.iter().copied().filter(|v| *v >= lo && *v <= hi).collect();
+        sorted.sort_by(f64::total_cmp);
+        sorted
+    }
+
+    /// Edges including both ends, in transformed space
+    pub fn edges(&self) -> &[f64] {
+        &self.edges
+    }
+
+    /// Edges between bins, in transformed space
+    pub fn interior(&self) -> &[f64] {
+        match self.edges.len() {
+            0..=2 => &[],
+            n => &self.edges[1..n - 1],
+        }
+    }
+
+    /// Number of computed bins
+    pub fn len(&self) -> usize {
+        self.edges.len().saturating_sub(1)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The bin holding a value in transformed space. Bins include their
+    /// lower edge, and the last one its upper edge as well.
+    pub fn bin(&self, value: f64) -> Option<usize> {
+        let (&first, &last) = (self.edges.first()?, self.edges.last()?);
+        if !(first..=last).contains(&value) {
+            return None;
+        }
+        let above = self.edges.partition_point(|&e| e <= value);
+        Some(above.saturating_sub(1).min(self.len().saturating_sub(1)))
+    }
+
+    /// Middle of a bin, in transformed space
+    pub fn center(&self, bin: usize) -> Option<f64> {
+        Some((self.edges.get(bin)? + self.edges.get(bin + 1)?) / 2.0)
+    }
+}
+
+impl Default for Binning {
+    fn default() -> Self {
+        Self::new(BinMethod::default(), 5)
+    }
+}
+
+// Quantile of sorted values, interpolating between order statistics
+fn quantile(sorted: &[f64], p: f64) -> Option<f64> {
+    let last = sorted.len().checked_sub(1)?;
+    let h = last as f64 * p;
+    let below = h.floor() as usize;
+    let above = (below + 1).min(last);
+    Some(sorted[below] + (h - below as f64) * (sorted[above] - sorted[below]))
+}
+
+// Fisher-Jenks optimal classification of sorted values into k classes,
+// returning the edges between classes (halfway between neighbouring classes)
+fn jenks(sorted: &[f64], k: usize) -> Vec<f64> {
+    let values: Vec<f64> = if sorted.len() > JENKS_SAMPLE {
+        (0..JENKS_SAMPLE)
+            .filter_map(|i| quantile(sorted, i as f64 / (JENKS_SAMPLE - 1) as f64))
+            .collect()
+    } else {
+        sorted.to_vec()
+    };
+    let n = values.len();
+    if k < 2 {
+        return Vec::new();
+    }
+    if n <= k {
+        // One class per value at most
+        return values.windows(2).filter(|w| w[0] < w[1]).map(|w| (w[0] + w[1]) / 2.0).collect();
+    }
+
+    // lower[l][j]: 1-based index of the first value in the last class when
+    // the first l values are split into j classes; cost[l][j]: its variance
+    let mut lower = vec![vec![0usize; k + 1]; n + 1];
+    let mut cost = vec![vec![f64::INFINITY; k + 1]; n + 1];
+    for j in 1..=k {
+        lower[1][j] = 1;
+        cost[1][j] = 0.0;
+    }
+    for l in 2..=n {
+        let (mut sum, mut sum_sq) = (0.0, 0.0);
+        let mut variance = 0.0;
+        for m in 1..=l {
+            let start = l - m + 1;
+            let v = values[start - 1];
+            sum += v;
+            sum_sq += v * v;
+            variance = sum_sq - sum * sum / m as f64;
+            let before = start - 1;
+            if before == 0 {
+                continue;
+            }
+            for j in 2..=k {
+                let candidate = variance + cost[before][j - 1];
+                if cost[l][j] >= candidate {
+                    lower[l][j] = start;
+                    cost[l][j] = candidate;
+                }
+            }
+        }
+        lower[l][1] = 1;
+        cost[l][1] = variance;
+    }
+
+    let mut edges = Vec::with_capacity(k - 1);
+    let mut end = n;
+    for j in (2..=k).rev() {
+        let start = lower[end][j];
+        if start < 2 {
+            break;
+        }
+        edges.push((values[start - 2] + values[start - 1]) / 2.0);
+        end = start - 1;
+    }
+    edges.reverse();
+    edges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scale::transform::IdentityTransform;
+
+    fn computed(method: BinMethod, n_bins: usize, values: &[f64]) -> Binning {
+        let mut binning = Binning::new(method, n_bins);
+        binning.observe(values.iter().copied());
+        binning.compute(None, None, &IdentityTransform);
+        binning
+    }
+
+    #[test]
+    fn test_equal_and_pretty_edges() {
+        let binning = computed(BinMethod::Equal, 4, &[0.0, 3.0, 10.0]);
+        assert_eq!(binning.edges(), [0.0, 2.5, 5.0, 7.5, 10.0]);
+        assert_eq!(binning.bin(2.5), Some(1));
+        assert_eq!(binning.bin(10.0), Some(3));
+        assert_eq!(binning.bin(10.5), None);
+        assert_eq!(binning.center(0), Some(1.25));
+
+        let binning = computed(BinMethod::Pretty, 5, &[0.3, 9.6]);
+        assert_eq!(binning.interior(), [2.0, 4.0, 6.0, 8.0]);
+    }
+
+    #[test]
+    fn test_quantile_edges() {
+        let values: Vec<f64> = (1..=100).map(|v| (v * v) as f64).collect();
+        let binning = computed(BinMethod::Quantile, 4, &values);
+        assert_eq!(binning.len(), 4);
+        for bin in 0..4 {
+            let count = values.iter().filter(|&&v| binning.bin(v) == Some(bin)).count();
+            assert!((24..=26).contains(&count), "bin {} holds {}", bin, count);
+        }
+    }
+
+    #[test]
+    fn test_jenks_finds_clusters() {
+        let values = [1.0, 1.5, 2.0, 10.0, 10.5, 11.0, 30.0, 31.0];
+        let binning = computed(BinMethod::Jenks, 3, &values);
+        assert_eq!(binning.interior(), [6.0, 20.5]);
+    }
+
+    #[test]
+    fn test_explicit_breaks_and_range() {
+        let mut binning = Binning::default();
+        binning.observe([1.0, 9.0].into_iter());
+        binning.compute(Some((0.0, 20.0)), Some(&[5.0, 25.0, 10.0]), &IdentityTransform);
+        assert_eq!(binning.edges(), [0.0, 5.0, 10.0, 20.0]);
+    }
+}
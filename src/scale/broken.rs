@@ -0,0 +1,192 @@
+//! Broken (discontinuous) positional axes
+//!
+//! An [`AxisBreak`] collapses a range of an x or y scale so that an outlier
+//! doesn't squash the rest of the data. The axis is split into segments
+//! separated by a small gap, and the segment after a break can be drawn
+//! larger or smaller than the rest. Geoms are clipped at the gaps.
+
+use crate::scale::transform::Transform;
+
+/// Fraction of the axis left empty at each break
+const GAP: f64 = 0.03;
+
+/// A range of an x or y scale to leave out, in data space
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AxisBreak {
+    pub from: f64,
+    pub to: f64,
+    /// Relative size of the segment after the break, per unit of data
+    pub scale: f64,
+}
+
+impl AxisBreak {
+    /// Collapse the range from `from` to `to`
+    pub fn new(from: f64, to: f64) -> Self {
+        Self { from, to, scale: 1.0 }
+    }
+
+    /// Draw the segment after the break this many times larger, e.g. 0.25
+    /// to shrink the segment holding an outlier
+    pub fn scale(mut self, scale: f64) -> Self {
+        self.scale = scale;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Segment {
+    start: f64,  // In transformed space
+    end: f64,
+    rate: f64,   // Normalized length per transformed unit
+    offset: f64, // Normalized position of the start
+}
+
+/// The mapping from transformed space to [0, 1] along a broken axis
+#[derive(Debug, Clone)]
+pub(crate) struct BrokenAxis {
+    segments: Vec<Segment>,
+    /// Whether the domain runs from high to low, mirroring the axis
+    reversed: bool,
+}
+
+impl BrokenAxis {
+    /// Split a domain (in transformed space) at the breaks that fall
+    /// inside it, keeping its orientation. Returns `None` if none do.
+    pub fn new(breaks: &[AxisBreak], transform: &dyn Transform, domain: (f64, f64)) -> Option<Self> {
+        let (d0, d1) = (domain.0.min(domain.1), domain.0.max(domain.1));
+        let mut collapsed: Vec<(f64, f64, f64)> = breaks
+            .iter()
+            .filter_map(|b| {
+                let (a, c) = (transform.transform(b.from), transform.transform(b.to));
+                let (lo, hi) = (a.min(c), a.max(c));
+                (lo > d0 && hi < d1).then_some((lo, hi, b.scale))
+            })
+            .collect();
+        if collapsed.is_empty() {
+            return None;
+        }
+        collapsed.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        // Segment bounds and relative rates, before normalizing
+        let mut bounds = vec![(d0, collapsed[0].0, 1.0)];
+        for (i, &(_, hi, scale)) in collapsed.iter().enumerate() {
+            let end = collapsed.get(i + 1).map_or(d1, |next| next.0);
+            bounds.push((hi, end, bounds[i].2 * scale));
+        }
+        let total: f64 = bounds.iter().map(|&(start, end, rate)| (end - start) * rate).sum();
+        let available = 1.0 - GAP * collapsed.len() as f64;
+
+        let mut offset = 0.0;
+        let segments = bounds
+            .into_iter()
+            .map(|(start, end, rate)| {
+                let rate = rate * available / total;
+                let segment = Segment { start, end, rate, offset };
+                offset += (end - start) * rate + GAP;
+                segment
+            })
+            .collect();
+        Some(Self {
+            segments,
+            reversed: domain.0 > domain.1,
+        })
+    }
+
+    /// Map a value in transformed space to [0, 1]. Values inside a
+    /// collapsed range map to the middle of its gap, where they are
+    /// clipped; values outside the domain continue the end segments.
+    pub fn normalize(&self, t: f64) -> f64 {
+        let normalized = self.normalize_increasing(t);
+        if self.reversed { 1.0 - normalized } else { normalized }
+    }
+
+    fn normalize_increasing(&self, t: f64) -> f64 {
+        let first = self.segments[0];
+        if t < first.start {
+            return first.offset + (t - first.start) * first.rate;
+        }
+        for (i, segment) in self.segments.iter().enumerate() {
+            if t <= segment.end {
+                return segment.offset + (t - segment.start).max(0.0) * segment.rate;
+            }
+            match self.segments.get(i + 1) {
+                Some(next) if t < next.start => return next.offset - GAP / 2.0,
+                Some(_) => {}
+                None => return segment.offset + (t - segment.start) * segment.rate,
+            }
+        }
+        unreachable!("the last segment handles every remaining value")
+    }
+
+    /// The gaps between segments, as normalized (start, end) positions
+    /// in order along the axis
+    pub fn gaps(&self) -> Vec<(f64, f64)> {
+        let gaps = self.segments.windows(2).map(|w| (w[1].offset - GAP, w[1].offset));
+        match self.reversed {
+            true => gaps.rev().map(|(start, end)| (1.0 - end, 1.0 - start)).collect(),
+            false => gaps.collect(),
+        }
+    }
+
+    /// The ranges of the segments, in transformed space
+    pub fn ranges(&self) -> Vec<(f64, f64)> {
+        self.segments.iter().map(|s| (s.start, s.end)).collect()
+    }
+
+    /// Whether a value in transformed space falls inside a collapsed range
+    pub fn is_collapsed(&self, t: f64) -> bool {
+        self.segments.windows(2).any(|w| t > w[0].end && t < w[1].start)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scale::transform::IdentityTransform;
+
+    #[test]
+    fn test_broken_axis_mapping() {
+        let breaks = [AxisBreak::new(100.0, 900.0)];
+        let axis = BrokenAxis::new(&breaks, &IdentityTransform, (0.0, 1000.0)).unwrap();
+
+        // Two equal segments share the space left by the gap
+        assert_eq!(axis.normalize(0.0), 0.0);
+        assert!((axis.normalize(100.0) - 0.485).abs() < 1e-9);
+        assert!((axis.normalize(900.0) - 0.515).abs() < 1e-9);
+        assert!((axis.normalize(1000.0) - 1.0).abs() < 1e-9);
+        assert!((axis.normalize(500.0) - 0.5).abs() < 1e-9);
+        assert!(axis.is_collapsed(500.0) && !axis.is_collapsed(50.0));
+        let gaps = axis.gaps();
+        assert_eq!(gaps.len(), 1);
+        assert!((gaps[0].0 - 0.485).abs() < 1e-9 && (gaps[0].1 - 0.515).abs() < 1e-9);
+        assert_eq!(axis.ranges(), vec![(0.0, 100.0), (900.0, 1000.0)]);
+    }
+
+    #[test]
+    fn test_scaled_segment_and_outside_domain() {
+        // The segment after the break is drawn at a quarter of the size
+        let breaks = [AxisBreak::new(10.0, 90.0).scale(0.25)];
+        let axis = BrokenAxis::new(&breaks, &IdentityTransform, (0.0, 100.0)).unwrap();
+        let first = axis.normalize(10.0);
+        let second = 1.0 - axis.normalize(90.0);
+        assert!((first - 4.0 * second).abs() < 1e-9);
+        assert!(axis.normalize(-10.0) < 0.0 && axis.normalize(110.0) > 1.0);
+
+        // Breaks outside the domain are ignored
+        assert!(BrokenAxis::new(&breaks, &IdentityTransform, (20.0, 80.0)).is_none());
+    }
+
+    #[test]
+    fn test_reversed_domain() {
+        let breaks = [AxisBreak::new(10.0, 20.0), AxisBreak::new(60.0, 90.0).scale(0.25)];
+        let axis = BrokenAxis::new(&breaks, &IdentityTransform, (100.0, 0.0)).unwrap();
+        assert!((axis.normalize(100.0) - 0.0).abs() < 1e-9);
+        assert!((axis.normalize(0.0) - 1.0).abs() < 1e-9);
+        assert!(axis.normalize(95.0) < axis.normalize(5.0));
+        let gaps = axis.gaps();
+        assert!(gaps[0].0 < gaps[0].1 && gaps[0].1 < gaps[1].0);
+        assert!((gaps[0].0 - axis.normalize(90.0)).abs() < 1e-9);
+        assert!((gaps[0].1 - axis.normalize(60.0)).abs() < 1e-9);
+        assert!((gaps[1].1 - axis.normalize(10.0)).abs() < 1e-9);
+    }
+}
@@ -1,7 +1,17 @@
 use crate::data::{PrimitiveType, VectorIter};
+use crate::error::PlotError;
+use crate::scale::binned::Binning;
+use crate::scale::identity::{self, Identity};
+use crate::scale::labels::LabelFormatter;
 use crate::scale::traits::{ContinuousDomainScale, DiscreteDomainScale};
+use crate::scale::manual::{self, ManualValues};
+use crate::scale::transform::{IdentityTransform, Transform};
+use crate::scale::utils::ExplicitBreaks;
+use crate::scale::{Expansion, OutOfBounds};
 use crate::theme::{Color, color};
+use crate::utils::data::numeric_values;
 use crate::utils::set::DiscreteSet;
+use crate::visuals::colorspace::ColorSpace;
 use crate::visuals::palette::okabe_ito_palette;
 
 
@@ -10,6 +20,9 @@ use crate::visuals::palette::okabe_ito_palette;
 pub struct DiscreteColorScale {
     palette: Vec<Color>,
     elements: DiscreteSet,
+    manual: Option<ManualValues<Color>>,
+    identity: Option<Identity>,
+    na_value: Color,
 }
 
 impl DiscreteColorScale {
@@ -18,13 +31,36 @@ impl DiscreteColorScale {
         Self {
             palette: okabe_ito_palette(),
             elements: DiscreteSet::new(),
+            manual: None,
+            identity: None,
+            na_value: color::GREY50,
         }
     }
 
+    /// Set the color of the level for missing values (default grey50)
+    pub fn set_na_value(&mut self, na_value: Color) {
+        self.na_value = na_value;
+    }
+
     /// Set a custom color palette.
     pub fn set_palette(&mut self, palette: Vec<Color>) {
         self.palette = palette;
     }
+
+    /// Map chosen levels to chosen colors, see [`manual`].
+    pub fn set_manual(&mut self, manual: ManualValues<Color>) {
+        self.manual = Some(manual);
+    }
+
+    /// Read colors from the data itself, see [`identity`].
+    pub fn set_identity(&mut self, identity: Identity) {
+        self.identity = Some(identity);
+    }
+
+    /// The identity scale installed on this scale, if any
+    pub fn identity(&self) -> Option<&Identity> {
+        self.identity.as_ref()
+    }
 }
 
 impl Default for DiscreteColorScale {
@@ -44,6 +80,10 @@ impl super::traits::DiscreteDomainScale for DiscreteColorScale {
         &self.elements
     }
 
+    fn categories_mut(&mut self) -> &mut DiscreteSet {
+        &mut self.elements
+    }
+
     fn add_categories(&mut self, categories: DiscreteSet) {
         self.elements.union(&categories);
     }
@@ -51,38 +91,65 @@ impl super::traits::DiscreteDomainScale for DiscreteColorScale {
 
 impl super::traits::ColorRangeScale for DiscreteColorScale {
     fn map_value<T: PrimitiveType>(&self, value: &T) -> Option<Color> {
-        let ordinal = match value.to_primitive() {
-            crate::data::PrimitiveValue::Int(x) => self.elements.ordinal(&x),
-            crate::data::PrimitiveValue::Float(_) => None,
-            crate::data::PrimitiveValue::Str(x) => self.elements.ordinal(&x),
-            crate::data::PrimitiveValue::Bool(x) => self.elements.ordinal(&x),
-        }?;
-        Some(self.palette[ordinal])
+        if self.identity.is_some() {
+            return identity::color(value.to_primitive());
+        }
+        let ordinal = value.to_discrete().and_then(|level| self.elements.ordinal(&level));
+        let missing = ordinal.and_then(|i| self.elements.get_at(i)).is_some_and(|level| level.is_na());
+        if missing && self.manual.as_ref().and_then(ManualValues::na_value).is_none() {
+            return Some(self.na_value);
+        }
+        manual::resolve(self.manual.as_ref(), &self.elements, ordinal, |i| self.palette[i])
     }
 }
 
 /// Continuous color scale that maps numeric values to a gradient.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct ContinuousColorScale {
-    domain: (f64, f64),
+    domain: Option<(f64, f64)>, // Domain in transformed space
     colors: Vec<Color>,
-    breaks: Vec<f64>,
+    positions: Option<Vec<f64>>, // Color stops in [0, 1]; evenly spaced if None
+    space: ColorSpace,
+    midpoint: Option<f64>,      // Data space value mapped to the middle of the gradient
+    bins: Option<Binning>,
+    breaks: Vec<f64>,           // Breaks in data space
     labels: Vec<String>,
+    lower_bound: Option<f64>,
+    upper_bound: Option<f64>,
+    explicit: ExplicitBreaks,
+    oob: OutOfBounds,
+    expansion: Expansion,
+    transform: Box<dyn Transform>,
 }
 
 impl ContinuousColorScale {
     /// Create a new continuous color gradient with a list of colors.
     /// Colors are interpolated evenly across the domain.
     pub fn new(domain: (f64, f64), colors: Vec<Color>) -> Self {
+        let mut scale = Self::untrained(colors);
+        scale.domain = Some(domain);
+        scale.compute_breaks(3); // Default 3 breaks for legends
+        scale
+    }
+
+    fn untrained(colors: Vec<Color>) -> Self {
         assert!(!colors.is_empty(), "Must provide at least one color");
-        let mut scale = Self { 
-            domain, 
+        Self {
+            domain: None,
             colors,
+            positions: None,
+            space: ColorSpace::default(),
+            midpoint: None,
+            bins: None,
             breaks: Vec::new(),
             labels: Vec::new(),
-        };
-        scale.compute_breaks(3); // Default 3 breaks for legends
-        scale
+            lower_bound: None,
+            upper_bound: None,
+            explicit: ExplicitBreaks::default(),
+            oob: OutOfBounds::default(),
+            expansion: Expansion::default(),
+            transform: Box::new(IdentityTransform),
+        }
     }
 
     /// Create a two-color gradient (for backwards compatibility).
@@ -92,31 +159,98 @@ impl ContinuousColorScale {
 
     /// Create a default blue to black gradient (like ggplot2).
     pub fn default_gradient(domain: (f64, f64)) -> Self {
-        Self::new(
-            domain,
-            vec![
-                color::NAVYBLUE,   // dark blue (low values)
-                color::LIGHTBLUE3, // light blue (high values)
-            ],
-        )
+        Self::new(domain, default_gradient_colors())
     }
 
     /// Compute breaks and labels for this scale
+    ///
+    /// On a binned scale this also places the bin edges, and the breaks are
+    /// the edges between bins.
     pub fn compute_breaks(&mut self, n: usize) {
-        // Use high density weight (5.0) to strongly prefer exactly n breaks for compact legends
-        // Clamp breaks to domain to ensure color bar doesn't show values outside the data range
-        self.breaks = super::utils::extended_breaks_weighted_clamped(self.domain, n, 5.0, true);
-        self.labels = super::utils::format_breaks(&self.breaks);
+        let Some(domain) = self.domain else {
+            return;
+        };
+        let transform = &self.transform;
+        if let Some(bins) = &mut self.bins {
+            let range = match (self.lower_bound, self.upper_bound) {
+                (Some(lo), Some(hi)) => Some((transform.transform(lo), transform.transform(hi))),
+                _ => None,
+            };
+            bins.compute(range, self.explicit.breaks.as_deref(), transform.as_ref());
+        }
+        let bins = &self.bins;
+        let (breaks, labels) = self.explicit.resolve(
+            transform.as_ref(),
+            domain,
+            || match bins {
+                Some(bins) => bins.interior().iter().map(|&e| transform.inverse(e)).collect(),
+                // Use high density weight (5.0) to strongly prefer exactly n breaks for compact legends
+                // Clamp breaks to domain to ensure color bar doesn't show values outside the data range
+                None => super::utils::extended_breaks_weighted_clamped(domain, n, 5.0, true)
+                    .into_iter()
+                    .map(|b| transform.inverse(b))
+                    .collect(),
+            },
+            super::utils::format_breaks,
+        );
+        self.breaks = breaks;
+        self.labels = labels;
     }
 
-    /// Get the computed breaks
-    pub fn breaks(&self) -> &[f64] {
-        &self.breaks
+    /// The colors interpolated across the domain
+    pub fn colors(&self) -> &[Color] {
+        &self.colors
     }
 
-    /// Get the computed labels
-    pub fn labels(&self) -> &[String] {
-        &self.labels
+    /// Replace the colors, optionally placing each at a position in [0, 1]
+    /// (one per color, ascending) instead of spacing them evenly
+    pub fn set_colors(&mut self, colors: Vec<Color>, positions: Option<Vec<f64>>) {
+        assert!(!colors.is_empty(), "Must provide at least one color");
+        self.colors = colors;
+        self.positions = positions;
+    }
+
+    /// Set the color space the gradient is interpolated in (RGB by default)
+    pub fn set_space(&mut self, space: ColorSpace) {
+        self.space = space;
+    }
+
+    /// Center a diverging gradient on this data value
+    ///
+    /// The side of the domain furthest from the midpoint spans half the
+    /// gradient, so equal distances either side get equally strong colors.
+    pub fn set_midpoint(&mut self, midpoint: f64) {
+        self.midpoint = Some(midpoint);
+    }
+
+    /// Cut the domain into bins, each shown in a single color
+    pub fn set_bins(&mut self, bins: Binning) {
+        self.bins = Some(bins);
+        self.compute_breaks(3);
+    }
+
+    /// The bins, if this is a binned scale
+    pub fn bins(&self) -> Option<&Binning> {
+        self.bins.as_ref()
+    }
+
+    /// The color of each bin, low to high
+    pub fn bin_colors(&self) -> Vec<Color> {
+        let Some(bins) = &self.bins else {
+            return Vec::new();
+        };
+        let domain = self.domain.unwrap_or((0.0, 1.0));
+        (0..bins.len()).map(|bin| self.bin_color(bins, bin, domain)).collect()
+    }
+
+    // Bins are colored at even steps along the gradient, or by where their
+    // centers fall when the gradient has a midpoint
+    fn bin_color(&self, bins: &Binning, bin: usize, domain: (f64, f64)) -> Color {
+        let t = match (self.midpoint, bins.center(bin)) {
+            (Some(_), Some(center)) => self.rescale(center, domain),
+            _ => (bin as f64 + 0.5) / bins.len() as f64,
+        };
+        self.interpolate_color(t)
     }
 
     /// Interpolate between colors in the palette.
@@ -128,57 +262,228 @@ impl ContinuousColorScale {
         }
 
         // Determine which segment of the color palette we're in
-        let segment_count = self.colors.len() - 1;
-        let scaled = t * segment_count as f64;
-        let segment = (scaled.floor() as usize).min(segment_count - 1);
-        let t = scaled - segment as f64;
+        let (segment, t) = match &self.positions {
+            Some(positions) => {
+                let Some(end) = positions.iter().position(|&p| p > t) else {
+                    return self.colors[self.colors.len() - 1];
+                };
+                if end == 0 {
+                    return self.colors[0];
+                }
+                let (start, stop) = (positions[end - 1], positions[end]);
+                (end - 1, (t - start) / (stop - start))
+            }
+            None => {
+                let segment_count = self.colors.len() - 1;
+                let scaled = t * segment_count as f64;
+                let segment = (scaled.floor() as usize).min(segment_count - 1);
+                (segment, scaled - segment as f64)
+            }
+        };
 
         // Interpolate between the two colors in this segment
-        let Color(r1, g1, b1, a1) = self.colors[segment];
-        let Color(r2, g2, b2, a2) = self.colors[segment + 1];
+        self.space.interpolate(self.colors[segment], self.colors[segment + 1], t)
+    }
 
-        let r = (r1 as f64 + t * (r2 as f64 - r1 as f64)) as u8;
-        let g = (g1 as f64 + t * (g2 as f64 - g1 as f64)) as u8;
-        let b = (b1 as f64 + t * (b2 as f64 - b1 as f64)) as u8;
-        let a = (a1 as f64 + t * (a2 as f64 - a1 as f64)) as u8;
+    /// Position in [0, 1] along the gradient of a value in transformed space
+    fn rescale(&self, v: f64, (min_domain, max_domain): (f64, f64)) -> f64 {
+        let midpoint = self
+            .midpoint
+            .map(|m| self.transform.transform(m))
+            .filter(|m| m.is_finite());
+        let t = match midpoint {
+            Some(m) => {
+                let half = (min_domain - m).abs().max((max_domain - m).abs());
+                if half > 0.0 {
+                    0.5 + (v - m) / (2.0 * half)
+                } else {
+                    0.5
+                }
+            }
+            None => (v - min_domain) / (max_domain - min_domain),
+        };
+        t.clamp(0.0, 1.0)
+    }
+}
 
-        Color(r, g, b, a)
+/// Gradient settings gathered by a scale builder
+///
+/// Checked and applied after the rest of the scale's configuration, so a
+/// midpoint is validated against the scale's final transform.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Gradient {
+    pub colors: Option<Vec<Color>>,
+    pub values: Option<Vec<f64>>,
+    pub midpoint: Option<f64>,
+    pub space: Option<ColorSpace>,
+}
+
+impl Gradient {
+    /// The first setting present, for rejecting them on non-color scales
+    pub fn setting(&self) -> Option<&'static str> {
+        [
+            ("colors", self.colors.is_some()),
+            ("values", self.values.is_some()),
+            ("midpoint", self.midpoint.is_some()),
+            ("space", self.space.is_some()),
+        ]
+        .into_iter()
+        .find(|(_, set)| *set)
+        .map(|(name, _)| name)
     }
+
+    pub fn apply_to(self, scale: &mut ContinuousColorScale) -> Result<(), PlotError> {
+        let invalid = |details: String| Err(PlotError::InvalidScaleParameters { details });
+
+        if let Some(n) = self.colors.as_ref().map(Vec::len).filter(|&n| n < 2) {
+            return invalid(format!("a gradient needs at least two colors, got {}", n));
+        }
+        if let Some(values) = &self.values {
+            let n_colors = self.colors.as_ref().map_or(scale.colors.len(), Vec::len);
+            if values.len() != n_colors {
+                return invalid(format!("{} values given for {} colors", values.len(), n_colors));
+            }
+            if values.iter().any(|v| !(0.0..=1.0).contains(v)) || values.windows(2).any(|w| w[0] > w[1]) {
+                return invalid(format!("values must ascend within [0, 1], got {:?}", values));
+            }
+        }
+        if let Some(midpoint) = self.midpoint.filter(|&m| !scale.transform.transform(m).is_finite()) {
+            return invalid(format!("midpoint {} is outside the {} scale's domain", midpoint, scale.transform.name()));
+        }
+
+        match (self.colors, self.values) {
+            (Some(colors), values) => scale.set_colors(colors, values),
+            (None, Some(values)) => scale.positions = Some(values),
+            (None, None) => {}
+        }
+        if let Some(midpoint) = self.midpoint {
+            scale.set_midpoint(midpoint);
+        }
+        if let Some(space) = self.space {
+            scale.set_space(space);
+        }
+        Ok(())
+    }
+}
+
+fn default_gradient_colors() -> Vec<Color> {
+    vec![
+        color::NAVYBLUE,   // dark blue (low values)
+        color::LIGHTBLUE3, // light blue (high values)
+    ]
 }
 
 impl Default for ContinuousColorScale {
     fn default() -> Self {
-        Self::default_gradient((0.0, 1.0))
+        Self::untrained(default_gradient_colors())
+    }
+}
+
+impl std::fmt::Debug for ContinuousColorScale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ContinuousColorScale")
+            .field("domain", &self.domain)
+            .field("colors", &self.colors)
+            .field("positions", &self.positions)
+            .field("space", &self.space)
+            .field("midpoint", &self.midpoint)
+            .field("bins", &self.bins)
+            .field("breaks", &self.breaks)
+            .field("labels", &self.labels)
+            .field("lower_bound", &self.lower_bound)
+            .field("upper_bound", &self.upper_bound)
+            .field("explicit", &self.explicit)
+            .field("oob", &self.oob)
+            .field("expansion", &self.expansion)
+            .field("transform", &self.transform.name())
+            .finish()
     }
 }
 
 impl super::traits::ScaleBase for ContinuousColorScale {
     fn train<'a>(&mut self, iter: VectorIter<'a>) {
-        self.train_continuous(iter);
+        let Some(bins) = &mut self.bins else {
+            self.train_continuous(iter);
+            return;
+        };
+        // Quantile and Jenks bins need the values, not just their range
+        let values = numeric_values(iter);
+        bins.observe(values.iter().map(|&v| self.transform.transform(v)));
+        self.train_continuous(VectorIter::Float(Box::new(values.into_iter())));
     }
 }
 
 impl super::traits::ContinuousDomainScale for ContinuousColorScale {
     fn domain(&self) -> Option<(f64, f64)> {
-        Some(self.domain)
+        self.domain
     }
 
     fn set_domain(&mut self, domain: (f64, f64)) {
-        self.domain = domain;
+        self.domain = Some(domain);
         self.compute_breaks(3); // Recompute breaks when domain changes (3 for legends)
     }
 
     fn limits(&self) -> (Option<f64>, Option<f64>) {
-        (None, None)
+        (self.lower_bound, self.upper_bound)
+    }
+
+    fn set_limits(&mut self, limits: (f64, f64)) {
+        self.lower_bound = Some(limits.0);
+        self.upper_bound = Some(limits.1);
     }
 
     fn breaks(&self) -> &[f64] {
         &self.breaks
     }
 
+    fn set_breaks(&mut self, breaks: Vec<f64>) {
+        self.explicit.breaks = Some(breaks);
+        self.compute_breaks(3);
+    }
+
     fn labels(&self) -> &[String] {
         &self.labels
     }
+
+    fn set_labels(&mut self, labels: Vec<String>) {
+        self.explicit.labels = Some(labels);
+        self.compute_breaks(3);
+    }
+
+    fn set_label_formatter(&mut self, formatter: Box<dyn LabelFormatter>) {
+        self.explicit.formatter = Some(formatter);
+        self.compute_breaks(3);
+    }
+
+    fn transform(&self) -> &dyn Transform {
+        self.transform.as_ref()
+    }
+
+    fn set_transform(&mut self, transform: Box<dyn Transform>) {
+        self.transform = transform;
+        self.domain = None;
+        self.breaks.clear();
+        self.labels.clear();
+        if let Some(bins) = &mut self.bins {
+            bins.reset();
+        }
+    }
+
+    fn oob(&self) -> OutOfBounds {
+        self.oob
+    }
+
+    fn set_oob(&mut self, oob: OutOfBounds) {
+        self.oob = oob;
+    }
+
+    fn expansion(&self) -> Expansion {
+        self.expansion
+    }
+
+    fn set_expansion(&mut self, expansion: Expansion) {
+        self.expansion = expansion;
+    }
 }
 
 impl super::traits::ColorRangeScale for ContinuousColorScale {
@@ -189,11 +494,26 @@ impl super::traits::ColorRangeScale for ContinuousColorScale {
             crate::data::PrimitiveValue::Str(_) => None,
             crate::data::PrimitiveValue::Bool(_) => None,
         }?;
-        let (min_domain, max_domain) = self.domain;
-        if v < min_domain || v > max_domain {
+        if !self.transform.is_in_domain(v) {
+            return None;
+        }
+        let v = self.transform.transform(v);
+        if !v.is_finite() {
+            return None;
+        }
+        let v = self.apply_oob(v)?;
+        let (min_domain, max_domain) = self.domain.unwrap_or((0.0, 1.0));
+        if self.oob != OutOfBounds::Keep && (v < min_domain || v > max_domain) {
             return None;
         }
-        let t = (v - min_domain) / (max_domain - min_domain);
+        if let Some(bins) = self.bins.as_ref().filter(|bins| !bins.is_empty()) {
+            // Kept values past the outer edges take the end bins
+            let (first, last) = (bins.edges()[0], bins.edges()[bins.len()]);
+            let bin = bins.bin(v.clamp(first, last))?;
+            return Some(self.bin_color(bins, bin, (min_domain, max_domain)));
+        }
+        // Kept values past the domain take the end colors of the gradient
+        let t = self.rescale(v, (min_domain, max_domain));
         Some(self.interpolate_color(t))
     }
 }
@@ -208,4 +528,65 @@ mod tests {
         assert_eq!(scale.palette.len(), 8);
     }
 
+    fn map(scale: &ContinuousColorScale, v: f64) -> Color {
+        use crate::scale::traits::ColorRangeScale;
+        scale.map_value(&v).unwrap()
+    }
+
+    #[test]
+    fn test_positions_place_colors() {
+        let (red, green, blue) = (Color::rgb(255, 0, 0), Color::rgb(0, 255, 0), Color::rgb(0, 0, 255));
+        let mut scale = ContinuousColorScale::new((0.0, 10.0), vec![red, green, blue]);
+        assert_eq!(map(&scale, 5.0), green);
+
+        scale.set_colors(vec![red, green, blue], Some(vec![0.0, 0.8, 1.0]));
+        assert_eq!(map(&scale, 8.0), green);
+        assert_eq!(map(&scale, 4.0), Color::rgb(127, 127, 0));
+        assert_eq!(map(&scale, 9.0), Color::rgb(0, 127, 127));
+
+        // Stops need not cover the ends; the end colors extend outwards
+        scale.set_colors(vec![red, blue], Some(vec![0.2, 0.6]));
+        assert_eq!(map(&scale, 1.0), red);
+        assert_eq!(map(&scale, 7.0), blue);
+    }
+
+    #[test]
+    fn test_midpoint_centers_gradient() {
+        let (low, mid, high) = (Color::rgb(0, 0, 255), Color::rgb(255, 255, 255), Color::rgb(255, 0, 0));
+        let mut scale = ContinuousColorScale::new((-2.0, 8.0), vec![low, mid, high]);
+        scale.set_midpoint(0.0);
+        assert_eq!(map(&scale, 0.0), mid);
+        assert_eq!(map(&scale, 8.0), high);
+        // -2 is a quarter of the way from the midpoint to the far end
+        assert_eq!(map(&scale, -2.0), Color::rgb(191, 191, 255));
+    }
+
+    #[test]
+    fn test_gradient_settings_validated() {
+        let mut scale = ContinuousColorScale::default();
+        let apply = |scale: &mut ContinuousColorScale, gradient: Gradient| gradient.apply_to(scale);
+
+        let one_color = Gradient { colors: Some(vec![color::RED]), ..Gradient::default() };
+        assert!(matches!(apply(&mut scale, one_color), Err(PlotError::InvalidScaleParameters { .. })));
+
+        let mismatched = Gradient { values: Some(vec![0.0, 0.5, 1.0]), ..Gradient::default() };
+        assert!(apply(&mut scale, mismatched).is_err());
+
+        let descending = Gradient { values: Some(vec![1.0, 0.0]), ..Gradient::default() };
+        assert!(apply(&mut scale, descending).is_err());
+
+        scale.set_transform(Box::new(crate::scale::transform::Log10Transform));
+        let midpoint = Gradient { midpoint: Some(0.0), ..Gradient::default() };
+        assert!(apply(&mut scale, midpoint).is_err());
+
+        let valid = Gradient {
+            values: Some(vec![0.0, 0.25]),
+            midpoint: Some(1.0),
+            space: Some(ColorSpace::Oklab),
+            ..Gradient::default()
+        };
+        apply(&mut scale, valid).unwrap();
+        assert_eq!(scale.space, ColorSpace::Oklab);
+        assert_eq!(scale.midpoint, Some(1.0));
+    }
 }
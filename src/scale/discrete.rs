@@ -0,0 +1,667 @@
+//! Discrete scale configuration
+//!
+//! Levels of a discrete scale are sorted by default. A [`DiscreteScaleBuilder`]
+//! can instead fix the levels with `limits`, keep them in order of
+//! appearance, order them by a summary of another column, reverse them and
+//! relabel them. Categorical columns keep their own level order, and a
+//! builder with `drop(false)` keeps levels no row uses. Missing values of a
+//! discrete column form their own level, labelled "NA", which comes last
+//! and is drawn in the scale's `na_value` color.
+
+use crate::aesthetics::{Aesthetic, AestheticDomain};
+use crate::data::{DataSource, DiscreteValue};
+use crate::error::PlotError;
+use crate::layer::Layer;
+use crate::scale::ScaleSet;
+use crate::scale::traits::DiscreteDomainScale;
+use crate::theme::Color;
+use crate::utils::set::{DiscreteSet, LevelOrder};
+
+/// Identifies which discrete scale a builder configures
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiscreteAesthetic {
+    X,
+    Y,
+    Color,
+    Fill,
+    Shape,
+    Linetype,
+    Size,
+    Alpha,
+}
+
+impl DiscreteAesthetic {
+    pub(crate) const ALL: [DiscreteAesthetic; 8] = [
+        DiscreteAesthetic::X,
+        DiscreteAesthetic::Y,
+        DiscreteAesthetic::Color,
+        DiscreteAesthetic::Fill,
+        DiscreteAesthetic::Shape,
+        DiscreteAesthetic::Linetype,
+        DiscreteAesthetic::Size,
+        DiscreteAesthetic::Alpha,
+    ];
+
+    /// The aesthetic whose mapped column feeds this scale
+    pub(crate) fn aesthetic(self) -> Aesthetic {
+        use AestheticDomain::Discrete;
+        match self {
+            DiscreteAesthetic::X => Aesthetic::X(Discrete),
+            DiscreteAesthetic::Y => Aesthetic::Y(Discrete),
+            DiscreteAesthetic::Color => Aesthetic::Color(Discrete),
+            DiscreteAesthetic::Fill => Aesthetic::Fill(Discrete),
+            DiscreteAesthetic::Shape => Aesthetic::Shape,
+            DiscreteAesthetic::Linetype => Aesthetic::Linetype,
+            DiscreteAesthetic::Size => Aesthetic::Size(Discrete),
+            DiscreteAesthetic::Alpha => Aesthetic::Alpha(Discrete),
+        }
+    }
+
+    pub(crate) fn categories(self, scales: &ScaleSet) -> &DiscreteSet {
+        match self {
+            DiscreteAesthetic::X => scales.x_discrete.categories(),
+            DiscreteAesthetic::Y => scales.y_discrete.categories(),
+            DiscreteAesthetic::Color => scales.color_discrete.categories(),
+            DiscreteAesthetic::Fill => scales.fill_discrete.categories(),
+            DiscreteAesthetic::Shape => scales.shape_scale.categories(),
+            DiscreteAesthetic::Linetype => scales.linestyle_scale.categories(),
+            DiscreteAesthetic::Size => scales.size_discrete.categories(),
+            DiscreteAesthetic::Alpha => scales.alpha_discrete.categories(),
+        }
+    }
+
+    fn categories_mut(self, scales: &mut ScaleSet) -> &mut DiscreteSet {
+        match self {
+            DiscreteAesthetic::X => scales.x_discrete.categories_mut(),
+            DiscreteAesthetic::Y => scales.y_discrete.categories_mut(),
+            DiscreteAesthetic::Color => scales.color_discrete.categories_mut(),
+            DiscreteAesthetic::Fill => scales.fill_discrete.categories_mut(),
+            DiscreteAesthetic::Shape => scales.shape_scale.categories_mut(),
+            DiscreteAesthetic::Linetype => scales.linestyle_scale.categories_mut(),
+            DiscreteAesthetic::Size => scales.size_discrete.categories_mut(),
+            DiscreteAesthetic::Alpha => scales.alpha_discrete.categories_mut(),
+        }
+    }
+}
+
+/// Summary of another column used to order levels, as in R's `reorder()`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LevelSummary {
+    #[default]
+    Mean,
+    Median,
+    Sum,
+    Min,
+    Max,
+}
+
+impl LevelSummary {
+    fn summarise(self, values: &mut [f64]) -> f64 {
+        let n = values.len() as f64;
+        match self {
+            LevelSummary::Mean => values.iter().sum::<f64>() / n,
+            LevelSummary::Sum => values.iter().sum(),
+            LevelSummary::Min => values.iter().copied().fold(f64::INFINITY, f64::min),
+            LevelSummary::Max => values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            LevelSummary::Median => {
+                values.sort_by(f64::total_cmp);
+                let mid = values.len() / 2;
+                if values.len().is_multiple_of(2) {
+                    (values[mid - 1] + values[mid]) / 2.0
+                } else {
+                    values[mid]
+                }
+            }
+        }
+    }
+}
+
+/// Builder for configuring discrete scales (position, color, fill, shape,
+/// linetype, size, alpha)
+#[derive(Debug, Clone)]
+pub struct DiscreteScaleBuilder {
+    pub(crate) aesthetic: DiscreteAesthetic,
+    limits: Option<Vec<DiscreteValue>>,
+    appearance: bool,
+    reorder: Option<(String, LevelSummary)>,
+    reverse: bool,
+    labels: Option<Vec<String>>,
+    relabel: Vec<(DiscreteValue, String)>,
+    na_value: Option<Color>,
+    drop: bool,
+}
+
+impl DiscreteScaleBuilder {
+    pub fn new(aesthetic: DiscreteAesthetic) -> Self {
+        Self {
+            aesthetic,
+            limits: None,
+            appearance: false,
+            reorder: None,
+            reverse: false,
+            labels: None,
+            relabel: Vec::new(),
+            na_value: None,
+            drop: true,
+        }
+    }
+
+    /// Use exactly these levels, in this order
+    ///
+    /// Levels missing from the data still get a slot; rows whose level is
+    /// not listed are dropped before stats run.
+    pub fn limits<L: Into<DiscreteValue>>(mut self, limits: impl IntoIterator<Item = L>) -> Self {
+        self.limits = Some(limits.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Order levels as they first appear in the data instead of sorting them
+    pub fn in_order(mut self) -> Self {
+        self.appearance = true;
+        self
+    }
+
+    /// Order levels by a summary of a numeric column, ascending
+    pub fn reorder_by(mut self, column: impl Into<String>, summary: LevelSummary) -> Self {
+        self.reorder = Some((column.into(), summary));
+        self
+    }
+
+    /// Reverse the level order
+    pub fn reverse(mut self) -> Self {
+        self.reverse = true;
+        self
+    }
+
+    /// Label the levels, in display order
+    pub fn labels<S: Into<String>>(mut self, labels: impl IntoIterator<Item = S>) -> Self {
+        self.labels = Some(labels.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Label a single level
+    pub fn label(mut self, level: impl Into<DiscreteValue>, label: impl Into<String>) -> Self {
+        self.relabel.push((level.into(), label.into()));
+        self
+    }
+
+    /// Color for missing values on a color or fill scale (default grey50)
+    pub fn na_value(mut self, color: Color) -> Self {
+        self.na_value = Some(color);
+        self
+    }
+
+    /// Drop levels of a categorical column that no row uses (default true)
+    pub fn drop(mut self, drop: bool) -> Self {
+        self.drop = drop;
+        self
+    }
+
+    /// Apply the order, limits and labels to a scale set
+    ///
+    /// Ordering by another column needs the layer data, so it is applied
+    /// separately by [`DiscreteScaleBuilder::apply_reorder`].
+    pub(crate) fn apply_to(&self, scales: &mut ScaleSet) -> Result<(), PlotError> {
+        let orderings = [
+            ("limits", self.limits.is_some()),
+            ("in_order", self.appearance),
+            ("reorder_by", self.reorder.is_some()),
+        ];
+        let chosen: Vec<&str> = orderings.iter().filter(|(_, set)| *set).map(|(name, _)| *name).collect();
+        if chosen.len() > 1 {
+            return Err(PlotError::InvalidScaleParameters {
+                details: format!("a discrete scale takes only one of {}", chosen.join(", ")),
+            });
+        }
+
+        if let Some(na_value) = self.na_value {
+            match self.aesthetic {
+                DiscreteAesthetic::Color => scales.color_discrete.set_na_value(na_value),
+                DiscreteAesthetic::Fill => scales.fill_discrete.set_na_value(na_value),
+                _ => {
+                    return Err(PlotError::InvalidScaleParameters {
+                        details: "na_value applies to discrete color and fill scales only".to_string(),
+                    });
+                }
+            }
+        }
+
+        let set = self.aesthetic.categories_mut(scales);
+        if let Some(limits) = &self.limits {
+            if limits.is_empty() {
+                return Err(PlotError::InvalidScaleParameters {
+                    details: "discrete limits must list at least one level".to_string(),
+                });
+            }
+            set.set_limits(limits.clone());
+        }
+        if self.appearance {
+            set.set_order(LevelOrder::Appearance);
+        }
+        if self.reverse {
+            set.set_reverse(true);
+        }
+        if let Some(labels) = &self.labels {
+            set.set_labels(labels.clone());
+        }
+        for (level, label) in &self.relabel {
+            set.relabel(level.clone(), label.clone());
+        }
+        Ok(())
+    }
+
+    /// Order the levels by a summary of another column, read from the
+    /// layers' data before any stat runs
+    pub(crate) fn apply_reorder(
+        &self,
+        layers: &[Layer],
+        scales: &mut ScaleSet,
+        parent_data: &dyn DataSource,
+    ) -> Result<(), PlotError> {
+        let Some((column, summary)) = &self.reorder else {
+            return Ok(());
+        };
+
+        let aes = self.aesthetic.aesthetic();
+        let mut grouped: Vec<(DiscreteValue, Vec<f64>)> = Vec::new();
+        for layer in layers {
+            for (level, value) in layer.levels_with(&aes, column, parent_data)? {
+                if value.is_nan() {
+                    continue;
+                }
+                match grouped.iter_mut().find(|(other, _)| *other == level) {
+                    Some((_, values)) => values.push(value),
+                    None => grouped.push((level, vec![value])),
+                }
+            }
+        }
+
+        let keys = grouped
+            .into_iter()
+            .map(|(level, mut values)| (level, summary.summarise(&mut values)))
+            .collect();
+        self.aesthetic.categories_mut(scales).set_order(LevelOrder::ByKey(keys));
+        Ok(())
+    }
+
+    /// Check positional labels against the trained levels
+    pub(crate) fn check_labels(&self, scales: &ScaleSet) -> Result<(), PlotError> {
+        let set = self.aesthetic.categories(scales);
+        match set.labels_len() {
+            Some(labels_count) if labels_count != set.len() => Err(PlotError::ScaleMismatch {
+                breaks_count: set.len(),
+                labels_count,
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Order discrete scales by the levels of categorical columns mapped to
+/// them, keeping unused levels where a builder asks for `drop(false)`
+///
+/// Runs before the builders are applied so their own ordering wins.
+pub(crate) fn apply_levels(
+    builders: &[DiscreteScaleBuilder],
+    layers: &[Layer],
+    scales: &mut ScaleSet,
+    parent_data: &dyn DataSource,
+) {
+    for scale_aes in DiscreteAesthetic::ALL {
+        let aes = scale_aes.aesthetic();
+        let Some(levels) = layers.iter().find_map(|layer| layer.column_levels(&aes, parent_data)) else {
+            continue;
+        };
+        let levels: Vec<DiscreteValue> = levels.into_iter().map(DiscreteValue::from).collect();
+        let keep_unused = builders.iter().any(|builder| builder.aesthetic == scale_aes && !builder.drop);
+
+        let set = scale_aes.categories_mut(scales);
+        if keep_unused {
+            for level in &levels {
+                set.add(level);
+            }
+        }
+        let keys = levels.into_iter().enumerate().map(|(i, level)| (level, i as f64)).collect();
+        set.set_order(LevelOrder::ByKey(keys));
+    }
+}
+
+/// Create a discrete scale builder for the x aesthetic
+pub fn scale_x_discrete() -> DiscreteScaleBuilder {
+    DiscreteScaleBuilder::new(DiscreteAesthetic::X)
+}
+
+/// Create a discrete scale builder for the y aesthetic
+pub fn scale_y_discrete() -> DiscreteScaleBuilder {
+    DiscreteScaleBuilder::new(DiscreteAesthetic::Y)
+}
+
+/// Create a discrete scale builder for the color aesthetic
+pub fn scale_color_discrete() -> DiscreteScaleBuilder {
+    DiscreteScaleBuilder::new(DiscreteAesthetic::Color)
+}
+
+/// Create a discrete scale builder for the fill aesthetic
+pub fn scale_fill_discrete() -> DiscreteScaleBuilder {
+    DiscreteScaleBuilder::new(DiscreteAesthetic::Fill)
+}
+
+/// Create a discrete scale builder for the shape aesthetic
+pub fn scale_shape_discrete() -> DiscreteScaleBuilder {
+    DiscreteScaleBuilder::new(DiscreteAesthetic::Shape)
+}
+
+/// Create a discrete scale builder for the linetype aesthetic
+pub fn scale_linetype_discrete() -> DiscreteScaleBuilder {
+    DiscreteScaleBuilder::new(DiscreteAesthetic::Linetype)
+}
+
+/// Create a discrete scale builder for the size aesthetic
+pub fn scale_size_discrete() -> DiscreteScaleBuilder {
+    DiscreteScaleBuilder::new(DiscreteAesthetic::Size)
+}
+
+/// Create a discrete scale builder for the alpha aesthetic
+pub fn scale_alpha_discrete() -> DiscreteScaleBuilder {
+    DiscreteScaleBuilder::new(DiscreteAesthetic::Alpha)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aesthetics::builder::{
+        ColorDiscreteAesBuilder, FillDiscreteAesBuilder, ShapeAesBuilder, XContinuousAesBuilder,
+        XDiscreteAesBuilder, YContinuousAesBuilder,
+    };
+    use crate::data::{Categorical, VectorValue};
+    use crate::error::to_io_error;
+    use crate::geom::bar::geom_bar;
+    use crate::geom::point::geom_point;
+    use crate::layer::LayerBuilderExt;
+    use crate::plot::plot;
+    use crate::position::dodge::Dodge;
+    use crate::scale::traits::{ColorRangeScale, ContinuousRangeScale};
+    use crate::theme::color;
+    use crate::theme::traits::{AreaElement, PointElement};
+    use crate::utils::dataframe::DataFrame;
+    use crate::utils::mtcars::mtcars;
+
+    fn init_test_logging() {
+        let _ = env_logger::builder()
+            .is_test(true)
+            .filter_level(log::LevelFilter::Debug)
+            .try_init();
+    }
+
+    fn months() -> Box<dyn DataSource> {
+        Box::new(DataFrame::from_columns(vec![
+            ("month", VectorValue::from(vec!["Jan", "Feb", "Mar", "Apr", "May", "Jun"])),
+            ("rain", VectorValue::from(vec![78.0, 52.0, 61.0, 44.0, 49.0, 45.0])),
+        ]))
+    }
+
+    fn x_levels(scales: &ScaleSet) -> Vec<String> {
+        scales.x_discrete.categories().iter().map(|level| level.to_string()).collect()
+    }
+
+    #[test]
+    fn discrete_order_and_reverse() {
+        let data = months();
+        let build = |scale: DiscreteScaleBuilder| {
+            let p = plot(&data).aes(|a| {
+                a.x_discrete("month");
+                a.y_continuous("rain");
+            }) + geom_bar()
+                + scale;
+            p.build().map_err(to_io_error).expect("Failed to build plot")
+        };
+
+        let sorted = build(scale_x_discrete());
+        assert_eq!(x_levels(&sorted.scales), ["Apr", "Feb", "Jan", "Jun", "Mar", "May"]);
+
+        let in_order = build(scale_x_discrete().in_order());
+        assert_eq!(x_levels(&in_order.scales), ["Jan", "Feb", "Mar", "Apr", "May", "Jun"]);
+        assert_eq!(in_order.scales.x_discrete.map_value(&"Jan".to_string()), Some(0.5 / 6.0));
+
+        let reversed = build(scale_x_discrete().in_order().reverse());
+        assert_eq!(x_levels(&reversed.scales), ["Jun", "May", "Apr", "Mar", "Feb", "Jan"]);
+
+        let by_rain = build(scale_x_discrete().reorder_by("rain", LevelSummary::Mean));
+        assert_eq!(x_levels(&by_rain.scales), ["Apr", "Jun", "May", "Feb", "Mar", "Jan"]);
+    }
+
+    #[test]
+    fn discrete_limits_and_labels() {
+        let data = mtcars();
+        let p = plot(&data).aes(|a| {
+            a.x_continuous("wt");
+            a.y_continuous("mpg");
+            a.color_discrete("cyl");
+        }) + geom_point()
+            + scale_color_discrete()
+                .limits([8, 4, 5])
+                .labels(["eight", "four", "five"])
+                .label(4, "4 cyl");
+        let p = p.build().map_err(to_io_error).expect("Failed to build plot");
+
+        let categories = p.scales.color_discrete.categories();
+        let labels: Vec<String> = (0..categories.len()).filter_map(|i| categories.label_at(i)).collect();
+        assert_eq!(labels, ["eight", "4 cyl", "five"]);
+        assert_eq!(p.scales.color_discrete.map_value(&6i64), None);
+        // Six-cylinder cars are dropped with their level
+        let rows = p.layers[0].data(p.data.as_ref()).len();
+        assert_eq!(rows, 32 - 7);
+    }
+
+    #[test]
+    fn discrete_invalid_settings() {
+        let data = months();
+        let build = |scale: DiscreteScaleBuilder| {
+            let p = plot(&data).aes(|a| {
+                a.x_discrete("month");
+                a.y_continuous("rain");
+            }) + geom_bar()
+                + scale;
+            p.build()
+        };
+
+        assert!(build(scale_x_discrete().limits(["Jan"]).in_order()).is_err());
+        assert!(build(scale_x_discrete().limits(Vec::<&str>::new())).is_err());
+        assert!(matches!(
+            build(scale_x_discrete().labels(["a", "b"])),
+            Err(PlotError::ScaleMismatch { breaks_count: 6, labels_count: 2 })
+        ));
+        assert!(matches!(
+            build(scale_x_discrete().reorder_by("missing", LevelSummary::Sum)),
+            Err(PlotError::MissingColumn { .. })
+        ));
+        assert!(matches!(
+            build(scale_x_discrete().reorder_by("month", LevelSummary::Sum)),
+            Err(PlotError::InvalidColumnType { .. })
+        ));
+    }
+
+    #[test]
+    fn level_summaries() {
+        let mut values = [3.0, 1.0, 2.0, 10.0];
+        assert_eq!(LevelSummary::Mean.summarise(&mut values), 4.0);
+        assert_eq!(LevelSummary::Median.summarise(&mut values), 2.5);
+        assert_eq!(LevelSummary::Sum.summarise(&mut values), 16.0);
+        assert_eq!(LevelSummary::Min.summarise(&mut values), 1.0);
+        assert_eq!(LevelSummary::Max.summarise(&mut values), 10.0);
+    }
+
+    #[test]
+    fn discrete_scale_1() {
+        init_test_logging();
+
+        let data = months();
+        let builder = plot(&data).aes(|a| {
+            a.x_discrete("month");
+            a.y_continuous("rain");
+        }) + geom_bar().fill(color::STEELBLUE)
+            + scale_x_discrete().in_order();
+
+        let p = builder
+            .build()
+            .map_err(to_io_error)
+            .expect("Failed to build plot");
+        p.save("tests/images/discrete_scale_1.png", 800, 600)
+            .map_err(to_io_error)
+            .expect("Failed to save plot image");
+    }
+
+    #[test]
+    fn discrete_scale_2_limits() {
+        init_test_logging();
+
+        let data = mtcars();
+        let builder = plot(&data).aes(|a| {
+            a.x_continuous("wt");
+            a.y_continuous("mpg");
+            a.color_discrete("cyl");
+        }) + geom_point().size(3.0)
+            + scale_color_discrete()
+                .limits([8, 4])
+                .labels(["Eight", "Four"]);
+
+        let p = builder
+            .build()
+            .map_err(to_io_error)
+            .expect("Failed to build plot");
+        p.save("tests/images/discrete_scale_2_limits.png", 800, 600)
+            .map_err(to_io_error)
+            .expect("Failed to save plot image");
+    }
+
+    #[test]
+    fn discrete_missing_level() {
+        init_test_logging();
+
+        let mut df = DataFrame::new();
+        df.add_column("x", vec![1.0, 2.0, 3.0, 4.0, 5.0]);
+        df.add_optional_column("y", vec![Some(2.0), Some(3.0), Some(1.0), None, Some(4.0)]);
+        df.add_optional_column("group", vec![Some("b"), None, Some("a"), Some("b"), None]);
+        let data: Box<dyn DataSource> = Box::new(df);
+
+        let build = |na_rm: bool| {
+            let p = plot(&data).aes(|a| {
+                a.x_continuous("x");
+                a.y_continuous("y");
+                a.color_discrete("group");
+            }) + geom_point().na_rm(na_rm)
+                + scale_color_discrete().na_value(color::BLACK);
+            p.build().map_err(to_io_error).expect("Failed to build plot")
+        };
+
+        // Missing groups form a last "NA" level; the missing y is dropped
+        let p = build(false);
+        let levels: Vec<String> = p.scales.color_discrete.categories().iter().map(|l| l.to_string()).collect();
+        assert_eq!(levels, ["a", "b", "NA"]);
+        assert_eq!(p.scales.color_discrete.map_value(&DiscreteValue::Na), Some(color::BLACK));
+        assert_eq!(p.layers[0].data(p.data.as_ref()).len(), 4);
+        p.save("tests/images/discrete_missing_level.png", 800, 600)
+            .map_err(to_io_error)
+            .expect("Failed to save plot image");
+
+        // na_rm drops every row with a missing value
+        let p = build(true);
+        let levels: Vec<String> = p.scales.color_discrete.categories().iter().map(|l| l.to_string()).collect();
+        assert_eq!(levels, ["a", "b"]);
+        assert_eq!(p.layers[0].data(p.data.as_ref()).len(), 2);
+
+        let p = plot(&data).aes(|a| {
+            a.x_continuous("x");
+            a.y_continuous("y");
+            a.shape("group");
+        }) + geom_point()
+            + scale_shape_discrete().na_value(color::BLACK);
+        assert!(matches!(p.build(), Err(PlotError::InvalidScaleParameters { .. })));
+    }
+
+    #[test]
+    fn discrete_missing_level_apart_from_na_string() {
+        init_test_logging();
+
+        let mut df = DataFrame::new();
+        df.add_column("x", vec![1.0, 2.0, 3.0, 4.0]);
+        df.add_column("y", vec![2.0, 3.0, 1.0, 4.0]);
+        df.add_optional_column("region", vec![Some("NA"), None, Some("EU"), Some("NA")]);
+        df.add_optional_column("cyl", vec![Some(8_i64), None, Some(10), Some(4)]);
+        let data: Box<dyn DataSource> = Box::new(df);
+
+        // "NA" (North America) is an ordinary level next to the missing one
+        let p = (plot(&data).aes(|a| {
+            a.x_continuous("x");
+            a.y_continuous("y");
+            a.color_discrete("region");
+        }) + geom_point())
+        .build()
+        .map_err(to_io_error)
+        .expect("Failed to build plot");
+        let levels: Vec<DiscreteValue> = p.scales.color_discrete.categories().iter().collect();
+        assert_eq!(levels, [DiscreteValue::from("EU"), DiscreteValue::from("NA"), DiscreteValue::Na]);
+        assert_ne!(p.scales.color_discrete.map_value(&"NA".to_string()), Some(color::GREY50));
+
+        // Integer levels keep every row and their numeric order
+        let p = (plot(&data).aes(|a| {
+            a.x_continuous("x");
+            a.y_continuous("y");
+            a.color_discrete("cyl");
+        }) + geom_point())
+        .build()
+        .map_err(to_io_error)
+        .expect("Failed to build plot");
+        let labels: Vec<String> = p.scales.color_discrete.categories().iter().map(|l| l.to_string()).collect();
+        assert_eq!(labels, ["4", "8", "10", "NA"]);
+        assert_eq!(p.layers[0].data(p.data.as_ref()).len(), 4);
+    }
+
+    #[test]
+    fn discrete_categorical_levels() {
+        init_test_logging();
+
+        let size = Categorical::new(
+            ["large", "small", "medium", "large", "small", "medium"],
+            ["small", "medium", "large", "huge"],
+        );
+        let data: Box<dyn DataSource> = Box::new(DataFrame::from_columns(vec![
+            ("site", VectorValue::from(vec!["a", "a", "a", "b", "b", "b"])),
+            ("count", VectorValue::from(vec![3.0, 5.0, 4.0, 6.0, 2.0, 7.0])),
+            ("size", VectorValue::from(size)),
+        ]));
+        let fill_levels = |scales: &ScaleSet| -> Vec<String> {
+            scales.fill_discrete.categories().iter().map(|l| l.to_string()).collect()
+        };
+        let build = |scale: DiscreteScaleBuilder| {
+            let p = plot(&data).aes(|a| {
+                a.x_discrete("site");
+                a.y_continuous("count");
+                a.fill_discrete("size");
+            }) + geom_bar().position(Dodge::default())
+                + scale;
+            p.build().map_err(to_io_error).expect("Failed to build plot")
+        };
+
+        // Levels follow the column, not sorted order, and unused ones are dropped
+        let p = build(scale_fill_discrete());
+        assert_eq!(fill_levels(&p.scales), ["small", "medium", "large"]);
+
+        // Dodged bars follow the level order too
+        let offsets: Vec<f64> = p.layers[0]
+            .mapping
+            .get_resolved_float(&Aesthetic::XOffset)
+            .expect("Dodge sets x offsets")
+            .collect();
+        assert!(offsets[1] < offsets[2] && offsets[2] < offsets[0]);
+        p.save("tests/images/discrete_categorical_levels.png", 800, 600)
+            .map_err(to_io_error)
+            .expect("Failed to save plot image");
+
+        let p = build(scale_fill_discrete().drop(false));
+        assert_eq!(fill_levels(&p.scales), ["small", "medium", "large", "huge"]);
+
+        let p = build(scale_fill_discrete().reverse());
+        assert_eq!(fill_levels(&p.scales), ["large", "medium", "small"]);
+    }
+}
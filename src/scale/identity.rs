@@ -0,0 +1,262 @@
+//! Identity scales
+//!
+//! An identity scale takes the mapped data as the visual values themselves
+//! instead of mapping it through a palette or range: a column of "#ff0000"
+//! strings colors points red, and a column of 3.0s draws them at size 3.
+//! Identity scales have no legend unless one is asked for, which then lists
+//! the distinct values.
+
+use crate::data::PrimitiveValue;
+use crate::scale::ScaleSet;
+use crate::theme::Color;
+use crate::visuals::{LineStyle, Shape};
+
+/// Most distinct values a continuous identity scale lists in its legend;
+/// with more, the legend shows the scale's breaks instead
+const MAX_LEGEND_VALUES: usize = 12;
+
+/// State of an identity scale, kept by each scale it is installed on
+#[derive(Debug, Clone, Default)]
+pub struct Identity {
+    legend: bool,
+    values: Vec<f64>, // Distinct values seen by a continuous scale, sorted
+}
+
+impl Identity {
+    /// Whether the scale gets a legend
+    pub fn legend(&self) -> bool {
+        self.legend
+    }
+
+    /// Record the values a continuous scale is trained on
+    pub(crate) fn observe(&mut self, values: &[f64]) {
+        for &value in values.iter().filter(|v| v.is_finite()) {
+            if self.values.len() > MAX_LEGEND_VALUES {
+                return;
+            }
+            if let Err(i) = self.values.binary_search_by(|v| v.total_cmp(&value)) {
+                self.values.insert(i, value);
+            }
+        }
+    }
+
+    /// The distinct values for a legend, or `None` if there are too many
+    pub fn values(&self) -> Option<&[f64]> {
+        (self.values.len() <= MAX_LEGEND_VALUES).then_some(self.values.as_slice())
+    }
+}
+
+/// A color written as a string ("#ff0000", "rgb(255, 0, 0)" or "red"), or
+/// an RGBA integer
+pub(crate) fn color(value: PrimitiveValue) -> Option<Color> {
+    match value {
+        PrimitiveValue::Str(s) => s.parse().ok(),
+        PrimitiveValue::Int(rgba) => Some(Color::from(rgba)),
+        _ => None,
+    }
+}
+
+/// A size or alpha given as a number, or a string holding one
+pub(crate) fn number(value: PrimitiveValue) -> Option<f64> {
+    let number = match value {
+        PrimitiveValue::Int(x) => x as f64,
+        PrimitiveValue::Float(x) => x,
+        PrimitiveValue::Str(s) => s.trim().parse().ok()?,
+        PrimitiveValue::Bool(_) => return None,
+    };
+    number.is_finite().then_some(number)
+}
+
+/// A shape given by its code (see `Shape::from`), its name, or a single
+/// character to draw
+pub(crate) fn shape(value: PrimitiveValue) -> Option<Shape> {
+    match value {
+        PrimitiveValue::Int(code) => Some(Shape::from(code)),
+        PrimitiveValue::Str(s) => match s.to_ascii_lowercase().as_str() {
+            "circle" => Some(Shape::Circle),
+            "square" => Some(Shape::Square),
+            "triangle" => Some(Shape::Triangle),
+            "diamond" => Some(Shape::Diamond),
+            "cross" => Some(Shape::Cross),
+            "plus" => Some(Shape::Plus),
+            _ => {
+                let mut chars = s.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Some(Shape::Other(c)),
+                    _ => None,
+                }
+            }
+        },
+        _ => None,
+    }
+}
+
+/// A line style given by name ("solid", "dashed", "dotted", "dotdash",
+/// "longdash") or as a dash pattern (see `LineStyle::from`)
+pub(crate) fn linetype(value: PrimitiveValue) -> Option<LineStyle> {
+    match value {
+        PrimitiveValue::Str(s) => Some(match s.to_ascii_lowercase().as_str() {
+            "solid" => LineStyle::Solid,
+            "dashed" => LineStyle::from("-"),
+            "dotted" => LineStyle::from("."),
+            "dotdash" => LineStyle::from("-."),
+            "longdash" => LineStyle::from("- "),
+            _ => LineStyle::from(s.as_str()),
+        }),
+        _ => None,
+    }
+}
+
+/// Identifies which aesthetic an identity scale applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdentityAesthetic {
+    Color,
+    Fill,
+    Shape,
+    Linetype,
+    Size,
+    Alpha,
+}
+
+/// Builder for an identity scale
+#[derive(Debug, Clone)]
+pub struct IdentityScaleBuilder {
+    aesthetic: IdentityAesthetic,
+    legend: bool,
+}
+
+impl IdentityScaleBuilder {
+    fn new(aesthetic: IdentityAesthetic) -> Self {
+        Self {
+            aesthetic,
+            legend: false,
+        }
+    }
+
+    /// Draw a legend listing the distinct values (default: no legend)
+    pub fn legend(mut self) -> Self {
+        self.legend = true;
+        self
+    }
+
+    /// Install the scale on the discrete and continuous scales of its
+    /// aesthetic. Colors, shapes and line styles are read from discrete
+    /// data only.
+    pub(crate) fn apply_to(self, scales: &mut ScaleSet) {
+        let identity = Identity {
+            legend: self.legend,
+            values: Vec::new(),
+        };
+        match self.aesthetic {
+            IdentityAesthetic::Color => scales.color_discrete.set_identity(identity),
+            IdentityAesthetic::Fill => scales.fill_discrete.set_identity(identity),
+            IdentityAesthetic::Shape => scales.shape_scale.set_identity(identity),
+            IdentityAesthetic::Linetype => scales.linestyle_scale.set_identity(identity),
+            IdentityAesthetic::Size => {
+                scales.size_continuous.set_identity(identity.clone());
+                scales.size_discrete.set_identity(identity);
+            }
+            IdentityAesthetic::Alpha => {
+                scales.alpha_continuous.set_identity(identity.clone());
+                scales.alpha_discrete.set_identity(identity);
+            }
+        }
+    }
+}
+
+/// Use the mapped data as colors, e.g. "#ff0000", "rgb(255, 0, 0)" or "red"
+pub fn scale_color_identity() -> IdentityScaleBuilder {
+    IdentityScaleBuilder::new(IdentityAesthetic::Color)
+}
+
+/// Use the mapped data as fill colors
+pub fn scale_fill_identity() -> IdentityScaleBuilder {
+    IdentityScaleBuilder::new(IdentityAesthetic::Fill)
+}
+
+/// Use the mapped data as shapes, by code, name or character
+pub fn scale_shape_identity() -> IdentityScaleBuilder {
+    IdentityScaleBuilder::new(IdentityAesthetic::Shape)
+}
+
+/// Use the mapped data as line styles, by name or dash pattern
+pub fn scale_linetype_identity() -> IdentityScaleBuilder {
+    IdentityScaleBuilder::new(IdentityAesthetic::Linetype)
+}
+
+/// Use the mapped data as sizes, in points
+pub fn scale_size_identity() -> IdentityScaleBuilder {
+    IdentityScaleBuilder::new(IdentityAesthetic::Size)
+}
+
+/// Use the mapped data as opacities in [0, 1]
+pub fn scale_alpha_identity() -> IdentityScaleBuilder {
+    IdentityScaleBuilder::new(IdentityAesthetic::Alpha)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aesthetics::builder::{
+        ColorDiscreteAesBuilder, SizeContinuousAesBuilder, XContinuousAesBuilder,
+        YContinuousAesBuilder,
+    };
+    use crate::data::{VectorIter, VectorValue};
+    use crate::error::to_io_error;
+    use crate::geom::point::geom_point;
+    use crate::plot::plot;
+    use crate::scale::traits::{ColorRangeScale, ContinuousRangeScale, ScaleBase, ShapeRangeScale};
+    use crate::theme::color;
+    use crate::utils::dataframe::DataFrame;
+
+    #[test]
+    fn identity_values() {
+        let mut scales = ScaleSet::default();
+        scale_color_identity().apply_to(&mut scales);
+        scale_size_identity().legend().apply_to(&mut scales);
+        scale_shape_identity().apply_to(&mut scales);
+
+        let color = &scales.color_discrete;
+        assert_eq!(color.map_value(&"#ff0000".to_string()), Some(Color(255, 0, 0, 255)));
+        assert_eq!(color.map_value(&"navyblue".to_string()), Some(color::NAVYBLUE));
+        assert_eq!(color.map_value(&"not a color".to_string()), None);
+
+        let size = &mut scales.size_continuous;
+        size.train(VectorIter::Float(Box::new(vec![3.0, 1.0, 3.0, 8.5].into_iter())));
+        assert_eq!(size.map_value(&8.5), Some(8.5));
+        assert_eq!(size.map_value(&-1.0), None);
+        assert_eq!(size.identity().and_then(Identity::values), Some(&[1.0, 3.0, 8.5][..]));
+
+        assert_eq!(scales.shape_scale.map_value(&"triangle".to_string()), Some(Shape::Triangle));
+        assert_eq!(scales.shape_scale.map_value(&2i64), Some(Shape::Triangle));
+        assert_eq!(scales.shape_scale.map_value(&"x".to_string()), Some(Shape::Other('x')));
+        assert_eq!(linetype("dashed".into()), Some(LineStyle::from("-")));
+    }
+
+    #[test]
+    fn identity_scale_1() {
+        let mut df = DataFrame::new();
+        df.add_column("x", VectorValue::from(vec![1.0, 2.0, 3.0, 4.0]));
+        df.add_column("y", VectorValue::from(vec![2.0, 1.0, 4.0, 3.0]));
+        df.add_column("color", VectorValue::from(vec!["#e41a1c", "rgb(55, 126, 184)", "forestgreen", "#984ea3"]));
+        df.add_column("size", VectorValue::from(vec![2.0, 4.0, 6.0, 8.0]));
+        let data: Box<dyn crate::data::DataSource> = Box::new(df);
+
+        let builder = plot(&data).aes(|a| {
+            a.x_continuous("x");
+            a.y_continuous("y");
+            a.color_discrete("color");
+            a.size_continuous("size");
+        }) + geom_point()
+            + scale_color_identity()
+            + scale_size_identity().legend();
+
+        let p = builder
+            .build()
+            .map_err(to_io_error)
+            .expect("Failed to build plot");
+        p.save("tests/images/identity_scale_1.png", 800, 600)
+            .map_err(to_io_error)
+            .expect("Failed to save plot image");
+    }
+}
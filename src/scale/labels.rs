@@ -0,0 +1,516 @@
+//! Label formatters for continuous scales
+//!
+//! A [`LabelFormatter`] turns a scale's breaks (in data space) into labels.
+//! Attach one to a scale with `ContinuousScaleBuilder::label_formatter`, or
+//! to a legend with `LegendGuide::label_formatter`. Formatters see all the
+//! breaks at once, so they can pick a precision that tells them apart.
+
+/// Formats a set of breaks, in data space, as labels
+pub trait LabelFormatter: Send + Sync {
+    /// Format the breaks, returning one label per break
+    fn format(&self, breaks: &[f64]) -> Vec<String>;
+
+    /// A short name for debugging
+    fn name(&self) -> &str;
+
+    /// Clone this formatter into a Box
+    fn box_clone(&self) -> Box<dyn LabelFormatter>;
+}
+
+impl Clone for Box<dyn LabelFormatter> {
+    fn clone(&self) -> Box<dyn LabelFormatter> {
+        self.box_clone()
+    }
+}
+
+impl std::fmt::Debug for dyn LabelFormatter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "LabelFormatter({})", self.name())
+    }
+}
+
+/// Plain numbers with a prefix, suffix, multiplier and thousands separator.
+/// Percent and currency labels are numbers with a preset scale or prefix.
+#[derive(Debug, Clone)]
+pub struct NumberFormat {
+    name: &'static str,
+    accuracy: Option<f64>,
+    scale: f64,
+    prefix: String,
+    suffix: String,
+    big_mark: String,
+}
+
+impl NumberFormat {
+    fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            accuracy: None,
+            scale: 1.0,
+            prefix: String::new(),
+            suffix: String::new(),
+            big_mark: String::new(),
+        }
+    }
+
+    /// Round labels to a multiple of this, e.g. 0.1 for one decimal place.
+    /// By default just enough decimals are shown to tell the breaks apart.
+    pub fn accuracy(mut self, accuracy: f64) -> Self {
+        self.accuracy = Some(accuracy);
+        self
+    }
+
+    /// Multiply values by this before formatting, e.g. 100 for percentages
+    pub fn scale(mut self, scale: f64) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Text placed before the number (after any minus sign)
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Text placed after the number
+    pub fn suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.suffix = suffix.into();
+        self
+    }
+
+    /// Separator between groups of three digits, e.g. "," or a thin space
+    pub fn big_mark(mut self, mark: impl Into<String>) -> Self {
+        self.big_mark = mark.into();
+        self
+    }
+}
+
+impl LabelFormatter for NumberFormat {
+    fn format(&self, breaks: &[f64]) -> Vec<String> {
+        let scaled: Vec<f64> = breaks.iter().map(|b| b * self.scale).collect();
+        let decimals = match self.accuracy {
+            Some(accuracy) => accuracy_decimals(accuracy),
+            None => auto_decimals(&scaled),
+        };
+        scaled
+            .iter()
+            .map(|&v| {
+                let v = match self.accuracy {
+                    Some(accuracy) if accuracy > 0.0 => (v / accuracy).round() * accuracy,
+                    _ => v,
+                };
+                let digits = group_digits(&format!("{:.*}", decimals, v.abs()), &self.big_mark);
+                let sign = if is_negative(v, decimals) { "-" } else { "" };
+                format!("{}{}{}{}", sign, self.prefix, digits, self.suffix)
+            })
+            .collect()
+    }
+
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn box_clone(&self) -> Box<dyn LabelFormatter> {
+        Box::new(self.clone())
+    }
+}
+
+/// Plain numbers, e.g. "1500" or "2.5"
+pub fn label_number() -> NumberFormat {
+    NumberFormat::new("number")
+}
+
+/// Numbers with thousands separators, e.g. "1,500,000"
+pub fn label_comma() -> NumberFormat {
+    NumberFormat::new("comma").big_mark(",")
+}
+
+/// Proportions as percentages, e.g. 0.25 as "25%"
+pub fn label_percent() -> NumberFormat {
+    NumberFormat::new("percent").scale(100.0).suffix("%").big_mark(",")
+}
+
+/// Amounts of money with a currency prefix and/or suffix, e.g.
+/// `label_currency("€", "")` or `label_currency("", " kr")`
+pub fn label_currency(prefix: impl Into<String>, suffix: impl Into<String>) -> NumberFormat {
+    NumberFormat::new("currency").prefix(prefix).suffix(suffix).big_mark(",")
+}
+
+/// Amounts in dollars, e.g. "$1,500" or "-$20"
+pub fn label_dollar() -> NumberFormat {
+    label_currency("$", "")
+}
+
+// SI prefixes from pico to peta, by power of 1000
+const SI_PREFIXES: [(i32, &str); 10] = [
+    (-12, "p"),
+    (-9, "n"),
+    (-6, "µ"),
+    (-3, "m"),
+    (0, ""),
+    (3, "k"),
+    (6, "M"),
+    (9, "G"),
+    (12, "T"),
+    (15, "P"),
+];
+
+/// Numbers with SI (metric) prefixes, e.g. "1.5k", "20M" or "3 µs"
+#[derive(Debug, Clone)]
+pub struct SiFormat {
+    unit: String,
+    accuracy: Option<f64>,
+}
+
+impl SiFormat {
+    /// Round the scaled number to a multiple of this, e.g. 1 for "2k"
+    /// rather than "1.5k"
+    pub fn accuracy(mut self, accuracy: f64) -> Self {
+        self.accuracy = Some(accuracy);
+        self
+    }
+}
+
+impl LabelFormatter for SiFormat {
+    fn format(&self, breaks: &[f64]) -> Vec<String> {
+        breaks
+            .iter()
+            .map(|&v| {
+                let round = |scaled: f64| match self.accuracy {
+                    Some(accuracy) if accuracy > 0.0 => (scaled / accuracy).round() * accuracy,
+                    _ => (scaled * 100.0).round() / 100.0,
+                };
+                let (rounded, exponent) = if v == 0.0 || !v.is_finite() {
+                    (v, 0)
+                } else {
+                    let mut exponent = ((v.abs().log10() / 3.0).floor() as i32 * 3).clamp(-12, 15);
+                    let mut rounded = round(v / 10f64.powi(exponent));
+                    // Rounding may carry the number into the next prefix
+                    if rounded.abs() >= 1000.0 && exponent < 15 {
+                        exponent += 3;
+                        rounded = round(v / 10f64.powi(exponent));
+                    }
+                    (rounded, exponent)
+                };
+                let prefix = SI_PREFIXES.iter().find(|(e, _)| *e == exponent).map_or("", |(_, p)| p);
+                let number = match self.accuracy {
+                    Some(accuracy) if accuracy > 0.0 => format!("{:.*}", accuracy_decimals(accuracy), rounded),
+                    _ => trimmed(rounded, 2),
+                };
+                let space = if self.unit.is_empty() { "" } else { " " };
+                format!("{}{}{}{}", number, space, prefix, self.unit)
+            })
+            .collect()
+    }
+
+    fn name(&self) -> &str {
+        "si"
+    }
+
+    fn box_clone(&self) -> Box<dyn LabelFormatter> {
+        Box::new(self.clone())
+    }
+}
+
+/// Numbers with SI prefixes and an optional unit, e.g. `label_si("")` gives
+/// "1.5k" and `label_si("g")` gives "1.5 kg"
+pub fn label_si(unit: impl Into<String>) -> SiFormat {
+    SiFormat {
+        unit: unit.into(),
+        accuracy: None,
+    }
+}
+
+/// Numbers in scientific notation with a superscript exponent, e.g. "2.5×10⁻³"
+#[derive(Debug, Clone)]
+pub struct ScientificFormat {
+    digits: usize,
+}
+
+impl ScientificFormat {
+    /// Show at most this many significant digits (3 by default)
+    pub fn digits(mut self, digits: usize) -> Self {
+        self.digits = digits.max(1);
+        self
+    }
+}
+
+impl LabelFormatter for ScientificFormat {
+    fn format(&self, breaks: &[f64]) -> Vec<String> {
+        breaks
+            .iter()
+            .map(|&v| {
+                if v == 0.0 || !v.is_finite() {
+                    return trimmed(v, 0);
+                }
+                let mut exponent = v.abs().log10().floor() as i32;
+                let decimals = self.digits - 1;
+                let rounding = 10f64.powi(decimals as i32);
+                let mut mantissa = (v / 10f64.powi(exponent) * rounding).round() / rounding;
+                // Rounding the mantissa may carry it to 10
+                if mantissa.abs() >= 10.0 {
+                    exponent += 1;
+                    mantissa /= 10.0;
+                }
+                format!("{}×10{}", trimmed(mantissa, decimals), superscript(exponent))
+            })
+            .collect()
+    }
+
+    fn name(&self) -> &str {
+        "scientific"
+    }
+
+    fn box_clone(&self) -> Box<dyn LabelFormatter> {
+        Box::new(self.clone())
+    }
+}
+
+/// Numbers in scientific notation with a superscript exponent, e.g. "2.5×10⁻³"
+pub fn label_scientific() -> ScientificFormat {
+    ScientificFormat { digits: 3 }
+}
+
+/// Sizes in bytes, e.g. "1.5 KiB" or "20 MiB"
+#[derive(Debug, Clone)]
+pub struct BytesFormat {
+    decimal: bool,
+}
+
+impl BytesFormat {
+    /// Use powers of 1000 (kB, MB, GB) instead of 1024 (KiB, MiB, GiB)
+    pub fn decimal(mut self) -> Self {
+        self.decimal = true;
+        self
+    }
+}
+
+impl LabelFormatter for BytesFormat {
+    fn format(&self, breaks: &[f64]) -> Vec<String> {
+        let (base, units): (f64, [&str; 6]) = if self.decimal {
+            (1000.0, ["B", "kB", "MB", "GB", "TB", "PB"])
+        } else {
+            (1024.0, ["B", "KiB", "MiB", "GiB", "TiB", "PiB"])
+        };
+        breaks
+            .iter()
+            .map(|&v| {
+                let mut scaled = v;
+                let mut unit = 0;
+                while scaled.abs() >= base && unit < units.len() - 1 {
+                    scaled /= base;
+                    unit += 1;
+                }
+                format!("{} {}", trimmed(scaled, 1), units[unit])
+            })
+            .collect()
+    }
+
+    fn name(&self) -> &str {
+        "bytes"
+    }
+
+    fn box_clone(&self) -> Box<dyn LabelFormatter> {
+        Box::new(self.clone())
+    }
+}
+
+/// Sizes in bytes with binary units, e.g. "1.5 KiB"; `.decimal()` for kB
+pub fn label_bytes() -> BytesFormat {
+    BytesFormat { decimal: false }
+}
+
+/// Whole numbers as ordinals, e.g. "1st", "2nd", "11th", "23rd"
+#[derive(Debug, Clone)]
+pub struct OrdinalFormat;
+
+impl LabelFormatter for OrdinalFormat {
+    fn format(&self, breaks: &[f64]) -> Vec<String> {
+        breaks
+            .iter()
+            .map(|&v| {
+                let n = v.round() as i64;
+                let suffix = match (n.abs() % 10, n.abs() % 100) {
+                    (_, 11..=13) => "th",
+                    (1, _) => "st",
+                    (2, _) => "nd",
+                    (3, _) => "rd",
+                    _ => "th",
+                };
+                format!("{}{}", n, suffix)
+            })
+            .collect()
+    }
+
+    fn name(&self) -> &str {
+        "ordinal"
+    }
+
+    fn box_clone(&self) -> Box<dyn LabelFormatter> {
+        Box::new(self.clone())
+    }
+}
+
+/// Whole numbers as ordinals, e.g. "1st", "2nd", "11th", "23rd"
+pub fn label_ordinal() -> OrdinalFormat {
+    OrdinalFormat
+}
+
+/// P-values, showing values below the accuracy as e.g. "<0.001"
+#[derive(Debug, Clone)]
+pub struct PValueFormat {
+    accuracy: f64,
+}
+
+impl PValueFormat {
+    /// The smallest p-value shown as a number (0.001 by default)
+    pub fn accuracy(mut self, accuracy: f64) -> Self {
+        self.accuracy = accuracy;
+        self
+    }
+}
+
+impl LabelFormatter for PValueFormat {
+    fn format(&self, breaks: &[f64]) -> Vec<String> {
+        let decimals = accuracy_decimals(self.accuracy);
+        breaks
+            .iter()
+            .map(|&p| {
+                if p < self.accuracy {
+                    format!("<{:.*}", decimals, self.accuracy)
+                } else if p > 1.0 - self.accuracy {
+                    format!(">{:.*}", decimals, 1.0 - self.accuracy)
+                } else {
+                    format!("{:.*}", decimals, p)
+                }
+            })
+            .collect()
+    }
+
+    fn name(&self) -> &str {
+        "pvalue"
+    }
+
+    fn box_clone(&self) -> Box<dyn LabelFormatter> {
+        Box::new(self.clone())
+    }
+}
+
+/// P-values, showing values below 0.001 as "<0.001"
+pub fn label_pvalue() -> PValueFormat {
+    PValueFormat { accuracy: 0.001 }
+}
+
+// Decimal places needed to show multiples of `accuracy`
+fn accuracy_decimals(accuracy: f64) -> usize {
+    if accuracy <= 0.0 || !accuracy.is_finite() {
+        return 0;
+    }
+    (-accuracy.log10() - 1e-9).ceil().max(0.0) as usize
+}
+
+// Fewest decimal places that show every value exactly, but no more than
+// two beyond what the spacing between them needs
+fn auto_decimals(values: &[f64]) -> usize {
+    let spacing = values
+        .windows(2)
+        .map(|w| (w[1] - w[0]).abs())
+        .filter(|&d| d > 1e-10)
+        .min_by(f64::total_cmp)
+        .map_or(0, accuracy_decimals);
+    (0..spacing + 2)
+        .find(|&d| values.iter().all(|&v| is_exact(v, d)))
+        .unwrap_or(spacing + 2)
+}
+
+fn is_exact(value: f64, decimals: usize) -> bool {
+    let shifted = value * 10f64.powi(decimals as i32);
+    (shifted - shifted.round()).abs() < 1e-6 * shifted.abs().max(1.0)
+}
+
+// A value with at most `max_decimals` decimals, trailing zeros dropped
+fn trimmed(value: f64, max_decimals: usize) -> String {
+    let decimals = (0..max_decimals).find(|&d| is_exact(value, d)).unwrap_or(max_decimals);
+    let formatted = format!("{:.*}", decimals, value);
+    if formatted.starts_with('-') && formatted[1..].chars().all(|c| c == '0' || c == '.') {
+        formatted[1..].to_string()
+    } else {
+        formatted
+    }
+}
+
+// Whether a value still shows as negative after rounding
+fn is_negative(value: f64, decimals: usize) -> bool {
+    value < 0.0 && format!("{:.*}", decimals, value.abs()).chars().any(|c| c.is_ascii_digit() && c != '0')
+}
+
+fn group_digits(number: &str, mark: &str) -> String {
+    if mark.is_empty() {
+        return number.to_string();
+    }
+    let (integer, fraction) = number.split_at(number.find('.').unwrap_or(number.len()));
+    let mut grouped = String::new();
+    for (i, digit) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i).is_multiple_of(3) {
+            grouped.push_str(mark);
+        }
+        grouped.push(digit);
+    }
+    grouped + fraction
+}
+
+fn superscript(exponent: i32) -> String {
+    exponent
+        .to_string()
+        .chars()
+        .map(|c| match c {
+            '-' => '⁻',
+            '0' => '⁰',
+            '1' => '¹',
+            '2' => '²',
+            '3' => '³',
+            '4' => '⁴',
+            '5' => '⁵',
+            '6' => '⁶',
+            '7' => '⁷',
+            '8' => '⁸',
+            _ => '⁹',
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_number_formats() {
+        assert_eq!(label_comma().format(&[0.0, 1500.0, 1_250_000.0]), ["0", "1,500", "1,250,000"]);
+        assert_eq!(label_number().format(&[0.25, 0.5, 0.75]), ["0.25", "0.50", "0.75"]);
+        assert_eq!(label_percent().format(&[0.0, 0.125, 0.25]), ["0.0%", "12.5%", "25.0%"]);
+        assert_eq!(label_percent().accuracy(1.0).format(&[0.123, 0.5]), ["12%", "50%"]);
+        assert_eq!(label_dollar().format(&[-20.0, 0.0, 1500.0]), ["-$20", "$0", "$1,500"]);
+        assert_eq!(label_currency("", " kr").accuracy(0.01).format(&[3.5]), ["3.50 kr"]);
+        assert_eq!(label_number().accuracy(5.0).format(&[12.0, -1.0]), ["10", "0"]);
+    }
+
+    #[test]
+    fn test_si_and_bytes() {
+        assert_eq!(label_si("").format(&[0.0, 1500.0, 2e6, 3e9]), ["0", "1.5k", "2M", "3G"]);
+        assert_eq!(label_si("s").format(&[0.002, 5e-6]), ["2 ms", "5 µs"]);
+        assert_eq!(label_si("").accuracy(1.0).format(&[1499.0]), ["1k"]);
+        assert_eq!(label_si("").format(&[999_999.0, -999.999]), ["1M", "-1k"]);
+        assert_eq!(label_si("").accuracy(1.0).format(&[999_600.0]), ["1M"]);
+        assert_eq!(label_si("").accuracy(0.0).format(&[1500.0]), ["1.5k"]);
+        assert_eq!(label_bytes().format(&[512.0, 1536.0, 20.0 * 1024.0 * 1024.0]), ["512 B", "1.5 KiB", "20 MiB"]);
+        assert_eq!(label_bytes().decimal().format(&[1500.0]), ["1.5 kB"]);
+    }
+
+    #[test]
+    fn test_scientific_ordinal_pvalue() {
+        assert_eq!(label_scientific().format(&[0.0, 0.0025, 1e5]), ["0", "2.5×10⁻³", "1×10⁵"]);
+        assert_eq!(label_scientific().digits(2).format(&[9.96e4]), ["1×10⁵"]);
+        assert_eq!(label_ordinal().format(&[1.0, 2.0, 3.0, 11.0, 12.0, 22.0, 113.0]), ["1st", "2nd", "3rd", "11th", "12th", "22nd", "113th"]);
+        assert_eq!(label_pvalue().format(&[0.00001, 0.02, 0.9999]), ["<0.001", "0.020", ">0.999"]);
+    }
+}
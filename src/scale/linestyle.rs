@@ -1,4 +1,6 @@
 use crate::data::{DiscreteType, VectorIter};
+use crate::scale::identity::{self, Identity};
+use crate::scale::manual::{self, ManualValues};
 use crate::scale::traits::DiscreteDomainScale;
 use crate::utils::set::DiscreteSet;
 use crate::utils::DashPatterns;
@@ -9,6 +11,8 @@ use crate::visuals::LineStyle;
 pub struct LineStyleScale {
     linestyles: Vec<LineStyle>,
     elements: DiscreteSet,
+    manual: Option<ManualValues<LineStyle>>,
+    identity: Option<Identity>,
 }
 
 impl LineStyleScale {
@@ -17,9 +21,26 @@ impl LineStyleScale {
         Self {
             linestyles,
             elements: DiscreteSet::new(),
+            manual: None,
+            identity: None,
         }
     }
 
+    /// Map chosen levels to chosen line styles, see [`manual`].
+    pub fn set_manual(&mut self, manual: ManualValues<LineStyle>) {
+        self.manual = Some(manual);
+    }
+
+    /// Read line styles from the data itself, see [`identity`].
+    pub fn set_identity(&mut self, identity: Identity) {
+        self.identity = Some(identity);
+    }
+
+    /// The identity scale installed on this scale, if any
+    pub fn identity(&self) -> Option<&Identity> {
+        self.identity.as_ref()
+    }
+
     /// Create a default discrete linestyle scale with distinct patterns.
     /// Uses the DashPatterns iterator to generate non-redundant patterns.
     pub fn default_linestyles() -> Self {
@@ -48,6 +69,10 @@ impl super::traits::DiscreteDomainScale for LineStyleScale {
         &self.elements
     }
 
+    fn categories_mut(&mut self) -> &mut DiscreteSet {
+        &mut self.elements
+    }
+
     fn add_categories(&mut self, categories: DiscreteSet) {
         self.elements.union(&categories);
     }
@@ -55,9 +80,13 @@ impl super::traits::DiscreteDomainScale for LineStyleScale {
 
 impl super::traits::LineStyleRangeScale for LineStyleScale {
     fn map_value<T: DiscreteType>(&self, value: &T) -> Option<LineStyle> {
-        let ordinal = self.elements.ordinal(value)?;
-        let linestyle = self.linestyles[ordinal % self.linestyles.len()].clone();
-        Some(linestyle)
+        if self.identity.is_some() {
+            return identity::linetype(value.to_primitive());
+        }
+        let ordinal = self.elements.ordinal(value);
+        manual::resolve(self.manual.as_ref(), &self.elements, ordinal, |i| {
+            self.linestyles[i % self.linestyles.len()].clone()
+        })
     }
 }
 
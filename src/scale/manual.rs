@@ -0,0 +1,418 @@
+//! Manual discrete scales
+//!
+//! A manual scale maps named levels to chosen values, e.g. "control" to grey
+//! and "treated" to red. Levels that aren't listed take the `fallback`
+//! value, or the scale's default palette when no fallback is given, and
+//! values that aren't levels of the scale at all, or are missing, take the
+//! `na_value`.
+
+use crate::data::DiscreteValue;
+use crate::error::PlotError;
+use crate::scale::ScaleSet;
+use crate::theme::Color;
+use crate::utils::set::DiscreteSet;
+use crate::visuals::{LineStyle, Shape};
+
+/// Level → value lookup used by a manual scale
+#[derive(Debug, Clone)]
+pub struct ManualValues<T> {
+    values: Vec<(DiscreteValue, T)>,
+    fallback: Option<T>,
+    na_value: Option<T>,
+}
+
+impl<T> Default for ManualValues<T> {
+    fn default() -> Self {
+        Self {
+            values: Vec::new(),
+            fallback: None,
+            na_value: None,
+        }
+    }
+}
+
+impl<T: Clone> ManualValues<T> {
+    /// The value for a level of the scale: the listed value, else the
+    /// fallback. `None` leaves the choice to the scale's own palette.
+    pub fn get(&self, level: &DiscreteValue) -> Option<T> {
+        self.values
+            .iter()
+            .find(|(listed, _)| listed == level)
+            .map(|(_, value)| value.clone())
+            .or_else(|| self.fallback.clone())
+    }
+
+    /// The value for missing data and data that isn't a level of the scale
+    pub fn na_value(&self) -> Option<T> {
+        self.na_value.clone()
+    }
+
+    fn check(&self, valid: impl Fn(&T) -> bool, what: &str) -> Result<(), PlotError> {
+        for (i, (level, value)) in self.values.iter().enumerate() {
+            if self.values[..i].iter().any(|(other, _)| other == level) {
+                return Err(PlotError::InvalidScaleParameters {
+                    details: format!("level '{}' is listed twice in a manual scale", level.to_string()),
+                });
+            }
+            if !valid(value) {
+                return Err(PlotError::InvalidScaleParameters {
+                    details: format!("invalid {} for level '{}'", what, level.to_string()),
+                });
+            }
+        }
+        if self.fallback.iter().chain(self.na_value.iter()).any(|v| !valid(v)) {
+            return Err(PlotError::InvalidScaleParameters {
+                details: format!("invalid {} for the fallback or na_value", what),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Resolve a value of a discrete scale, consulting the manual mapping if
+/// there is one. `ordinal` is the value's position among the trained levels
+/// and `palette` supplies the scale's default for a given ordinal.
+pub(crate) fn resolve<T: Clone>(
+    manual: Option<&ManualValues<T>>,
+    elements: &DiscreteSet,
+    ordinal: Option<usize>,
+    palette: impl FnOnce(usize) -> T,
+) -> Option<T> {
+    match (manual, ordinal) {
+        (None, ordinal) => ordinal.map(palette),
+        (Some(manual), None) => manual.na_value(),
+        (Some(manual), Some(ordinal)) => elements
+            .get_at(ordinal)
+            .and_then(|level| {
+                if level.is_na() {
+                    manual.na_value().or_else(|| manual.get(&level))
+                } else {
+                    manual.get(&level)
+                }
+            })
+            .or_else(|| Some(palette(ordinal))),
+    }
+}
+
+/// Identifies which aesthetic a manual scale applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManualAesthetic {
+    Color,
+    Fill,
+    Shape,
+    Linetype,
+    Size,
+    Alpha,
+}
+
+/// Builder for a manual discrete scale
+#[derive(Debug, Clone)]
+pub struct ManualScaleBuilder<T> {
+    aesthetic: ManualAesthetic,
+    values: ManualValues<T>,
+}
+
+impl<T> ManualScaleBuilder<T> {
+    fn new(aesthetic: ManualAesthetic) -> Self {
+        Self {
+            aesthetic,
+            values: ManualValues::default(),
+        }
+    }
+
+    /// Map one level to a value
+    pub fn value(mut self, level: impl Into<DiscreteValue>, value: impl Into<T>) -> Self {
+        self.values.values.push((level.into(), value.into()));
+        self
+    }
+
+    /// Map several levels at once, e.g. from a `HashMap` or a list of pairs
+    pub fn values<L, V>(mut self, values: impl IntoIterator<Item = (L, V)>) -> Self
+    where
+        L: Into<DiscreteValue>,
+        V: Into<T>,
+    {
+        self.values
+            .values
+            .extend(values.into_iter().map(|(level, value)| (level.into(), value.into())));
+        self
+    }
+
+    /// Value for levels that aren't listed (default: the scale's palette)
+    pub fn fallback(mut self, value: impl Into<T>) -> Self {
+        self.values.fallback = Some(value.into());
+        self
+    }
+
+    /// Value for missing data and data that isn't a level of the scale
+    pub fn na_value(mut self, value: impl Into<T>) -> Self {
+        self.values.na_value = Some(value.into());
+        self
+    }
+}
+
+/// A configured manual scale, ready to be installed in a [`ScaleSet`]
+#[derive(Debug, Clone)]
+pub enum ManualScale {
+    Color(ManualValues<Color>),
+    Fill(ManualValues<Color>),
+    Shape(ManualValues<Shape>),
+    Linetype(ManualValues<LineStyle>),
+    Size(ManualValues<f64>),
+    Alpha(ManualValues<f64>),
+}
+
+impl ManualScale {
+    /// Validate the mapping and install it on the matching discrete scale
+    pub(crate) fn apply_to(self, scales: &mut ScaleSet) -> Result<(), PlotError> {
+        match self {
+            ManualScale::Color(values) => {
+                values.check(|_| true, "color")?;
+                scales.color_discrete.set_manual(values);
+            }
+            ManualScale::Fill(values) => {
+                values.check(|_| true, "fill")?;
+                scales.fill_discrete.set_manual(values);
+            }
+            ManualScale::Shape(values) => {
+                values.check(|_| true, "shape")?;
+                scales.shape_scale.set_manual(values);
+            }
+            ManualScale::Linetype(values) => {
+                values.check(|_| true, "linetype")?;
+                scales.linestyle_scale.set_manual(values);
+            }
+            ManualScale::Size(values) => {
+                values.check(|size| size.is_finite() && *size >= 0.0, "size")?;
+                scales.size_discrete.set_manual(values);
+            }
+            ManualScale::Alpha(values) => {
+                values.check(|alpha| (0.0..=1.0).contains(alpha), "alpha")?;
+                scales.alpha_discrete.set_manual(values);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<ManualScaleBuilder<Color>> for ManualScale {
+    fn from(builder: ManualScaleBuilder<Color>) -> Self {
+        match builder.aesthetic {
+            ManualAesthetic::Fill => ManualScale::Fill(builder.values),
+            _ => ManualScale::Color(builder.values),
+        }
+    }
+}
+
+impl From<ManualScaleBuilder<Shape>> for ManualScale {
+    fn from(builder: ManualScaleBuilder<Shape>) -> Self {
+        ManualScale::Shape(builder.values)
+    }
+}
+
+impl From<ManualScaleBuilder<LineStyle>> for ManualScale {
+    fn from(builder: ManualScaleBuilder<LineStyle>) -> Self {
+        ManualScale::Linetype(builder.values)
+    }
+}
+
+impl From<ManualScaleBuilder<f64>> for ManualScale {
+    fn from(builder: ManualScaleBuilder<f64>) -> Self {
+        match builder.aesthetic {
+            ManualAesthetic::Alpha => ManualScale::Alpha(builder.values),
+            _ => ManualScale::Size(builder.values),
+        }
+    }
+}
+
+/// Map discrete levels of the color aesthetic to chosen colors
+pub fn scale_color_manual() -> ManualScaleBuilder<Color> {
+    ManualScaleBuilder::new(ManualAesthetic::Color)
+}
+
+/// Map discrete levels of the fill aesthetic to chosen colors
+pub fn scale_fill_manual() -> ManualScaleBuilder<Color> {
+    ManualScaleBuilder::new(ManualAesthetic::Fill)
+}
+
+/// Map discrete levels of the shape aesthetic to chosen shapes
+pub fn scale_shape_manual() -> ManualScaleBuilder<Shape> {
+    ManualScaleBuilder::new(ManualAesthetic::Shape)
+}
+
+/// Map discrete levels of the linetype aesthetic to chosen line styles
+pub fn scale_linetype_manual() -> ManualScaleBuilder<LineStyle> {
+    ManualScaleBuilder::new(ManualAesthetic::Linetype)
+}
+
+/// Map discrete levels of the size aesthetic to chosen sizes
+pub fn scale_size_manual() -> ManualScaleBuilder<f64> {
+    ManualScaleBuilder::new(ManualAesthetic::Size)
+}
+
+/// Map discrete levels of the alpha aesthetic to chosen opacities in [0, 1]
+pub fn scale_alpha_manual() -> ManualScaleBuilder<f64> {
+    ManualScaleBuilder::new(ManualAesthetic::Alpha)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aesthetics::builder::{
+        ColorDiscreteAesBuilder, ShapeAesBuilder, XContinuousAesBuilder, YContinuousAesBuilder,
+    };
+    use crate::error::to_io_error;
+    use crate::geom::line::geom_line;
+    use crate::geom::point::geom_point;
+    use crate::plot::plot;
+    use crate::scale::traits::{ColorRangeScale, ContinuousRangeScale, DiscreteDomainScale};
+    use crate::theme::color;
+    use crate::theme::traits::PointElement;
+    use crate::utils::mtcars::mtcars;
+
+    fn init_test_logging() {
+        let _ = env_logger::builder()
+            .is_test(true)
+            .filter_level(log::LevelFilter::Debug)
+            .try_init();
+    }
+
+    fn levels<T: crate::data::DiscreteType>(values: &[T]) -> DiscreteSet {
+        let mut set = DiscreteSet::new();
+        for value in values {
+            set.add(value);
+        }
+        set.build();
+        set
+    }
+
+    fn trained_color(names: &[&str], manual: ManualScale) -> ScaleSet {
+        let names: Vec<String> = names.iter().map(|name| name.to_string()).collect();
+        let mut scales = ScaleSet::default();
+        scales.color_discrete.add_categories(levels(&names));
+        manual.apply_to(&mut scales).unwrap();
+        scales
+    }
+
+    #[test]
+    fn manual_lookup_and_fallback() {
+        let manual = scale_color_manual()
+            .value("control", color::GREY)
+            .value("treated", color::RED)
+            .fallback(color::NAVYBLUE)
+            .na_value(color::BLACK);
+        let scales = trained_color(&["control", "treated", "placebo"], manual.into());
+        let scale = &scales.color_discrete;
+
+        assert_eq!(scale.map_value(&"control".to_string()), Some(color::GREY));
+        assert_eq!(scale.map_value(&"treated".to_string()), Some(color::RED));
+        assert_eq!(scale.map_value(&"placebo".to_string()), Some(color::NAVYBLUE));
+        // Not a level of the scale, or not discrete at all
+        assert_eq!(scale.map_value(&"unseen".to_string()), Some(color::BLACK));
+        assert_eq!(scale.map_value(&1.5), Some(color::BLACK));
+    }
+
+    #[test]
+    fn manual_without_fallback_uses_palette() {
+        let manual = scale_color_manual().values([("treated", color::RED)]);
+        let scales = trained_color(&["control", "treated"], manual.into());
+        let palette = crate::visuals::palette::okabe_ito_palette();
+
+        // "control" is the first level, so it keeps the palette's first color
+        let scale = &scales.color_discrete;
+        assert_eq!(scale.map_value(&"control".to_string()), Some(palette[0]));
+        assert_eq!(scale.map_value(&"treated".to_string()), Some(color::RED));
+        assert_eq!(scale.map_value(&"unseen".to_string()), None);
+    }
+
+    #[test]
+    fn manual_numeric_levels() {
+        let mut scales = ScaleSet::default();
+        scales.size_discrete.add_categories(levels(&[4i64, 6, 8]));
+        ManualScale::from(scale_size_manual().value(4, 1.0).value(8, 9.0))
+            .apply_to(&mut scales)
+            .unwrap();
+
+        assert_eq!(scales.size_discrete.map_value(&4i64), Some(1.0));
+        assert_eq!(scales.size_discrete.map_value(&8i64), Some(9.0));
+        // Unlisted middle level falls back to its place in the default range
+        assert_eq!(scales.size_discrete.map_value(&6i64), Some(4.0));
+    }
+
+    #[test]
+    fn manual_validation() {
+        let mut scales = ScaleSet::default();
+        let duplicate = scale_shape_manual()
+            .value("a", Shape::Circle)
+            .value("a", Shape::Square);
+        assert!(ManualScale::from(duplicate).apply_to(&mut scales).is_err());
+
+        let alpha = scale_alpha_manual().value("a", 1.5);
+        assert!(ManualScale::from(alpha).apply_to(&mut scales).is_err());
+
+        let size = scale_size_manual().value("a", 2.0).na_value(-1.0);
+        assert!(ManualScale::from(size).apply_to(&mut scales).is_err());
+
+        let linetype = scale_linetype_manual()
+            .value(true, LineStyle::Solid)
+            .value(false, LineStyle::from("-"));
+        assert!(ManualScale::from(linetype).apply_to(&mut scales).is_ok());
+    }
+
+    #[test]
+    fn manual_scale_1() {
+        init_test_logging();
+
+        let data = mtcars();
+        let builder = plot(&data).aes(|a| {
+            a.x_continuous("wt");
+            a.y_continuous("mpg");
+            a.color_discrete("cyl");
+            a.shape("cyl");
+        }) + geom_point().size(3.0)
+            + scale_color_manual()
+                .value(4, color::GREY)
+                .value(8, color::RED)
+                .fallback(color::NAVYBLUE)
+            + scale_shape_manual()
+                .value(4, Shape::Circle)
+                .value(6, Shape::Triangle)
+                .value(8, Shape::Square);
+
+        let p = builder
+            .build()
+            .map_err(to_io_error)
+            .expect("Failed to build plot");
+        p.save("tests/images/manual_scale_1.png", 800, 600)
+            .map_err(to_io_error)
+            .expect("Failed to save plot image");
+    }
+
+    #[test]
+    fn manual_scale_2_linetype() {
+        init_test_logging();
+
+        let data = mtcars();
+        let builder = plot(&data).aes(|a| {
+            a.x_continuous("wt");
+            a.y_continuous("mpg");
+        }) + geom_line().aes(|a| {
+            a.linestyle("cyl");
+        }) + scale_linetype_manual()
+            .value(4, LineStyle::Solid)
+            .value(6, LineStyle::from("-"))
+            .value(8, LineStyle::from("."));
+
+        let p = builder
+            .build()
+            .map_err(to_io_error)
+            .expect("Failed to build plot");
+
+        use crate::scale::traits::LineStyleRangeScale;
+        assert_eq!(p.scales.linestyle_scale.map_value(&8i64), Some(LineStyle::from(".")));
+
+        p.save("tests/images/manual_scale_2_linetype.png", 800, 600)
+            .map_err(to_io_error)
+            .expect("Failed to save plot image");
+    }
+}
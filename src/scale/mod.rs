@@ -1,14 +1,18 @@
 use crate::{
     aesthetics::{AesMap, AesValue, Aesthetic},
-    data::{ContinuousType, DataSource, DiscreteType},
+    data::{ContinuousType, DataSource, DiscreteType, VectorIter},
     error::PlotError,
-    scale::traits::{ColorRangeScale, ContinuousRangeScale, LineStyleRangeScale, ScaleBase, ShapeRangeScale},
+    theme::Color,
+    scale::traits::{ColorRangeScale, ContinuousDomainScale, ContinuousRangeScale, DiscreteDomainScale, LineStyleRangeScale, ScaleBase, ShapeRangeScale},
     utils::{
         data::{ContinuousVectorVisitor, DiscreteVectorVisitor, Vectorable},
         set::DiscreteSet,
     },
+    visuals::{colorspace::ColorSpace, palette::ViridisOption},
 };
 
+pub mod labels;
+pub mod temporal;
 pub mod transform;
 
 /// Specifies the type of scale required for an aesthetic
@@ -40,11 +44,17 @@ pub struct CategoricalInfo {
 pub mod traits;
 pub mod utils;
 
+pub mod binned;
+pub mod broken;
 pub mod color;
 pub mod positional;
 pub mod shape;
 pub mod size;
 pub mod linestyle;
+pub mod discrete;
+pub mod identity;
+pub mod manual;
+pub mod secondary;
 
 #[derive(Debug, Clone)]
 pub struct ScaleSet {
@@ -101,7 +111,7 @@ impl ScaleSet {
             Aesthetic::X(domain) | Aesthetic::Xmin(domain) | Aesthetic::Xmax(domain) => {
                 match domain {
                     Continuous => self.x_continuous.train(iter),
-                    Discrete => self.x_discrete.train(iter),
+                    Discrete => train_levels(&mut self.x_discrete, mapping, aesthetic, iter),
                 }
             }
             Aesthetic::XIntercept | Aesthetic::XBegin | Aesthetic::XEnd | Aesthetic::XOffset => {
@@ -110,7 +120,7 @@ impl ScaleSet {
             Aesthetic::Y(domain) | Aesthetic::Ymin(domain) | Aesthetic::Ymax(domain) => {
                 match domain {
                     Continuous => self.y_continuous.train(iter),
-                    Discrete => self.y_discrete.train(iter),
+                    Discrete => train_levels(&mut self.y_discrete, mapping, aesthetic, iter),
                 }
             }
             Aesthetic::YIntercept | Aesthetic::YBegin | Aesthetic::YEnd | Aesthetic::YOffset => {
@@ -119,25 +129,31 @@ impl ScaleSet {
             Aesthetic::Lower => self.y_continuous.train(iter),
             Aesthetic::Middle => self.y_continuous.train(iter),
             Aesthetic::Upper => self.y_continuous.train(iter),
+            Aesthetic::NotchLower | Aesthetic::NotchUpper => self.y_continuous.train(iter),
             Aesthetic::Color(domain) => match domain {
                 Continuous => self.color_continuous.train(iter),
-                Discrete => self.color_discrete.train(iter),
+                Discrete => train_levels(&mut self.color_discrete, mapping, aesthetic, iter),
             },
             Aesthetic::Fill(domain) => match domain {
                 Continuous => self.fill_continuous.train(iter),
-                Discrete => self.fill_discrete.train(iter),
+                Discrete => train_levels(&mut self.fill_discrete, mapping, aesthetic, iter),
             },
             Aesthetic::Alpha(domain) => match domain {
                 Continuous => self.alpha_continuous.train(iter),
-                Discrete => self.alpha_discrete.train(iter),
+                Discrete => train_levels(&mut self.alpha_discrete, mapping, aesthetic, iter),
             },
             Aesthetic::Size(domain) => match domain {
                 Continuous => self.size_continuous.train(iter),
-                Discrete => self.size_discrete.train(iter),
+                Discrete => train_levels(&mut self.size_discrete, mapping, aesthetic, iter),
             },
-            Aesthetic::Shape => self.shape_scale.train(iter),
-            Aesthetic::Linetype => self.linestyle_scale.train(iter),
-            Aesthetic::Group | Aesthetic::Label | Aesthetic::Width | Aesthetic::Height => {
+            Aesthetic::Shape => train_levels(&mut self.shape_scale, mapping, aesthetic, iter),
+            Aesthetic::Linetype => train_levels(&mut self.linestyle_scale, mapping, aesthetic, iter),
+            Aesthetic::Group
+            | Aesthetic::Label
+            | Aesthetic::Width
+            | Aesthetic::Height
+            | Aesthetic::RelVarWidth
+            | Aesthetic::Z => {
                 // No scale training needed for these aesthetics
             }
         }
@@ -169,7 +185,8 @@ impl ScaleSet {
                 }
             }
             Aesthetic::YIntercept | Aesthetic::YBegin | Aesthetic::YEnd | Aesthetic::Lower
-            | Aesthetic::Middle | Aesthetic::Upper | Aesthetic::YOffset => {
+            | Aesthetic::Middle | Aesthetic::Upper | Aesthetic::NotchLower | Aesthetic::NotchUpper
+            | Aesthetic::YOffset => {
                 self.y_continuous.map_aesthetic_value(value, data)
             }
             Aesthetic::Color(domain) => match domain {
@@ -193,6 +210,27 @@ impl ScaleSet {
             _ => Ok(value.clone()), // No scaling needed for other aesthetics
         }
     }
+
+    /// The identity scale behind an aesthetic property in a domain, if any
+    pub fn identity(
+        &self,
+        property: crate::aesthetics::AestheticProperty,
+        domain: crate::aesthetics::AestheticDomain,
+    ) -> Option<&identity::Identity> {
+        use crate::aesthetics::{AestheticDomain::*, AestheticProperty};
+
+        match (property, domain) {
+            (AestheticProperty::Color, Discrete) => self.color_discrete.identity(),
+            (AestheticProperty::Fill, Discrete) => self.fill_discrete.identity(),
+            (AestheticProperty::Shape, _) => self.shape_scale.identity(),
+            (AestheticProperty::Linetype, _) => self.linestyle_scale.identity(),
+            (AestheticProperty::Size, Continuous) => self.size_continuous.identity(),
+            (AestheticProperty::Size, Discrete) => self.size_discrete.identity(),
+            (AestheticProperty::Alpha, Continuous) => self.alpha_continuous.identity(),
+            (AestheticProperty::Alpha, Discrete) => self.alpha_discrete.identity(),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -212,17 +250,23 @@ pub enum ScaleIdentifier {
     Linestyle,
 }
 
-pub(crate) struct ContinuousScaleTrainer {
+/// Tracks the range of values in transformed space, skipping values outside
+/// the transform's domain
+pub(crate) struct ContinuousScaleTrainer<'a> {
+    transform: &'a dyn transform::Transform,
     pub bounds: Option<(f64, f64)>,
 }
 
-impl ContinuousScaleTrainer {
-    pub fn new() -> Self {
-        Self { bounds: None }
+impl<'a> ContinuousScaleTrainer<'a> {
+    pub fn new(transform: &'a dyn transform::Transform) -> Self {
+        Self {
+            transform,
+            bounds: None,
+        }
     }
 }
 
-impl ContinuousVectorVisitor for ContinuousScaleTrainer {
+impl ContinuousVectorVisitor for ContinuousScaleTrainer<'_> {
     type Output = ();
 
     fn visit<T: Vectorable + ContinuousType>(
@@ -233,12 +277,15 @@ impl ContinuousVectorVisitor for ContinuousScaleTrainer {
         let mut max_value = f64::NEG_INFINITY;
         for v in value {
             let v_f64 = v.to_f64();
-            if v_f64 < min_value {
-                min_value = v_f64;
+            if !self.transform.is_in_domain(v_f64) {
+                continue;
             }
-            if v_f64 > max_value {
-                max_value = v_f64;
+            let t = self.transform.transform(v_f64);
+            if !t.is_finite() {
+                continue;
             }
+            min_value = min_value.min(t);
+            max_value = max_value.max(t);
         }
         if min_value != f64::INFINITY || max_value != f64::NEG_INFINITY {
             self.bounds = Some((min_value, max_value));
@@ -247,6 +294,20 @@ impl ContinuousVectorVisitor for ContinuousScaleTrainer {
     }
 }
 
+// Train a discrete scale on an aesthetic's levels, where missing values
+// have their own level, or on its raw values when they have no levels
+fn train_levels<S: DiscreteDomainScale>(
+    scale: &mut S,
+    mapping: &AesMap,
+    aesthetic: &Aesthetic,
+    iter: VectorIter<'_>,
+) {
+    match mapping.get_resolved_discrete(aesthetic) {
+        Some(levels) => scale.train_levels(levels),
+        None => scale.train(iter),
+    }
+}
+
 pub(crate) struct DiscreteScaleTrainer {
     pub categories: DiscreteSet,
 }
@@ -277,14 +338,29 @@ impl DiscreteVectorVisitor for DiscreteScaleTrainer {
 // Scale Builders
 // ============================================================================
 
-/// Builder for configuring continuous positional scales (x, y axes)
+/// Builder for configuring continuous scales (position, color, fill, size, alpha)
 #[derive(Clone)]
 pub struct ContinuousScaleBuilder {
     pub(crate) aesthetic: ScaleAesthetic,
     pub(crate) transform: Option<Box<dyn transform::Transform>>,
     pub(crate) limits: Option<(f64, f64)>,
     pub(crate) breaks: Option<Vec<f64>>,
+    pub(crate) minor_breaks: Option<Vec<f64>>,
+    pub(crate) sec_axis: Option<secondary::SecondaryAxis>,
+    pub(crate) axis_breaks: Vec<broken::AxisBreak>,
     pub(crate) labels: Option<Vec<String>>,
+    pub(crate) label_formatter: Option<Box<dyn labels::LabelFormatter>>,
+    pub(crate) oob: Option<OutOfBounds>,
+    pub(crate) expand: Option<Expansion>,
+    pub(crate) temporal: Option<temporal::TemporalKind>,
+    pub(crate) date_breaks: Option<String>,
+    pub(crate) date_labels: Option<String>,
+    pub(crate) date_limits: Option<(String, String)>,
+    pub(crate) timezone: Option<String>,
+    pub(crate) gradient: color::Gradient,
+    pub(crate) binned: bool,
+    pub(crate) bin_method: Option<binned::BinMethod>,
+    pub(crate) n_bins: Option<usize>,
 }
 
 /// Identifies which aesthetic this scale applies to
@@ -298,6 +374,79 @@ pub enum ScaleAesthetic {
     AlphaContinuous,
 }
 
+/// What a continuous scale does with values outside its limits
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutOfBounds {
+    /// Drop the value (it becomes NA and is not drawn)
+    #[default]
+    Censor,
+    /// Clamp the value to the nearest limit
+    Squish,
+    /// Keep the value as is; it may be drawn outside the panel and clipped
+    Keep,
+}
+
+impl OutOfBounds {
+    /// Apply the policy to a value, given limits in the same space
+    pub(crate) fn apply(&self, value: f64, limits: (f64, f64)) -> Option<f64> {
+        let (lo, hi) = (limits.0.min(limits.1), limits.0.max(limits.1));
+        if value >= lo && value <= hi {
+            return Some(value);
+        }
+        match self {
+            OutOfBounds::Censor => None,
+            OutOfBounds::Squish => Some(value.clamp(lo, hi)),
+            OutOfBounds::Keep => Some(value),
+        }
+    }
+}
+
+/// Padding added around a continuous scale's trained range
+///
+/// Both parts are applied in transformed space: the range is widened by
+/// `mult` times its width and then by `add`, separately for the lower and
+/// upper end. The default is 5% on each side, as in ggplot2.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Expansion {
+    pub mult: (f64, f64),
+    pub add: (f64, f64),
+}
+
+impl Expansion {
+    pub fn new(mult: (f64, f64), add: (f64, f64)) -> Self {
+        Self { mult, add }
+    }
+
+    /// Multiplicative expansion, the same on both sides
+    pub fn mult(mult: f64) -> Self {
+        Self::new((mult, mult), (0.0, 0.0))
+    }
+
+    /// Additive expansion, the same on both sides
+    pub fn add(add: f64) -> Self {
+        Self::new((0.0, 0.0), (add, add))
+    }
+
+    /// No expansion; the domain is exactly the data range or limits
+    pub fn none() -> Self {
+        Self::new((0.0, 0.0), (0.0, 0.0))
+    }
+
+    pub(crate) fn expand(&self, range: (f64, f64)) -> (f64, f64) {
+        let width = range.1 - range.0;
+        (
+            range.0 - width * self.mult.0 - self.add.0,
+            range.1 + width * self.mult.1 + self.add.1,
+        )
+    }
+}
+
+impl Default for Expansion {
+    fn default() -> Self {
+        Self::mult(0.05)
+    }
+}
+
 impl ContinuousScaleBuilder {
     pub fn new(aesthetic: ScaleAesthetic) -> Self {
         Self {
@@ -305,7 +454,22 @@ impl ContinuousScaleBuilder {
             transform: None,
             limits: None,
             breaks: None,
+            minor_breaks: None,
+            sec_axis: None,
+            axis_breaks: Vec::new(),
             labels: None,
+            label_formatter: None,
+            oob: None,
+            expand: None,
+            temporal: None,
+            date_breaks: None,
+            date_labels: None,
+            date_limits: None,
+            timezone: None,
+            gradient: color::Gradient::default(),
+            binned: false,
+            bin_method: None,
+            n_bins: None,
         }
     }
 
@@ -327,44 +491,344 @@ impl ContinuousScaleBuilder {
         self
     }
 
+    /// Set explicit minor break positions (x and y scales only). Minor
+    /// breaks fall midway between the major breaks by default, or at
+    /// 2–9 × 10ⁿ on a log10 scale; an empty list turns them off.
+    pub fn minor_breaks(mut self, breaks: Vec<f64>) -> Self {
+        self.minor_breaks = Some(breaks);
+        self
+    }
+
+    /// Add a secondary axis on the opposite side of the panel, e.g.
+    /// `secondary::sec_axis(|c| c * 9.0 / 5.0 + 32.0).name("°F")`
+    /// (x and y scales only)
+    pub fn sec_axis(mut self, axis: secondary::SecondaryAxis) -> Self {
+        self.sec_axis = Some(axis);
+        self
+    }
+
+    /// Collapse a range of the scale, e.g. `broken::AxisBreak::new(100.0, 900.0)`,
+    /// leaving a marked gap in the axis (x and y scales only). Call again
+    /// to collapse more ranges.
+    pub fn axis_break(mut self, axis_break: broken::AxisBreak) -> Self {
+        self.axis_breaks.push(axis_break);
+        self
+    }
+
     /// Set explicit labels for breaks
     pub fn labels(mut self, labels: Vec<String>) -> Self {
         self.labels = Some(labels);
         self
     }
 
+    /// Format break labels with a [`labels::LabelFormatter`], e.g.
+    /// `labels::label_percent()`, instead of the scale's default
+    pub fn label_formatter(mut self, formatter: impl labels::LabelFormatter + 'static) -> Self {
+        self.label_formatter = Some(Box::new(formatter));
+        self
+    }
+
+    /// Set what happens to values outside the limits (censored by default)
+    ///
+    /// For x and y this is applied before stats run, so a stat only sees
+    /// the data within the limits. Use `coord_cartesian()` to zoom the view
+    /// without dropping data.
+    pub fn oob(mut self, oob: OutOfBounds) -> Self {
+        self.oob = Some(oob);
+        self
+    }
+
+    /// Set the padding around the scale's range
+    pub fn expand(mut self, expansion: Expansion) -> Self {
+        self.expand = Some(expansion);
+        self
+    }
+
+    /// Place breaks on a calendar interval, e.g. "1 month" or "6 hours"
+    /// (date, datetime and duration scales only)
+    pub fn date_breaks(mut self, interval: impl Into<String>) -> Self {
+        self.date_breaks = Some(interval.into());
+        self
+    }
+
+    /// Format break labels with a strftime-style pattern, e.g. "%b %Y"
+    /// (date and datetime scales only)
+    pub fn date_labels(mut self, format: impl Into<String>) -> Self {
+        self.date_labels = Some(format.into());
+        self
+    }
+
+    /// Set the limits from strings the scale can parse, e.g. "2007-03-01"
+    /// or "PT2H" (date, datetime and duration scales only)
+    pub fn date_limits(mut self, min: impl Into<String>, max: impl Into<String>) -> Self {
+        self.date_limits = Some((min.into(), max.into()));
+        self
+    }
+
+    /// Place breaks and format labels in an IANA time zone such as
+    /// "America/New_York" (datetime scales only; UTC by default)
+    pub fn timezone(mut self, tz: impl Into<String>) -> Self {
+        self.timezone = Some(tz.into());
+        self
+    }
+
+    /// Set the gradient's colors, low to high (color and fill scales only)
+    pub fn colors(mut self, colors: Vec<Color>) -> Self {
+        self.gradient.colors = Some(colors);
+        self
+    }
+
+    /// Place each gradient color at a position in [0, 1] of the domain
+    /// instead of spacing them evenly (color and fill scales only)
+    pub fn values(mut self, values: Vec<f64>) -> Self {
+        self.gradient.values = Some(values);
+        self
+    }
+
+    /// Center the gradient on this data value (color and fill scales only)
+    pub fn midpoint(mut self, midpoint: f64) -> Self {
+        self.gradient.midpoint = Some(midpoint);
+        self
+    }
+
+    /// Interpolate the gradient in this color space (color and fill scales only)
+    pub fn space(mut self, space: ColorSpace) -> Self {
+        self.gradient.space = Some(space);
+        self
+    }
+
+    /// Place bin edges with this method when no breaks are given (binned
+    /// scales only; round numbers by default)
+    pub fn bin_method(mut self, method: binned::BinMethod) -> Self {
+        self.bin_method = Some(method);
+        self
+    }
+
+    /// Aim for this many bins (binned scales only; 5 by default). Explicit
+    /// `breaks()` are used as the bin edges instead.
+    pub fn n_bins(mut self, n: usize) -> Self {
+        self.n_bins = Some(n);
+        self
+    }
+
+    fn binning(&self) -> Result<Option<binned::Binning>, PlotError> {
+        if !self.binned {
+            let setting = [("bin_method", self.bin_method.is_some()), ("n_bins", self.n_bins.is_some())]
+                .into_iter()
+                .find(|(_, set)| *set);
+            return match setting {
+                Some((name, _)) => Err(PlotError::InvalidScaleParameters {
+                    details: format!("{} requires a binned scale", name),
+                }),
+                None => Ok(None),
+            };
+        }
+        let n_bins = self.n_bins.unwrap_or(5);
+        if n_bins == 0 {
+            return Err(PlotError::InvalidScaleParameters {
+                details: "a binned scale needs at least one bin".to_string(),
+            });
+        }
+        Ok(Some(binned::Binning::new(self.bin_method.unwrap_or_default(), n_bins)))
+    }
+
     /// Apply this builder's configuration to a scale set
-    pub(crate) fn apply_to(self, scales: &mut ScaleSet) {
+    ///
+    /// Settings that cannot be honoured (inverted limits, limits outside
+    /// the transform's domain, labels that don't match the breaks) are
+    /// reported as errors instead of being dropped.
+    pub(crate) fn apply_to(mut self, scales: &mut ScaleSet) -> Result<(), PlotError> {
         use ScaleAesthetic::*;
-        
-        match self.aesthetic {
-            XContinuous => {
-                if let Some(transform) = self.transform {
-                    scales.x_continuous.set_transform(transform);
+
+        let gradient = std::mem::take(&mut self.gradient);
+        let bins = self.binning()?;
+        let minor_breaks = self.minor_breaks.take();
+        let sec_axis = self.sec_axis.take();
+        let axis_breaks = std::mem::take(&mut self.axis_breaks);
+        if !matches!(self.aesthetic, XContinuous | YContinuous) {
+            let setting = [
+                ("minor_breaks", minor_breaks.is_some()),
+                ("sec_axis", sec_axis.is_some()),
+                ("axis_break", !axis_breaks.is_empty()),
+            ]
+            .into_iter()
+            .find(|(_, set)| *set);
+            if let Some((name, _)) = setting {
+                return Err(PlotError::InvalidScaleParameters {
+                    details: format!("{} requires an x or y scale", name),
+                });
+            }
+        }
+        if let Some(axis) = &sec_axis {
+            let breaks_count = axis.explicit_breaks().map_or(0, <[f64]>::len);
+            if let Some(labels) = axis.explicit_labels().filter(|labels| labels.len() != breaks_count) {
+                return Err(PlotError::ScaleMismatch {
+                    breaks_count,
+                    labels_count: labels.len(),
+                });
+            }
+        }
+        let color_scale = match self.aesthetic {
+            ColorContinuous => &mut scales.color_continuous,
+            FillContinuous => &mut scales.fill_continuous,
+            _ => {
+                if let Some(name) = gradient.setting() {
+                    return Err(PlotError::InvalidScaleParameters {
+                        details: format!("{} requires a color or fill scale", name),
+                    });
+                }
+                let positional = match self.aesthetic {
+                    XContinuous => &mut scales.x_continuous,
+                    YContinuous => &mut scales.y_continuous,
+                    SizeContinuous if bins.is_none() => return self.configure(&mut scales.size_continuous),
+                    AlphaContinuous if bins.is_none() => return self.configure(&mut scales.alpha_continuous),
+                    _ => {
+                        return Err(PlotError::InvalidScaleParameters {
+                            details: "binning requires an x, y, color or fill scale".to_string(),
+                        });
+                    }
+                };
+                self.configure(positional)?;
+                if let Some(bins) = bins {
+                    positional.set_bins(bins);
+                }
+                if let Some(minor_breaks) = minor_breaks {
+                    positional.set_minor_breaks(minor_breaks);
+                }
+                if let Some(axis) = sec_axis {
+                    positional.set_secondary(axis);
                 }
-                // TODO: apply limits, breaks, labels when those features are added
+                if !axis_breaks.is_empty() {
+                    check_axis_breaks(&axis_breaks, positional.transform.as_ref())?;
+                    positional.set_axis_breaks(axis_breaks);
+                }
+                return Ok(());
             }
-            YContinuous => {
-                if let Some(transform) = self.transform {
-                    scales.y_continuous.set_transform(transform);
+        };
+        self.configure(color_scale)?;
+        gradient.apply_to(color_scale)?;
+        if let Some(bins) = bins {
+            color_scale.set_bins(bins);
+        }
+        Ok(())
+    }
+
+    fn configure<S: ContinuousDomainScale>(self, scale: &mut S) -> Result<(), PlotError> {
+        if let Some(transform) = self.transform {
+            if self.temporal.is_some() {
+                return Err(PlotError::InvalidScaleParameters {
+                    details: "date, datetime and duration scales don't take a transform".to_string(),
+                });
+            }
+            scale.set_transform(transform);
+        }
+
+        match self.temporal {
+            Some(kind) => scale.set_transform(temporal::build_transform(
+                kind,
+                self.date_breaks.as_deref(),
+                self.date_labels.as_deref(),
+                self.timezone.as_deref(),
+            )?),
+            None => {
+                let setting = [
+                    ("date_breaks", self.date_breaks.is_some()),
+                    ("date_labels", self.date_labels.is_some()),
+                    ("date_limits", self.date_limits.is_some()),
+                    ("timezone", self.timezone.is_some()),
+                ]
+                .into_iter()
+                .find(|(_, set)| *set);
+                if let Some((name, _)) = setting {
+                    return Err(PlotError::InvalidScaleParameters {
+                        details: format!("{} requires a date, datetime or duration scale", name),
+                    });
                 }
             }
-            ColorContinuous => {
-                // TODO: implement when continuous color scales support transforms
+        }
+
+        let mut limits = self.limits;
+        if let Some((min, max)) = &self.date_limits {
+            let parse = |value: &str| {
+                scale.transform().parse(value).ok_or_else(|| PlotError::InvalidScaleParameters {
+                    details: format!("can't parse limit '{}' on a {} scale", value, scale.transform().name()),
+                })
+            };
+            limits = Some((parse(min)?, parse(max)?));
+        }
+
+        if let Some((min, max)) = limits {
+            let transform = scale.transform();
+            let in_domain = transform.transform(min).is_finite() && transform.transform(max).is_finite();
+            if !in_domain || min >= max {
+                return Err(PlotError::InvalidLimits { min, max });
             }
-            FillContinuous => {
-                // TODO: implement when continuous fill scales support transforms
+            scale.set_limits((min, max));
+        }
+
+        if let Some(oob) = self.oob {
+            scale.set_oob(oob);
+        }
+
+        if let Some(expansion) = self.expand {
+            let values = [expansion.mult.0, expansion.mult.1, expansion.add.0, expansion.add.1];
+            if values.iter().any(|v| !v.is_finite()) {
+                return Err(PlotError::InvalidScaleParameters {
+                    details: format!("expansion must be finite, got {:?}", expansion),
+                });
             }
-            SizeContinuous => {
-                // TODO: implement when continuous size scales support transforms
+            scale.set_expansion(expansion);
+        }
+
+        if let Some(formatter) = self.label_formatter {
+            if self.labels.is_some() {
+                return Err(PlotError::InvalidScaleParameters {
+                    details: "labels and label_formatter can't both be set".to_string(),
+                });
             }
-            AlphaContinuous => {
-                if let Some(transform) = self.transform {
-                    scales.alpha_continuous.set_transform(transform);
+            scale.set_label_formatter(formatter);
+        }
+
+        match (self.breaks, self.labels) {
+            (Some(breaks), Some(labels)) => {
+                if breaks.len() != labels.len() {
+                    return Err(PlotError::ScaleMismatch {
+                        breaks_count: breaks.len(),
+                        labels_count: labels.len(),
+                    });
                 }
+                scale.set_breaks(breaks);
+                scale.set_labels(labels);
+            }
+            (Some(breaks), None) => scale.set_breaks(breaks),
+            (None, Some(labels)) => {
+                return Err(PlotError::ScaleMismatch {
+                    breaks_count: 0,
+                    labels_count: labels.len(),
+                });
             }
+            (None, None) => {}
+        }
+
+        Ok(())
+    }
+}
+
+/// Axis breaks must be ordered, non-overlapping ranges the transform can
+/// represent, each with a positive scale
+fn check_axis_breaks(axis_breaks: &[broken::AxisBreak], transform: &dyn transform::Transform) -> Result<(), PlotError> {
+    let mut sorted = axis_breaks.to_vec();
+    sorted.sort_by(|a, b| a.from.total_cmp(&b.from));
+    for (i, b) in sorted.iter().enumerate() {
+        let representable = transform.transform(b.from).is_finite() && transform.transform(b.to).is_finite();
+        let overlaps = i > 0 && b.from <= sorted[i - 1].to;
+        if !representable || b.from >= b.to || overlaps || !(b.scale.is_finite() && b.scale > 0.0) {
+            return Err(PlotError::InvalidScaleParameters {
+                details: format!("invalid axis break from {} to {} (scale {})", b.from, b.to, b.scale),
+            });
         }
     }
+    Ok(())
 }
 
 /// Create a continuous scale builder for the x aesthetic
@@ -377,6 +841,50 @@ pub fn scale_y_continuous() -> ContinuousScaleBuilder {
     ContinuousScaleBuilder::new(ScaleAesthetic::YContinuous)
 }
 
+fn temporal_scale(aesthetic: ScaleAesthetic, kind: temporal::TemporalKind) -> ContinuousScaleBuilder {
+    let mut builder = ContinuousScaleBuilder::new(aesthetic);
+    builder.temporal = Some(kind);
+    builder
+}
+
+/// Create a date scale for the x aesthetic
+///
+/// Values are days since 1970-01-01; string columns are parsed as ISO dates.
+pub fn scale_x_date() -> ContinuousScaleBuilder {
+    temporal_scale(ScaleAesthetic::XContinuous, temporal::TemporalKind::Date)
+}
+
+/// Create a date scale for the y aesthetic
+pub fn scale_y_date() -> ContinuousScaleBuilder {
+    temporal_scale(ScaleAesthetic::YContinuous, temporal::TemporalKind::Date)
+}
+
+/// Create a date-time scale for the x aesthetic
+///
+/// Values are seconds since the Unix epoch; string columns are parsed as
+/// RFC 3339 timestamps or as civil date-times in the scale's time zone.
+pub fn scale_x_datetime() -> ContinuousScaleBuilder {
+    temporal_scale(ScaleAesthetic::XContinuous, temporal::TemporalKind::DateTime)
+}
+
+/// Create a date-time scale for the y aesthetic
+pub fn scale_y_datetime() -> ContinuousScaleBuilder {
+    temporal_scale(ScaleAesthetic::YContinuous, temporal::TemporalKind::DateTime)
+}
+
+/// Create a duration scale for the x aesthetic
+///
+/// Values are seconds; string columns are parsed as ISO 8601 ("PT1H30M") or
+/// friendly ("1h 30m") durations.
+pub fn scale_x_duration() -> ContinuousScaleBuilder {
+    temporal_scale(ScaleAesthetic::XContinuous, temporal::TemporalKind::Duration)
+}
+
+/// Create a duration scale for the y aesthetic
+pub fn scale_y_duration() -> ContinuousScaleBuilder {
+    temporal_scale(ScaleAesthetic::YContinuous, temporal::TemporalKind::Duration)
+}
+
 /// Create a continuous scale builder for the color aesthetic
 pub fn scale_color_continuous() -> ContinuousScaleBuilder {
     ContinuousScaleBuilder::new(ScaleAesthetic::ColorContinuous)
@@ -387,6 +895,91 @@ pub fn scale_fill_continuous() -> ContinuousScaleBuilder {
     ContinuousScaleBuilder::new(ScaleAesthetic::FillContinuous)
 }
 
+fn gradient_scale(aesthetic: ScaleAesthetic, colors: Vec<Color>) -> ContinuousScaleBuilder {
+    // Like ggplot2, the gradient constructors interpolate in Lab
+    ContinuousScaleBuilder::new(aesthetic).colors(colors).space(ColorSpace::Lab)
+}
+
+/// Create a two-color gradient scale for the color aesthetic
+pub fn scale_color_gradient(low: Color, high: Color) -> ContinuousScaleBuilder {
+    gradient_scale(ScaleAesthetic::ColorContinuous, vec![low, high])
+}
+
+/// Create a two-color gradient scale for the fill aesthetic
+pub fn scale_fill_gradient(low: Color, high: Color) -> ContinuousScaleBuilder {
+    gradient_scale(ScaleAesthetic::FillContinuous, vec![low, high])
+}
+
+/// Create a diverging gradient scale for the color aesthetic, passing
+/// through `mid` at the midpoint (0 unless set with `.midpoint()`)
+pub fn scale_color_gradient2(low: Color, mid: Color, high: Color) -> ContinuousScaleBuilder {
+    gradient_scale(ScaleAesthetic::ColorContinuous, vec![low, mid, high]).midpoint(0.0)
+}
+
+/// Create a diverging gradient scale for the fill aesthetic, passing
+/// through `mid` at the midpoint (0 unless set with `.midpoint()`)
+pub fn scale_fill_gradient2(low: Color, mid: Color, high: Color) -> ContinuousScaleBuilder {
+    gradient_scale(ScaleAesthetic::FillContinuous, vec![low, mid, high]).midpoint(0.0)
+}
+
+/// Create a gradient scale through any number of colors for the color
+/// aesthetic; place them with `.values()`
+pub fn scale_color_gradientn(colors: Vec<Color>) -> ContinuousScaleBuilder {
+    gradient_scale(ScaleAesthetic::ColorContinuous, colors)
+}
+
+/// Create a gradient scale through any number of colors for the fill
+/// aesthetic; place them with `.values()`
+pub fn scale_fill_gradientn(colors: Vec<Color>) -> ContinuousScaleBuilder {
+    gradient_scale(ScaleAesthetic::FillContinuous, colors)
+}
+
+/// Create a continuous viridis-family scale for the color aesthetic
+pub fn scale_color_viridis_c(option: ViridisOption) -> ContinuousScaleBuilder {
+    gradient_scale(ScaleAesthetic::ColorContinuous, option.colors())
+}
+
+/// Create a continuous viridis-family scale for the fill aesthetic
+pub fn scale_fill_viridis_c(option: ViridisOption) -> ContinuousScaleBuilder {
+    gradient_scale(ScaleAesthetic::FillContinuous, option.colors())
+}
+
+fn binned_scale(builder: ContinuousScaleBuilder) -> ContinuousScaleBuilder {
+    ContinuousScaleBuilder { binned: true, ..builder }
+}
+
+/// Create a binned scale for the x aesthetic; values are counted per bin
+pub fn scale_x_binned() -> ContinuousScaleBuilder {
+    binned_scale(ContinuousScaleBuilder::new(ScaleAesthetic::XContinuous))
+}
+
+/// Create a binned scale for the y aesthetic; values are counted per bin
+pub fn scale_y_binned() -> ContinuousScaleBuilder {
+    binned_scale(ContinuousScaleBuilder::new(ScaleAesthetic::YContinuous))
+}
+
+/// Create a binned scale for the color aesthetic, one color per bin
+pub fn scale_color_binned() -> ContinuousScaleBuilder {
+    binned_scale(ContinuousScaleBuilder::new(ScaleAesthetic::ColorContinuous))
+}
+
+/// Create a binned scale for the fill aesthetic, one color per bin
+pub fn scale_fill_binned() -> ContinuousScaleBuilder {
+    binned_scale(ContinuousScaleBuilder::new(ScaleAesthetic::FillContinuous))
+}
+
+/// Create a binned scale for the color aesthetic with bin colors taken
+/// from a two-color gradient
+pub fn scale_color_steps(low: Color, high: Color) -> ContinuousScaleBuilder {
+    binned_scale(scale_color_gradient(low, high))
+}
+
+/// Create a binned scale for the fill aesthetic with bin colors taken
+/// from a two-color gradient
+pub fn scale_fill_steps(low: Color, high: Color) -> ContinuousScaleBuilder {
+    binned_scale(scale_fill_gradient(low, high))
+}
+
 /// Create a continuous scale builder for the size aesthetic
 pub fn scale_size_continuous() -> ContinuousScaleBuilder {
     ContinuousScaleBuilder::new(ScaleAesthetic::SizeContinuous)
@@ -396,3 +989,543 @@ pub fn scale_size_continuous() -> ContinuousScaleBuilder {
 pub fn scale_alpha_continuous() -> ContinuousScaleBuilder {
     ContinuousScaleBuilder::new(ScaleAesthetic::AlphaContinuous)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aesthetics::builder::{
+        ColorContinuousAesBuilder, SizeContinuousAesBuilder, XContinuousAesBuilder, YContinuousAesBuilder,
+    };
+    use crate::data::{VectorIter, VectorValue};
+    use crate::error::to_io_error;
+    use crate::geom::bar::geom_bar;
+    use crate::layer::LayerBuilderExt;
+    use crate::geom::point::geom_point;
+    use crate::geom::rect::geom_rect;
+    use crate::plot::plot;
+    use crate::scale::positional::ContinuousPositionalScale;
+    use crate::scale::transform::{Log10Transform, ReciprocalTransform, ReverseTransform};
+    use crate::stat::count::Count;
+    use crate::theme::color;
+    use crate::theme::traits::PointElement;
+    use crate::utils::dataframe::DataFrame;
+    use crate::utils::mtcars::mtcars;
+
+    fn init_test_logging() {
+        let _ = env_logger::builder()
+            .is_test(true)
+            .filter_level(log::LevelFilter::Debug)
+            .try_init();
+    }
+
+    #[test]
+    fn test_limits_breaks_labels_applied_to_color() {
+        let mut scales = ScaleSet::default();
+        scale_color_continuous()
+            .limits(0.0, 10.0)
+            .breaks(vec![0.0, 5.0, 10.0])
+            .labels(vec!["low".into(), "mid".into(), "high".into()])
+            .apply_to(&mut scales)
+            .unwrap();
+
+        let scale = &mut scales.color_continuous;
+        assert_eq!(scale.limits(), (Some(0.0), Some(10.0)));
+
+        // Training on narrower data still spans the limits
+        scale.train(VectorIter::Float(Box::new(vec![2.0, 3.0].into_iter())));
+        let (lo, hi) = scale.domain().unwrap();
+        assert!(lo < 0.0 && hi > 10.0);
+        assert_eq!(scale.breaks(), &[0.0, 5.0, 10.0]);
+        assert_eq!(scale.labels(), &["low", "mid", "high"]);
+    }
+
+    #[test]
+    fn test_transform_applied_to_size() {
+        let mut scales = ScaleSet::default();
+        scale_size_continuous()
+            .transform(Box::new(Log10Transform))
+            .apply_to(&mut scales)
+            .unwrap();
+
+        let scale = &mut scales.size_continuous;
+        assert_eq!(scale.transform().name(), "log10");
+        scale.train(VectorIter::Float(Box::new(vec![1.0, 1000.0].into_iter())));
+        let (lo, hi) = scale.domain().unwrap();
+        assert!(lo < 0.0 && hi > 3.0 && hi < 4.0);
+
+        // Equal steps in log space give equal steps in size
+        let s1 = scale.map_value(&1.0).unwrap();
+        let s10 = scale.map_value(&10.0).unwrap();
+        let s100 = scale.map_value(&100.0).unwrap();
+        assert!(s1 < s10 && s10 < s100);
+        assert!(scale.map_value(&-1.0).is_none());
+    }
+
+    #[test]
+    fn test_breaks_outside_domain_dropped() {
+        let mut scales = ScaleSet::default();
+        scale_x_continuous()
+            .breaks(vec![-100.0, 1.0, 2.0, 100.0])
+            .labels(vec!["a".into(), "b".into(), "c".into(), "d".into()])
+            .apply_to(&mut scales)
+            .unwrap();
+
+        let scale = &mut scales.x_continuous;
+        scale.train(VectorIter::Float(Box::new(vec![0.0, 3.0].into_iter())));
+        scale.compute_breaks(5);
+        assert_eq!(scale.breaks(), &[1.0, 2.0]);
+        assert_eq!(scale.labels(), &["b", "c"]);
+    }
+
+    #[test]
+    fn test_label_formatter_applied() {
+        let mut scales = ScaleSet::default();
+        scale_y_continuous()
+            .breaks(vec![0.0, 0.25, 0.5])
+            .label_formatter(labels::label_percent().accuracy(1.0))
+            .apply_to(&mut scales)
+            .unwrap();
+
+        let scale = &mut scales.y_continuous;
+        scale.train(VectorIter::Float(Box::new(vec![0.0, 0.5].into_iter())));
+        scale.compute_breaks(5);
+        assert_eq!(scale.labels(), &["0%", "25%", "50%"]);
+
+        // A formatter relabels breaks that are already computed
+        scales.x_continuous.train(VectorIter::Float(Box::new(vec![0.0, 3000.0].into_iter())));
+        scales.x_continuous.compute_breaks(5);
+        scales.x_continuous.set_label_formatter(Box::new(labels::label_si("")));
+        assert!(scales.x_continuous.labels().contains(&"2k".to_string()));
+
+        let err = scale_color_continuous()
+            .breaks(vec![1.0])
+            .labels(vec!["one".into()])
+            .label_formatter(labels::label_comma())
+            .apply_to(&mut scales);
+        assert!(matches!(err, Err(PlotError::InvalidScaleParameters { .. })));
+    }
+
+    #[test]
+    fn test_minor_breaks() {
+        let mut scales = ScaleSet::default();
+        scale_x_continuous()
+            .minor_breaks(vec![-100.0, 0.5, 1.5])
+            .apply_to(&mut scales)
+            .unwrap();
+        let scale = &mut scales.x_continuous;
+        scale.train(VectorIter::Float(Box::new(vec![0.0, 3.0].into_iter())));
+        scale.compute_breaks(5);
+        assert_eq!(scale.minor_breaks(), &[0.5, 1.5]);
+
+        // By default minor breaks fall between the majors
+        let scale = &mut scales.y_continuous;
+        scale.train(VectorIter::Float(Box::new(vec![0.0, 100.0].into_iter())));
+        scale.compute_breaks(5);
+        let breaks = scale.breaks().to_vec();
+        assert!(scale.minor_breaks().contains(&((breaks[0] + breaks[1]) / 2.0)));
+
+        let err = scale_fill_continuous().minor_breaks(vec![1.0]).apply_to(&mut scales);
+        assert!(matches!(err, Err(PlotError::InvalidScaleParameters { .. })));
+    }
+
+    #[test]
+    fn test_sec_axis() {
+        let mut scales = ScaleSet::default();
+        scale_y_continuous()
+            .sec_axis(secondary::sec_axis(|c| c * 9.0 / 5.0 + 32.0).name("°F"))
+            .apply_to(&mut scales)
+            .unwrap();
+        let scale = &mut scales.y_continuous;
+        scale.train(VectorIter::Float(Box::new(vec![0.0, 100.0].into_iter())));
+        scale.compute_breaks(5);
+        assert_eq!(scale.secondary().and_then(|axis| axis.title()), Some("°F"));
+        assert!(scale.secondary_labels().contains(&"100".to_string()));
+        assert_eq!(scale.secondary_breaks().len(), scale.secondary_labels().len());
+
+        let err = scale_size_continuous().sec_axis(secondary::dup_axis()).apply_to(&mut scales);
+        assert!(matches!(err, Err(PlotError::InvalidScaleParameters { .. })));
+
+        let err = scale_x_continuous()
+            .sec_axis(secondary::dup_axis().breaks(vec![1.0, 2.0]).labels(vec!["one".into()]))
+            .apply_to(&mut scales);
+        assert!(matches!(err, Err(PlotError::ScaleMismatch { breaks_count: 2, labels_count: 1 })));
+    }
+
+    #[test]
+    fn test_axis_breaks() {
+        let mut scales = ScaleSet::default();
+        scale_y_continuous()
+            .axis_break(broken::AxisBreak::new(100.0, 900.0))
+            .apply_to(&mut scales)
+            .unwrap();
+        let scale = &mut scales.y_continuous;
+        scale.train(VectorIter::Float(Box::new(vec![0.0, 50.0, 950.0, 1000.0].into_iter())));
+        scale.compute_breaks(5);
+        assert_eq!(scale.gaps().len(), 1);
+        assert!(scale.map_value(&50.0).unwrap() < scale.map_value(&950.0).unwrap());
+        assert!(scale.map_value(&100.0).unwrap() < 0.5 && scale.map_value(&900.0).unwrap() > 0.5);
+        // No breaks are placed inside the collapsed range
+        assert!(scale.breaks().iter().all(|&b| !(b > 100.0 && b < 900.0)));
+        assert_eq!(scale.breaks().len(), scale.labels().len());
+
+        let err = scale_size_continuous()
+            .axis_break(broken::AxisBreak::new(1.0, 2.0))
+            .apply_to(&mut scales);
+        assert!(matches!(err, Err(PlotError::InvalidScaleParameters { .. })));
+
+        let err = scale_x_continuous()
+            .axis_break(broken::AxisBreak::new(1.0, 5.0))
+            .axis_break(broken::AxisBreak::new(4.0, 8.0))
+            .apply_to(&mut scales);
+        assert!(matches!(err, Err(PlotError::InvalidScaleParameters { .. })));
+    }
+
+    #[test]
+    fn test_invalid_limits_rejected() {
+        let mut scales = ScaleSet::default();
+        let err = scale_fill_continuous().limits(5.0, 1.0).apply_to(&mut scales);
+        assert!(matches!(err, Err(PlotError::InvalidLimits { .. })));
+
+        // Limits the transform can't represent are rejected too
+        let err = scale_y_continuous()
+            .transform(Box::new(Log10Transform))
+            .limits(0.0, 10.0)
+            .apply_to(&mut scales);
+        assert!(matches!(err, Err(PlotError::InvalidLimits { .. })));
+    }
+
+    #[test]
+    fn test_mismatched_labels_rejected() {
+        let mut scales = ScaleSet::default();
+        let err = scale_alpha_continuous()
+            .breaks(vec![1.0, 2.0])
+            .labels(vec!["one".into()])
+            .apply_to(&mut scales);
+        assert!(matches!(
+            err,
+            Err(PlotError::ScaleMismatch {
+                breaks_count: 2,
+                labels_count: 1
+            })
+        ));
+
+        let err = scale_size_continuous()
+            .labels(vec!["one".into()])
+            .apply_to(&mut scales);
+        assert!(matches!(
+            err,
+            Err(PlotError::ScaleMismatch {
+                breaks_count: 0,
+                labels_count: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn test_oob_policies() {
+        assert_eq!(OutOfBounds::Censor.apply(5.0, (0.0, 10.0)), Some(5.0));
+        assert_eq!(OutOfBounds::Censor.apply(12.0, (0.0, 10.0)), None);
+        assert_eq!(OutOfBounds::Squish.apply(12.0, (0.0, 10.0)), Some(10.0));
+        assert_eq!(OutOfBounds::Squish.apply(-3.0, (10.0, 0.0)), Some(0.0));
+        assert_eq!(OutOfBounds::Keep.apply(12.0, (0.0, 10.0)), Some(12.0));
+    }
+
+    #[test]
+    fn test_oob_applied_when_mapping() {
+        let mut scales = ScaleSet::default();
+        scale_x_continuous()
+            .limits(0.0, 10.0)
+            .oob(OutOfBounds::Squish)
+            .expand(Expansion::none())
+            .apply_to(&mut scales)
+            .unwrap();
+        scale_color_continuous()
+            .limits(0.0, 10.0)
+            .apply_to(&mut scales)
+            .unwrap();
+
+        scales.x_continuous.train(VectorIter::Float(Box::new(vec![1.0, 2.0].into_iter())));
+        assert_eq!(scales.x_continuous.domain(), Some((0.0, 10.0)));
+        assert_eq!(scales.x_continuous.map_value(&20.0), Some(1.0));
+
+        scales.color_continuous.train(VectorIter::Float(Box::new(vec![1.0, 2.0].into_iter())));
+        assert!(scales.color_continuous.map_value(&20.0).is_none());
+    }
+
+    #[test]
+    fn test_reciprocal_scale_skips_zero() {
+        let mut scale = ContinuousPositionalScale::with_transform(Box::new(ReciprocalTransform));
+        scale.train(VectorIter::Float(Box::new(vec![-1.0, -0.5, 0.0, 0.5, 1.0].into_iter())));
+
+        let (lo, hi) = scale.domain().unwrap();
+        assert!(lo < hi);
+        assert!(lo <= -2.0 && hi >= 2.0);
+        assert!(scale.map_value(&0.0).is_none());
+        let mapped = scale.map_value(&0.5).unwrap();
+        assert!((0.0..=1.0).contains(&mapped));
+    }
+
+    #[test]
+    fn test_reverse_scale_has_ordered_domain() {
+        let mut scale = ContinuousPositionalScale::with_transform(Box::new(ReverseTransform));
+        scale.train(VectorIter::Float(Box::new(vec![1.0, 5.0].into_iter())));
+
+        let (lo, hi) = scale.domain().unwrap();
+        assert!(lo < hi);
+        assert!(scale.map_value(&1.0).unwrap() > scale.map_value(&5.0).unwrap());
+        scale.compute_breaks(5);
+        assert!(!scale.breaks().is_empty());
+    }
+
+    #[test]
+    fn test_expansion() {
+        assert_eq!(Expansion::default().expand((0.0, 10.0)), (-0.5, 10.5));
+        assert_eq!(Expansion::add(1.0).expand((0.0, 10.0)), (-1.0, 11.0));
+        assert_eq!(
+            Expansion::new((0.0, 0.1), (2.0, 0.0)).expand((0.0, 10.0)),
+            (-2.0, 11.0)
+        );
+
+        let mut scales = ScaleSet::default();
+        scale_y_continuous()
+            .expand(Expansion::add(1.0))
+            .apply_to(&mut scales)
+            .unwrap();
+        scales.y_continuous.train(VectorIter::Float(Box::new(vec![3.0, 5.0].into_iter())));
+        assert_eq!(scales.y_continuous.domain(), Some((2.0, 6.0)));
+
+        let err = scale_y_continuous()
+            .expand(Expansion::mult(f64::NAN))
+            .apply_to(&mut scales);
+        assert!(matches!(err, Err(PlotError::InvalidScaleParameters { .. })));
+    }
+
+    #[test]
+    fn continuous_scale_settings_1() {
+        init_test_logging();
+
+        let data: Box<dyn DataSource> = Box::new(DataFrame::from_columns(vec![
+            ("x", VectorValue::from(vec![1.0, 2.0, 3.0, 4.0, 5.0])),
+            ("y", VectorValue::from(vec![2.0, 4.0, 3.0, 5.0, 1.0])),
+            ("v", VectorValue::from(vec![1.0, 10.0, 100.0, 1000.0, 10000.0])),
+        ]));
+
+        let builder = plot(&data).aes(|a| {
+            a.x_continuous("x");
+            a.y_continuous("y");
+            a.size_continuous("v");
+        }) + geom_point()
+            + scale_x_continuous()
+                .limits(0.0, 6.0)
+                .breaks(vec![0.0, 3.0, 6.0])
+                .labels(vec!["zero".into(), "three".into(), "six".into()])
+            + scale_size_continuous().transform(Box::new(Log10Transform));
+
+        let p = builder
+            .build()
+            .map_err(to_io_error)
+            .expect("Failed to build plot");
+        p.save("tests/images/continuous_scale_settings_1.png", 800, 600)
+            .map_err(to_io_error)
+            .expect("Failed to save plot image");
+    }
+
+    #[test]
+    fn continuous_scale_settings_2() {
+        init_test_logging();
+
+        let data: Box<dyn DataSource> = Box::new(DataFrame::from_columns(vec![
+            ("xmin", VectorValue::from(vec![0.0, 1.0, 2.0, 0.0, 1.0, 2.0])),
+            ("xmax", VectorValue::from(vec![1.0, 2.0, 3.0, 1.0, 2.0, 3.0])),
+            ("ymin", VectorValue::from(vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0])),
+            ("ymax", VectorValue::from(vec![1.0, 1.0, 1.0, 2.0, 2.0, 2.0])),
+            ("v", VectorValue::from(vec![1.0, 5.0, 20.0, 50.0, 200.0, 900.0])),
+        ]));
+
+        let builder = plot(&data) + geom_rect().aes(|a| {
+            a.xmin("xmin");
+            a.xmax("xmax");
+            a.ymin("ymin");
+            a.ymax("ymax");
+            a.fill_continuous("v");
+        }) + scale_fill_continuous()
+            .transform(Box::new(Log10Transform))
+            .breaks(vec![1.0, 10.0, 100.0, 1000.0]);
+
+        let p = builder
+            .build()
+            .map_err(to_io_error)
+            .expect("Failed to build plot");
+        p.save("tests/images/continuous_scale_settings_2.png", 800, 600)
+            .map_err(to_io_error)
+            .expect("Failed to save plot image");
+    }
+
+    #[test]
+    fn test_gradient_settings_need_color_scale() {
+        let mut scales = ScaleSet::default();
+        let err = scale_size_continuous().midpoint(0.0).apply_to(&mut scales);
+        assert!(matches!(err, Err(PlotError::InvalidScaleParameters { .. })));
+
+        scale_fill_gradient2(color::BLUE, color::WHITE, color::RED)
+            .midpoint(5.0)
+            .apply_to(&mut scales)
+            .unwrap();
+        let fill = &mut scales.fill_continuous;
+        fill.train(VectorIter::Float(Box::new(vec![0.0, 20.0].into_iter())));
+        assert_eq!(fill.map_value(&5.0), Some(color::WHITE));
+        let (_, hi) = fill.domain().unwrap();
+        assert_eq!(fill.map_value(&hi), Some(color::RED));
+        assert_eq!(scales.color_continuous.colors().len(), 2);
+
+        let err = scale_color_gradientn(vec![color::BLUE, color::RED])
+            .values(vec![0.0, 0.5, 1.0])
+            .apply_to(&mut scales);
+        assert!(matches!(err, Err(PlotError::InvalidScaleParameters { .. })));
+    }
+
+    #[test]
+    fn gradient_scale_1_diverging() {
+        init_test_logging();
+
+        let data = mtcars();
+        let builder = plot(&data).aes(|a| {
+            a.x_continuous("wt");
+            a.y_continuous("mpg");
+            a.color_continuous("mpg");
+        }) + geom_point().size(4.0)
+            + scale_color_gradient2(color::FIREBRICK, color::GRAY90, color::NAVY).midpoint(20.0);
+
+        let p = builder
+            .build()
+            .map_err(to_io_error)
+            .expect("Failed to build plot");
+        p.save("tests/images/gradient_scale_1_diverging.png", 800, 600)
+            .map_err(to_io_error)
+            .expect("Failed to save plot image");
+    }
+
+    #[test]
+    fn gradient_scale_2_viridis() {
+        init_test_logging();
+
+        let (mut xmin, mut ymin, mut v) = (Vec::new(), Vec::new(), Vec::new());
+        for i in 0..12 {
+            for j in 0..8 {
+                xmin.push(i as f64);
+                ymin.push(j as f64);
+                v.push(((i as f64 - 5.5).powi(2) + (j as f64 - 3.5).powi(2)).sqrt());
+            }
+        }
+        let xmax: Vec<f64> = xmin.iter().map(|x| x + 1.0).collect();
+        let ymax: Vec<f64> = ymin.iter().map(|y| y + 1.0).collect();
+        let data: Box<dyn DataSource> = Box::new(DataFrame::from_columns(vec![
+            ("xmin", VectorValue::from(xmin)),
+            ("xmax", VectorValue::from(xmax)),
+            ("ymin", VectorValue::from(ymin)),
+            ("ymax", VectorValue::from(ymax)),
+            ("v", VectorValue::from(v)),
+        ]));
+
+        let builder = plot(&data) + geom_rect().aes(|a| {
+            a.xmin("xmin");
+            a.xmax("xmax");
+            a.ymin("ymin");
+            a.ymax("ymax");
+            a.fill_continuous("v");
+        }) + scale_fill_viridis_c(ViridisOption::Magma).space(ColorSpace::Oklab);
+
+        let p = builder
+            .build()
+            .map_err(to_io_error)
+            .expect("Failed to build plot");
+        p.save("tests/images/gradient_scale_2_viridis.png", 800, 600)
+            .map_err(to_io_error)
+            .expect("Failed to save plot image");
+    }
+
+    #[test]
+    fn test_binned_color_scale() {
+        let mut scales = ScaleSet::default();
+        scale_fill_steps(color::WHITE, color::NAVY)
+            .breaks(vec![10.0, 20.0])
+            .apply_to(&mut scales)
+            .unwrap();
+        let fill = &mut scales.fill_continuous;
+        fill.train(VectorIter::Float(Box::new(vec![0.0, 5.0, 15.0, 30.0].into_iter())));
+
+        let bins = fill.bins().unwrap();
+        assert_eq!(bins.edges(), [0.0, 10.0, 20.0, 30.0]);
+        assert_eq!(fill.breaks(), [10.0, 20.0]);
+        assert_eq!(fill.bin_colors().len(), 3);
+        assert_eq!(fill.map_value(&1.0), fill.map_value(&9.0));
+        assert_ne!(fill.map_value(&9.0), fill.map_value(&10.0));
+        assert_eq!(fill.map_value(&30.0), Some(fill.bin_colors()[2]));
+
+        let err = scale_fill_continuous().n_bins(4).apply_to(&mut scales);
+        assert!(matches!(err, Err(PlotError::InvalidScaleParameters { .. })));
+        let err = scale_fill_binned().n_bins(0).apply_to(&mut scales);
+        assert!(matches!(err, Err(PlotError::InvalidScaleParameters { .. })));
+    }
+
+    #[test]
+    fn test_binned_x_counts_per_bin() {
+        let data = mtcars();
+        let p = (plot(&data).aes(|a| {
+            a.x_continuous("mpg");
+        }) + geom_bar().stat(Count::default())
+            + scale_x_binned().breaks(vec![15.0, 20.0, 25.0]))
+        .build()
+        .unwrap();
+
+        let bins = p.scales.x_continuous.bins().unwrap();
+        assert_eq!(bins.edges(), [10.4, 15.0, 20.0, 25.0, 33.9]);
+        assert_eq!(p.scales.x_continuous.breaks(), [15.0, 20.0, 25.0]);
+        // 32 cars in 4 bins: one bar each
+        let layer = &p.layers[0];
+        assert_eq!(layer.data.as_ref().unwrap().len(), 4);
+    }
+
+    #[test]
+    fn binned_scale_1_steps() {
+        init_test_logging();
+
+        let data = mtcars();
+        let builder = plot(&data).aes(|a| {
+            a.x_continuous("wt");
+            a.y_continuous("mpg");
+            a.color_continuous("hp");
+        }) + geom_point().size(4.0)
+            + scale_color_binned()
+                .colors(ViridisOption::Viridis.colors())
+                .bin_method(binned::BinMethod::Quantile)
+                .n_bins(4);
+
+        let p = builder
+            .build()
+            .map_err(to_io_error)
+            .expect("Failed to build plot");
+        p.save("tests/images/binned_scale_1_steps.png", 800, 600)
+            .map_err(to_io_error)
+            .expect("Failed to save plot image");
+    }
+
+    #[test]
+    fn binned_scale_2_x() {
+        init_test_logging();
+
+        let data = mtcars();
+        let builder = plot(&data).aes(|a| {
+            a.x_continuous("mpg");
+        }) + geom_bar().stat(Count::default())
+            + scale_x_binned().bin_method(binned::BinMethod::Equal).n_bins(6);
+
+        let p = builder
+            .build()
+            .map_err(to_io_error)
+            .expect("Failed to build plot");
+        p.save("tests/images/binned_scale_2_x.png", 800, 600)
+            .map_err(to_io_error)
+            .expect("Failed to save plot image");
+    }
+}
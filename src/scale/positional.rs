@@ -1,7 +1,16 @@
 use crate::{
     data::{PrimitiveType, VectorIter},
+    scale::binned::Binning,
+    scale::identity::{self, Identity},
+    scale::broken::{AxisBreak, BrokenAxis},
+    scale::labels::LabelFormatter,
+    scale::manual::{self, ManualValues},
+    scale::secondary::SecondaryAxis,
     scale::traits::{ContinuousDomainScale, DiscreteDomainScale},
     scale::transform::{Transform, IdentityTransform},
+    scale::utils::ExplicitBreaks,
+    scale::{Expansion, OutOfBounds},
+    utils::data::numeric_values,
     utils::set::DiscreteSet,
 };
 
@@ -9,9 +18,21 @@ use crate::{
 pub struct ContinuousPositionalScale {
     domain: Option<(f64, f64)>,  // Domain in transformed space
     breaks: Vec<f64>,            // Breaks in data space
+    minor_breaks: Vec<f64>,      // Minor breaks in data space
     labels: Vec<String>,
     lower_bound: Option<f64>,
     upper_bound: Option<f64>,
+    explicit: ExplicitBreaks,
+    oob: OutOfBounds,
+    expansion: Expansion,
+    bins: Option<Binning>,
+    secondary: Option<SecondaryAxis>,
+    secondary_breaks: Vec<f64>,  // Secondary breaks, placed in primary data space
+    secondary_labels: Vec<String>,
+    axis_breaks: Vec<AxisBreak>,
+    broken: Option<BrokenAxis>,  // Segments of the domain between axis breaks
+    identity: Option<Identity>,
+    zoomed: bool,                // Domain set by a coord zoom
     pub transform: Box<dyn Transform>,
 }
 
@@ -20,9 +41,21 @@ impl ContinuousPositionalScale {
         Self {
             domain: None,
             breaks: Vec::new(),
+            minor_breaks: Vec::new(),
             labels: Vec::new(),
             lower_bound: None,
             upper_bound: None,
+            explicit: ExplicitBreaks::default(),
+            oob: OutOfBounds::default(),
+            expansion: Expansion::default(),
+            bins: None,
+            secondary: None,
+            secondary_breaks: Vec::new(),
+            secondary_labels: Vec::new(),
+            axis_breaks: Vec::new(),
+            broken: None,
+            identity: None,
+            zoomed: false,
             transform: Box::new(IdentityTransform),
         }
     }
@@ -32,41 +65,207 @@ impl ContinuousPositionalScale {
         Self {
             domain: None,
             breaks: Vec::new(),
+            minor_breaks: Vec::new(),
             labels: Vec::new(),
             lower_bound: None,
             upper_bound: None,
+            explicit: ExplicitBreaks::default(),
+            oob: OutOfBounds::default(),
+            expansion: Expansion::default(),
+            bins: None,
+            secondary: None,
+            secondary_breaks: Vec::new(),
+            secondary_labels: Vec::new(),
+            axis_breaks: Vec::new(),
+            broken: None,
+            identity: None,
+            zoomed: false,
             transform,
         }
     }
 
-    /// Set the transformation for this scale
-    pub fn set_transform(&mut self, transform: Box<dyn Transform>) {
-        self.transform = transform;
-        // Clear domain and breaks since they're no longer valid
-        self.domain = None;
-        self.breaks.clear();
-        self.labels.clear();
-    }
-
-    /// Compute breaks and labels for this scale
+    /// Compute breaks, minor breaks and labels for this scale
     ///
-    /// Uses the transform's break generation and formatting.
+    /// Uses explicit breaks and labels when set, then the edges between
+    /// bins on a binned scale, and otherwise the transform's break
+    /// generation and formatting. Minor breaks fall between the majors
+    /// unless set explicitly.
     /// Should be called after training the domain.
     pub fn compute_breaks(&mut self, n: usize) {
-        if let Some((min_transformed, max_transformed)) = self.domain {
-            // Inverse transform to get data space limits
-            let min_data = self.transform.inverse(min_transformed);
-            let max_data = self.transform.inverse(max_transformed);
-            
-            // Use transform's break generation (returns breaks in data space)
-            self.breaks = self.transform.breaks((min_data, max_data), n);
-            
-            // Use transform's formatting for labels
-            self.labels = self.breaks.iter()
-                .map(|&b| self.transform.format(b))
-                .collect();
+        if let Some(domain) = self.domain {
+            let transform = &self.transform;
+            let bins = &self.bins;
+            let broken = &self.broken;
+            let (mut breaks, mut labels) = self.explicit.resolve(
+                transform.as_ref(),
+                domain,
+                || match (bins, broken) {
+                    (Some(bins), _) => bins.interior().iter().map(|&e| transform.inverse(e)).collect(),
+                    (None, Some(broken)) => segment_breaks(broken, transform.as_ref(), n),
+                    // Inverse transform to get data space limits
+                    (None, None) => {
+                        // Decreasing transforms swap the ends in data space
+                        let (a, b) = (transform.inverse(domain.0), transform.inverse(domain.1));
+                        transform.breaks((a.min(b), a.max(b)), n)
+                    }
+                },
+                |breaks| transform.format_breaks(breaks),
+            );
+            let mut minor_breaks = self.explicit.resolve_minor(transform.as_ref(), domain, &breaks);
+            let (mut secondary_breaks, mut secondary_labels) = match &self.secondary {
+                Some(secondary) => secondary.resolve(transform.as_ref(), domain, n),
+                None => (Vec::new(), Vec::new()),
+            };
+
+            // Breaks inside a collapsed range aren't drawn
+            if let Some(broken) = broken {
+                let shown = |b: &f64| !broken.is_collapsed(transform.transform(*b));
+                let keep: Vec<bool> = breaks.iter().map(shown).collect();
+                breaks.retain(shown);
+                labels = labels.into_iter().zip(&keep).filter(|(_, k)| **k).map(|(l, _)| l).collect();
+                minor_breaks.retain(shown);
+                let keep: Vec<bool> = secondary_breaks.iter().map(shown).collect();
+                secondary_breaks.retain(shown);
+                secondary_labels = secondary_labels.into_iter().zip(&keep).filter(|(_, k)| **k).map(|(l, _)| l).collect();
+            }
+
+            self.breaks = breaks;
+            self.labels = labels;
+            self.minor_breaks = minor_breaks;
+            self.secondary_breaks = secondary_breaks;
+            self.secondary_labels = secondary_labels;
         }
     }
+
+    /// Collapse ranges of this scale, drawing a gap at each
+    pub fn set_axis_breaks(&mut self, axis_breaks: Vec<AxisBreak>) {
+        self.axis_breaks = axis_breaks;
+        self.update_broken();
+    }
+
+    /// The collapsed ranges of this scale
+    pub fn axis_breaks(&self) -> &[AxisBreak] {
+        &self.axis_breaks
+    }
+
+    /// The gaps drawn at the axis breaks, as normalized (start, end)
+    /// positions along the axis
+    pub fn gaps(&self) -> Vec<(f64, f64)> {
+        self.broken.as_ref().map(BrokenAxis::gaps).unwrap_or_default()
+    }
+
+    fn update_broken(&mut self) {
+        self.broken = match self.domain {
+            Some(domain) if !self.axis_breaks.is_empty() => {
+                BrokenAxis::new(&self.axis_breaks, self.transform.as_ref(), domain)
+            }
+            _ => None,
+        };
+    }
+
+    /// Read values from the data itself, see [`identity`]. Used by the
+    /// continuous alpha scale.
+    pub fn set_identity(&mut self, identity: Identity) {
+        self.identity = Some(identity);
+    }
+
+    /// The identity scale installed on this scale, if any
+    pub fn identity(&self) -> Option<&Identity> {
+        self.identity.as_ref()
+    }
+
+    /// Show this scale in other units on the opposite side of the panel
+    pub fn set_secondary(&mut self, secondary: SecondaryAxis) {
+        self.secondary = Some(secondary);
+    }
+
+    /// The secondary axis, if one is attached
+    pub fn secondary(&self) -> Option<&SecondaryAxis> {
+        self.secondary.as_ref()
+    }
+
+    /// Secondary axis break positions, in this scale's data space
+    pub fn secondary_breaks(&self) -> &[f64] {
+        &self.secondary_breaks
+    }
+
+    /// Labels for the secondary axis breaks, in the secondary units
+    pub fn secondary_labels(&self) -> &[String] {
+        &self.secondary_labels
+    }
+
+    /// Minor break positions, in data space; drawn as minor grid lines
+    pub fn minor_breaks(&self) -> &[f64] {
+        &self.minor_breaks
+    }
+
+    /// Set explicit minor break positions, in data space. An empty list
+    /// turns the minor grid off for this scale.
+    pub fn set_minor_breaks(&mut self, minor_breaks: Vec<f64>) {
+        self.explicit.minor = Some(minor_breaks);
+    }
+
+    /// Cut the scale into bins; values are moved to the middle of their
+    /// bin before stats run
+    pub fn set_bins(&mut self, bins: Binning) {
+        self.bins = Some(bins);
+    }
+
+    /// The bins, if this is a binned scale
+    pub fn bins(&self) -> Option<&Binning> {
+        self.bins.as_ref()
+    }
+
+    /// Place the bin edges from the values seen by [`Binning::observe`]
+    pub(crate) fn compute_bins(&mut self) {
+        let transform = self.transform.as_ref();
+        let Some(bins) = &mut self.bins else {
+            return;
+        };
+        let range = match (self.lower_bound, self.upper_bound) {
+            (Some(lo), Some(hi)) => Some((transform.transform(lo), transform.transform(hi))),
+            _ => None,
+        };
+        bins.compute(range, self.explicit.breaks.as_deref(), transform);
+    }
+
+    /// Record data space values for bins placed from the data
+    pub(crate) fn observe_bins(&mut self, values: &[f64]) {
+        let transform = self.transform.as_ref();
+        if let Some(bins) = &mut self.bins {
+            bins.observe(values.iter().map(|&v| transform.transform(v)));
+        }
+    }
+
+    /// Move a data space value to the middle of its bin
+    pub(crate) fn bin_center(&self, value: f64) -> Option<f64> {
+        let bins = self.bins.as_ref()?;
+        let t = self.transform.transform(value);
+        let center = bins.center(bins.bin(t)?)?;
+        Some(self.transform.inverse(center))
+    }
+}
+
+/// Breaks for each segment of a broken axis, in data space, in proportion
+/// to the space the segment takes up
+fn segment_breaks(broken: &BrokenAxis, transform: &dyn Transform, n: usize) -> Vec<f64> {
+    let mut breaks: Vec<f64> = Vec::new();
+    for (start, end) in broken.ranges() {
+        let share = (broken.normalize(end) - broken.normalize(start)).abs();
+        let n_segment = ((n as f64 * share).round() as usize).max(2);
+        let (a, b) = (transform.inverse(start), transform.inverse(end));
+        breaks.extend(
+            transform
+                .breaks((a.min(b), a.max(b)), n_segment)
+                .into_iter()
+                .filter(|&b| {
+                    let t = transform.transform(b);
+                    t >= start && t <= end
+                }),
+        );
+    }
+    breaks.dedup();
+    breaks
 }
 
 impl Default for ContinuousPositionalScale {
@@ -80,9 +279,18 @@ impl std::fmt::Debug for ContinuousPositionalScale {
         f.debug_struct("ContinuousPositionalScale")
             .field("domain", &self.domain)
             .field("breaks", &self.breaks)
+            .field("minor_breaks", &self.minor_breaks)
             .field("labels", &self.labels)
             .field("lower_bound", &self.lower_bound)
             .field("upper_bound", &self.upper_bound)
+            .field("explicit", &self.explicit)
+            .field("oob", &self.oob)
+            .field("expansion", &self.expansion)
+            .field("bins", &self.bins)
+            .field("secondary", &self.secondary)
+            .field("axis_breaks", &self.axis_breaks)
+            .field("identity", &self.identity)
+            .field("zoomed", &self.zoomed)
             .field("transform", &self.transform.name())
             .finish()
     }
@@ -90,7 +298,14 @@ impl std::fmt::Debug for ContinuousPositionalScale {
 
 impl super::traits::ScaleBase for ContinuousPositionalScale {
     fn train<'a>(&mut self, iter: VectorIter<'a>) {
-        self.train_continuous(iter);
+        let Some(identity) = &mut self.identity else {
+            self.train_continuous(iter);
+            return;
+        };
+        // An identity legend lists the distinct values
+        let values = numeric_values(iter);
+        identity.observe(&values);
+        self.train_continuous(VectorIter::Float(Box::new(values.into_iter())));
     }
 }
 
@@ -101,83 +316,86 @@ impl super::traits::ContinuousDomainScale for ContinuousPositionalScale {
 
     fn set_domain(&mut self, domain: (f64, f64)) {
         self.domain = Some(domain);
+        self.update_broken();
+    }
+
+    fn zoom(&mut self, domain: (f64, f64)) {
+        self.set_domain(domain);
+        self.zoomed = true;
     }
 
     fn limits(&self) -> (Option<f64>, Option<f64>) {
         (self.lower_bound, self.upper_bound)
     }
 
+    fn set_limits(&mut self, limits: (f64, f64)) {
+        self.lower_bound = Some(limits.0);
+        self.upper_bound = Some(limits.1);
+    }
+
     fn breaks(&self) -> &[f64] {
         &self.breaks
     }
 
+    fn set_breaks(&mut self, breaks: Vec<f64>) {
+        self.explicit.breaks = Some(breaks);
+    }
+
     fn labels(&self) -> &[String] {
         &self.labels
     }
 
-    fn train_continuous<'a>(&mut self, iter: VectorIter<'a>) {
-        use crate::scale::ContinuousScaleTrainer;
-        use crate::utils::data::visit_c;
-        
-        let mut trainer = ContinuousScaleTrainer::new();
-        visit_c(iter, &mut trainer).unwrap();
-        
-        if let Some((obs_min_value, obs_max_value)) = trainer.bounds {
-            // Apply transformation to the observed bounds
-            let transformed_min = self.transform.transform(obs_min_value);
-            let transformed_max = self.transform.transform(obs_max_value);
-            
-            // Check if transformation is valid
-            if !transformed_min.is_finite() || !transformed_max.is_finite() {
-                log::warn!(
-                    "Transform {} produced non-finite values for domain ({}, {})",
-                    self.transform.name(),
-                    obs_min_value,
-                    obs_max_value
-                );
-                return;
-            }
-            
-            // Note: limits are stored in data space, need to transform them
-            let (min_limit, max_limit) = self.limits();
-            let min_value = if let Some(limit) = min_limit {
-                self.transform.transform(limit)
-            } else {
-                transformed_min
-            };
-            let max_value = if let Some(limit) = max_limit {
-                self.transform.transform(limit)
-            } else {
-                transformed_max
-            };
+    fn set_labels(&mut self, labels: Vec<String>) {
+        self.explicit.labels = Some(labels);
+    }
 
-            // Apply 5% expansion on each side (ggplot2 default) in transformed space
-            let range = max_value - min_value;
-            let expansion = range * 0.05;
-            let min_value = min_value - expansion;
-            let max_value = max_value + expansion;
-
-            // Merge with existing domain if present
-            if let Some((min_existing, max_existing)) = self.domain() {
-                let min_value = min_value.min(min_existing);
-                let max_value = max_value.max(max_existing);
-                self.set_domain((min_value, max_value));
-            } else {
-                self.set_domain((min_value, max_value));
-            }
-            
-            log::info!(
-                "Trained continuous scale domain to ({}, {}) in {} space with 5% expansion",
-                min_value,
-                max_value,
-                self.transform.name()
-            );
+    fn set_label_formatter(&mut self, formatter: Box<dyn LabelFormatter>) {
+        if self.explicit.labels.is_none() {
+            self.labels = formatter.format(&self.breaks);
+        }
+        self.explicit.formatter = Some(formatter);
+    }
+
+    fn transform(&self) -> &dyn Transform {
+        self.transform.as_ref()
+    }
+
+    fn set_transform(&mut self, transform: Box<dyn Transform>) {
+        self.transform = transform;
+        // Clear domain and breaks since they're no longer valid
+        self.domain = None;
+        self.broken = None;
+        self.zoomed = false;
+        self.breaks.clear();
+        self.minor_breaks.clear();
+        self.labels.clear();
+        if let Some(bins) = &mut self.bins {
+            bins.reset();
         }
     }
+
+    fn oob(&self) -> OutOfBounds {
+        self.oob
+    }
+
+    fn set_oob(&mut self, oob: OutOfBounds) {
+        self.oob = oob;
+    }
+
+    fn expansion(&self) -> Expansion {
+        self.expansion
+    }
+
+    fn set_expansion(&mut self, expansion: Expansion) {
+        self.expansion = expansion;
+    }
 }
 
 impl super::traits::ContinuousRangeScale for ContinuousPositionalScale {
     fn map_value<T: PrimitiveType>(&self, value: &T) -> Option<f64> {
+        if self.identity.is_some() {
+            return identity::number(value.to_primitive()).filter(|alpha| (0.0..=1.0).contains(alpha));
+        }
         let value = match value.to_primitive() {
             crate::data::PrimitiveValue::Int(x) => Some(x as f64),
             crate::data::PrimitiveValue::Float(x) => Some(x),
@@ -185,6 +403,12 @@ impl super::traits::ContinuousRangeScale for ContinuousPositionalScale {
             crate::data::PrimitiveValue::Bool(_) => None,
         }?;
         
+        // Values outside the transform's domain (e.g. zero for a reciprocal)
+        // have no position
+        if !self.transform.is_in_domain(value) {
+            return None;
+        }
+
         // Apply the transformation to the data value
         let transformed_value = self.transform.transform(value);
         
@@ -195,12 +419,22 @@ impl super::traits::ContinuousRangeScale for ContinuousPositionalScale {
             return None;
         }
         
+        // Values outside the limits follow the oob policy
+        let transformed_value = self.apply_oob(transformed_value)?;
+
+        // Values outside the domain are dropped, unless the oob policy keeps
+        // them or a coord zoom crops the view; those map past [0, 1] and are
+        // clipped when drawn
         let (d0, d1) = self.domain.unwrap();
-        if transformed_value < d0.min(d1) || transformed_value > d0.max(d1) {
+        let outside = transformed_value < d0.min(d1) || transformed_value > d0.max(d1);
+        if outside && !self.zoomed && self.oob != OutOfBounds::Keep {
             return None;
         }
 
-        let normalized = (transformed_value - d0) / (d1 - d0);
+        let normalized = match &self.broken {
+            Some(broken) => broken.normalize(transformed_value),
+            None => (transformed_value - d0) / (d1 - d0),
+        };
         log::debug!(
             "Mapping {} (transformed: {}) with domain ({}, {}) -> {}",
             value,
@@ -218,15 +452,36 @@ impl super::traits::ContinuousPositionalScale for ContinuousPositionalScale {}
 #[derive(Debug, Clone)]
 pub struct DiscretePositionalScale {
     elements: DiscreteSet,
+    manual: Option<ManualValues<f64>>,
+    identity: Option<Identity>,
 }
 
 impl DiscretePositionalScale {
     pub fn new() -> Self {
         Self {
             elements: DiscreteSet::new(),
+            manual: None,
+            identity: None,
         }
     }
 
+    /// Map chosen levels to chosen values, see [`manual`]. Used by the
+    /// discrete alpha scale.
+    pub fn set_manual(&mut self, manual: ManualValues<f64>) {
+        self.manual = Some(manual);
+    }
+
+    /// Read values from the data itself, see [`identity`]. Used by the
+    /// discrete alpha scale.
+    pub fn set_identity(&mut self, identity: Identity) {
+        self.identity = Some(identity);
+    }
+
+    /// The identity scale installed on this scale, if any
+    pub fn identity(&self) -> Option<&Identity> {
+        self.identity.as_ref()
+    }
+
     /// Get the break positions for discrete categories
     /// Returns the normalized position (0-1) for each category
     pub fn breaks(&self) -> Vec<f64> {
@@ -240,9 +495,11 @@ impl DiscretePositionalScale {
     }
 
     /// Get the labels for discrete categories
-    /// Returns the string representation of each category
+    /// Returns the label of each category, in display order
     pub fn labels(&self) -> Vec<String> {
-        self.elements.iter().map(|v| v.to_string()).collect()
+        (0..self.elements.len())
+            .filter_map(|i| self.elements.label_at(i))
+            .collect()
     }
 }
 
@@ -263,6 +520,10 @@ impl super::traits::DiscreteDomainScale for DiscretePositionalScale {
         &self.elements
     }
 
+    fn categories_mut(&mut self) -> &mut DiscreteSet {
+        &mut self.elements
+    }
+
     fn add_categories(&mut self, categories: DiscreteSet) {
         self.elements.union(&categories);
     }
@@ -270,13 +531,16 @@ impl super::traits::DiscreteDomainScale for DiscretePositionalScale {
 
 impl super::traits::ContinuousRangeScale for DiscretePositionalScale {
     fn map_value<T: PrimitiveType>(&self, value: &T) -> Option<f64> {
-        let ordinal = match value.to_primitive() {
-            crate::data::PrimitiveValue::Int(x) => Some(self.elements.ordinal(&x)?),
-            crate::data::PrimitiveValue::Float(_) => None,
-            crate::data::PrimitiveValue::Str(x) => Some(self.elements.ordinal(&x.to_string())?),
-            crate::data::PrimitiveValue::Bool(x) => Some(self.elements.ordinal(&x)?),
-        }?;
+        if self.identity.is_some() {
+            return identity::number(value.to_primitive()).filter(|alpha| (0.0..=1.0).contains(alpha));
+        }
+        let ordinal = match value.to_discrete() {
+            Some(level) => Some(self.elements.ordinal(&level)?),
+            None => None,
+        };
         let n = self.len() as f64;
-        Some((ordinal as f64 + 0.5) / n)
+        manual::resolve(self.manual.as_ref(), &self.elements, ordinal, |ordinal| {
+            (ordinal as f64 + 0.5) / n
+        })
     }
 }
@@ -0,0 +1,202 @@
+//! Secondary axes for positional scales
+//!
+//! A [`SecondaryAxis`] shows an x or y scale in other units on the opposite
+//! side of the panel, e.g. °F beside °C or percentages beside counts. It is
+//! a one-to-one transformation of the primary scale and has no data of its
+//! own: its breaks are chosen in the secondary units and mapped back onto
+//! the primary scale to be drawn.
+
+use std::sync::Arc;
+
+use crate::scale::labels::LabelFormatter;
+use crate::scale::transform::{Transform, compute_breaks};
+
+// Points sampled across the primary domain to invert the transformation
+const SAMPLES: usize = 1000;
+
+/// A secondary axis, attached with `ContinuousScaleBuilder::sec_axis`
+#[derive(Clone)]
+pub struct SecondaryAxis {
+    transform: Arc<dyn Fn(f64) -> f64 + Send + Sync>,
+    name: Option<String>,
+    breaks: Option<Vec<f64>>,
+    labels: Option<Vec<String>>,
+    formatter: Option<Box<dyn LabelFormatter>>,
+}
+
+impl std::fmt::Debug for SecondaryAxis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SecondaryAxis")
+            .field("name", &self.name)
+            .field("breaks", &self.breaks)
+            .field("labels", &self.labels)
+            .field("formatter", &self.formatter)
+            .finish()
+    }
+}
+
+impl SecondaryAxis {
+    /// Set the title drawn beside the secondary axis
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set explicit break positions, in the secondary axis's units
+    pub fn breaks(mut self, breaks: Vec<f64>) -> Self {
+        self.breaks = Some(breaks);
+        self
+    }
+
+    /// Set explicit labels, one per explicit break
+    pub fn labels(mut self, labels: Vec<String>) -> Self {
+        self.labels = Some(labels);
+        self
+    }
+
+    /// Format break labels with a [`LabelFormatter`]
+    pub fn label_formatter(mut self, formatter: impl LabelFormatter + 'static) -> Self {
+        self.formatter = Some(Box::new(formatter));
+        self
+    }
+
+    /// The title drawn beside the axis, if any
+    pub fn title(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub(crate) fn explicit_breaks(&self) -> Option<&[f64]> {
+        self.breaks.as_deref()
+    }
+
+    pub(crate) fn explicit_labels(&self) -> Option<&[String]> {
+        self.labels.as_deref()
+    }
+
+    /// Place the secondary breaks for a primary scale with the given
+    /// transform and domain (in transformed space).
+    ///
+    /// Returns the break positions in the primary scale's data space,
+    /// together with their labels. A transformation that isn't monotonic
+    /// over the domain gives no breaks.
+    pub(crate) fn resolve(&self, transform: &dyn Transform, domain: (f64, f64), n: usize) -> (Vec<f64>, Vec<String>) {
+        let (lo, hi) = (domain.0.min(domain.1), domain.0.max(domain.1));
+        let positions: Vec<f64> = (0..=SAMPLES)
+            .map(|i| lo + (hi - lo) * i as f64 / SAMPLES as f64)
+            .collect();
+        let values: Vec<f64> = positions
+            .iter()
+            .map(|&t| (self.transform)(transform.inverse(t)))
+            .collect();
+
+        let increasing = values.windows(2).all(|w| w[1] > w[0]);
+        let decreasing = values.windows(2).all(|w| w[1] < w[0]);
+        if values.iter().any(|v| !v.is_finite()) || !(increasing || decreasing) {
+            log::warn!("Secondary axis transformation is not monotonic over the scale's range; no breaks drawn");
+            return (Vec::new(), Vec::new());
+        }
+
+        let (first, last) = (values[0], values[SAMPLES]);
+        let (sec_lo, sec_hi) = (first.min(last), first.max(last));
+        let breaks = match &self.breaks {
+            Some(breaks) => breaks.clone(),
+            None => compute_breaks(sec_lo, sec_hi, n),
+        };
+
+        // Find each break between two samples, then bisect for its position
+        let mut kept = Vec::new();
+        let mut located = Vec::new();
+        for (i, &b) in breaks.iter().enumerate() {
+            if !(sec_lo..=sec_hi).contains(&b) {
+                continue;
+            }
+            let Some(j) = values.windows(2).position(|w| (w[0] - b) * (w[1] - b) <= 0.0) else {
+                continue;
+            };
+            let offset = |t: f64| (self.transform)(transform.inverse(t)) - b;
+            let (mut left, mut right) = (positions[j], positions[j + 1]);
+            for _ in 0..60 {
+                let mid = (left + right) / 2.0;
+                if offset(left) * offset(mid) <= 0.0 {
+                    right = mid;
+                } else {
+                    left = mid;
+                }
+            }
+            let t = (left + right) / 2.0;
+            kept.push(i);
+            located.push((transform.inverse(t), b));
+        }
+
+        let secondary_values: Vec<f64> = located.iter().map(|&(_, b)| b).collect();
+        let labels = match (&self.labels, &self.formatter) {
+            (Some(labels), _) if labels.len() == breaks.len() => {
+                kept.iter().map(|&i| labels[i].clone()).collect()
+            }
+            (_, Some(formatter)) => formatter.format(&secondary_values),
+            _ => super::utils::format_breaks(&secondary_values),
+        };
+        (located.into_iter().map(|(p, _)| p).collect(), labels)
+    }
+}
+
+/// Create a secondary axis showing the primary scale through `transform`,
+/// e.g. `sec_axis(|c| c * 9.0 / 5.0 + 32.0).name("°F")` beside a scale in
+/// °C. The transformation must be monotonic over the scale's range.
+pub fn sec_axis(transform: impl Fn(f64) -> f64 + Send + Sync + 'static) -> SecondaryAxis {
+    SecondaryAxis {
+        transform: Arc::new(transform),
+        name: None,
+        breaks: None,
+        labels: None,
+        formatter: None,
+    }
+}
+
+/// Create a secondary axis repeating the primary scale
+pub fn dup_axis() -> SecondaryAxis {
+    sec_axis(|x| x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scale::labels::label_percent;
+    use crate::scale::transform::{IdentityTransform, Log10Transform};
+
+    #[test]
+    fn test_fahrenheit_axis() {
+        let axis = sec_axis(|c| c * 9.0 / 5.0 + 32.0).name("°F");
+        let (positions, labels) = axis.resolve(&IdentityTransform, (0.0, 100.0), 5);
+        assert_eq!(labels.first().map(String::as_str), Some("50"));
+        assert_eq!(labels.last().map(String::as_str), Some("200"));
+        // 212°F sits at 100°C, so 200°F is just below it
+        let last = *positions.last().unwrap();
+        assert!((last - (200.0 - 32.0) * 5.0 / 9.0).abs() < 1e-6);
+        assert_eq!(axis.title(), Some("°F"));
+    }
+
+    #[test]
+    fn test_explicit_breaks_and_formatter() {
+        // Counts up to 200 shown as a share of the total
+        let axis = sec_axis(|n| n / 200.0)
+            .breaks(vec![0.0, 0.5, 1.0, 2.0])
+            .label_formatter(label_percent().accuracy(1.0));
+        let (positions, labels) = axis.resolve(&IdentityTransform, (0.0, 200.0), 5);
+        assert_eq!(labels, ["0%", "50%", "100%"]);
+        assert!((positions[1] - 100.0).abs() < 1e-6);
+
+        // Decreasing transformations on a log scale
+        let axis = sec_axis(|x| 1.0 / x).breaks(vec![0.1, 0.01]).labels(vec!["a".into(), "b".into()]);
+        let (positions, labels) = axis.resolve(&Log10Transform, (0.0, 3.0), 5);
+        assert_eq!(labels, ["a", "b"]);
+        assert!((positions[0] - 10.0).abs() < 1e-6 && (positions[1] - 100.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_non_monotonic_gives_no_breaks() {
+        let axis = sec_axis(|x| x * x);
+        let (positions, labels) = axis.resolve(&IdentityTransform, (-1.0, 1.0), 5);
+        assert!(positions.is_empty() && labels.is_empty());
+    }
+}
@@ -1,4 +1,6 @@
 use crate::data::{DiscreteType, VectorIter};
+use crate::scale::identity::{self, Identity};
+use crate::scale::manual::{self, ManualValues};
 use crate::scale::traits::DiscreteDomainScale;
 use crate::utils::set::DiscreteSet;
 use crate::visuals::Shape;
@@ -8,6 +10,8 @@ use crate::visuals::Shape;
 pub struct ShapeScale {
     shapes: Vec<Shape>,
     elements: DiscreteSet,
+    manual: Option<ManualValues<Shape>>,
+    identity: Option<Identity>,
 }
 
 impl ShapeScale {
@@ -16,9 +20,26 @@ impl ShapeScale {
         Self {
             shapes,
             elements: DiscreteSet::new(),
+            manual: None,
+            identity: None,
         }
     }
 
+    /// Map chosen levels to chosen shapes, see [`manual`].
+    pub fn set_manual(&mut self, manual: ManualValues<Shape>) {
+        self.manual = Some(manual);
+    }
+
+    /// Read shapes from the data itself, see [`identity`].
+    pub fn set_identity(&mut self, identity: Identity) {
+        self.identity = Some(identity);
+    }
+
+    /// The identity scale installed on this scale, if any
+    pub fn identity(&self) -> Option<&Identity> {
+        self.identity.as_ref()
+    }
+
     /// Create a default discrete shape scale with standard shapes.
     pub fn default_shapes() -> Self {
         Self::new(vec![
@@ -49,6 +70,10 @@ impl super::traits::DiscreteDomainScale for ShapeScale {
         &self.elements
     }
 
+    fn categories_mut(&mut self) -> &mut DiscreteSet {
+        &mut self.elements
+    }
+
     fn add_categories(&mut self, categories: DiscreteSet) {
         self.elements.union(&categories);
     }
@@ -56,9 +81,13 @@ impl super::traits::DiscreteDomainScale for ShapeScale {
 
 impl super::traits::ShapeRangeScale for ShapeScale {
     fn map_value<T: DiscreteType>(&self, value: &T) -> Option<Shape> {
-        let ordinal = self.elements.ordinal(value)?;
-        let shape = self.shapes[ordinal % self.shapes.len()];
-        Some(shape)
+        if self.identity.is_some() {
+            return identity::shape(value.to_primitive());
+        }
+        let ordinal = self.elements.ordinal(value);
+        manual::resolve(self.manual.as_ref(), &self.elements, ordinal, |i| {
+            self.shapes[i % self.shapes.len()]
+        })
     }
 }
 
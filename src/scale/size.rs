@@ -1,33 +1,57 @@
-use crate::{data::{PrimitiveType, VectorIter}, scale::traits::{ContinuousDomainScale, DiscreteDomainScale}, utils::set::DiscreteSet};
+use crate::{
+    data::{PrimitiveType, VectorIter},
+    scale::identity::{self, Identity},
+    scale::labels::LabelFormatter,
+    scale::manual::{self, ManualValues},
+    scale::traits::{ContinuousDomainScale, DiscreteDomainScale},
+    scale::transform::{IdentityTransform, Transform},
+    scale::utils::ExplicitBreaks,
+    scale::{Expansion, OutOfBounds},
+    utils::data::numeric_values,
+    utils::set::DiscreteSet,
+};
 
-
-
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct ContinuousSizeScale {
-    domain: (f64, f64),
+    domain: Option<(f64, f64)>, // Domain in transformed space
+    breaks: Vec<f64>,           // Breaks in data space
+    labels: Vec<String>,
     lower_bound: Option<f64>,
     upper_bound: Option<f64>,
+    explicit: ExplicitBreaks,
+    oob: OutOfBounds,
+    expansion: Expansion,
+    transform: Box<dyn Transform>,
     /// Output size range (min_size, max_size) in visual units (e.g., pixels)
     /// Default is (1.0, 6.0) following ggplot2
     range: (f64, f64),
     /// Whether to scale by area (true) or by radius (false)
     /// Default is true for perceptual uniformity (ggplot2 default)
     scale_area: bool,
+    identity: Option<Identity>,
 }
 
 impl ContinuousSizeScale {
     pub fn new() -> Self {
         Self {
-            domain: (0.0, 1.0),
+            domain: None,
+            breaks: Vec::new(),
+            labels: Vec::new(),
             lower_bound: None,
             upper_bound: None,
+            explicit: ExplicitBreaks::default(),
+            oob: OutOfBounds::default(),
+            expansion: Expansion::default(),
+            transform: Box::new(IdentityTransform),
             range: (1.0, 6.0),
             scale_area: true,
-         }
+            identity: None,
+        }
     }
 
     pub fn with_limits(mut self, limits: (f64, f64)) -> Self {
-        self.domain = limits;
+        self.domain = Some(limits);
+        self.compute_breaks(4);
         self
     }
 
@@ -53,6 +77,56 @@ impl ContinuousSizeScale {
         self.scale_area = scale_area;
         self
     }
+
+    /// Read sizes from the data itself, see [`identity`].
+    pub fn set_identity(&mut self, identity: Identity) {
+        self.identity = Some(identity);
+    }
+
+    /// The identity scale installed on this scale, if any
+    pub fn identity(&self) -> Option<&Identity> {
+        self.identity.as_ref()
+    }
+
+    /// Compute breaks and labels for this scale's legend
+    pub fn compute_breaks(&mut self, n: usize) {
+        let Some(domain) = self.domain else {
+            return;
+        };
+        let transform = &self.transform;
+        let (breaks, labels) = self.explicit.resolve(
+            transform.as_ref(),
+            domain,
+            || {
+                super::utils::extended_breaks_weighted_clamped(domain, n, 5.0, true)
+                    .into_iter()
+                    .map(|b| transform.inverse(b))
+                    .collect()
+            },
+            super::utils::format_breaks,
+        );
+        self.breaks = breaks;
+        self.labels = labels;
+    }
+}
+
+impl std::fmt::Debug for ContinuousSizeScale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ContinuousSizeScale")
+            .field("domain", &self.domain)
+            .field("breaks", &self.breaks)
+            .field("labels", &self.labels)
+            .field("lower_bound", &self.lower_bound)
+            .field("upper_bound", &self.upper_bound)
+            .field("explicit", &self.explicit)
+            .field("oob", &self.oob)
+            .field("expansion", &self.expansion)
+            .field("transform", &self.transform.name())
+            .field("range", &self.range)
+            .field("scale_area", &self.scale_area)
+            .field("identity", &self.identity)
+            .finish()
+    }
 }
 
 impl Default for ContinuousSizeScale {
@@ -63,48 +137,114 @@ impl Default for ContinuousSizeScale {
 
 impl super::traits::ScaleBase for ContinuousSizeScale {
     fn train<'a>(&mut self, iter: VectorIter<'a>) {
-        self.train_continuous(iter);
+        let Some(identity) = &mut self.identity else {
+            self.train_continuous(iter);
+            return;
+        };
+        // An identity legend lists the distinct values
+        let values = numeric_values(iter);
+        identity.observe(&values);
+        self.train_continuous(VectorIter::Float(Box::new(values.into_iter())));
     }
 }
 
 impl super::traits::ContinuousDomainScale for ContinuousSizeScale {
     fn domain(&self) -> Option<(f64, f64)> {
-        Some(self.domain)
+        self.domain
     }
 
     fn set_domain(&mut self, domain: (f64, f64)) {
-        self.domain = domain;
+        self.domain = Some(domain);
+        self.compute_breaks(4);
     }
 
     fn limits(&self) -> (Option<f64>, Option<f64>) {
         (self.lower_bound, self.upper_bound)
     }
 
+    fn set_limits(&mut self, limits: (f64, f64)) {
+        self.lower_bound = Some(limits.0);
+        self.upper_bound = Some(limits.1);
+    }
+
     fn breaks(&self) -> &[f64] {
-        &[]
+        &self.breaks
+    }
+
+    fn set_breaks(&mut self, breaks: Vec<f64>) {
+        self.explicit.breaks = Some(breaks);
+        self.compute_breaks(4);
     }
 
     fn labels(&self) -> &[String] {
-        &[]
+        &self.labels
+    }
+
+    fn set_labels(&mut self, labels: Vec<String>) {
+        self.explicit.labels = Some(labels);
+        self.compute_breaks(4);
+    }
+
+    fn set_label_formatter(&mut self, formatter: Box<dyn LabelFormatter>) {
+        self.explicit.formatter = Some(formatter);
+        self.compute_breaks(4);
+    }
+
+    fn transform(&self) -> &dyn Transform {
+        self.transform.as_ref()
+    }
+
+    fn set_transform(&mut self, transform: Box<dyn Transform>) {
+        self.transform = transform;
+        self.domain = None;
+        self.breaks.clear();
+        self.labels.clear();
+    }
+
+    fn oob(&self) -> OutOfBounds {
+        self.oob
+    }
+
+    fn set_oob(&mut self, oob: OutOfBounds) {
+        self.oob = oob;
+    }
+
+    fn expansion(&self) -> Expansion {
+        self.expansion
+    }
+
+    fn set_expansion(&mut self, expansion: Expansion) {
+        self.expansion = expansion;
     }
 }
 
 impl super::traits::ContinuousRangeScale for ContinuousSizeScale {
     fn map_value<T: crate::data::PrimitiveType>(&self, value: &T) -> Option<f64> {
+        if self.identity.is_some() {
+            return identity::number(value.to_primitive()).filter(|size| *size >= 0.0);
+        }
         let v = match value.to_primitive() {
             crate::data::PrimitiveValue::Int(x) => Some(x as f64),
             crate::data::PrimitiveValue::Float(x) => Some(x),
             crate::data::PrimitiveValue::Str(_) => None,
             crate::data::PrimitiveValue::Bool(_) => None,
         }?;
-        let (min_domain, max_domain) = self.domain;
-        if v < min_domain || v > max_domain {
+        if !self.transform.is_in_domain(v) {
+            return None;
+        }
+        let v = self.transform.transform(v);
+        if !v.is_finite() {
+            return None;
+        }
+        let v = self.apply_oob(v)?;
+        let (min_domain, max_domain) = self.domain.unwrap_or((0.0, 1.0));
+        if self.oob != OutOfBounds::Keep && (v < min_domain || v > max_domain) {
             return None;
         }
         
-        // Normalize to [0, 1]
+        // Normalize to [0, 1]; kept values past the domain get the end sizes
         let t = if max_domain > min_domain {
-            (v - min_domain) / (max_domain - min_domain)
+            ((v - min_domain) / (max_domain - min_domain)).clamp(0.0, 1.0)
         } else {
             0.5 // If domain has no range, use middle of output range
         };
@@ -134,6 +274,8 @@ pub struct DiscreteSizeScale {
     /// Output size range (min_size, max_size) in visual units (e.g., pixels)
     /// Default is (2.0, 6.0) to match ggplot2's discrete size scale
     range: (f64, f64),
+    manual: Option<ManualValues<f64>>,
+    identity: Option<Identity>,
 }
 
 impl DiscreteSizeScale {
@@ -142,9 +284,26 @@ impl DiscreteSizeScale {
         Self {
             elements: DiscreteSet::new(),
             range: (2.0, 6.0),
+            manual: None,
+            identity: None,
         }
     }
 
+    /// Map chosen levels to chosen sizes, see [`manual`].
+    pub fn set_manual(&mut self, manual: ManualValues<f64>) {
+        self.manual = Some(manual);
+    }
+
+    /// Read sizes from the data itself, see [`identity`].
+    pub fn set_identity(&mut self, identity: Identity) {
+        self.identity = Some(identity);
+    }
+
+    /// The identity scale installed on this scale, if any
+    pub fn identity(&self) -> Option<&Identity> {
+        self.identity.as_ref()
+    }
+
     /// Set the output size range (min, max) in visual units
     pub fn with_range(mut self, range: (f64, f64)) -> Self {
         self.range = range;
@@ -169,6 +328,10 @@ impl super::traits::DiscreteDomainScale for DiscreteSizeScale {
         &self.elements
     }
 
+    fn categories_mut(&mut self) -> &mut DiscreteSet {
+        &mut self.elements
+    }
+
     fn add_categories(&mut self, categories: DiscreteSet) {
         self.elements.union(&categories);
     }
@@ -176,19 +339,17 @@ impl super::traits::DiscreteDomainScale for DiscreteSizeScale {
 
 impl super::traits::ContinuousRangeScale for DiscreteSizeScale {
     fn map_value<T: PrimitiveType>(&self, value: &T) -> Option<f64> {
-        let ordinal = match value.to_primitive() {
-            crate::data::PrimitiveValue::Int(x) => self.elements.ordinal(&x),
-            crate::data::PrimitiveValue::Float(_) => None,
-            crate::data::PrimitiveValue::Str(x) => self.elements.ordinal(&x),
-            crate::data::PrimitiveValue::Bool(x) => self.elements.ordinal(&x),
-        }?;
-        
+        if self.identity.is_some() {
+            return identity::number(value.to_primitive()).filter(|size| *size >= 0.0);
+        }
+        let ordinal = value.to_discrete().and_then(|level| self.elements.ordinal(&level));
+
         // Map ordinal position to size range
-        let n_categories = self.elements.len().max(1);
-        let t = (ordinal as f64) / ((n_categories - 1).max(1) as f64);
-        let (min_size, max_size) = self.range;
-        let size = min_size + t * (max_size - min_size);
-        
-        Some(size)
+        manual::resolve(self.manual.as_ref(), &self.elements, ordinal, |ordinal| {
+            let n_categories = self.elements.len().max(1);
+            let t = (ordinal as f64) / ((n_categories - 1).max(1) as f64);
+            let (min_size, max_size) = self.range;
+            min_size + t * (max_size - min_size)
+        })
     }
 }
\ No newline at end of file
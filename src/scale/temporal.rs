@@ -0,0 +1,847 @@
+//! Date, date-time and duration transformations
+//!
+//! Dates are stored as days since 1970-01-01, date-times as seconds since
+//! the Unix epoch and durations as seconds, so the scales themselves stay
+//! continuous. The transforms
+//! here only change how breaks and labels are chosen: breaks land on
+//! calendar units (years, quarters, months, weeks, days, hours, ...) and
+//! labels are formatted with strftime-style patterns.
+
+use std::str::FromStr;
+
+use jiff::civil::{Date, DateTime};
+use jiff::tz::TimeZone;
+use jiff::{RoundMode, SignedDuration, Span, Timestamp, Unit, Zoned, ZonedRound};
+
+use crate::error::PlotError;
+use crate::scale::transform::Transform;
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// A calendar unit used to place date breaks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalendarUnit {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Quarter,
+    Year,
+}
+
+impl CalendarUnit {
+    /// Average length of the unit in seconds
+    fn seconds(&self) -> f64 {
+        match self {
+            CalendarUnit::Second => 1.0,
+            CalendarUnit::Minute => 60.0,
+            CalendarUnit::Hour => 3_600.0,
+            CalendarUnit::Day => SECONDS_PER_DAY,
+            CalendarUnit::Week => 7.0 * SECONDS_PER_DAY,
+            CalendarUnit::Month => 30.436_875 * SECONDS_PER_DAY,
+            CalendarUnit::Quarter => 3.0 * 30.436_875 * SECONDS_PER_DAY,
+            CalendarUnit::Year => 365.242_5 * SECONDS_PER_DAY,
+        }
+    }
+}
+
+/// A break interval such as "1 month" or "6 hours"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CalendarInterval {
+    pub count: i64,
+    pub unit: CalendarUnit,
+}
+
+/// Candidate intervals for automatic breaks, from finest to coarsest
+const AUTO_INTERVALS: [(i64, CalendarUnit); 30] = [
+    (1, CalendarUnit::Second),
+    (2, CalendarUnit::Second),
+    (5, CalendarUnit::Second),
+    (10, CalendarUnit::Second),
+    (15, CalendarUnit::Second),
+    (30, CalendarUnit::Second),
+    (1, CalendarUnit::Minute),
+    (2, CalendarUnit::Minute),
+    (5, CalendarUnit::Minute),
+    (10, CalendarUnit::Minute),
+    (15, CalendarUnit::Minute),
+    (30, CalendarUnit::Minute),
+    (1, CalendarUnit::Hour),
+    (3, CalendarUnit::Hour),
+    (6, CalendarUnit::Hour),
+    (12, CalendarUnit::Hour),
+    (1, CalendarUnit::Day),
+    (2, CalendarUnit::Day),
+    (1, CalendarUnit::Week),
+    (2, CalendarUnit::Week),
+    (1, CalendarUnit::Month),
+    (2, CalendarUnit::Month),
+    (1, CalendarUnit::Quarter),
+    (6, CalendarUnit::Month),
+    (1, CalendarUnit::Year),
+    (2, CalendarUnit::Year),
+    (5, CalendarUnit::Year),
+    (10, CalendarUnit::Year),
+    (25, CalendarUnit::Year),
+    (100, CalendarUnit::Year),
+];
+
+impl CalendarInterval {
+    pub fn new(count: i64, unit: CalendarUnit) -> Self {
+        Self { count, unit }
+    }
+
+    /// Pick the finest interval giving at most `n` breaks over `range` seconds
+    pub fn auto(range: f64, n: usize) -> Self {
+        let n = n.max(1) as f64;
+        AUTO_INTERVALS
+            .iter()
+            .map(|&(count, unit)| Self::new(count, unit))
+            .find(|interval| range / interval.seconds() <= n)
+            .unwrap_or(Self::new(1000, CalendarUnit::Year))
+    }
+
+    fn seconds(&self) -> f64 {
+        self.count as f64 * self.unit.seconds()
+    }
+
+    fn span(&self, k: i64) -> Span {
+        let n = self.count * k;
+        match self.unit {
+            CalendarUnit::Second => Span::new().seconds(n),
+            CalendarUnit::Minute => Span::new().minutes(n),
+            CalendarUnit::Hour => Span::new().hours(n),
+            CalendarUnit::Day => Span::new().days(n),
+            CalendarUnit::Week => Span::new().weeks(n),
+            CalendarUnit::Month => Span::new().months(n),
+            CalendarUnit::Quarter => Span::new().months(3 * n),
+            CalendarUnit::Year => Span::new().years(n),
+        }
+    }
+
+    /// Round a time down to the start of the interval containing it
+    fn floor(&self, z: &Zoned) -> Result<Zoned, jiff::Error> {
+        let step = self.count.max(1);
+        let align = |value: i64, base: i64| value - (value - base).rem_euclid(step);
+        match self.unit {
+            CalendarUnit::Year => {
+                let year = align(z.year() as i64, 0);
+                z.first_of_year()?.with().year(year as i16).build()
+            }
+            CalendarUnit::Month | CalendarUnit::Quarter => {
+                let months = if self.unit == CalendarUnit::Quarter { 3 * step } else { step };
+                let m0 = z.month() as i64 - 1;
+                let month = m0 - m0.rem_euclid(months) + 1;
+                z.first_of_month()?.with().month(month as i8).build()
+            }
+            CalendarUnit::Week => {
+                let day = z.start_of_day()?;
+                let back = day.weekday().to_monday_zero_offset() as i64;
+                day.checked_sub(Span::new().days(back))
+            }
+            CalendarUnit::Day => {
+                let day = align(z.day() as i64, 1);
+                z.start_of_day()?.with().day(day as i8).build()
+            }
+            CalendarUnit::Hour => {
+                let hour = align(z.hour() as i64, 0);
+                truncate(z, Unit::Hour)?.with().hour(hour as i8).build()
+            }
+            CalendarUnit::Minute => {
+                let minute = align(z.minute() as i64, 0);
+                truncate(z, Unit::Minute)?.with().minute(minute as i8).build()
+            }
+            CalendarUnit::Second => {
+                let second = align(z.second() as i64, 0);
+                truncate(z, Unit::Second)?.with().second(second as i8).build()
+            }
+        }
+    }
+
+    /// Breaks on this interval covering `limits` (seconds since the epoch)
+    fn breaks(&self, limits: (f64, f64), tz: &TimeZone) -> Vec<f64> {
+        let (lo, hi) = limits;
+        if !lo.is_finite() || !hi.is_finite() || lo > hi || self.count <= 0 {
+            return Vec::new();
+        }
+        let Some(start) = to_zoned(lo, tz).and_then(|z| self.floor(&z).ok()) else {
+            return Vec::new();
+        };
+
+        let mut breaks = Vec::new();
+        // Step from the aligned start so month lengths don't make breaks drift
+        for k in 0.. {
+            let Ok(z) = start.checked_add(self.span(k)) else {
+                break;
+            };
+            let t = seconds(&z);
+            if t > hi || breaks.len() > 1000 {
+                break;
+            }
+            if t >= lo {
+                breaks.push(t);
+            }
+        }
+        breaks
+    }
+}
+
+impl FromStr for CalendarInterval {
+    type Err = PlotError;
+
+    /// Parse intervals like "1 month", "2 weeks", "quarter" or "15 mins"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || PlotError::InvalidScaleParameters {
+            details: format!("invalid date break interval '{}'", s),
+        };
+        let parts: Vec<&str> = s.split_whitespace().collect();
+        let (count, unit) = match parts.as_slice() {
+            [unit] => (1, *unit),
+            [count, unit] => (count.parse::<i64>().map_err(|_| invalid())?, *unit),
+            _ => return Err(invalid()),
+        };
+        if count <= 0 {
+            return Err(invalid());
+        }
+        let unit = match unit.to_lowercase().trim_end_matches('s') {
+            "sec" | "second" => CalendarUnit::Second,
+            "min" | "minute" => CalendarUnit::Minute,
+            "hour" => CalendarUnit::Hour,
+            "day" => CalendarUnit::Day,
+            "week" => CalendarUnit::Week,
+            "month" => CalendarUnit::Month,
+            "quarter" => CalendarUnit::Quarter,
+            "year" => CalendarUnit::Year,
+            _ => return Err(invalid()),
+        };
+        Ok(Self::new(count, unit))
+    }
+}
+
+fn truncate(z: &Zoned, unit: Unit) -> Result<Zoned, jiff::Error> {
+    z.round(ZonedRound::new().smallest(unit).mode(RoundMode::Trunc))
+}
+
+fn to_zoned(seconds: f64, tz: &TimeZone) -> Option<Zoned> {
+    let duration = SignedDuration::try_from_secs_f64(seconds).ok()?;
+    let timestamp = Timestamp::from_duration(duration).ok()?;
+    Some(timestamp.to_zoned(tz.clone()))
+}
+
+fn seconds(z: &Zoned) -> f64 {
+    z.timestamp().as_duration().as_secs_f64()
+}
+
+/// Default label format for breaks spaced `step` seconds apart
+fn default_format(breaks: &[f64], date_only: bool) -> &'static str {
+    let step = breaks
+        .windows(2)
+        .map(|w| w[1] - w[0])
+        .fold(f64::INFINITY, f64::min);
+    if step >= 360.0 * SECONDS_PER_DAY {
+        "%Y"
+    } else if step >= 28.0 * SECONDS_PER_DAY {
+        "%b %Y"
+    } else if step >= SECONDS_PER_DAY || date_only {
+        "%b %d"
+    } else if step >= 60.0 {
+        let first = breaks.first().copied().unwrap_or(0.0);
+        let last = breaks.last().copied().unwrap_or(0.0);
+        if last - first >= SECONDS_PER_DAY {
+            "%b %d %H:%M"
+        } else {
+            "%H:%M"
+        }
+    } else {
+        "%H:%M:%S"
+    }
+}
+
+fn format_seconds(seconds: f64, tz: &TimeZone, format: &str) -> Option<String> {
+    let z = to_zoned(seconds, tz)?;
+    jiff::fmt::strtime::format(format, &z).ok()
+}
+
+/// Check that a strftime-style pattern can be used for labels
+pub(crate) fn validate_format(format: &str) -> Result<(), PlotError> {
+    format_seconds(0.0, &TimeZone::UTC, format)
+        .map(|_| ())
+        .ok_or_else(|| PlotError::InvalidScaleParameters {
+            details: format!("invalid date label format '{}'", format),
+        })
+}
+
+/// Look up a time zone by IANA name (e.g. "Europe/Paris"), "UTC" or a fixed
+/// offset such as "+05:30" (as used by Arrow timestamps)
+pub(crate) fn time_zone(name: &str) -> Result<TimeZone, PlotError> {
+    if let Some(offset) = fixed_offset(name) {
+        return Ok(TimeZone::fixed(offset));
+    }
+    TimeZone::get(name).map_err(|e| PlotError::InvalidScaleParameters {
+        details: format!("unknown time zone '{}': {}", name, e),
+    })
+}
+
+fn fixed_offset(name: &str) -> Option<jiff::tz::Offset> {
+    let sign = match name.chars().next()? {
+        '+' => 1,
+        '-' => -1,
+        _ => return None,
+    };
+    let (hours, minutes) = name[1..].split_once(':').unwrap_or((&name[1..], "0"));
+    let seconds = hours.parse::<i32>().ok()? * 3600 + minutes.parse::<i32>().ok()? * 60;
+    jiff::tz::Offset::from_seconds(sign * seconds).ok()
+}
+
+/// Whether a scale holds dates or date-times
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TemporalKind {
+    Date,
+    DateTime,
+    Duration,
+}
+
+/// Build the transform for a date, datetime or duration scale from builder settings
+pub(crate) fn build_transform(
+    kind: TemporalKind,
+    breaks: Option<&str>,
+    labels: Option<&str>,
+    tz: Option<&str>,
+) -> Result<Box<dyn Transform>, PlotError> {
+    let breaks_every = breaks.map(CalendarInterval::from_str).transpose()?;
+    if let Some(format) = labels {
+        validate_format(format)?;
+    }
+
+    let transform: Box<dyn Transform> = match kind {
+        TemporalKind::Date => {
+            if tz.is_some() {
+                return Err(PlotError::InvalidScaleParameters {
+                    details: "timezone requires a datetime scale".to_string(),
+                });
+            }
+            let mut transform = DateTransform::new();
+            transform.breaks_every = breaks_every;
+            transform.format = labels.map(String::from);
+            Box::new(transform)
+        }
+        TemporalKind::DateTime => {
+            let mut transform = DateTimeTransform::new();
+            if let Some(tz) = tz {
+                transform.tz = time_zone(tz)?;
+            }
+            transform.breaks_every = breaks_every;
+            transform.format = labels.map(String::from);
+            Box::new(transform)
+        }
+        TemporalKind::Duration => {
+            if tz.is_some() || labels.is_some() {
+                return Err(PlotError::InvalidScaleParameters {
+                    details: "duration scales don't take a timezone or date labels".to_string(),
+                });
+            }
+            if breaks_every.is_some_and(|interval| interval.unit.seconds() > CalendarUnit::Week.seconds()) {
+                return Err(PlotError::InvalidScaleParameters {
+                    details: "duration breaks must be weeks or shorter".to_string(),
+                });
+            }
+            Box::new(DurationTransform { breaks_every })
+        }
+    };
+    Ok(transform)
+}
+
+/// Convert a civil date to days since 1970-01-01
+pub fn date_to_days(date: Date) -> f64 {
+    date.duration_since(Date::constant(1970, 1, 1)).as_secs_f64() / SECONDS_PER_DAY
+}
+
+/// Dates as days since 1970-01-01, with calendar breaks and labels
+#[derive(Debug, Clone, Default)]
+pub struct DateTransform {
+    breaks_every: Option<CalendarInterval>,
+    format: Option<String>,
+}
+
+impl DateTransform {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Place breaks every `interval` instead of choosing one from the range
+    pub fn breaks_every(mut self, interval: CalendarInterval) -> Self {
+        self.breaks_every = Some(interval);
+        self
+    }
+
+    /// Format labels with a strftime-style pattern, e.g. "%b %Y"
+    pub fn labels(mut self, format: impl Into<String>) -> Self {
+        self.format = Some(format.into());
+        self
+    }
+}
+
+impl Transform for DateTransform {
+    fn transform(&self, x: f64) -> f64 {
+        x
+    }
+
+    fn inverse(&self, x: f64) -> f64 {
+        x
+    }
+
+    fn domain(&self) -> (f64, f64) {
+        (f64::NEG_INFINITY, f64::INFINITY)
+    }
+
+    fn breaks(&self, limits: (f64, f64), n: usize) -> Vec<f64> {
+        let seconds = (limits.0 * SECONDS_PER_DAY, limits.1 * SECONDS_PER_DAY);
+        let mut interval = self
+            .breaks_every
+            .unwrap_or_else(|| CalendarInterval::auto(seconds.1 - seconds.0, n));
+        // Dates have no time of day, so never break finer than a day
+        if interval.unit.seconds() < SECONDS_PER_DAY {
+            interval = CalendarInterval::new(1, CalendarUnit::Day);
+        }
+        interval
+            .breaks(seconds, &TimeZone::UTC)
+            .into_iter()
+            .map(|s| s / SECONDS_PER_DAY)
+            .collect()
+    }
+
+    fn format(&self, x: f64) -> String {
+        self.format_breaks(&[x]).pop().unwrap_or_default()
+    }
+
+    fn format_breaks(&self, breaks: &[f64]) -> Vec<String> {
+        let seconds: Vec<f64> = breaks.iter().map(|&b| b * SECONDS_PER_DAY).collect();
+        let format = self
+            .format
+            .as_deref()
+            .unwrap_or_else(|| default_format(&seconds, true));
+        seconds
+            .iter()
+            .map(|&s| format_seconds(s, &TimeZone::UTC, format).unwrap_or_default())
+            .collect()
+    }
+
+    fn parse(&self, value: &str) -> Option<f64> {
+        let value = value.trim();
+        if let Ok(date) = value.parse::<Date>() {
+            return Some(date_to_days(date));
+        }
+        value.parse::<DateTime>().ok().map(|dt| date_to_days(dt.date()))
+    }
+
+    fn name(&self) -> &str {
+        "date"
+    }
+
+    fn box_clone(&self) -> Box<dyn Transform> {
+        Box::new(self.clone())
+    }
+}
+
+/// Date-times as seconds since the Unix epoch, with calendar breaks and
+/// labels in a given time zone (UTC by default)
+#[derive(Debug, Clone)]
+pub struct DateTimeTransform {
+    tz: TimeZone,
+    breaks_every: Option<CalendarInterval>,
+    format: Option<String>,
+}
+
+impl DateTimeTransform {
+    pub fn new() -> Self {
+        Self {
+            tz: TimeZone::UTC,
+            breaks_every: None,
+            format: None,
+        }
+    }
+
+    /// Place breaks and format labels in this time zone
+    pub fn time_zone(mut self, tz: TimeZone) -> Self {
+        self.tz = tz;
+        self
+    }
+
+    /// Place breaks every `interval` instead of choosing one from the range
+    pub fn breaks_every(mut self, interval: CalendarInterval) -> Self {
+        self.breaks_every = Some(interval);
+        self
+    }
+
+    /// Format labels with a strftime-style pattern, e.g. "%H:%M"
+    pub fn labels(mut self, format: impl Into<String>) -> Self {
+        self.format = Some(format.into());
+        self
+    }
+}
+
+impl Default for DateTimeTransform {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Transform for DateTimeTransform {
+    fn transform(&self, x: f64) -> f64 {
+        x
+    }
+
+    fn inverse(&self, x: f64) -> f64 {
+        x
+    }
+
+    fn domain(&self) -> (f64, f64) {
+        (f64::NEG_INFINITY, f64::INFINITY)
+    }
+
+    fn breaks(&self, limits: (f64, f64), n: usize) -> Vec<f64> {
+        let interval = self
+            .breaks_every
+            .unwrap_or_else(|| CalendarInterval::auto(limits.1 - limits.0, n));
+        interval.breaks(limits, &self.tz)
+    }
+
+    fn format(&self, x: f64) -> String {
+        self.format_breaks(&[x]).pop().unwrap_or_default()
+    }
+
+    fn format_breaks(&self, breaks: &[f64]) -> Vec<String> {
+        let format = self
+            .format
+            .as_deref()
+            .unwrap_or_else(|| default_format(breaks, false));
+        breaks
+            .iter()
+            .map(|&s| format_seconds(s, &self.tz, format).unwrap_or_default())
+            .collect()
+    }
+
+    /// Accepts RFC 3339 timestamps ("2024-03-01T12:00:00Z"), civil
+    /// date-times interpreted in the scale's time zone, and plain dates
+    fn parse(&self, value: &str) -> Option<f64> {
+        let value = value.trim();
+        if let Ok(ts) = value.parse::<Timestamp>() {
+            return Some(ts.as_duration().as_secs_f64());
+        }
+        let civil = value
+            .parse::<DateTime>()
+            .or_else(|_| value.parse::<Date>().map(|d| d.to_datetime(jiff::civil::Time::midnight())))
+            .ok()?;
+        let zoned = civil.to_zoned(self.tz.clone()).ok()?;
+        Some(seconds(&zoned))
+    }
+
+    fn name(&self) -> &str {
+        "datetime"
+    }
+
+    fn box_clone(&self) -> Box<dyn Transform> {
+        Box::new(self.clone())
+    }
+}
+
+/// Durations in seconds, with breaks on whole seconds, minutes, hours,
+/// days or weeks and clock-style labels ("1:30:00", "2d")
+#[derive(Debug, Clone, Default)]
+pub struct DurationTransform {
+    breaks_every: Option<CalendarInterval>,
+}
+
+impl DurationTransform {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Place breaks every `interval` (weeks or shorter)
+    pub fn breaks_every(mut self, interval: CalendarInterval) -> Self {
+        self.breaks_every = Some(interval);
+        self
+    }
+}
+
+fn format_duration(seconds: f64, step: f64) -> String {
+    let sign = if seconds < 0.0 { "-" } else { "" };
+    let total = seconds.abs().round() as i64;
+    if step >= SECONDS_PER_DAY && total % 86_400 == 0 {
+        return format!("{}{}d", sign, total / 86_400);
+    }
+    let (hours, minutes, secs) = (total / 3600, total % 3600 / 60, total % 60);
+    if step >= 60.0 && secs == 0 {
+        format!("{}{}:{:02}", sign, hours, minutes)
+    } else {
+        format!("{}{}:{:02}:{:02}", sign, hours, minutes, secs)
+    }
+}
+
+impl Transform for DurationTransform {
+    fn transform(&self, x: f64) -> f64 {
+        x
+    }
+
+    fn inverse(&self, x: f64) -> f64 {
+        x
+    }
+
+    fn domain(&self) -> (f64, f64) {
+        (f64::NEG_INFINITY, f64::INFINITY)
+    }
+
+    fn breaks(&self, limits: (f64, f64), n: usize) -> Vec<f64> {
+        let (lo, hi) = limits;
+        if !lo.is_finite() || !hi.is_finite() || lo > hi {
+            return Vec::new();
+        }
+        let interval = self.breaks_every.unwrap_or_else(|| {
+            // Months and years have no fixed length, so cap automatic steps at weeks
+            let auto = CalendarInterval::auto(hi - lo, n);
+            if auto.seconds() > CalendarUnit::Week.seconds() {
+                let weeks = ((hi - lo) / n.max(1) as f64 / CalendarUnit::Week.seconds()).ceil() as i64;
+                CalendarInterval::new(weeks.max(1), CalendarUnit::Week)
+            } else {
+                auto
+            }
+        });
+        let step = interval.seconds();
+        let first = (lo / step).ceil() as i64;
+        let last = (hi / step).floor() as i64;
+        (first..=last).take(1000).map(|k| k as f64 * step).collect()
+    }
+
+    fn format(&self, x: f64) -> String {
+        format_duration(x, 1.0)
+    }
+
+    fn format_breaks(&self, breaks: &[f64]) -> Vec<String> {
+        let step = breaks
+            .windows(2)
+            .map(|w| w[1] - w[0])
+            .fold(f64::INFINITY, f64::min);
+        breaks.iter().map(|&b| format_duration(b, step)).collect()
+    }
+
+    /// Accepts ISO 8601 ("PT1H30M") and friendly ("1h 30m") durations
+    fn parse(&self, value: &str) -> Option<f64> {
+        value.trim().parse::<SignedDuration>().ok().map(|d| d.as_secs_f64())
+    }
+
+    fn name(&self) -> &str {
+        "duration"
+    }
+
+    fn box_clone(&self) -> Box<dyn Transform> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aesthetics::builder::{XContinuousAesBuilder, YContinuousAesBuilder};
+    use crate::error::to_io_error;
+    use crate::geom::line::geom_line;
+    use crate::plot::plot;
+    use crate::scale::traits::ContinuousDomainScale;
+    use crate::scale::{ScaleSet, scale_x_continuous, scale_x_date, scale_x_datetime};
+    use crate::utils::sp500::sp500;
+
+    fn day(s: &str) -> f64 {
+        DateTransform::new().parse(s).unwrap()
+    }
+
+    #[test]
+    fn test_parse_interval() {
+        let parsed: CalendarInterval = "1 month".parse().unwrap();
+        assert_eq!(parsed, CalendarInterval::new(1, CalendarUnit::Month));
+        let parsed: CalendarInterval = "15 mins".parse().unwrap();
+        assert_eq!(parsed, CalendarInterval::new(15, CalendarUnit::Minute));
+        let parsed: CalendarInterval = "quarter".parse().unwrap();
+        assert_eq!(parsed, CalendarInterval::new(1, CalendarUnit::Quarter));
+        assert!("3 fortnights".parse::<CalendarInterval>().is_err());
+        assert!("0 days".parse::<CalendarInterval>().is_err());
+    }
+
+    #[test]
+    fn test_date_parse_and_format() {
+        let trans = DateTransform::new();
+        assert_eq!(day("1970-01-02"), 1.0);
+        assert_eq!(day("2007-01-03"), 13516.0);
+        assert_eq!(trans.parse("2007-01-03T15:00:00"), Some(13516.0));
+        assert_eq!(trans.parse("not a date"), None);
+        assert_eq!(trans.clone().labels("%Y/%m/%d").format(13516.0), "2007/01/03");
+    }
+
+    #[test]
+    fn test_date_breaks_by_range() {
+        let trans = DateTransform::new();
+
+        // A year of data breaks on quarters
+        let breaks = trans.breaks((day("2007-01-03"), day("2007-12-31")), 5);
+        let labels = trans.format_breaks(&breaks);
+        assert_eq!(labels, vec!["Apr 2007", "Jul 2007", "Oct 2007"]);
+
+        // A few weeks break on weeks starting on Monday
+        let breaks = trans.breaks((day("2024-03-01"), day("2024-03-31")), 5);
+        assert_eq!(breaks[0], day("2024-03-04"));
+        assert_eq!(trans.format_breaks(&breaks)[0], "Mar 04");
+
+        // Decades break on years
+        let breaks = trans.breaks((day("1990-06-01"), day("2020-06-01")), 5);
+        assert_eq!(trans.format_breaks(&breaks), vec!["2000", "2010", "2020"]);
+    }
+
+    #[test]
+    fn test_explicit_date_breaks() {
+        let trans = DateTransform::new()
+            .breaks_every("1 month".parse().unwrap())
+            .labels("%b");
+        let breaks = trans.breaks((day("2007-01-15"), day("2007-04-15")), 5);
+        assert_eq!(trans.format_breaks(&breaks), vec!["Feb", "Mar", "Apr"]);
+    }
+
+    #[test]
+    fn test_datetime_time_zone() {
+        let utc = DateTimeTransform::new();
+        let paris = DateTimeTransform::new().time_zone(time_zone("Europe/Paris").unwrap());
+
+        let t = utc.parse("2024-07-01T00:00:00Z").unwrap();
+        assert_eq!(paris.parse("2024-07-01T02:00:00"), Some(t));
+        assert_eq!(utc.clone().labels("%H:%M").format(t), "00:00");
+        assert_eq!(paris.clone().labels("%H:%M").format(t), "02:00");
+
+        // Six hours of data break on whole hours in local time
+        let breaks = paris.breaks((t - 1800.0, t + 6.0 * 3600.0), 5);
+        assert_eq!(paris.format_breaks(&breaks)[0], "03:00");
+        assert!(time_zone("Mars/Olympus_Mons").is_err());
+    }
+
+    fn init_test_logging() {
+        let _ = env_logger::builder()
+            .is_test(true)
+            .filter_level(log::LevelFilter::Debug)
+            .try_init();
+    }
+
+    #[test]
+    fn date_scale_1() {
+
+        init_test_logging();
+
+        let data = sp500();
+        let builder = plot(&data).aes(|a| {
+            a.x_continuous("date");
+            a.y_continuous("close");
+        }) + geom_line()
+            + scale_x_date().date_breaks("2 months").date_labels("%b");
+
+        let p = builder
+            .build()
+            .map_err(to_io_error)
+            .expect("Failed to build plot");
+
+        let labels = p.scales.x_continuous.labels();
+        assert_eq!(labels.first().map(String::as_str), Some("Jan"));
+        assert!(labels.iter().all(|l| ["Jan", "Mar", "May", "Jul", "Sep", "Nov"].contains(&l.as_str())));
+
+        p.save("tests/images/date_scale_1.png", 800, 600)
+            .map_err(to_io_error)
+            .expect("Failed to save plot image");
+    }
+
+    #[test]
+    fn date_scale_2() {
+
+        init_test_logging();
+
+        // Limits given as dates drop the rows outside them
+        let data = sp500();
+        let builder = plot(&data).aes(|a| {
+            a.x_continuous("date");
+            a.y_continuous("close");
+        }) + geom_line()
+            + scale_x_date().date_limits("2007-06-01", "2007-08-31");
+
+        let p = builder
+            .build()
+            .map_err(to_io_error)
+            .expect("Failed to build plot");
+
+        let labels = p.scales.x_continuous.labels();
+        assert!(labels.iter().any(|l| l.starts_with("Jul")));
+
+        p.save("tests/images/date_scale_2.png", 800, 600)
+            .map_err(to_io_error)
+            .expect("Failed to save plot image");
+    }
+
+    #[test]
+    fn date_column_scale() {
+        use crate::data::{DataSource, VectorValue};
+        use crate::utils::dataframe::DataFrame;
+
+        // Native date and date-time columns pick their scale without parsing
+        let mut df = DataFrame::new();
+        df.add_column("day", vec![Date::constant(2024, 1, 1), Date::constant(2024, 3, 1)]);
+        df.add_column("at", VectorValue::DateTime(vec![0.0, 7_200.0], Some("Asia/Tokyo".to_string())));
+        df.add_column("y", vec![1.0, 2.0]);
+        let data: Box<dyn DataSource> = Box::new(df);
+
+        let p = plot(&data).aes(|a| {
+            a.x_continuous("day");
+            a.y_continuous("at");
+        }) + geom_line();
+        let p = p.build().map_err(to_io_error).expect("Failed to build plot");
+
+        assert_eq!(p.scales.x_continuous.transform().name(), "date");
+        let (lo, hi) = p.scales.x_continuous.domain().unwrap();
+        assert!(lo <= 19_723.0 && hi >= 19_783.0 && hi - lo < 70.0);
+        // Labels are shown in the column's time zone
+        assert_eq!(p.scales.y_continuous.transform().name(), "datetime");
+        assert!(p.scales.y_continuous.labels().contains(&"09:00".to_string()));
+    }
+
+    #[test]
+    fn test_datetime_scale_builder() {
+        let mut scales = ScaleSet::default();
+        scale_x_datetime()
+            .timezone("America/New_York")
+            .date_breaks("6 hours")
+            .apply_to(&mut scales)
+            .unwrap();
+        assert_eq!(scales.x_continuous.transform().name(), "datetime");
+
+        let mut scales = ScaleSet::default();
+        let result = scale_x_date().timezone("UTC").apply_to(&mut scales);
+        assert!(matches!(result, Err(PlotError::InvalidScaleParameters { .. })));
+        let result = scale_x_continuous().date_breaks("1 month").apply_to(&mut scales);
+        assert!(matches!(result, Err(PlotError::InvalidScaleParameters { .. })));
+        let result = scale_x_date().date_limits("2007-01-01", "soon").apply_to(&mut scales);
+        assert!(matches!(result, Err(PlotError::InvalidScaleParameters { .. })));
+        let result = scale_x_datetime().timezone("Mars/Olympus").apply_to(&mut scales);
+        assert!(matches!(result, Err(PlotError::InvalidScaleParameters { .. })));
+        assert!(time_zone("+05:30").is_ok());
+    }
+
+    #[test]
+    fn test_duration_breaks() {
+        let trans = DurationTransform::new();
+        assert_eq!(trans.parse("PT1H30M"), Some(5400.0));
+        assert_eq!(trans.parse("2h 15m"), Some(8100.0));
+
+        let breaks = trans.breaks((0.0, 4.0 * 3600.0), 5);
+        assert_eq!(trans.format_breaks(&breaks), vec!["0:00", "1:00", "2:00", "3:00", "4:00"]);
+        let breaks = trans.breaks((0.0, 30.0 * SECONDS_PER_DAY), 5);
+        assert_eq!(trans.format_breaks(&breaks), vec!["0d", "7d", "14d", "21d", "28d"]);
+        assert_eq!(trans.format(95.0), "0:01:35");
+
+        let mut scales = ScaleSet::default();
+        let result = crate::scale::scale_x_duration().date_breaks("1 month").apply_to(&mut scales);
+        assert!(matches!(result, Err(PlotError::InvalidScaleParameters { .. })));
+    }
+}
@@ -3,10 +3,12 @@ use core::panic;
 use crate::PlotError;
 use crate::aesthetics::{AesValue, AestheticDomain};
 use crate::data::{
-    DataSource, DiscreteType, GenericVector, PrimitiveType, PrimitiveValue, VectorIter,
+    DataSource, DiscreteType, DiscreteValue, GenericVector, PrimitiveType, PrimitiveValue, VectorIter,
 };
 use crate::error::{DataType, Result};
-use crate::scale::{ContinuousScaleTrainer, DiscreteScaleTrainer};
+use crate::scale::labels::LabelFormatter;
+use crate::scale::transform::Transform;
+use crate::scale::{ContinuousScaleTrainer, DiscreteScaleTrainer, Expansion, OutOfBounds};
 use crate::theme::Color;
 
 use crate::utils::data::{visit_c, visit_d};
@@ -56,41 +58,108 @@ pub trait ScaleBase: Default + Clone + Send + Sync {
 }
 
 pub trait ContinuousDomainScale: ScaleBase {
+    /// The trained domain, in transformed space
     fn domain(&self) -> Option<(f64, f64)>;
 
     fn set_domain(&mut self, domain: (f64, f64));
 
+    /// Crop the view to `domain`, in transformed space. Unlike a domain set
+    /// with `set_domain`, values outside it are not dropped when mapped.
+    fn zoom(&mut self, domain: (f64, f64)) {
+        self.set_domain(domain);
+    }
+
+    /// Explicit limits, in data space
     fn limits(&self) -> (Option<f64>, Option<f64>);
 
+    /// Set explicit limits, in data space. The domain is trained from the
+    /// limits rather than from the data.
+    fn set_limits(&mut self, limits: (f64, f64));
+
+    /// Break positions, in data space
     fn breaks(&self) -> &[f64];
 
+    /// Set explicit break positions, in data space, replacing the computed ones
+    fn set_breaks(&mut self, breaks: Vec<f64>);
+
     fn labels(&self) -> &[String];
 
+    /// Set explicit labels, one per explicit break
+    fn set_labels(&mut self, labels: Vec<String>);
+
+    /// Label breaks with this formatter instead of the transform's
+    /// default. Breaks that are already computed are relabelled.
+    fn set_label_formatter(&mut self, formatter: Box<dyn LabelFormatter>);
+
+    fn transform(&self) -> &dyn Transform;
+
+    /// Set the transformation. Any trained domain is discarded.
+    fn set_transform(&mut self, transform: Box<dyn Transform>);
+
+    fn oob(&self) -> OutOfBounds;
+
+    /// Set what happens to values outside the limits
+    fn set_oob(&mut self, oob: OutOfBounds);
+
+    fn expansion(&self) -> Expansion;
+
+    /// Set the padding applied around the trained range
+    fn set_expansion(&mut self, expansion: Expansion);
+
+    /// Apply the out-of-bounds policy to a value in transformed space.
+    /// Scales without limits accept every finite value.
+    fn apply_oob(&self, value: f64) -> Option<f64> {
+        match self.limits() {
+            (Some(min), Some(max)) => {
+                let limits = (self.transform().transform(min), self.transform().transform(max));
+                self.oob().apply(value, limits)
+            }
+            _ => Some(value),
+        }
+    }
+
     fn train_continuous<'a>(&mut self, iter: VectorIter<'a>) {
-        let mut trainer = ContinuousScaleTrainer::new();
+        let mut trainer = ContinuousScaleTrainer::new(self.transform());
         visit_c(iter, &mut trainer).unwrap();
-        if let Some((obs_min_value, obs_max_value)) = trainer.bounds {
-            let (min_limit, max_limit) = self.limits();
-            let min_value = min_limit.unwrap_or(obs_min_value);
-            let max_value = max_limit.unwrap_or(obs_max_value);
 
-            // Apply 5% expansion on each side (ggplot2 default)
-            let range = max_value - min_value;
-            let expansion = range * 0.05;
-            let min_value = min_value - expansion;
-            let max_value = max_value + expansion;
+        if let Some(bounds) = trainer.bounds {
+            // Limits are stored in data space and replace the observed range
+            let (lo, hi) = match self.limits() {
+                (Some(min), Some(max)) => {
+                    (self.transform().transform(min), self.transform().transform(max))
+                }
+                _ => bounds,
+            };
+
+            if !lo.is_finite() || !hi.is_finite() {
+                log::warn!(
+                    "Transform {} produced non-finite values for domain ({}, {})",
+                    self.transform().name(),
+                    lo,
+                    hi
+                );
+                return;
+            }
+
+            // Decreasing transforms (reverse, reciprocal) map the limits to
+            // a reversed range; order it before expanding and merging
+            let range = (lo.min(hi), lo.max(hi));
+
+            // Expand the range in transformed space (5% on each side by default)
+            let (min_value, max_value) = self.expansion().expand(range);
 
             if let Some((min_existing, max_existing)) = self.domain() {
-                let min_value = min_value.min(min_existing);
-                let max_value = max_value.max(max_existing);
+                let min_value = min_value.min(min_existing.min(max_existing));
+                let max_value = max_value.max(min_existing.max(max_existing));
                 self.set_domain((min_value, max_value));
             } else {
                 self.set_domain((min_value, max_value));
             }
             log::info!(
-                "Trained continuous scale domain to ({}, {}) with 5% expansion",
+                "Trained continuous scale domain to ({}, {}) in {} space",
                 min_value,
-                max_value
+                max_value,
+                self.transform().name()
             );
         }
     }
@@ -99,6 +168,8 @@ pub trait ContinuousDomainScale: ScaleBase {
 pub trait DiscreteDomainScale: ScaleBase {
     fn categories(&self) -> &DiscreteSet;
 
+    fn categories_mut(&mut self) -> &mut DiscreteSet;
+
     fn add_categories(&mut self, categories: DiscreteSet);
 
     fn train_discrete<'a>(&mut self, iter: VectorIter<'a>) {
@@ -107,6 +178,15 @@ pub trait DiscreteDomainScale: ScaleBase {
         self.add_categories(trainer.categories);
     }
 
+    /// Train on discrete values, which may include the missing level
+    fn train_levels(&mut self, values: impl Iterator<Item = DiscreteValue>) {
+        let mut categories = DiscreteSet::new();
+        for value in values {
+            categories.add(&value);
+        }
+        self.add_categories(categories);
+    }
+
     fn len(&self) -> usize {
         self.categories().len()
     }
@@ -167,13 +247,21 @@ pub trait ContinuousRangeScale: ScaleBase {
         mapped_values
     }
 
+    /// Map a vector, reading its missing values as [`DiscreteValue::Na`]
+    fn map_vector(&self, values: &dyn GenericVector) -> Vec<f64> {
+        match values.iter_discrete().filter(|_| values.null_count() > 0) {
+            Some(levels) => levels.map(|level| self.map_value(&level).unwrap_or(f64::NAN)).collect(),
+            None => self.map_vector_iter(values.iter()),
+        }
+    }
+
     fn map_aesthetic_value(&self, value: &AesValue, data: &dyn DataSource) -> Result<AesValue> {
         match value {
             AesValue::Column { name } => {
                 let column = DataSource::get(data, name).ok_or(PlotError::MissingColumn {
                     column: name.to_string(),
                 })?;
-                let values = self.map_vector_iter(column.iter());
+                let values = self.map_vector(column);
                 return Ok(AesValue::vector(values, Some(name.clone())));
             }
             AesValue::Constant { value } => {
@@ -188,7 +276,7 @@ pub trait ContinuousRangeScale: ScaleBase {
                 })
             }
             AesValue::Vector { values, name } => {
-                let mapped_values = self.map_vector_iter(values.iter());
+                let mapped_values = self.map_vector(values.as_ref());
                 Ok(AesValue::vector(mapped_values, name.clone()))
             }
         }
@@ -250,13 +338,21 @@ pub trait ColorRangeScale: ScaleBase {
         colors
     }
 
+    /// Map a vector, reading its missing values as [`DiscreteValue::Na`]
+    fn map_vector(&self, values: &dyn GenericVector) -> Vec<Color> {
+        match values.iter_discrete().filter(|_| values.null_count() > 0) {
+            Some(levels) => levels.filter_map(|level| self.map_value(&level)).collect(),
+            None => self.map_vector_iter(values.iter()),
+        }
+    }
+
     fn map_aesthetic_value(&self, value: &AesValue, data: &dyn DataSource) -> Result<AesValue> {
         match value {
             AesValue::Column { name } => {
                 let column = DataSource::get(data, name).ok_or(PlotError::MissingColumn {
                     column: name.clone(),
                 })?;
-                let colors: Vec<Color> = self.map_vector_iter(column.iter());
+                let colors: Vec<Color> = self.map_vector(column);
                 let color_values: Vec<i64> = colors.iter().map(|c| i64::from(*c)).collect();
                 Ok(AesValue::vector(color_values, Some(name.clone())))
             }
@@ -274,7 +370,7 @@ pub trait ColorRangeScale: ScaleBase {
                 values,
                 name: original_name,
             } => {
-                let colors = self.map_vector_iter(values.iter());
+                let colors = self.map_vector(values.as_ref());
                 let color_values: Vec<i64> = colors.iter().map(|c| i64::from(*c)).collect();
                 Ok(AesValue::vector(color_values, original_name.clone()))
             }
@@ -333,13 +429,21 @@ pub trait ShapeRangeScale: ScaleBase {
         shapes
     }
 
+    /// Map a vector, reading its missing values as [`DiscreteValue::Na`]
+    fn map_vector(&self, values: &dyn GenericVector) -> Vec<Shape> {
+        match values.iter_discrete().filter(|_| values.null_count() > 0) {
+            Some(levels) => levels.filter_map(|level| self.map_value(&level)).collect(),
+            None => self.map_vector_iter(values.iter()),
+        }
+    }
+
     fn map_aesthetic_value(&self, value: &AesValue, data: &dyn DataSource) -> Result<AesValue> {
         match value {
             AesValue::Column { name } => {
                 let column = DataSource::get(data, name).ok_or(PlotError::MissingColumn {
                     column: name.to_string(),
                 })?;
-                let shapes: Vec<Shape> = self.map_vector_iter(column.iter());
+                let shapes: Vec<Shape> = self.map_vector(column);
                 let shape_values: Vec<i64> = shapes.iter().map(|s| i64::from(*s)).collect();
                 Ok(AesValue::vector(shape_values, Some(name.clone())))
             }
@@ -357,7 +461,7 @@ pub trait ShapeRangeScale: ScaleBase {
                 values,
                 name,
             } => {
-                let shapes = self.map_vector_iter(values.iter());
+                let shapes = self.map_vector(values.as_ref());
                 let shape_values: Vec<i64> = shapes.iter().map(|s| i64::from(*s)).collect();
                 Ok(AesValue::vector(shape_values, name.clone()))
             }
@@ -439,13 +543,21 @@ pub trait LineStyleRangeScale: ScaleBase {
         linestyles
     }
 
+    /// Map a vector, reading its missing values as [`DiscreteValue::Na`]
+    fn map_vector(&self, values: &dyn GenericVector) -> Vec<LineStyle> {
+        match values.iter_discrete().filter(|_| values.null_count() > 0) {
+            Some(levels) => levels.filter_map(|level| self.map_value(&level)).collect(),
+            None => self.map_vector_iter(values.iter()),
+        }
+    }
+
     fn map_aesthetic_value(&self, value: &AesValue, data: &dyn DataSource) -> Result<AesValue> {
         match value {
             AesValue::Column { name } => {
                 let column = DataSource::get(data, name).ok_or(PlotError::MissingColumn {
                     column: name.to_string(),
                 })?;
-                let linestyles: Vec<LineStyle> = self.map_vector_iter(column.iter());
+                let linestyles: Vec<LineStyle> = self.map_vector(column);
                 // Convert to String representation for storage
                 let linestyle_strings: Vec<String> = linestyles.iter()
                     .map(|ls| format!("{:?}", ls))
@@ -466,7 +578,7 @@ pub trait LineStyleRangeScale: ScaleBase {
                 values,
                 name,
             } => {
-                let linestyles = self.map_vector_iter(values.iter());
+                let linestyles = self.map_vector(values.as_ref());
                 let linestyle_strings: Vec<String> = linestyles.iter()
                     .map(|ls| format!("{:?}", ls))
                     .collect();
@@ -11,6 +11,10 @@
 //! - `breaks()`: Generates sensible break points in the transformed space
 //! - `format()`: Formats values for display
 //!
+//! Available transformations: identity, sqrt, log10, log with any base
+//! (log2, natural log), log1p, pseudo-log, asinh, logit, probit,
+//! reciprocal, exp and reverse. [`compose`] chains two of them.
+//!
 //! # Example
 //!
 //! ```ignore
@@ -22,6 +26,8 @@
 //! let back = trans.inverse(transformed); // 100.0
 //! ```
 
+use crate::error::PlotError;
+
 /// A transformation that can be applied to scale data.
 ///
 /// Transformations are applied to data values before they are mapped to
@@ -107,6 +113,25 @@ pub trait Transform: Send + Sync {
     /// A vector of break positions in data space
     fn breaks(&self, limits: (f64, f64), n: usize) -> Vec<f64>;
 
+    /// Generate minor break positions between the major breaks.
+    ///
+    /// The default places a minor break midway between each pair of major
+    /// breaks in transformed space, continuing one step past either end to
+    /// fill the limits. Base-10 log scales override this to place minor
+    /// breaks at 2–9 × 10ⁿ.
+    ///
+    /// # Arguments
+    ///
+    /// * `major` - The major breaks, in data space
+    /// * `limits` - The data limits (min, max) in data space
+    ///
+    /// # Returns
+    ///
+    /// Minor break positions in data space, excluding the major breaks
+    fn minor_breaks(&self, major: &[f64], limits: (f64, f64)) -> Vec<f64> {
+        regular_minor_breaks(self, major, limits)
+    }
+
     /// Format a value for display (e.g., axis labels).
     ///
     /// Provides transformation-specific formatting. For example:
@@ -133,6 +158,24 @@ pub trait Transform: Send + Sync {
         }
     }
 
+    /// Format a full set of breaks for display.
+    ///
+    /// Transformations whose labels depend on the spacing of the breaks
+    /// (e.g. dates, labelled by year or by month) override this; the
+    /// default formats each break on its own.
+    fn format_breaks(&self, breaks: &[f64]) -> Vec<String> {
+        breaks.iter().map(|&b| self.format(b)).collect()
+    }
+
+    /// Parse a text value into data space.
+    ///
+    /// Used for data that arrives as strings, such as ISO dates on a date
+    /// scale. Returns `None` when the transformation doesn't accept text
+    /// or the value can't be parsed.
+    fn parse(&self, _value: &str) -> Option<f64> {
+        None
+    }
+
     /// Get a human-readable name for this transformation.
     ///
     /// Used for error messages, debugging, and documentation.
@@ -214,6 +257,64 @@ pub(crate) fn compute_breaks(min: f64, max: f64, n: usize) -> Vec<f64> {
     breaks
 }
 
+/// Minor breaks midway between major breaks, in transformed space.
+///
+/// One extra step is taken past the first and last major break, so the
+/// minor grid reaches the edges of the limits.
+pub(crate) fn regular_minor_breaks<T: Transform + ?Sized>(
+    trans: &T,
+    major: &[f64],
+    limits: (f64, f64),
+) -> Vec<f64> {
+    let mut major: Vec<f64> = major
+        .iter()
+        .map(|&b| trans.transform(b))
+        .filter(|b| b.is_finite())
+        .collect();
+    major.sort_by(f64::total_cmp);
+    let (lo, hi) = (trans.transform(limits.0), trans.transform(limits.1));
+    let (lo, hi) = (lo.min(hi), lo.max(hi));
+    if major.len() < 2 || !lo.is_finite() || !hi.is_finite() {
+        return vec![];
+    }
+
+    let first_step = major[1] - major[0];
+    let last_step = major[major.len() - 1] - major[major.len() - 2];
+    let mut minor = vec![major[0] - first_step / 2.0];
+    minor.extend(major.windows(2).map(|w| (w[0] + w[1]) / 2.0));
+    minor.push(major[major.len() - 1] + last_step / 2.0);
+    minor
+        .into_iter()
+        .filter(|&m| m >= lo && m <= hi)
+        .map(|m| trans.inverse(m))
+        .collect()
+}
+
+/// Minor breaks at 2–9 × 10ⁿ within the limits, for base-10 log scales.
+///
+/// Powers of ten that aren't major breaks (because the majors were
+/// thinned) are minor breaks too. Over more than six decades only the
+/// powers of ten are used, to keep the grid readable.
+fn decade_minor_breaks(major: &[f64], limits: (f64, f64)) -> Vec<f64> {
+    let (min, max) = (limits.0.min(limits.1), limits.0.max(limits.1));
+    if min <= 0.0 || !max.is_finite() {
+        return vec![];
+    }
+    let lo = min.log10().floor() as i32;
+    let hi = max.log10().ceil() as i32;
+    let multiples: &[f64] = if hi - lo > 6 {
+        &[1.0]
+    } else {
+        &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]
+    };
+
+    let is_major = |v: f64| major.iter().any(|&b| (b - v).abs() <= 1e-9 * v);
+    (lo..=hi)
+        .flat_map(|k| multiples.iter().map(move |&m| m * 10f64.powi(k)))
+        .filter(|&v| v >= min && v <= max && !is_major(v))
+        .collect()
+}
+
 // ============================================================================
 // Concrete Transform Implementations
 // ============================================================================
@@ -426,6 +527,10 @@ impl Transform for Log10Transform {
         }
     }
 
+    fn minor_breaks(&self, major: &[f64], limits: (f64, f64)) -> Vec<f64> {
+        decade_minor_breaks(major, limits)
+    }
+
     fn name(&self) -> &str {
         "log10"
     }
@@ -480,6 +585,617 @@ impl Transform for ReverseTransform {
     }
 }
 
+/// Render an integer exponent with Unicode superscript digits
+fn superscript(n: i64) -> String {
+    n.to_string()
+        .chars()
+        .map(|c| match c {
+            '-' => '⁻',
+            '0' => '⁰',
+            '1' => '¹',
+            '2' => '²',
+            '3' => '³',
+            '4' => '⁴',
+            '5' => '⁵',
+            '6' => '⁶',
+            '7' => '⁷',
+            '8' => '⁸',
+            _ => '⁹',
+        })
+        .collect()
+}
+
+/// Keep every k-th break so that at most about `n` remain
+fn thin_breaks(breaks: Vec<f64>, n: usize) -> Vec<f64> {
+    let n = n.max(2);
+    if breaks.len() <= n + 1 {
+        return breaks;
+    }
+    let step = breaks.len().div_ceil(n);
+    breaks.into_iter().step_by(step).collect()
+}
+
+/// Breaks at 0 and at ±base^k, for transforms that are linear near zero and
+/// logarithmic in the tails
+fn signed_log_breaks(limits: (f64, f64), base: f64, n: usize) -> Vec<f64> {
+    let (min, max) = (limits.0.min(limits.1), limits.0.max(limits.1));
+    if !min.is_finite() || !max.is_finite() || min >= max {
+        return vec![];
+    }
+    let largest = min.abs().max(max.abs());
+    if largest < 1.0 {
+        return compute_breaks(min, max, n);
+    }
+    let top = largest.log(base).ceil() as i32;
+
+    let mut powers = vec![];
+    for k in 0..=top {
+        powers.push(base.powi(k));
+    }
+    let mut breaks: Vec<f64> = powers.iter().rev().map(|p| -p).collect();
+    breaks.push(0.0);
+    breaks.extend(powers);
+    breaks.retain(|&b| b >= min && b <= max);
+
+    // Thin each side separately so that zero is always kept
+    let negative: Vec<f64> = breaks.iter().copied().filter(|&b| b < 0.0).rev().collect();
+    let positive: Vec<f64> = breaks.iter().copied().filter(|&b| b > 0.0).collect();
+    let mut result: Vec<f64> = thin_breaks(negative, n).into_iter().rev().collect();
+    if breaks.contains(&0.0) {
+        result.push(0.0);
+    }
+    result.extend(thin_breaks(positive, n));
+    result
+}
+
+/// Logarithmic transformation with an arbitrary base: f(x) = log_b(x)
+///
+/// Use [`LogTransform::log2`] and [`LogTransform::ln`] for the common
+/// bases. Breaks land on integer powers of the base and are labelled as
+/// powers (e.g. 2⁵); other values are printed as plain numbers.
+///
+/// # Example
+///
+/// ```ignore
+/// let trans = LogTransform::log2();
+/// assert_eq!(trans.transform(8.0), 3.0);
+/// assert_eq!(trans.format(32.0), "2⁵");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct LogTransform {
+    base: f64,
+}
+
+impl LogTransform {
+    /// Logarithm in the given base, which must be positive and not 1
+    pub fn new(base: f64) -> Result<Self, PlotError> {
+        if !(base > 0.0 && base != 1.0 && base.is_finite()) {
+            return Err(PlotError::InvalidScaleParameters {
+                details: format!("log base must be positive and not 1, got {}", base),
+            });
+        }
+        Ok(Self { base })
+    }
+
+    /// Base-2 logarithm
+    pub fn log2() -> Self {
+        Self { base: 2.0 }
+    }
+
+    /// Natural logarithm
+    pub fn ln() -> Self {
+        Self { base: std::f64::consts::E }
+    }
+
+    fn base_label(&self) -> String {
+        if self.base == std::f64::consts::E {
+            "e".to_string()
+        } else if self.base.fract() == 0.0 {
+            format!("{:.0}", self.base)
+        } else {
+            format!("{}", self.base)
+        }
+    }
+}
+
+impl Transform for LogTransform {
+    fn transform(&self, x: f64) -> f64 {
+        if x <= 0.0 { f64::NAN } else { x.log(self.base) }
+    }
+
+    fn inverse(&self, x: f64) -> f64 {
+        self.base.powf(x)
+    }
+
+    fn domain(&self) -> (f64, f64) {
+        (1e-300, f64::INFINITY)
+    }
+
+    fn breaks(&self, limits: (f64, f64), n: usize) -> Vec<f64> {
+        let (min, max) = (limits.0.min(limits.1), limits.0.max(limits.1));
+        if min <= 0.0 || !max.is_finite() {
+            return vec![];
+        }
+        let lo = self.transform(min).ceil() as i64;
+        let hi = self.transform(max).floor() as i64;
+        if hi - lo < 1 {
+            // Less than two powers in range, fall back to linear breaks
+            return compute_breaks(min, max, n);
+        }
+        let powers = (lo..=hi).map(|k| self.base.powi(k as i32)).collect();
+        thin_breaks(powers, n)
+    }
+
+    fn format(&self, x: f64) -> String {
+        if x <= 0.0 {
+            return "NA".to_string();
+        }
+        let exponent = self.transform(x);
+        if (exponent - exponent.round()).abs() < 1e-9 {
+            format!("{}{}", self.base_label(), superscript(exponent.round() as i64))
+        } else if (x.round() - x).abs() < 1e-9 && x < 1e6 {
+            format!("{:.0}", x)
+        } else {
+            format!("{:.3}", x)
+        }
+    }
+
+    fn minor_breaks(&self, major: &[f64], limits: (f64, f64)) -> Vec<f64> {
+        if self.base == 10.0 {
+            decade_minor_breaks(major, limits)
+        } else {
+            regular_minor_breaks(self, major, limits)
+        }
+    }
+
+    fn name(&self) -> &str {
+        if self.base == 2.0 {
+            "log2"
+        } else if self.base == std::f64::consts::E {
+            "log"
+        } else {
+            "log-base"
+        }
+    }
+
+    fn box_clone(&self) -> Box<dyn Transform> {
+        Box::new(*self)
+    }
+}
+
+/// Log-plus-one transformation: f(x) = ln(1 + x)
+///
+/// Handles counts with zeros, which a plain log cannot. Breaks land on 0
+/// and powers of 10.
+#[derive(Debug, Clone, Copy)]
+pub struct Log1pTransform;
+
+impl Transform for Log1pTransform {
+    fn transform(&self, x: f64) -> f64 {
+        if x <= -1.0 { f64::NAN } else { x.ln_1p() }
+    }
+
+    fn inverse(&self, x: f64) -> f64 {
+        x.exp_m1()
+    }
+
+    fn domain(&self) -> (f64, f64) {
+        (-1.0 + f64::EPSILON, f64::INFINITY)
+    }
+
+    fn breaks(&self, limits: (f64, f64), n: usize) -> Vec<f64> {
+        signed_log_breaks(limits, 10.0, n)
+    }
+
+    fn name(&self) -> &str {
+        "log1p"
+    }
+
+    fn box_clone(&self) -> Box<dyn Transform> {
+        Box::new(*self)
+    }
+}
+
+/// Pseudo-log transformation: f(x) = asinh(x / 2σ) / ln(b)
+///
+/// Behaves linearly within about `sigma` of zero and like a base-`b` log
+/// further out, for either sign, so it suits signed data spanning several
+/// orders of magnitude.
+#[derive(Debug, Clone, Copy)]
+pub struct PseudoLogTransform {
+    sigma: f64,
+    base: f64,
+}
+
+impl PseudoLogTransform {
+    /// Pseudo-log with linear region `sigma` and base `base`
+    pub fn new(sigma: f64, base: f64) -> Result<Self, PlotError> {
+        if !(sigma > 0.0 && sigma.is_finite()) {
+            return Err(PlotError::InvalidScaleParameters {
+                details: format!("pseudo-log sigma must be positive, got {}", sigma),
+            });
+        }
+        if !(base > 1.0 && base.is_finite()) {
+            return Err(PlotError::InvalidScaleParameters {
+                details: format!("pseudo-log base must be greater than 1, got {}", base),
+            });
+        }
+        Ok(Self { sigma, base })
+    }
+}
+
+impl Default for PseudoLogTransform {
+    /// Linear within 1 of zero, natural log beyond (as in ggplot2)
+    fn default() -> Self {
+        Self { sigma: 1.0, base: std::f64::consts::E }
+    }
+}
+
+impl Transform for PseudoLogTransform {
+    fn transform(&self, x: f64) -> f64 {
+        (x / (2.0 * self.sigma)).asinh() / self.base.ln()
+    }
+
+    fn inverse(&self, x: f64) -> f64 {
+        2.0 * self.sigma * (x * self.base.ln()).sinh()
+    }
+
+    fn domain(&self) -> (f64, f64) {
+        (f64::NEG_INFINITY, f64::INFINITY)
+    }
+
+    /// Breaks at powers of a whole-number base; a natural or fractional
+    /// base, whose powers aren't round numbers, breaks at powers of 10
+    fn breaks(&self, limits: (f64, f64), n: usize) -> Vec<f64> {
+        let base = if self.base.fract() == 0.0 { self.base } else { 10.0 };
+        signed_log_breaks(limits, base, n)
+    }
+
+    fn name(&self) -> &str {
+        "pseudo_log"
+    }
+
+    fn box_clone(&self) -> Box<dyn Transform> {
+        Box::new(*self)
+    }
+}
+
+/// Inverse hyperbolic sine transformation: f(x) = asinh(x)
+///
+/// Like the pseudo-log, linear near zero and logarithmic in both tails.
+#[derive(Debug, Clone, Copy)]
+pub struct AsinhTransform;
+
+impl Transform for AsinhTransform {
+    fn transform(&self, x: f64) -> f64 {
+        x.asinh()
+    }
+
+    fn inverse(&self, x: f64) -> f64 {
+        x.sinh()
+    }
+
+    fn domain(&self) -> (f64, f64) {
+        (f64::NEG_INFINITY, f64::INFINITY)
+    }
+
+    fn breaks(&self, limits: (f64, f64), n: usize) -> Vec<f64> {
+        signed_log_breaks(limits, 10.0, n)
+    }
+
+    fn name(&self) -> &str {
+        "asinh"
+    }
+
+    fn box_clone(&self) -> Box<dyn Transform> {
+        Box::new(*self)
+    }
+}
+
+/// Probabilities used as breaks on logit and probit scales
+const PROBABILITY_BREAKS: [f64; 13] = [
+    0.0001, 0.001, 0.01, 0.05, 0.1, 0.25, 0.5, 0.75, 0.9, 0.95, 0.99, 0.999, 0.9999,
+];
+
+fn probability_breaks(limits: (f64, f64), n: usize) -> Vec<f64> {
+    let (min, max) = (limits.0.min(limits.1), limits.0.max(limits.1));
+    let breaks: Vec<f64> = PROBABILITY_BREAKS
+        .iter()
+        .copied()
+        .filter(|&p| p >= min && p <= max)
+        .collect();
+    if breaks.len() < 2 {
+        return compute_breaks(min, max, n);
+    }
+    // Drop the most extreme tails first, keeping the breaks symmetric around 0.5
+    let mut breaks = breaks;
+    while breaks.len() > n.max(3) + 2 {
+        breaks.remove(0);
+        breaks.pop();
+    }
+    breaks
+}
+
+fn format_probability(x: f64) -> String {
+    if !(0.0..=1.0).contains(&x) {
+        "NA".to_string()
+    } else {
+        // Enough digits to tell 0.999 from 0.9999
+        let formatted = format!("{:.4}", x);
+        formatted.trim_end_matches('0').trim_end_matches('.').to_string()
+    }
+}
+
+/// Logit transformation for proportions: f(p) = ln(p / (1 - p))
+#[derive(Debug, Clone, Copy)]
+pub struct LogitTransform;
+
+impl Transform for LogitTransform {
+    fn transform(&self, x: f64) -> f64 {
+        if x <= 0.0 || x >= 1.0 { f64::NAN } else { (x / (1.0 - x)).ln() }
+    }
+
+    fn inverse(&self, x: f64) -> f64 {
+        1.0 / (1.0 + (-x).exp())
+    }
+
+    fn domain(&self) -> (f64, f64) {
+        (f64::MIN_POSITIVE, 1.0 - f64::EPSILON)
+    }
+
+    fn breaks(&self, limits: (f64, f64), n: usize) -> Vec<f64> {
+        probability_breaks(limits, n)
+    }
+
+    fn format(&self, x: f64) -> String {
+        format_probability(x)
+    }
+
+    fn name(&self) -> &str {
+        "logit"
+    }
+
+    fn box_clone(&self) -> Box<dyn Transform> {
+        Box::new(*self)
+    }
+}
+
+/// Complementary error function (Numerical Recipes `erfcc`, |error| < 1.2e-7)
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let poly = -z * z - 1.265_512_23
+        + t * (1.000_023_68
+            + t * (0.374_091_96
+                + t * (0.096_784_18
+                    + t * (-0.186_288_06
+                        + t * (0.278_868_07
+                            + t * (-1.135_203_98
+                                + t * (1.488_515_87 + t * (-0.822_152_23 + t * 0.170_872_77))))))));
+    let r = t * poly.exp();
+    if x >= 0.0 { r } else { 2.0 - r }
+}
+
+/// Standard normal CDF
+fn normal_cdf(x: f64) -> f64 {
+    0.5 * erfc(-x / std::f64::consts::SQRT_2)
+}
+
+/// Standard normal quantile (Acklam's rational approximation)
+fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1, 2.209_460_984_245_205e2, -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2, -3.066_479_806_614_716e1, 2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1, 1.615_858_368_580_409e2, -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1, -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3, -3.223_964_580_411_365e-1, -2.400_758_277_161_838,
+        -2.549_732_539_343_734, 4.374_664_141_464_968, 2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3, 3.224_671_290_700_398e-1, 2.445_134_137_142_996,
+        3.754_408_661_907_416,
+    ];
+    const P_LOW: f64 = 0.024_25;
+
+    if p <= 0.0 || p >= 1.0 {
+        return f64::NAN;
+    }
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+    if p < P_LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - P_LOW {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    }
+}
+
+/// Probit transformation for proportions: f(p) = Φ⁻¹(p), the standard
+/// normal quantile
+#[derive(Debug, Clone, Copy)]
+pub struct ProbitTransform;
+
+impl Transform for ProbitTransform {
+    fn transform(&self, x: f64) -> f64 {
+        normal_quantile(x)
+    }
+
+    fn inverse(&self, x: f64) -> f64 {
+        normal_cdf(x)
+    }
+
+    fn domain(&self) -> (f64, f64) {
+        (f64::MIN_POSITIVE, 1.0 - f64::EPSILON)
+    }
+
+    fn breaks(&self, limits: (f64, f64), n: usize) -> Vec<f64> {
+        probability_breaks(limits, n)
+    }
+
+    fn format(&self, x: f64) -> String {
+        format_probability(x)
+    }
+
+    fn name(&self) -> &str {
+        "probit"
+    }
+
+    fn box_clone(&self) -> Box<dyn Transform> {
+        Box::new(*self)
+    }
+}
+
+/// Reciprocal transformation: f(x) = 1/x
+///
+/// Reverses the order of positive values; zero has no image.
+#[derive(Debug, Clone, Copy)]
+pub struct ReciprocalTransform;
+
+impl Transform for ReciprocalTransform {
+    fn transform(&self, x: f64) -> f64 {
+        if x == 0.0 { f64::NAN } else { 1.0 / x }
+    }
+
+    fn inverse(&self, x: f64) -> f64 {
+        1.0 / x
+    }
+
+    /// Every value but zero, which `is_in_domain` rejects
+    fn domain(&self) -> (f64, f64) {
+        (f64::NEG_INFINITY, f64::INFINITY)
+    }
+
+    fn is_in_domain(&self, x: f64) -> bool {
+        x != 0.0 && x.is_finite()
+    }
+
+    fn breaks(&self, limits: (f64, f64), n: usize) -> Vec<f64> {
+        compute_breaks(limits.0.min(limits.1), limits.0.max(limits.1), n)
+    }
+
+    fn name(&self) -> &str {
+        "reciprocal"
+    }
+
+    fn box_clone(&self) -> Box<dyn Transform> {
+        Box::new(*self)
+    }
+}
+
+/// Exponential transformation: f(x) = eˣ
+///
+/// The inverse of the natural log, for data that was logged upstream.
+#[derive(Debug, Clone, Copy)]
+pub struct ExpTransform;
+
+impl Transform for ExpTransform {
+    fn transform(&self, x: f64) -> f64 {
+        x.exp()
+    }
+
+    fn inverse(&self, x: f64) -> f64 {
+        if x <= 0.0 { f64::NAN } else { x.ln() }
+    }
+
+    fn domain(&self) -> (f64, f64) {
+        (f64::NEG_INFINITY, f64::INFINITY)
+    }
+
+    fn breaks(&self, limits: (f64, f64), n: usize) -> Vec<f64> {
+        compute_breaks(limits.0.min(limits.1), limits.0.max(limits.1), n)
+    }
+
+    fn name(&self) -> &str {
+        "exp"
+    }
+
+    fn box_clone(&self) -> Box<dyn Transform> {
+        Box::new(*self)
+    }
+}
+
+/// Composition of two transformations: f(x) = second(first(x))
+///
+/// Breaks and labels come from the first transformation, which sees the
+/// data space. For example, composing [`Log10Transform`] with
+/// [`ReverseTransform`] gives a reversed log scale with power-of-ten breaks.
+#[derive(Clone)]
+pub struct ComposeTransform {
+    first: Box<dyn Transform>,
+    second: Box<dyn Transform>,
+    name: String,
+}
+
+impl ComposeTransform {
+    pub fn new(first: Box<dyn Transform>, second: Box<dyn Transform>) -> Self {
+        let name = format!("{}+{}", first.name(), second.name());
+        Self { first, second, name }
+    }
+}
+
+impl std::fmt::Debug for ComposeTransform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ComposeTransform").field(&self.name).finish()
+    }
+}
+
+impl Transform for ComposeTransform {
+    fn transform(&self, x: f64) -> f64 {
+        self.second.transform(self.first.transform(x))
+    }
+
+    fn inverse(&self, x: f64) -> f64 {
+        self.first.inverse(self.second.inverse(x))
+    }
+
+    fn domain(&self) -> (f64, f64) {
+        self.first.domain()
+    }
+
+    fn breaks(&self, limits: (f64, f64), n: usize) -> Vec<f64> {
+        self.first.breaks(limits, n)
+    }
+
+    fn format(&self, x: f64) -> String {
+        self.first.format(x)
+    }
+
+    fn format_breaks(&self, breaks: &[f64]) -> Vec<String> {
+        self.first.format_breaks(breaks)
+    }
+
+    fn parse(&self, value: &str) -> Option<f64> {
+        self.first.parse(value)
+    }
+
+    fn is_in_domain(&self, x: f64) -> bool {
+        self.first.is_in_domain(x) && self.second.is_in_domain(self.first.transform(x))
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn box_clone(&self) -> Box<dyn Transform> {
+        Box::new(self.clone())
+    }
+}
+
+/// Compose two transformations, applying `first` and then `second`
+pub fn compose(first: impl Transform + 'static, second: impl Transform + 'static) -> ComposeTransform {
+    ComposeTransform::new(Box::new(first), Box::new(second))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
@@ -552,6 +1268,26 @@ mod tests {
         assert!(breaks.contains(&1000.0));
     }
 
+    #[test]
+    fn test_minor_breaks() {
+        // Midpoints between majors, one step past each end within the limits
+        let minor = IdentityTransform.minor_breaks(&[0.0, 10.0, 20.0], (-3.0, 22.0));
+        assert_eq!(minor, vec![5.0, 15.0]);
+        let minor = IdentityTransform.minor_breaks(&[0.0, 10.0, 20.0], (-6.0, 26.0));
+        assert_eq!(minor, vec![-5.0, 5.0, 15.0, 25.0]);
+
+        // Log10 minor breaks at 2-9 x 10^n, skipping the majors
+        let minor = Log10Transform.minor_breaks(&[1.0, 10.0, 100.0], (1.0, 100.0));
+        assert_eq!(minor.len(), 16);
+        assert_eq!(minor[0], 2.0);
+        assert!((minor[15] - 90.0).abs() < 1e-9);
+        assert!(!minor.contains(&10.0));
+
+        // Midpoints on a sqrt scale are taken in transformed space
+        let minor = SqrtTransform.minor_breaks(&[0.0, 4.0, 16.0], (0.0, 16.0));
+        assert_eq!(minor, vec![1.0, 9.0]);
+    }
+
     #[test]
     fn test_reverse_transform() {
         let trans = ReverseTransform;
@@ -625,4 +1361,112 @@ mod tests {
             .map_err(to_io_error)
             .expect("Failed to save plot image");
     }
+
+    #[test]
+    fn test_log_base_transforms() {
+        let log2 = LogTransform::log2();
+        assert_eq!(log2.transform(8.0), 3.0);
+        assert!((log2.inverse(log2.transform(5.0)) - 5.0).abs() < 1e-12);
+        assert_eq!(log2.breaks((1.0, 64.0), 10), vec![1.0, 2.0, 4.0, 8.0, 16.0, 32.0, 64.0]);
+        assert_eq!(log2.breaks((1.0, 1024.0), 5), vec![1.0, 8.0, 64.0, 512.0]);
+        assert_eq!(log2.format(32.0), "2⁵");
+        assert_eq!(log2.format(0.25), "2⁻²");
+        assert_eq!(log2.format(3.0), "3");
+
+        let ln = LogTransform::ln();
+        assert!((ln.transform(std::f64::consts::E) - 1.0).abs() < 1e-12);
+        assert_eq!(ln.format(std::f64::consts::E.powi(2)), "e²");
+        assert_eq!(ln.name(), "log");
+        assert!(ln.transform(0.0).is_nan());
+
+        let log3 = LogTransform::new(3.0).unwrap();
+        assert_eq!(log3.format(27.0), "3³");
+        assert!(matches!(LogTransform::new(1.0), Err(PlotError::InvalidScaleParameters { .. })));
+        assert!(matches!(LogTransform::new(-2.0), Err(PlotError::InvalidScaleParameters { .. })));
+    }
+
+    #[test]
+    fn test_signed_log_transforms() {
+        let log1p = Log1pTransform;
+        assert_eq!(log1p.transform(0.0), 0.0);
+        assert!((log1p.inverse(log1p.transform(99.0)) - 99.0).abs() < 1e-9);
+        assert_eq!(log1p.breaks((0.0, 1000.0), 5), vec![0.0, 1.0, 10.0, 100.0, 1000.0]);
+
+        let pseudo = PseudoLogTransform::default();
+        for x in [-1000.0, -1.0, 0.0, 0.5, 250.0] {
+            assert!((pseudo.inverse(pseudo.transform(x)) - x).abs() < 1e-9 * x.abs().max(1.0));
+        }
+        // Linear near zero, logarithmic in the tails
+        assert!((pseudo.transform(0.01) - 0.005).abs() < 1e-6);
+        assert!((pseudo.transform(1e6) - 1e6_f64.ln()).abs() < 1e-6);
+        assert_eq!(
+            pseudo.breaks((-100.0, 100.0), 5),
+            vec![-100.0, -10.0, -1.0, 0.0, 1.0, 10.0, 100.0]
+        );
+        let pseudo2 = PseudoLogTransform::new(1.0, 2.0).unwrap();
+        assert_eq!(pseudo2.breaks((0.0, 16.0), 10), vec![0.0, 1.0, 2.0, 4.0, 8.0, 16.0]);
+        assert!(matches!(PseudoLogTransform::new(0.0, 10.0), Err(PlotError::InvalidScaleParameters { .. })));
+        assert!(matches!(PseudoLogTransform::new(1.0, 0.5), Err(PlotError::InvalidScaleParameters { .. })));
+
+        let asinh = AsinhTransform;
+        assert!((asinh.inverse(asinh.transform(-42.0)) + 42.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_probability_transforms() {
+        let logit = LogitTransform;
+        assert_eq!(logit.transform(0.5), 0.0);
+        assert!((logit.inverse(logit.transform(0.9)) - 0.9).abs() < 1e-12);
+        assert!(logit.transform(1.0).is_nan());
+        assert_eq!(logit.breaks((0.01, 0.99), 5), vec![0.05, 0.1, 0.25, 0.5, 0.75, 0.9, 0.95]);
+        assert_eq!(logit.format(0.001), "0.001");
+
+        let probit = ProbitTransform;
+        assert!(probit.transform(0.5).abs() < 1e-9);
+        assert!((probit.transform(0.975) - 1.959_964).abs() < 1e-5);
+        assert!((probit.inverse(probit.transform(0.1)) - 0.1).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_reciprocal_exp_and_compose() {
+        let reciprocal = ReciprocalTransform;
+        assert_eq!(reciprocal.transform(4.0), 0.25);
+        assert!(reciprocal.transform(0.0).is_nan());
+        assert!(!reciprocal.is_in_domain(0.0));
+        assert!(reciprocal.is_in_domain(-0.5));
+
+        let exp = ExpTransform;
+        assert!((exp.inverse(exp.transform(2.0)) - 2.0).abs() < 1e-12);
+
+        let reversed_log = compose(Log10Transform, ReverseTransform);
+        assert_eq!(reversed_log.transform(100.0), -2.0);
+        assert_eq!(reversed_log.inverse(-2.0), 100.0);
+        assert_eq!(reversed_log.breaks((1.0, 1000.0), 5), vec![1.0, 10.0, 100.0, 1000.0]);
+        assert_eq!(reversed_log.name(), "log10+reverse");
+        assert!(!reversed_log.is_in_domain(-1.0));
+    }
+
+    #[test]
+    fn test_log2_transform_with_plot() {
+        let x: Vec<f64> = (0..11).map(|k| 2_f64.powi(k)).collect();
+        let y: Vec<f64> = vec![-300.0, -40.0, -5.0, -1.0, 0.0, 0.5, 2.0, 8.0, 60.0, 400.0, 2000.0];
+
+        let data: Box<dyn DataSource> =
+            Box::new(DataFrame::from_columns(vec![("x", x), ("y", y)]));
+
+        let builder = plot(&data).aes(|a| {
+            a.x_continuous("x");
+            a.y_continuous("y");
+        }) + geom_point().size(3.0)
+            + scale_x_continuous().transform(Box::new(LogTransform::log2()))
+            + scale_y_continuous().transform(Box::new(PseudoLogTransform::default()));
+
+        let p = builder
+            .build()
+            .map_err(to_io_error)
+            .expect("Failed to build plot");
+        p.save("tests/images/transform_points_log2_pseudo_log.png", 800, 600)
+            .map_err(to_io_error)
+            .expect("Failed to save plot image");
+    }
 }
@@ -1,5 +1,79 @@
 // Utility functions for scale operations
 
+use crate::scale::labels::LabelFormatter;
+use crate::scale::transform::Transform;
+
+/// Breaks and labels set explicitly on a continuous scale
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ExplicitBreaks {
+    pub breaks: Option<Vec<f64>>,
+    pub labels: Option<Vec<String>>,
+    pub formatter: Option<Box<dyn LabelFormatter>>,
+    pub minor: Option<Vec<f64>>,
+}
+
+impl ExplicitBreaks {
+    /// Resolve the breaks and labels for a scale with the given domain (in
+    /// transformed space).
+    ///
+    /// Explicit breaks falling outside the domain are dropped together with
+    /// their labels. Without explicit breaks, `auto` supplies them; without
+    /// explicit labels, the label formatter does, or else `format`.
+    pub fn resolve(
+        &self,
+        transform: &dyn Transform,
+        domain: (f64, f64),
+        auto: impl FnOnce() -> Vec<f64>,
+        format: impl FnOnce(&[f64]) -> Vec<String>,
+    ) -> (Vec<f64>, Vec<String>) {
+        let format = |breaks: &[f64]| match &self.formatter {
+            Some(formatter) => formatter.format(breaks),
+            None => format(breaks),
+        };
+        let Some(breaks) = &self.breaks else {
+            let breaks = auto();
+            let labels = format(&breaks);
+            return (breaks, labels);
+        };
+
+        let (lo, hi) = (domain.0.min(domain.1), domain.0.max(domain.1));
+        let keep: Vec<usize> = (0..breaks.len())
+            .filter(|&i| {
+                let t = transform.transform(breaks[i]);
+                t.is_finite() && t >= lo && t <= hi
+            })
+            .collect();
+        let kept: Vec<f64> = keep.iter().map(|&i| breaks[i]).collect();
+        let labels = match &self.labels {
+            Some(labels) if labels.len() == breaks.len() => {
+                keep.iter().map(|&i| labels[i].clone()).collect()
+            }
+            _ => format(&kept),
+        };
+        (kept, labels)
+    }
+
+    /// Resolve the minor breaks for a scale with the given domain (in
+    /// transformed space) and major breaks (in data space).
+    ///
+    /// Explicit minor breaks outside the domain are dropped; without them
+    /// the transform places minor breaks between the majors.
+    pub fn resolve_minor(&self, transform: &dyn Transform, domain: (f64, f64), major: &[f64]) -> Vec<f64> {
+        let (lo, hi) = (domain.0.min(domain.1), domain.0.max(domain.1));
+        match &self.minor {
+            Some(minor) => minor
+                .iter()
+                .copied()
+                .filter(|&b| {
+                    let t = transform.transform(b);
+                    t.is_finite() && t >= lo && t <= hi
+                })
+                .collect(),
+            None => transform.minor_breaks(major, (transform.inverse(lo), transform.inverse(hi))),
+        }
+    }
+}
+
 /// Generate axis breaks using Wilkinson's Extended algorithm.
 /// Returns a vector of break positions given a data range and desired number of breaks.
 pub fn extended_breaks(domain: (f64, f64), n: usize) -> Vec<f64> {
@@ -16,14 +16,14 @@ pub enum BinStrategy {
     Width(f64),
 }
 
-struct Binner {
+pub(super) struct Binner {
     binwidth: f64,
     min: f64,
     n_bins: usize,
 }
 
 impl Binner {
-    fn new(min: f64, max: f64, strategy: &BinStrategy) -> Self {
+    pub(super) fn new(min: f64, max: f64, strategy: &BinStrategy) -> Self {
         let (binwidth, n_bins) = match strategy {
             BinStrategy::Width(width) => {
                 let range = max - min;
@@ -43,23 +43,23 @@ impl Binner {
         }
     }
 
-    fn len(&self) -> usize {
+    pub(super) fn len(&self) -> usize {
         self.n_bins
     }
 
-    fn bin_of_value(&self, value: f64) -> usize {
+    pub(super) fn bin_of_value(&self, value: f64) -> usize {
         let idx = ((value - self.min) / self.binwidth).floor() as usize;
         let idx = idx.min((self.n_bins as usize) - 1);
         idx
     }
 
-    fn center_of_bin(&self, idx: usize) -> f64 {
+    pub(super) fn center_of_bin(&self, idx: usize) -> f64 {
         let bin_start = self.min + idx as f64 * self.binwidth;
         let bin_end = bin_start + self.binwidth;
         (bin_start + bin_end) / 2.0
     }
 
-    fn bin_bounds(&self, idx: usize) -> (f64, f64) {
+    pub(super) fn bin_bounds(&self, idx: usize) -> (f64, f64) {
         let bin_start = self.min + idx as f64 * self.binwidth;
         let bin_end = bin_start + self.binwidth;
         (bin_start, bin_end)
@@ -330,7 +330,7 @@ impl Stat for Bin {
     }
 }
 
-fn get_data_range<'a>(iter: VectorIter<'a>) -> Option<(f64, f64)> {
+pub(super) fn get_data_range<'a>(iter: VectorIter<'a>) -> Option<(f64, f64)> {
     match iter {
         VectorIter::Int(int_iter) => get_data_range_inner(int_iter),
         VectorIter::Float(float_iter) => get_data_range_inner(float_iter),
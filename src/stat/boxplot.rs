@@ -9,7 +9,7 @@ use std::collections::HashMap;
 use ordered_float::OrderedFloat;
 
 use crate::aesthetics::{AesMap, AesValue, Aesthetic, AestheticDomain, AestheticProperty};
-use crate::data::{ContinuousType, DiscreteType, PrimitiveType};
+use crate::data::{ContinuousType, DataSource, DiscreteType, DiscreteValue, PrimitiveType, VectorIter};
 use crate::error::{PlotError, Result};
 use crate::stat::Stat;
 use crate::utils::data::{
@@ -26,12 +26,17 @@ use crate::utils::dataframe::DataFrame;
 /// - `upper`: Third quartile (Q3, 75th percentile)
 /// - `ymax`: Upper whisker (maximum non-outlier value)
 ///
-/// Whisker endpoints are determined by the `coef` parameter (default 1.5).
-/// The whiskers extend to the most extreme data point within `coef × IQR`
-/// from the quartiles, where IQR (interquartile range) = Q3 - Q1.
+/// It also computes, for notched and variable-width boxplots:
+/// - `notchlower`, `notchupper`: Median ± 1.58 × IQR / √n
+/// - `relvarwidth`: √n relative to the largest box in the layer
 ///
-/// Outlier detection and rendering is handled by the geom, not the stat.
-/// This ensures each row in the computed data represents one box.
+/// Whisker endpoints are determined by the [`Whiskers`] rule. By default
+/// the whiskers extend to the most extreme data point within `coef × IQR`
+/// (`coef` defaults to 1.5) from the quartiles, where IQR (interquartile
+/// range) = Q3 - Q1.
+///
+/// Values beyond the whiskers are emitted as outlier rows (with only `y`
+/// set) unless `outliers` is false. Outlier rendering is handled by the geom.
 ///
 /// # Example
 ///
@@ -50,12 +55,33 @@ pub struct Boxplot {
     /// Coefficient for outlier detection (default: 1.5)
     /// Outliers are values beyond coef * IQR from quartiles
     pub coef: f64,
+
+    /// Rule for the whisker extent (default: `coef` × IQR)
+    pub whiskers: Whiskers,
+
+    /// Whether to emit rows for values beyond the whiskers (default: true)
+    pub outliers: bool,
+}
+
+/// Rule determining how far boxplot whiskers extend
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Whiskers {
+    /// Most extreme values within [`Boxplot::coef`] × IQR of the quartiles
+    Iqr,
+    /// Minimum and maximum of the data (no outliers)
+    MinMax,
+    /// Percentiles of the data, e.g. `lower: 0.05, upper: 0.95`
+    Percentile { lower: f64, upper: f64 },
 }
 
 impl Boxplot {
     /// Create a new Boxplot stat with default parameters
     pub fn new() -> Self {
-        Self { coef: 1.5 }
+        Self {
+            coef: 1.5,
+            whiskers: Whiskers::Iqr,
+            outliers: true,
+        }
     }
 
     /// Set the outlier coefficient
@@ -66,6 +92,19 @@ impl Boxplot {
     /// - 3.0: Far outliers only
     pub fn with_coef(mut self, coef: f64) -> Self {
         self.coef = coef;
+        self.whiskers = Whiskers::Iqr;
+        self
+    }
+
+    /// Set the whisker rule
+    pub fn with_whiskers(mut self, whiskers: Whiskers) -> Self {
+        self.whiskers = whiskers;
+        self
+    }
+
+    /// Set whether outlier rows are emitted
+    pub fn with_outliers(mut self, outliers: bool) -> Self {
+        self.outliers = outliers;
         self
     }
 }
@@ -81,19 +120,59 @@ impl Stat for Boxplot {
         super::StatAestheticRequirements::from((AestheticProperty::X, AestheticProperty::Y))
     }
 
+    /// Find the largest box (over x and the grouping aesthetics) so that
+    /// `relvarwidth` is comparable across groups.
+    fn compute_params(
+        &self,
+        data: &dyn DataSource,
+        mapping: &AesMap,
+        aesthetics: &[Aesthetic],
+    ) -> Result<Option<Box<dyn std::any::Any>>> {
+        let n = data.len();
+        let y_values: Vec<f64> = match aesthetics.get(1).and_then(|aes| mapping.get_vector_iter(aes, data)) {
+            Some(VectorIter::Float(iter)) => iter.collect(),
+            Some(VectorIter::Int(iter)) => iter.map(|v| v as f64).collect(),
+            _ => return Ok(None),
+        };
+
+        let key_columns: Vec<Vec<DiscreteValue>> = aesthetics
+            .iter()
+            .take(1)
+            .chain(mapping.aesthetics().filter(|aes| aes.is_grouping()))
+            .filter_map(|aes| {
+                mapping
+                    .get_iter_discrete(aes, data)
+                    .or_else(|| Some(mapping.get_vector_iter(aes, data)?.to_discrete_iter()))
+            })
+            .map(|iter| iter.collect::<Vec<_>>())
+            .filter(|column| column.len() == n)
+            .collect();
+
+        let mut counts: HashMap<Vec<DiscreteValue>, usize> = HashMap::new();
+        for (i, y) in y_values.iter().enumerate() {
+            if y.is_finite() {
+                let key = key_columns.iter().map(|column| column[i].clone()).collect();
+                *counts.entry(key).or_default() += 1;
+            }
+        }
+
+        Ok(counts.into_values().max().map(|max_n| Box::new(max_n) as Box<dyn std::any::Any>))
+    }
+
     fn compute_group(
             &self,
             aesthetics: Vec<Aesthetic>,
             iters: Vec<crate::data::VectorIter<'_>>,
-            _params: Option<&dyn std::any::Any>,
+            params: Option<&dyn std::any::Any>,
         ) -> Result<(DataFrame, AesMap)> {
             let mut both = aesthetics.into_iter().zip(iters.into_iter());
         if let Some((_x_aesthetic, x_iter)) = both.next() {
             if let Some((_y_aesthetic, y_iter)) = both.next() {
+                let max_n = params.and_then(|p| p.downcast_ref::<usize>()).copied();
                 return visit2_dc(
                     x_iter,
                     y_iter,
-                    &mut BoxplotCounter::new(self.coef),
+                    &mut BoxplotCounter::new(self.coef, self.whiskers, self.outliers, max_n),
                 );
             }
             panic!("Boxplot stat requires both x (discrete) and y (continuous) aesthetics");
@@ -133,11 +212,19 @@ fn percentile(sorted_data: &[OrderedFloat<f64>], p: f64) -> f64 {
 
 struct BoxplotCounter {
     coef: f64,
+    whiskers: Whiskers,
+    outliers: bool,
+    max_n: Option<usize>,
 }
 
 impl BoxplotCounter {
-    fn new(coef: f64) -> Self {
-        Self { coef }
+    fn new(coef: f64, whiskers: Whiskers, outliers: bool, max_n: Option<usize>) -> Self {
+        Self {
+            coef,
+            whiskers,
+            outliers,
+            max_n,
+        }
     }
 }
 
@@ -173,6 +260,15 @@ impl DiscreteContinuousVisitor2 for BoxplotCounter {
         let mut result_middle = Vec::new();
         let mut result_upper = Vec::new();
         let mut result_ymax = Vec::new();
+        let mut result_notchlower = Vec::new();
+        let mut result_notchupper = Vec::new();
+        let mut result_relvarwidth = Vec::new();
+
+        // Without layer-wide counts, widths are relative to the largest box here
+        let max_n = self
+            .max_n
+            .unwrap_or_else(|| pairs.iter().map(|(_, values)| values.len()).max().unwrap_or(1))
+            .max(1);
 
         for (x, mut values) in pairs.into_iter() {
             if values.is_empty() {
@@ -182,7 +278,9 @@ impl DiscreteContinuousVisitor2 for BoxplotCounter {
             values.sort();
 
             let (ymin, q1, median, q3, ymax, lower_fence, upper_fence) =
-                compute_five_number_summary(self.coef, &values);
+                compute_five_number_summary(self.coef, self.whiskers, &values);
+            let n = values.len() as f64;
+            let notch = 1.58 * (q3 - q1) / n.sqrt();
 
             // Add box statistics
             result_x.push(T::from_sortable(x.clone()));
@@ -192,6 +290,13 @@ impl DiscreteContinuousVisitor2 for BoxplotCounter {
             result_middle.push(median);
             result_upper.push(q3);
             result_ymax.push(ymax);
+            result_notchlower.push(median - notch);
+            result_notchupper.push(median + notch);
+            result_relvarwidth.push((n / max_n as f64).sqrt());
+
+            if !self.outliers {
+                continue;
+            }
 
             // Add outliers
             for &value in &values {
@@ -203,6 +308,9 @@ impl DiscreteContinuousVisitor2 for BoxplotCounter {
                     result_middle.push(f64::NAN);
                     result_upper.push(f64::NAN);
                     result_ymax.push(f64::NAN);
+                    result_notchlower.push(f64::NAN);
+                    result_notchupper.push(f64::NAN);
+                    result_relvarwidth.push(f64::NAN);
                 }
             }
         }
@@ -217,6 +325,9 @@ impl DiscreteContinuousVisitor2 for BoxplotCounter {
         data.add_column("middle", result_middle);
         data.add_column("upper", result_upper);
         data.add_column("ymax", result_ymax);
+        data.add_column("notchlower", result_notchlower);
+        data.add_column("notchupper", result_notchupper);
+        data.add_column("relvarwidth", result_relvarwidth);
 
         // Update mapping
         mapping.set(
@@ -246,14 +357,21 @@ impl DiscreteContinuousVisitor2 for BoxplotCounter {
         mapping.set(Aesthetic::Lower, AesValue::column("lower"));
         mapping.set(Aesthetic::Middle, AesValue::column("middle"));
         mapping.set(Aesthetic::Upper, AesValue::column("upper"));
+        mapping.set(Aesthetic::NotchLower, AesValue::column("notchlower"));
+        mapping.set(Aesthetic::NotchUpper, AesValue::column("notchupper"));
+        mapping.set(Aesthetic::RelVarWidth, AesValue::column("relvarwidth"));
 
         Ok((data, mapping))
     }
 }
 
 /// Compute five-number summary from sorted data
+///
+/// Returns (ymin, q1, median, q3, ymax, lower_fence, upper_fence); values
+/// outside the fences are outliers.
 fn compute_five_number_summary(
     coef: f64,
+    whiskers: Whiskers,
     sorted_data: &[OrderedFloat<f64>],
 ) -> (f64, f64, f64, f64, f64, f64, f64) {
     let n = sorted_data.len();
@@ -273,9 +391,17 @@ fn compute_five_number_summary(
     let q3 = percentile(sorted_data, 0.75);
 
     // Compute whiskers and outliers
-    let iqr = q3 - q1;
-    let lower_fence = q1 - coef * iqr;
-    let upper_fence = q3 + coef * iqr;
+    let (lower_fence, upper_fence) = match whiskers {
+        Whiskers::Iqr => {
+            let iqr = q3 - q1;
+            (q1 - coef * iqr, q3 + coef * iqr)
+        }
+        Whiskers::MinMax => (sorted_data[0].0, sorted_data[n - 1].0),
+        Whiskers::Percentile { lower, upper } => (
+            percentile(sorted_data, lower.clamp(0.0, 1.0)),
+            percentile(sorted_data, upper.clamp(0.0, 1.0)),
+        ),
+    };
 
     // Find whisker endpoints (min/max values within fences)
     let ymin = sorted_data
@@ -293,6 +419,12 @@ fn compute_five_number_summary(
         .unwrap_or(sorted_data[n - 1])
         .0;
 
+    // Percentile whiskers end exactly at the percentiles
+    let (ymin, ymax) = match whiskers {
+        Whiskers::Percentile { .. } => (lower_fence, upper_fence),
+        _ => (ymin, ymax),
+    };
+
     (ymin, q1, median, q3, ymax, lower_fence, upper_fence)
 }
 
@@ -318,7 +450,7 @@ mod tests {
         let data = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0];
         let data: Vec<OrderedFloat<f64>> = data.into_iter().map(OrderedFloat).collect();
         let (ymin, q1, median, q3, ymax, _lower_fence, _upper_fence) =
-            compute_five_number_summary(1.5, &data);
+            compute_five_number_summary(1.5, Whiskers::Iqr, &data);
 
         assert_eq!(median, 5.5);
         assert_eq!(q1, 3.25);
@@ -334,7 +466,7 @@ mod tests {
         let data = vec![1.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 100.0];
         let data: Vec<OrderedFloat<f64>> = data.into_iter().map(OrderedFloat).collect();
         let (ymin, q1, median, q3, ymax, _lower_fence, _upper_fence) =
-            compute_five_number_summary(1.5, &data);
+            compute_five_number_summary(1.5, Whiskers::Iqr, &data);
 
         // Q1 = 10, Q3 = 10, IQR = 0, fences at 10 ± 0 = 10
         // So 1.0 and 100.0 should be outliers, whiskers at 10
@@ -513,4 +645,70 @@ mod tests {
         assert!(x_vals.contains(&"A".to_string()));
         assert!(x_vals.contains(&"B".to_string()));
     }
+
+    #[test]
+    fn test_boxplot_whisker_rules_and_notches() {
+        use crate::aesthetics::{AesMap, AesValue, Aesthetic};
+        use crate::utils::dataframe::DataFrame;
+
+        // x=1 has 5 values including outliers, x=2 has 20 values
+        let mut x = vec![1; 5];
+        x.extend(vec![2; 20]);
+        let mut y = vec![1.0, 10.0, 11.0, 12.0, 100.0];
+        y.extend((0..20).map(|v| v as f64));
+        let mut df = DataFrame::new();
+        df.add_column("x", x);
+        df.add_column("y", y);
+        let df: Box<dyn DataSource> = Box::new(df);
+
+        let mut mapping = AesMap::new();
+        mapping.set(
+            Aesthetic::X(AestheticDomain::Discrete),
+            AesValue::column("x"),
+        );
+        mapping.set(
+            Aesthetic::Y(AestheticDomain::Continuous),
+            AesValue::column("y"),
+        );
+
+        let floats = |df: &DataFrame, name: &str| -> Vec<f64> {
+            df.get(name).unwrap().iter_float().unwrap().collect()
+        };
+
+        // Min/max whiskers produce no outlier rows
+        let stat = Boxplot::new().with_whiskers(Whiskers::MinMax);
+        let (computed, _) = stat.compute(df.as_ref(), &mapping).unwrap();
+        assert_eq!(floats(&computed, "ymin"), vec![1.0, 0.0]);
+        assert_eq!(floats(&computed, "ymax"), vec![100.0, 19.0]);
+
+        // Notch is median ± 1.58 IQR / sqrt(n); widths relative to the largest box
+        let notch = 1.58 * 2.0 / 5.0_f64.sqrt();
+        assert!((floats(&computed, "notchlower")[0] - (11.0 - notch)).abs() < 1e-10);
+        assert!((floats(&computed, "notchupper")[0] - (11.0 + notch)).abs() < 1e-10);
+        assert_eq!(floats(&computed, "relvarwidth"), vec![0.5, 1.0]);
+
+        // Outliers can be dropped entirely
+        let stat = Boxplot::new().with_outliers(false);
+        let (computed, _) = stat.compute(df.as_ref(), &mapping).unwrap();
+        assert_eq!(floats(&computed, "middle").len(), 2);
+
+        // The coef field sets the IQR rule directly
+        let mut stat = Boxplot::new();
+        stat.coef = 100.0;
+        let (computed, _) = stat.compute(df.as_ref(), &mapping).unwrap();
+        assert_eq!(floats(&computed, "ymax"), vec![100.0, 19.0]);
+        assert_eq!(floats(&computed, "middle").len(), 2);
+
+        // Percentile whiskers end at the percentiles
+        let stat = Boxplot::new().with_whiskers(Whiskers::Percentile {
+            lower: 0.05,
+            upper: 0.95,
+        });
+        let (computed, _) = stat.compute(df.as_ref(), &mapping).unwrap();
+        let ymax: Vec<f64> = floats(&computed, "ymax")
+            .into_iter()
+            .filter(|v| !v.is_nan())
+            .collect();
+        assert!((ymax[1] - 18.05).abs() < 1e-10);
+    }
 }
@@ -0,0 +1,349 @@
+//! Ellipse statistics
+//!
+//! Computes confidence ellipses (or circles of a fixed radius) around
+//! bivariate data, returned as polygon vertices.
+
+use crate::aesthetics::{AesMap, AesValue, Aesthetic, AestheticDomain, AestheticProperty};
+use crate::data::{ContinuousType, VectorIter};
+use crate::error::{PlotError, Result};
+use crate::stat::Stat;
+use crate::utils::data::{ContinuousContinuousVisitor2, Vectorable, visit2_cc};
+use crate::utils::dataframe::DataFrame;
+
+/// Type of ellipse to compute
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EllipseType {
+    /// Confidence ellipse assuming a multivariate t distribution,
+    /// with a robust covariance estimate (default)
+    T,
+    /// Confidence ellipse assuming a multivariate normal distribution
+    Norm,
+    /// Circle whose radius is `level`, in data units
+    Euclid,
+}
+
+/// Ellipse statistics computation
+///
+/// For each group, computes the ellipse enclosing the given `level` of a
+/// fitted bivariate distribution, or a circle of radius `level` for
+/// [`EllipseType::Euclid`].
+///
+/// # Output columns
+/// - `x`, `y`: polygon vertices, in order, with the first vertex repeated at the end
+///
+/// # Example
+///
+/// ```rust,ignore
+/// plot(&data).aes(|a| {
+///     a.x_continuous("wt");
+///     a.y_continuous("mpg");
+///     a.color_discrete("cyl");
+/// }) + geom_point() + geom_polygon().stat(Ellipse::default())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Ellipse {
+    /// Type of ellipse
+    pub kind: EllipseType,
+
+    /// Confidence level, or the radius for `EllipseType::Euclid` (default 0.95)
+    pub level: f64,
+
+    /// Number of segments in the polygon (default 51)
+    pub segments: usize,
+}
+
+impl Ellipse {
+    /// Create a new Ellipse stat with default parameters
+    pub fn new() -> Self {
+        Self {
+            kind: EllipseType::T,
+            level: 0.95,
+            segments: 51,
+        }
+    }
+
+    /// Set the type of ellipse
+    pub fn kind(mut self, kind: EllipseType) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Set the confidence level (or the radius for `EllipseType::Euclid`)
+    pub fn level(mut self, level: f64) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Set the number of polygon segments
+    pub fn segments(mut self, segments: usize) -> Self {
+        self.segments = segments.max(3);
+        self
+    }
+
+    /// Compute the ellipse vertices for one group
+    fn vertices(&self, x: &[f64], y: &[f64]) -> (Vec<f64>, Vec<f64>) {
+        let n = x.len();
+        if n < 3 {
+            log::warn!("Too few points to calculate an ellipse");
+            return (Vec::new(), Vec::new());
+        }
+
+        let (center, cov) = match self.kind {
+            EllipseType::T => robust_t_covariance(x, y, 5.0),
+            EllipseType::Norm | EllipseType::Euclid => covariance(x, y),
+        };
+
+        let (shape, radius) = match self.kind {
+            EllipseType::Euclid => {
+                let s = cov[0].min(cov[2]);
+                if s.is_nan() || s <= 0.0 {
+                    log::warn!("Zero variance in x or y, cannot calculate a euclid ellipse");
+                    return (Vec::new(), Vec::new());
+                }
+                let shape = [s, 0.0, s];
+                (shape, self.level / s.sqrt())
+            }
+            EllipseType::T | EllipseType::Norm => {
+                let level = self.level.clamp(0.0, 1.0 - 1e-12);
+                (cov, (2.0 * f_quantile_2(level, (n - 1) as f64)).sqrt())
+            }
+        };
+
+        // Upper-triangular Cholesky factor of the 2x2 shape matrix
+        let r11 = shape[0].max(0.0).sqrt();
+        let r12 = if r11 > 0.0 { shape[1] / r11 } else { 0.0 };
+        let r22 = (shape[2] - r12 * r12).max(0.0).sqrt();
+
+        let mut xs = Vec::with_capacity(self.segments + 1);
+        let mut ys = Vec::with_capacity(self.segments + 1);
+        for i in 0..=self.segments {
+            let angle = 2.0 * std::f64::consts::PI * i as f64 / self.segments as f64;
+            let (sin, cos) = angle.sin_cos();
+            xs.push(center.0 + radius * cos * r11);
+            ys.push(center.1 + radius * (cos * r12 + sin * r22));
+        }
+        (xs, ys)
+    }
+}
+
+impl Default for Ellipse {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Mean and sample covariance `[sxx, sxy, syy]`
+fn covariance(x: &[f64], y: &[f64]) -> ((f64, f64), [f64; 3]) {
+    let n = x.len() as f64;
+    let mx = x.iter().sum::<f64>() / n;
+    let my = y.iter().sum::<f64>() / n;
+    let mut cov = [0.0; 3];
+    for (xi, yi) in x.iter().zip(y) {
+        let (dx, dy) = (xi - mx, yi - my);
+        cov[0] += dx * dx;
+        cov[1] += dx * dy;
+        cov[2] += dy * dy;
+    }
+    for c in cov.iter_mut() {
+        *c /= n - 1.0;
+    }
+    ((mx, my), cov)
+}
+
+/// Robust location and covariance assuming a multivariate t distribution
+/// with `nu` degrees of freedom, by iterative reweighting (as in `MASS::cov.trob`)
+fn robust_t_covariance(x: &[f64], y: &[f64], nu: f64) -> ((f64, f64), [f64; 3]) {
+    let n = x.len();
+    let p = 2.0;
+    let mut weights = vec![1.0; n];
+    let mut center = (
+        x.iter().sum::<f64>() / n as f64,
+        y.iter().sum::<f64>() / n as f64,
+    );
+    let mut cov = [0.0; 3];
+
+    for _ in 0..25 {
+        let sum_w: f64 = weights.iter().sum();
+        let dx: Vec<f64> = x.iter().map(|v| v - center.0).collect();
+        let dy: Vec<f64> = y.iter().map(|v| v - center.1).collect();
+
+        // Weighted scatter matrix, and the final covariance estimate
+        let mut scatter = [0.0; 3];
+        cov = [0.0; 3];
+        for i in 0..n {
+            let terms = [dx[i] * dx[i], dx[i] * dy[i], dy[i] * dy[i]];
+            for k in 0..3 {
+                scatter[k] += weights[i] * terms[k] / sum_w;
+                cov[k] += weights[i] * terms[k] / n as f64;
+            }
+        }
+
+        let det = scatter[0] * scatter[2] - scatter[1] * scatter[1];
+        if det <= 0.0 {
+            break;
+        }
+
+        // Reweight by Mahalanobis distance
+        let previous = weights.clone();
+        for i in 0..n {
+            let q = (scatter[2] * dx[i] * dx[i] - 2.0 * scatter[1] * dx[i] * dy[i]
+                + scatter[0] * dy[i] * dy[i])
+                / det;
+            weights[i] = (nu + p) / (nu + q);
+        }
+        let sum_w: f64 = weights.iter().sum();
+        center = (
+            weights.iter().zip(x).map(|(w, v)| w * v).sum::<f64>() / sum_w,
+            weights.iter().zip(y).map(|(w, v)| w * v).sum::<f64>() / sum_w,
+        );
+
+        if weights
+            .iter()
+            .zip(previous.iter())
+            .all(|(w, w0)| (w - w0).abs() < 0.01)
+        {
+            break;
+        }
+    }
+
+    (center, cov)
+}
+
+/// Quantile of the F distribution with 2 and `df` degrees of freedom,
+/// which has the closed form CDF `1 - (1 + 2x/df)^(-df/2)`
+fn f_quantile_2(p: f64, df: f64) -> f64 {
+    df / 2.0 * ((1.0 - p).powf(-2.0 / df) - 1.0)
+}
+
+impl Stat for Ellipse {
+    fn aesthetic_requirements(&self) -> super::StatAestheticRequirements {
+        super::StatAestheticRequirements::from((AestheticProperty::X, AestheticProperty::Y))
+    }
+
+    fn compute_group(
+        &self,
+        _aesthetics: Vec<Aesthetic>,
+        iters: Vec<VectorIter<'_>>,
+        _params: Option<&dyn std::any::Any>,
+    ) -> Result<(DataFrame, AesMap)> {
+        let mut iters = iters.into_iter();
+        match (iters.next(), iters.next()) {
+            (Some(x_values), Some(y_values)) => {
+                visit2_cc(x_values, y_values, &mut EllipseVisitor { ellipse: self })
+            }
+            (Some(_), None) => Err(PlotError::MissingAesthetic {
+                aesthetic: Aesthetic::Y(AestheticDomain::Continuous),
+            }),
+            _ => Err(PlotError::MissingAesthetic {
+                aesthetic: Aesthetic::X(AestheticDomain::Continuous),
+            }),
+        }
+    }
+}
+
+struct EllipseVisitor<'a> {
+    ellipse: &'a Ellipse,
+}
+
+impl ContinuousContinuousVisitor2 for EllipseVisitor<'_> {
+    type Output = (DataFrame, AesMap);
+
+    fn visit<T: Vectorable + ContinuousType, U: Vectorable + ContinuousType>(
+        &mut self,
+        x_values: impl Iterator<Item = T>,
+        y_values: impl Iterator<Item = U>,
+    ) -> std::result::Result<Self::Output, PlotError> {
+        let (x, y): (Vec<f64>, Vec<f64>) = x_values
+            .map(|v| v.to_f64())
+            .zip(y_values.map(|v| v.to_f64()))
+            .filter(|(x, y)| x.is_finite() && y.is_finite())
+            .unzip();
+
+        let (xs, ys) = self.ellipse.vertices(&x, &y);
+
+        let mut data = DataFrame::new();
+        data.add_column("x", xs);
+        data.add_column("y", ys);
+
+        let mut mapping = AesMap::new();
+        mapping.set(
+            Aesthetic::X(AestheticDomain::Continuous),
+            AesValue::column("x"),
+        );
+        mapping.set(
+            Aesthetic::Y(AestheticDomain::Continuous),
+            AesValue::column("y"),
+        );
+
+        Ok((data, mapping))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_f_quantile_2() {
+        // qf(0.95, 2, 10) = 4.102821
+        assert!((f_quantile_2(0.95, 10.0) - 4.102821).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_euclid_circle() {
+        let x = vec![0.0, 1.0, 2.0, 1.0];
+        let y = vec![1.0, 0.0, 1.0, 2.0];
+        let (xs, ys) = Ellipse::new()
+            .kind(EllipseType::Euclid)
+            .level(3.0)
+            .vertices(&x, &y);
+
+        assert_eq!(xs.len(), 52);
+        for (x, y) in xs.iter().zip(ys.iter()) {
+            let r = ((x - 1.0).powi(2) + (y - 1.0).powi(2)).sqrt();
+            assert!((r - 3.0).abs() < 1e-10);
+        }
+        assert!((xs[0] - xs[51]).abs() < 1e-10 && (ys[0] - ys[51]).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_normal_ellipse_follows_correlation() {
+        let x: Vec<f64> = (0..20).map(|i| i as f64).collect();
+        let y: Vec<f64> = x
+            .iter()
+            .enumerate()
+            .map(|(i, v)| 2.0 * v + if i % 2 == 0 { 1.0 } else { -1.0 })
+            .collect();
+        let (xs, ys) = Ellipse::new().kind(EllipseType::Norm).vertices(&x, &y);
+
+        // Centered on the means, elongated along y = 2x
+        let cx = xs[..xs.len() - 1].iter().sum::<f64>() / (xs.len() - 1) as f64;
+        let cy = ys[..ys.len() - 1].iter().sum::<f64>() / (ys.len() - 1) as f64;
+        assert!((cx - 9.5).abs() < 0.1);
+        assert!((cy - 19.0).abs() < 0.2);
+
+        let (_, y_at_max_x) = xs
+            .iter()
+            .zip(ys.iter())
+            .fold((f64::NEG_INFINITY, 0.0), |acc, (&x, &y)| if x > acc.0 { (x, y) } else { acc });
+        assert!(y_at_max_x > cy);
+
+        // The t ellipse is similar for well-behaved data
+        let (txs, _) = Ellipse::new().vertices(&x, &y);
+        assert_eq!(txs.len(), xs.len());
+    }
+
+    #[test]
+    fn test_euclid_zero_variance() {
+        let (xs, ys) = Ellipse::new()
+            .kind(EllipseType::Euclid)
+            .vertices(&[1.0, 1.0, 1.0], &[1.0, 2.0, 3.0]);
+        assert!(xs.is_empty() && ys.is_empty());
+    }
+
+    #[test]
+    fn test_too_few_points() {
+        let (xs, ys) = Ellipse::new().vertices(&[1.0, 2.0], &[1.0, 2.0]);
+        assert!(xs.is_empty() && ys.is_empty());
+    }
+}
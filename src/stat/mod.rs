@@ -2,8 +2,10 @@ pub mod bin;
 pub mod boxplot;
 pub mod count;
 pub mod density;
+pub mod ellipse;
 pub mod smooth;
 pub mod summary;
+pub mod summary_bin;
 
 use std::any::Any;
 use std::collections::HashSet;
@@ -162,9 +164,8 @@ pub trait Stat: Send + Sync {
             .iter()
             .map(|aes| {
                 mapping
-                    .get_vector_iter(aes, data)
+                    .get_vector(aes, data)
                     .ok_or(PlotError::MissingAesthetic { aesthetic: *aes })
-                    .and_then(|iter| Ok(iter.to_vector()))
             })
             .collect::<Result<Vec<VectorValue>>>()?;
 
@@ -185,9 +186,8 @@ pub trait Stat: Send + Sync {
             .iter()
             .map(|aes| {
                 mapping
-                    .get_vector_iter(aes, data)
+                    .get_vector(aes, data)
                     .ok_or(PlotError::MissingAesthetic { aesthetic: *aes })
-                    .and_then(|iter| Ok(iter.to_vector()))
             })
             .collect::<Result<Vec<VectorValue>>>()?;
 
@@ -220,15 +220,15 @@ pub trait Stat: Send + Sync {
             let group_index_vector = vec![group_indices[0]; n];
 
             for (gv, avb) in group_values.iter().zip(group_aesthetic_values.iter_mut()) {
-                let group_column = gv.subset_iter(&group_index_vector).to_vector();
-                avb.append(&mut group_data, group_column);
+                let group_column = gv.subset(&group_index_vector);
+                avb.append(&mut group_data, group_column)?;
             }
 
             // Accumulate group_data into overall data by appending rows
             if final_data.is_empty() {
                 final_data = group_data;
             } else {
-                final_data.append(group_data);
+                final_data.append(group_data)?;
             }
 
             // The AesMap should be the same for all groups, so we can just use the last one
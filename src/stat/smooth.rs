@@ -2,10 +2,13 @@
 //!
 //! Computes fitted values and confidence intervals using various smoothing methods.
 
+use std::any::Any;
+
 use crate::aesthetics::{AesMap, AesValue, Aesthetic, AestheticDomain, AestheticProperty};
-use crate::data::ContinuousType;
+use crate::data::{ContinuousType, DataSource, DiscreteValue, VectorIter};
 use crate::error::{PlotError, Result};
 use crate::stat::Stat;
+use crate::stat::summary::t_quantile;
 use crate::utils::data::{ContinuousContinuousVisitor2, Vectorable, visit2_cc};
 use crate::utils::dataframe::DataFrame;
 
@@ -20,6 +23,16 @@ pub enum Method {
     Loess,
 }
 
+/// Type of interval reported in the `ymin`/`ymax` columns
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Interval {
+    /// Confidence interval for the fitted mean (default)
+    Confidence,
+    /// Prediction interval for a new observation, which also accounts for
+    /// the residual scatter around the fit
+    Prediction,
+}
+
 /// Smooth statistics computation
 ///
 /// Fits a curve to the data and computes confidence intervals.
@@ -31,9 +44,14 @@ pub enum Method {
 /// # Output columns
 /// - `x`: x values at which predictions are made (evenly spaced)
 /// - `y`: predicted y values
-/// - `ymin`: lower confidence bound
-/// - `ymax`: upper confidence bound
-/// - `se`: standard error of prediction
+/// - `ymin`: lower interval bound
+/// - `ymax`: upper interval bound
+/// - `se`: standard error of the fitted mean
+/// - `r_squared`, `residual_se`, `n_obs`: fit diagnostics (repeated on every row)
+/// - `intercept`, `slope`: model coefficients (`Method::Lm` only)
+///
+/// The diagnostics can be retrieved from a built plot with
+/// [`Plot::smooth_fits`](crate::plot::Plot::smooth_fits).
 ///
 /// # Example
 ///
@@ -55,6 +73,9 @@ pub struct Smooth {
     /// Confidence level (default 0.95 for 95% CI)
     pub level: f64,
 
+    /// Type of interval to compute (default confidence interval for the mean)
+    pub interval: Interval,
+
     /// Number of points to compute predictions at (default 80)
     pub n: usize,
 
@@ -72,6 +93,7 @@ impl Smooth {
         Self {
             method: Method::Loess,
             level: 0.95,
+            interval: Interval::Confidence,
             n: 80,
             se: true,
             span: 0.75,
@@ -90,6 +112,12 @@ impl Smooth {
         self
     }
 
+    /// Set the type of interval (confidence or prediction)
+    pub fn interval(mut self, interval: Interval) -> Self {
+        self.interval = interval;
+        self
+    }
+
     /// Set the number of prediction points
     pub fn n(mut self, n: usize) -> Self {
         self.n = n.max(2);
@@ -110,12 +138,44 @@ impl Smooth {
     }
 }
 
+impl Smooth {
+    /// Fit the model to the data and predict at `x_pred`
+    fn fit(&self, x_values: &[f64], y_values: &[f64], x_pred: &[f64]) -> Result<Fit> {
+        match self.method {
+            Method::Lm => {
+                let (intercept, slope, rse) = fit_linear_model(x_values, y_values)?;
+                Ok(predict_linear_model(
+                    x_pred, x_values, y_values, intercept, slope, rse,
+                ))
+            }
+            Method::Spline => fit_cubic_spline(x_values, y_values, x_pred),
+            Method::Loess => fit_loess(x_values, y_values, x_pred, self.span),
+        }
+    }
+}
+
 impl Default for Smooth {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// Result of fitting a smoother to one group of data
+struct Fit {
+    /// Predicted values at the prediction points
+    y: Vec<f64>,
+    /// Standard error of the fitted mean at the prediction points
+    se: Vec<f64>,
+    /// Residuals at the observed data points
+    residuals: Vec<f64>,
+    /// Residual standard error
+    rse: f64,
+    /// Residual degrees of freedom
+    df: usize,
+    /// Model coefficients (`[intercept, slope]` for `Method::Lm`, otherwise empty)
+    coefficients: Vec<f64>,
+}
+
 /// Compute linear regression: y = a + bx
 /// Returns (intercept, slope, residual_standard_error)
 fn fit_linear_model(x: &[f64], y: &[f64]) -> Result<(f64, f64, f64)> {
@@ -169,14 +229,7 @@ fn fit_linear_model(x: &[f64], y: &[f64]) -> Result<(f64, f64, f64)> {
 }
 
 /// Compute LOESS (Locally Estimated Scatterplot Smoothing)
-/// Returns (y_pred, ymin, ymax, se)
-fn fit_loess(
-    x_data: &[f64],
-    y_data: &[f64],
-    x_pred: &[f64],
-    span: f64,
-    level: f64,
-) -> Result<(Vec<f64>, Vec<f64>, Vec<f64>, Vec<f64>)> {
+fn fit_loess(x_data: &[f64], y_data: &[f64], x_pred: &[f64], span: f64) -> Result<Fit> {
     let n = x_data.len();
     if n < 3 {
         return Err(PlotError::no_valid_data(
@@ -198,183 +251,113 @@ fn fit_loess(
     let x_sorted: Vec<f64> = paired.iter().map(|(x, _)| *x).collect();
     let y_sorted: Vec<f64> = paired.iter().map(|(_, y)| *y).collect();
 
-    // Compute predictions using local weighted linear regression
-    let mut y_pred = Vec::with_capacity(x_pred.len());
-    let mut residuals_at_pred = Vec::with_capacity(x_pred.len());
-
-    for &x0 in x_pred {
-        // Find q nearest neighbors
-        let mut distances: Vec<(usize, f64)> = x_sorted
-            .iter()
-            .enumerate()
-            .map(|(i, &x)| (i, (x - x0).abs()))
-            .collect();
-        distances.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
-
-        // Get the q nearest points
-        let neighbors = &distances[..q];
-        let max_dist = neighbors.last().unwrap().1.max(1e-10);
-
-        // Compute tricube weights: w(u) = (1 - |u|^3)^3 for |u| < 1
-        let weights: Vec<f64> = neighbors
-            .iter()
-            .map(|(_, d)| {
-                let u = d / max_dist;
-                if u < 1.0 {
-                    let t = 1.0 - u.powi(3);
-                    t.powi(3)
-                } else {
-                    0.0
-                }
-            })
-            .collect();
-
-        // Fit weighted linear regression: y = a + b*x
-        let mut sum_w = 0.0;
-        let mut sum_wx = 0.0;
-        let mut sum_wy = 0.0;
-        let mut sum_wxx = 0.0;
-        let mut sum_wxy = 0.0;
-
-        for (j, &(i, _)) in neighbors.iter().enumerate() {
-            let w = weights[j];
-            let x = x_sorted[i];
-            let y = y_sorted[i];
-
-            sum_w += w;
-            sum_wx += w * x;
-            sum_wy += w * y;
-            sum_wxx += w * x * x;
-            sum_wxy += w * x * y;
-        }
-
-        // Solve for slope and intercept
-        let denom = sum_w * sum_wxx - sum_wx * sum_wx;
-        let (a, b) = if denom.abs() > 1e-10 {
-            let b = (sum_w * sum_wxy - sum_wx * sum_wy) / denom;
-            let a = (sum_wy - b * sum_wx) / sum_w;
-            (a, b)
-        } else {
-            // If denominator is too small, just use weighted mean
-            (sum_wy / sum_w, 0.0)
-        };
-
-        let y_fit = a + b * x0;
-        y_pred.push(y_fit);
-
-        // Estimate local residual variance for this prediction
-        let mut local_var = 0.0;
-        for (j, &(i, _)) in neighbors.iter().enumerate() {
-            let w = weights[j];
-            let x = x_sorted[i];
-            let y = y_sorted[i];
-            let residual = y - (a + b * x);
-            local_var += w * residual * residual;
-        }
-        local_var /= sum_w;
-        residuals_at_pred.push(local_var.sqrt());
-    }
-
-    // Compute global residual standard error
+    // Residuals at the data points; the self-weights sum to the trace of the
+    // smoother matrix, the equivalent number of parameters
     let mut all_residuals = Vec::with_capacity(n);
-    for i in 0..n {
-        let x = x_sorted[i];
-        let y = y_sorted[i];
-
-        // Fit at this point (same as above but for data point)
-        let mut distances: Vec<(usize, f64)> = x_sorted
-            .iter()
-            .enumerate()
-            .map(|(j, &xj)| (j, (xj - x).abs()))
-            .collect();
-        distances.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
-
-        let neighbors = &distances[..q];
-        let max_dist = neighbors.last().unwrap().1.max(1e-10);
-
-        let weights: Vec<f64> = neighbors
+    let mut trace = 0.0;
+    for (i, (&x, &y)) in x_sorted.iter().zip(&y_sorted).enumerate() {
+        let weights = loess_weights(&x_sorted, x, q);
+        let y_fit: f64 = weights.iter().map(|&(j, l)| l * y_sorted[j]).sum();
+        trace += weights
             .iter()
-            .map(|(_, d)| {
-                let u = d / max_dist;
-                if u < 1.0 {
-                    let t = 1.0 - u.powi(3);
-                    t.powi(3)
-                } else {
-                    0.0
-                }
-            })
-            .collect();
-
-        let mut sum_w = 0.0;
-        let mut sum_wx = 0.0;
-        let mut sum_wy = 0.0;
-        let mut sum_wxx = 0.0;
-        let mut sum_wxy = 0.0;
-
-        for (j, &(idx, _)) in neighbors.iter().enumerate() {
-            let w = weights[j];
-            let xj = x_sorted[idx];
-            let yj = y_sorted[idx];
-
-            sum_w += w;
-            sum_wx += w * xj;
-            sum_wy += w * yj;
-            sum_wxx += w * xj * xj;
-            sum_wxy += w * xj * yj;
-        }
-
-        let denom = sum_w * sum_wxx - sum_wx * sum_wx;
-        let (a, b) = if denom.abs() > 1e-10 {
-            let b = (sum_w * sum_wxy - sum_wx * sum_wy) / denom;
-            let a = (sum_wy - b * sum_wx) / sum_w;
-            (a, b)
-        } else {
-            (sum_wy / sum_w, 0.0)
-        };
-
-        let y_fit = a + b * x;
+            .filter(|&&(j, _)| j == i)
+            .map(|&(_, l)| l)
+            .sum::<f64>();
         all_residuals.push(y - y_fit);
     }
 
-    let rse = (all_residuals.iter().map(|r| r.powi(2)).sum::<f64>() / (n - 2) as f64).sqrt();
+    let df = n as f64 - trace;
+    let rse = residual_se(&all_residuals, df);
 
-    // Use t-value approximation for confidence bands
-    let z = match level {
-        l if l >= 0.99 => 2.576,
-        l if l >= 0.95 => 1.96,
-        l if l >= 0.90 => 1.645,
-        _ => 1.96,
-    };
+    // The fit is linear in y, so its standard error is rse * sqrt(sum of l_j^2)
+    let mut y_pred = Vec::with_capacity(x_pred.len());
+    let mut se = Vec::with_capacity(x_pred.len());
+    for &x0 in x_pred {
+        let weights = loess_weights(&x_sorted, x0, q);
+        y_pred.push(weights.iter().map(|&(j, l)| l * y_sorted[j]).sum());
+        se.push(rse * weights.iter().map(|(_, l)| l * l).sum::<f64>().sqrt());
+    }
 
-    // Combine global and local uncertainty
-    let se_vec: Vec<f64> = residuals_at_pred
+    Ok(Fit {
+        y: y_pred,
+        se,
+        residuals: all_residuals,
+        rse,
+        df: df.round().max(0.0) as usize,
+        coefficients: Vec::new(),
+    })
+}
+
+/// Smoother weights `(index, l_j)` of a local linear fit at `x0`, such that
+/// the fitted value is `sum(l_j * y_j)` over the `q` nearest neighbours
+fn loess_weights(x_sorted: &[f64], x0: f64, q: usize) -> Vec<(usize, f64)> {
+    // Find q nearest neighbors
+    let mut distances: Vec<(usize, f64)> = x_sorted
         .iter()
-        .map(|&local_se| (rse.powi(2) + local_se.powi(2)).sqrt())
+        .enumerate()
+        .map(|(i, &x)| (i, (x - x0).abs()))
         .collect();
+    distances.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
 
-    let ymin: Vec<f64> = y_pred
+    // Get the q nearest points
+    let neighbors = &distances[..q];
+    let max_dist = neighbors.last().unwrap().1.max(1e-10);
+
+    // Compute tricube weights: w(u) = (1 - |u|^3)^3 for |u| < 1
+    let weights: Vec<f64> = neighbors
         .iter()
-        .zip(se_vec.iter())
-        .map(|(&y, &se)| y - z * se)
+        .map(|(_, d)| {
+            let u = d / max_dist;
+            if u < 1.0 {
+                let t = 1.0 - u.powi(3);
+                t.powi(3)
+            } else {
+                0.0
+            }
+        })
         .collect();
-    let ymax: Vec<f64> = y_pred
+
+    // Weighted linear regression: l_j = w_j * (1/S + (x0 - xw)(x_j - xw) / Sxx)
+    let sum_w: f64 = weights.iter().sum();
+    let x_mean = neighbors
         .iter()
-        .zip(se_vec.iter())
-        .map(|(&y, &se)| y + z * se)
-        .collect();
+        .zip(&weights)
+        .map(|(&(i, _), w)| w * x_sorted[i])
+        .sum::<f64>()
+        / sum_w;
+    let sxx: f64 = neighbors
+        .iter()
+        .zip(&weights)
+        .map(|(&(i, _), w)| w * (x_sorted[i] - x_mean).powi(2))
+        .sum();
 
-    Ok((y_pred, ymin, ymax, se_vec))
+    neighbors
+        .iter()
+        .zip(&weights)
+        .map(|(&(i, _), w)| {
+            // If the spread is too small, just use the weighted mean
+            let slope_term = if sxx > 1e-10 * sum_w {
+                (x0 - x_mean) * (x_sorted[i] - x_mean) / sxx
+            } else {
+                0.0
+            };
+            (i, w * (1.0 / sum_w + slope_term))
+        })
+        .collect()
 }
 
-/// Compute cubic spline smoothing
-/// Returns (y_pred, ymin, ymax, se) where ymin/ymax are computed using
-/// residual-based confidence bands
-fn fit_cubic_spline(
-    x_data: &[f64],
-    y_data: &[f64],
-    x_pred: &[f64],
-    level: f64,
-) -> Result<(Vec<f64>, Vec<f64>, Vec<f64>, Vec<f64>)> {
+/// Residual standard error for `df` residual degrees of freedom
+fn residual_se(residuals: &[f64], df: f64) -> f64 {
+    if df > 0.0 {
+        (residuals.iter().map(|r| r.powi(2)).sum::<f64>() / df).sqrt()
+    } else {
+        0.0
+    }
+}
+
+/// Compute cubic spline smoothing; the standard errors follow from the spline
+/// being a linear combination of its knot values
+fn fit_cubic_spline(x_data: &[f64], y_data: &[f64], x_pred: &[f64]) -> Result<Fit> {
     use splines::{Interpolation, Key, Spline};
 
     let n = x_data.len();
@@ -491,154 +474,122 @@ fn fit_cubic_spline(
         }
     }
 
-    // Compute confidence intervals using local residual variance
-    // Similar to LOESS approach but using spline predictions
-
-    let x_sorted: Vec<f64> = paired.iter().map(|(x, _)| *x).collect();
-    let y_sorted: Vec<f64> = paired.iter().map(|(_, y)| *y).collect();
-
-    // Choose neighborhood size (similar to LOESS span concept)
-    // Use about 50% of data for local variance estimation (wider than LOESS to account for smoothing)
-    let q = ((n as f64 * 0.5).max(3.0) as usize).min(n);
-
-    // Compute local variance at each prediction point
-    let mut residuals_at_pred = Vec::with_capacity(x_pred.len());
-
-    for &x0 in x_pred {
-        // Find q nearest neighbors
-        let mut distances: Vec<(usize, f64)> = x_sorted
-            .iter()
-            .enumerate()
-            .map(|(i, &x)| (i, (x - x0).abs()))
-            .collect();
-        distances.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
-
-        let neighbors = &distances[..q];
-        let max_dist = neighbors.last().unwrap().1.max(1e-10);
-
-        // Compute tricube weights for local variance
-        let weights: Vec<f64> = neighbors
-            .iter()
-            .map(|(_, d)| {
-                let u = d / max_dist;
-                if u < 1.0 {
-                    let t = 1.0 - u.powi(3);
-                    t.powi(3)
-                } else {
-                    0.0
-                }
-            })
-            .collect();
-
-        // Compute weighted local variance
-        let mut sum_w = 0.0;
-        let mut local_var = 0.0;
-
-        for (j, &(i, _)) in neighbors.iter().enumerate() {
-            let w = weights[j];
-            let x = x_sorted[i];
-            let y = y_sorted[i];
-
-            // Get spline prediction at this data point
-            if let Some(y_fit) = spline.sample(x) {
-                let residual = y - y_fit;
-                local_var += w * residual * residual;
-                sum_w += w;
-            }
-        }
-
-        if sum_w > 0.0 {
-            local_var /= sum_w;
-        }
-
-        residuals_at_pred.push(local_var.sqrt());
-    }
-
-    // Compute global residual standard error
+    // Residuals at the data points the spline covers; each knot is
+    // interpolated, so the trace of the smoother matrix is the number of
+    // knots among them
     let mut all_residuals = Vec::with_capacity(n);
+    let mut trace = 0.0;
     for (x, y) in &paired {
         if let Some(y_fit) = spline.sample(*x) {
             all_residuals.push(y - y_fit);
+            if smoothed_keys.contains(&(*x, *y)) {
+                trace += 1.0;
+            }
         }
     }
 
-    let rse = (all_residuals.iter().map(|r| r.powi(2)).sum::<f64>() / (n - 4) as f64).sqrt();
+    let df = all_residuals.len() as f64 - trace;
+    let rse = residual_se(&all_residuals, df);
 
-    // Use t-value approximation for confidence bands
-    let z = match level {
-        l if l >= 0.99 => 2.576,
-        l if l >= 0.95 => 1.96,
-        l if l >= 0.90 => 1.645,
-        _ => 1.96,
-    };
-
-    // Combine global and local uncertainty (same as LOESS)
-    let se_vec: Vec<f64> = residuals_at_pred
+    // Standard error of the fitted curve: rse * sqrt(sum of l_k^2) over the knots
+    let knots: Vec<f64> = smoothed_keys.iter().map(|(x, _)| *x).collect();
+    let se = x_pred
         .iter()
-        .map(|&local_se| (rse.powi(2) + local_se.powi(2)).sqrt())
+        .map(|&x| rse * spline_weights(&knots, x).iter().map(|l| l * l).sum::<f64>().sqrt())
         .collect();
 
-    let ymin: Vec<f64> = y_pred
-        .iter()
-        .zip(se_vec.iter())
-        .map(|(&y, &se)| y - z * se)
-        .collect();
-    let ymax: Vec<f64> = y_pred
-        .iter()
-        .zip(se_vec.iter())
-        .map(|(&y, &se)| y + z * se)
-        .collect();
+    Ok(Fit {
+        y: y_pred,
+        se,
+        residuals: all_residuals,
+        rse,
+        df: df.round().max(0.0) as usize,
+        coefficients: Vec::new(),
+    })
+}
+
+/// Weights `l_k` of each knot in the Catmull-Rom spline value at `x`,
+/// found by interpolating the unit vectors
+fn spline_weights(knots: &[f64], x: f64) -> Vec<f64> {
+    use splines::{Interpolation, Key, Spline};
 
-    Ok((y_pred, ymin, ymax, se_vec))
+    (0..knots.len())
+        .map(|k| {
+            let unit = Spline::from_vec(
+                knots
+                    .iter()
+                    .enumerate()
+                    .map(|(j, &kx)| {
+                        Key::new(kx, if j == k { 1.0 } else { 0.0 }, Interpolation::CatmullRom)
+                    })
+                    .collect(),
+            );
+            // Outside the spline the prediction repeats the end values
+            unit.clamped_sample(x).unwrap_or(if x < knots[0] {
+                if k == 0 { 1.0 } else { 0.0 }
+            } else if k == knots.len() - 1 {
+                1.0
+            } else {
+                0.0
+            })
+        })
+        .collect()
 }
 
-/// Compute predictions and confidence intervals for linear model
+/// Compute predictions and standard errors of the fitted mean for a linear model
 fn predict_linear_model(
     x_pred: &[f64],
     x_data: &[f64],
+    y_data: &[f64],
     intercept: f64,
     slope: f64,
     rse: f64,
-    level: f64,
-) -> (Vec<f64>, Vec<f64>, Vec<f64>, Vec<f64>) {
+) -> Fit {
     let n = x_data.len();
     let x_mean = x_data.iter().sum::<f64>() / n as f64;
 
     // Compute sum of squared deviations for x
     let sxx: f64 = x_data.iter().map(|&x| (x - x_mean).powi(2)).sum();
 
-    // t-value for confidence interval (approximate with normal for now)
-    // For 95% CI, z ≈ 1.96
-    let z = match level {
-        l if l >= 0.99 => 2.576,
-        l if l >= 0.95 => 1.96,
-        l if l >= 0.90 => 1.645,
-        _ => 1.96,
-    };
-
     let mut y_pred = Vec::with_capacity(x_pred.len());
-    let mut ymin = Vec::with_capacity(x_pred.len());
-    let mut ymax = Vec::with_capacity(x_pred.len());
     let mut se = Vec::with_capacity(x_pred.len());
 
     for &x in x_pred {
-        let y = intercept + slope * x;
-
-        // Standard error of prediction
+        // Standard error of the fitted mean
         // SE = s * sqrt(1/n + (x - x_mean)^2 / sxx)
-        let se_pred = if sxx > 1e-10 {
+        let se_fit = if sxx > 1e-10 {
             rse * (1.0 / n as f64 + (x - x_mean).powi(2) / sxx).sqrt()
         } else {
             rse
         };
 
-        y_pred.push(y);
-        ymin.push(y - z * se_pred);
-        ymax.push(y + z * se_pred);
-        se.push(se_pred);
+        y_pred.push(intercept + slope * x);
+        se.push(se_fit);
+    }
+
+    let residuals = x_data
+        .iter()
+        .zip(y_data.iter())
+        .map(|(&x, &y)| y - (intercept + slope * x))
+        .collect();
+
+    Fit {
+        y: y_pred,
+        se,
+        residuals,
+        rse,
+        df: n.saturating_sub(2),
+        coefficients: vec![intercept, slope],
     }
+}
 
-    (y_pred, ymin, ymax, se)
+/// Coefficient of determination for observations `y` given the residuals of a fit
+fn r_squared(y: &[f64], residuals: &[f64]) -> f64 {
+    let n = y.len() as f64;
+    let y_mean = y.iter().sum::<f64>() / n;
+    let sst: f64 = y.iter().map(|&y| (y - y_mean).powi(2)).sum();
+    let sse: f64 = residuals.iter().map(|r| r.powi(2)).sum();
+    if sst > 1e-12 { 1.0 - sse / sst } else { 1.0 }
 }
 
 impl Stat for Smooth {
@@ -707,28 +658,38 @@ impl ContinuousContinuousVisitor2 for SmoothVisitor {
             .collect();
 
         // Fit model and compute predictions
-        let (y_pred, ymin_pred, ymax_pred, se_pred) = match self.smooth.method {
-            Method::Lm => {
-                let (intercept, slope, rse) = fit_linear_model(&x_values, &y_values)?;
-                predict_linear_model(&x_pred, &x_values, intercept, slope, rse, self.smooth.level)
-            }
-            Method::Spline => fit_cubic_spline(&x_values, &y_values, &x_pred, self.smooth.level)?,
-            Method::Loess => fit_loess(
-                &x_values,
-                &y_values,
-                &x_pred,
-                self.smooth.span,
-                self.smooth.level,
-            )?,
-        };
+        let fit = self.smooth.fit(&x_values, &y_values, &x_pred)?;
+
+        let t = t_quantile(self.smooth.level, fit.df.max(1));
+        let half_width: Vec<f64> = fit
+            .se
+            .iter()
+            .map(|&se| match self.smooth.interval {
+                Interval::Confidence => t * se,
+                Interval::Prediction => t * (se.powi(2) + fit.rse.powi(2)).sqrt(),
+            })
+            .collect();
+        let ymin: Vec<f64> = fit.y.iter().zip(&half_width).map(|(y, h)| y - h).collect();
+        let ymax: Vec<f64> = fit.y.iter().zip(&half_width).map(|(y, h)| y + h).collect();
+
+        let n_pred = x_pred.len();
+        let r_squared = r_squared(&y_values, &fit.residuals);
 
         // Create output dataframe
         let mut data = DataFrame::new();
         data.add_column("x", x_pred);
-        data.add_column("y", y_pred);
-        data.add_column("ymin", ymin_pred);
-        data.add_column("ymax", ymax_pred);
-        data.add_column("se", se_pred);
+        data.add_column("y", fit.y);
+        data.add_column("ymin", ymin);
+        data.add_column("ymax", ymax);
+        data.add_column("se", fit.se);
+        data.add_column("r_squared", vec![r_squared; n_pred]);
+        data.add_column("residual_se", vec![fit.rse; n_pred]);
+        data.add_column("n_obs", vec![x_values.len() as i64; n_pred]);
+        if let [intercept, slope] = fit.coefficients[..] {
+            data.add_column("intercept", vec![intercept; n_pred]);
+            data.add_column("slope", vec![slope; n_pred]);
+        }
+
         // Create mapping
         let mut mapping = AesMap::new();
         mapping.set(
@@ -752,9 +713,363 @@ impl ContinuousContinuousVisitor2 for SmoothVisitor {
     }
 }
 
+/// Columns produced by [`Smooth`]; any other column in its output is a grouping column
+const SMOOTH_COLUMNS: [&str; 10] = [
+    "x",
+    "y",
+    "ymin",
+    "ymax",
+    "se",
+    "r_squared",
+    "residual_se",
+    "n_obs",
+    "intercept",
+    "slope",
+];
+
+/// Summary of a model fitted by [`Smooth`] to one group of data
+#[derive(Debug, Clone, PartialEq)]
+pub struct SmoothFit {
+    /// Grouping column values identifying the group (empty if ungrouped)
+    pub group: Vec<(String, DiscreteValue)>,
+    /// Model coefficients (`[intercept, slope]` for `Method::Lm`, otherwise empty)
+    pub coefficients: Vec<f64>,
+    /// Coefficient of determination
+    pub r_squared: f64,
+    /// Residual standard error
+    pub residual_se: f64,
+    /// Number of observations the model was fitted to
+    pub n: usize,
+}
+
+impl SmoothFit {
+    /// Extract the fits from the output of the [`Smooth`] stat
+    ///
+    /// Returns an empty vector if the data does not contain smooth diagnostics.
+    pub fn from_data(data: &dyn DataSource) -> Vec<SmoothFit> {
+        let (Some(r_squared), Some(residual_se), Some(n_obs)) = (
+            data.get("r_squared").and_then(|v| v.iter_float()),
+            data.get("residual_se").and_then(|v| v.iter_float()),
+            data.get("n_obs").and_then(|v| v.iter_int()),
+        ) else {
+            return Vec::new();
+        };
+        let intercept: Option<Vec<f64>> = data
+            .get("intercept")
+            .and_then(|v| v.iter_float())
+            .map(|it| it.collect());
+        let slope: Option<Vec<f64>> = data
+            .get("slope")
+            .and_then(|v| v.iter_float())
+            .map(|it| it.collect());
+
+        let mut group_names: Vec<String> = data
+            .column_names()
+            .into_iter()
+            .filter(|name| !SMOOTH_COLUMNS.contains(&name.as_str()))
+            .collect();
+        group_names.sort();
+        let group_values: Vec<Vec<DiscreteValue>> = group_names
+            .iter()
+            .map(|name| {
+                data.get(name)
+                    .map(|v| v.iter_discrete().unwrap_or_else(|| v.iter().to_discrete_iter()).collect())
+                    .unwrap_or_default()
+            })
+            .collect();
+
+        let mut fits: Vec<SmoothFit> = Vec::new();
+        for (i, ((r_squared, residual_se), n)) in r_squared.zip(residual_se).zip(n_obs).enumerate() {
+            let group: Vec<(String, DiscreteValue)> = group_names
+                .iter()
+                .zip(group_values.iter())
+                .map(|(name, values)| (name.clone(), values[i].clone()))
+                .collect();
+            if fits.last().is_some_and(|fit| fit.group == group) {
+                continue;
+            }
+            let coefficients = match (&intercept, &slope) {
+                (Some(intercept), Some(slope)) => vec![intercept[i], slope[i]],
+                _ => Vec::new(),
+            };
+            fits.push(SmoothFit {
+                group,
+                coefficients,
+                r_squared,
+                residual_se,
+                n: n as usize,
+            });
+        }
+        fits
+    }
+
+    /// Format the fitted equation, e.g. `y = 2.00 + 3.00x` (linear models only)
+    pub fn equation(&self, digits: usize) -> Option<String> {
+        match self.coefficients[..] {
+            [intercept, slope] => {
+                let sign = if slope < 0.0 { '-' } else { '+' };
+                Some(format!(
+                    "y = {:.*} {} {:.*}x",
+                    digits,
+                    intercept,
+                    sign,
+                    digits,
+                    slope.abs()
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    /// Build a label from the equation and/or R²
+    pub fn label(&self, equation: bool, r_squared: bool, digits: usize) -> String {
+        let mut parts = Vec::new();
+        if equation && let Some(eq) = self.equation(digits) {
+            parts.push(eq);
+        }
+        if r_squared {
+            parts.push(format!("R² = {:.*}", digits, self.r_squared));
+        }
+        parts.join(", ")
+    }
+}
+
+/// Fitted equation and R² labels for a smooth
+///
+/// Fits the same model as [`Smooth`] to each group and produces one row per
+/// group with `x`, `y` and `label` columns, suitable for `geom_text`. Labels are
+/// placed at a fixed position relative to the data range, and labels for
+/// successive groups are stacked downwards.
+#[derive(Debug, Clone)]
+pub struct SmoothLabel {
+    /// Model to fit
+    pub smooth: Smooth,
+
+    /// Whether to include the fitted equation (linear models only, default true)
+    pub equation: bool,
+
+    /// Whether to include R² (default true)
+    pub r_squared: bool,
+
+    /// Number of decimal places (default 3)
+    pub digits: usize,
+
+    /// Horizontal position as a fraction of the x range (default 0.05)
+    pub label_x: f64,
+
+    /// Vertical position of the first label as a fraction of the y range (default 0.95)
+    pub label_y: f64,
+}
+
+impl SmoothLabel {
+    /// Create a new SmoothLabel stat with default parameters
+    pub fn new() -> Self {
+        Self {
+            smooth: Smooth::new(),
+            equation: true,
+            r_squared: true,
+            digits: 3,
+            label_x: 0.05,
+            label_y: 0.95,
+        }
+    }
+
+    /// Set the model to fit
+    pub fn smooth(mut self, smooth: Smooth) -> Self {
+        self.smooth = smooth;
+        self
+    }
+
+    /// Set the smoothing method
+    pub fn method(mut self, method: Method) -> Self {
+        self.smooth = self.smooth.method(method);
+        self
+    }
+
+    /// Set whether to include the fitted equation
+    pub fn equation(mut self, equation: bool) -> Self {
+        self.equation = equation;
+        self
+    }
+
+    /// Set whether to include R²
+    pub fn r_squared(mut self, r_squared: bool) -> Self {
+        self.r_squared = r_squared;
+        self
+    }
+
+    /// Set the number of decimal places
+    pub fn digits(mut self, digits: usize) -> Self {
+        self.digits = digits;
+        self
+    }
+
+    /// Set the label position as fractions of the x and y data ranges
+    pub fn position(mut self, label_x: f64, label_y: f64) -> Self {
+        self.label_x = label_x;
+        self.label_y = label_y;
+        self
+    }
+
+    /// Range of the x and y aesthetics over the whole data set
+    fn ranges(data: &dyn DataSource, mapping: &AesMap) -> Option<((f64, f64), (f64, f64))> {
+        let range = |aes: Aesthetic| -> Option<(f64, f64)> {
+            let values: Vec<f64> = match mapping.get_vector_iter(&aes, data)? {
+                VectorIter::Float(iter) => iter.collect(),
+                VectorIter::Int(iter) => iter.map(|v| v as f64).collect(),
+                _ => return None,
+            };
+            let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+            let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+            min.is_finite().then_some((min, max))
+        };
+        Some((
+            range(Aesthetic::X(AestheticDomain::Continuous))?,
+            range(Aesthetic::Y(AestheticDomain::Continuous))?,
+        ))
+    }
+}
+
+impl Default for SmoothLabel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Per-group label computation for [`SmoothLabel`], before the labels are stacked
+struct SmoothLabelRows<'a>(&'a SmoothLabel);
+
+impl Stat for SmoothLabelRows<'_> {
+    fn aesthetic_requirements(&self) -> super::StatAestheticRequirements {
+        self.0.aesthetic_requirements()
+    }
+
+    fn compute_params(
+        &self,
+        data: &dyn DataSource,
+        mapping: &AesMap,
+        _aesthetics: &[Aesthetic],
+    ) -> Result<Option<Box<dyn Any>>> {
+        let ranges = SmoothLabel::ranges(data, mapping)
+            .ok_or_else(|| PlotError::no_valid_data("No finite x/y values for smooth label"))?;
+        Ok(Some(Box::new(ranges)))
+    }
+
+    fn compute_group(
+        &self,
+        _aesthetics: Vec<Aesthetic>,
+        iters: Vec<VectorIter<'_>>,
+        params: Option<&dyn Any>,
+    ) -> Result<(DataFrame, AesMap)> {
+        let ((x_min, x_max), (y_min, y_max)) = params
+            .and_then(|p| p.downcast_ref::<((f64, f64), (f64, f64))>())
+            .copied()
+            .ok_or_else(|| PlotError::InvalidStatParameters {
+                details: "smooth label requires the data ranges".to_string(),
+            })?;
+
+        let mut iters = iters.into_iter();
+        let (Some(x_values), Some(y_values)) = (iters.next(), iters.next()) else {
+            return Err(PlotError::MissingAesthetic {
+                aesthetic: Aesthetic::Y(AestheticDomain::Continuous),
+            });
+        };
+        let mut visitor = SmoothLabelVisitor {
+            stat: self.0,
+            x: x_min + (x_max - x_min) * self.0.label_x,
+            y: y_min + (y_max - y_min) * self.0.label_y,
+        };
+        visit2_cc(x_values, y_values, &mut visitor)
+    }
+}
+
+struct SmoothLabelVisitor<'a> {
+    stat: &'a SmoothLabel,
+    x: f64,
+    y: f64,
+}
+
+impl ContinuousContinuousVisitor2 for SmoothLabelVisitor<'_> {
+    type Output = (DataFrame, AesMap);
+
+    fn visit<T: Vectorable + ContinuousType, U: Vectorable + ContinuousType>(
+        &mut self,
+        x_values: impl Iterator<Item = T>,
+        y_values: impl Iterator<Item = U>,
+    ) -> std::result::Result<Self::Output, PlotError> {
+        let x_values: Vec<f64> = x_values.map(|v| v.to_f64()).collect();
+        let y_values: Vec<f64> = y_values.map(|v| v.to_f64()).collect();
+
+        let fit = self.stat.smooth.fit(&x_values, &y_values, &[])?;
+        let summary = SmoothFit {
+            group: Vec::new(),
+            r_squared: r_squared(&y_values, &fit.residuals),
+            coefficients: fit.coefficients,
+            residual_se: fit.rse,
+            n: x_values.len(),
+        };
+        let label = summary.label(self.stat.equation, self.stat.r_squared, self.stat.digits);
+
+        let mut data = DataFrame::new();
+        data.add_column("x", vec![self.x]);
+        data.add_column("y", vec![self.y]);
+        data.add_column("label", vec![label]);
+
+        let mut mapping = AesMap::new();
+        mapping.set(
+            Aesthetic::X(AestheticDomain::Continuous),
+            AesValue::column("x"),
+        );
+        mapping.set(
+            Aesthetic::Y(AestheticDomain::Continuous),
+            AesValue::column("y"),
+        );
+        mapping.set(Aesthetic::Label, AesValue::column("label"));
+
+        Ok((data, mapping))
+    }
+}
+
+impl Stat for SmoothLabel {
+    fn aesthetic_requirements(&self) -> super::StatAestheticRequirements {
+        super::StatAestheticRequirements {
+            main: AestheticProperty::X,
+            secondary: Some(AestheticProperty::Y),
+            additional: vec![],
+        }
+    }
+
+    fn compute_group(
+        &self,
+        aesthetics: Vec<Aesthetic>,
+        iters: Vec<VectorIter<'_>>,
+        params: Option<&dyn Any>,
+    ) -> Result<(DataFrame, AesMap)> {
+        SmoothLabelRows(self).compute_group(aesthetics, iters, params)
+    }
+
+    fn compute(&self, data: &dyn DataSource, mapping: &AesMap) -> Result<(DataFrame, AesMap)> {
+        let (mut new_data, new_mapping) = SmoothLabelRows(self).compute(data, mapping)?;
+
+        // Stack the labels of successive groups so they don't overlap
+        if let Some(((_, _), (y_min, y_max))) = SmoothLabel::ranges(data, mapping) {
+            let step = (y_max - y_min) * 0.06;
+            let y: Vec<f64> = new_data
+                .get("y")
+                .and_then(|v| v.iter_float())
+                .map(|it| it.enumerate().map(|(i, y)| y - step * i as f64).collect())
+                .unwrap_or_default();
+            new_data.add_column("y", y);
+        }
+
+        Ok((new_data, new_mapping))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::data::IStr;
 
     #[test]
     fn test_linear_regression() {
@@ -792,4 +1107,161 @@ mod tests {
         assert!((slope - 3.0).abs() < 0.5, "Slope should be close to 3.0");
         assert!(rse > 0.0, "RSE should be positive");
     }
+
+    const LINEAR_X: [f64; 8] = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+    const LINEAR_Y: [f64; 8] = [5.1, 7.9, 11.2, 13.8, 17.1, 19.7, 23.4, 25.9];
+
+    fn linear_group(method: Method, interval: Interval) -> (DataFrame, AesMap) {
+        let x = LINEAR_X.to_vec();
+        let y = LINEAR_Y.to_vec();
+        Smooth::new()
+            .method(method)
+            .interval(interval)
+            .compute_group(
+                vec![],
+                vec![
+                    VectorIter::Float(Box::new(x.into_iter())),
+                    VectorIter::Float(Box::new(y.into_iter())),
+                ],
+                None,
+            )
+            .unwrap()
+    }
+
+    fn floats(data: &DataFrame, name: &str) -> Vec<f64> {
+        data.get(name).unwrap().iter_float().unwrap().collect()
+    }
+
+    #[test]
+    fn test_prediction_interval_wider_than_confidence() {
+        for method in [Method::Lm, Method::Loess] {
+            let (confidence, _) = linear_group(method, Interval::Confidence);
+            let (prediction, _) = linear_group(method, Interval::Prediction);
+
+            assert_eq!(floats(&confidence, "y"), floats(&prediction, "y"));
+            let conf_width = floats(&confidence, "ymax")
+                .into_iter()
+                .zip(floats(&confidence, "ymin"))
+                .map(|(hi, lo)| hi - lo);
+            let pred_width = floats(&prediction, "ymax")
+                .into_iter()
+                .zip(floats(&prediction, "ymin"))
+                .map(|(hi, lo)| hi - lo);
+            for (c, p) in conf_width.zip(pred_width) {
+                assert!(p > c, "prediction interval should be wider ({} <= {})", p, c);
+            }
+        }
+    }
+
+    #[test]
+    fn test_prediction_interval_adds_residual_se_once() {
+        for method in [Method::Lm, Method::Loess, Method::Spline] {
+            let (data, _) = linear_group(method, Interval::Prediction);
+            let rse = floats(&data, "residual_se")[0];
+            let fit = Smooth::new()
+                .method(method)
+                .fit(&LINEAR_X, &LINEAR_Y, &[])
+                .unwrap();
+            let t = t_quantile(0.95, fit.df.max(1));
+            for ((se, y), ymax) in floats(&data, "se")
+                .into_iter()
+                .zip(floats(&data, "y"))
+                .zip(floats(&data, "ymax"))
+            {
+                let expected = t * (se.powi(2) + rse.powi(2)).sqrt();
+                assert!((ymax - y - expected).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_loess_se_from_smoother_weights() {
+        // Symmetric neighbourhood: tricube weights (0, 0.669921875, 1, 0.669921875, 0)
+        // and no slope term at the centre, so l_j = w_j / sum(w)
+        let x = [-2.0, -1.0, 0.0, 1.0, 2.0];
+        let weights = loess_weights(&x, 0.0, 5);
+        let expected = [0.0, 0.2863105175, 0.4273789649, 0.2863105175, 0.0];
+        for (i, l) in weights {
+            assert!((l - expected[i]).abs() < 1e-9);
+        }
+
+        let y = [1.0, -0.5, 0.3, 1.2, -0.4];
+        let fit = fit_loess(&x, &y, &[0.0], 1.0).unwrap();
+        assert!(fit.rse > 0.0);
+        assert!((fit.se[0] - fit.rse * 0.5887276149).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_loess_weights_reproduce_lines() {
+        let x = [0.0, 0.5, 1.5, 2.0, 3.5, 4.0, 6.0];
+        for x0 in [0.0, 1.0, 3.7, 6.0] {
+            let weights = loess_weights(&x, x0, 5);
+            let sum: f64 = weights.iter().map(|(_, l)| l).sum();
+            let at: f64 = weights.iter().map(|&(i, l)| l * x[i]).sum();
+            assert!((sum - 1.0).abs() < 1e-12);
+            assert!((at - x0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_spline_se_from_knot_weights() {
+        // Catmull-Rom midway between evenly spaced knots: (-1, 9, 9, -1) / 16
+        let knots = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+        let weights = spline_weights(&knots, 2.5);
+        let expected = [0.0, -0.0625, 0.5625, 0.5625, -0.0625, 0.0];
+        for (l, e) in weights.iter().zip(expected) {
+            assert!((l - e).abs() < 1e-12);
+        }
+        // At a knot the spline interpolates that knot alone
+        assert_eq!(spline_weights(&knots, 2.0), vec![0.0, 0.0, 1.0, 0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_r_squared_perfect_fit() {
+        let y = vec![5.0, 8.0, 11.0, 14.0];
+        assert!((r_squared(&y, &[0.0; 4]) - 1.0).abs() < 1e-12);
+        assert!(r_squared(&y, &[1.0, -1.0, 1.0, -1.0]) < 1.0);
+    }
+
+    #[test]
+    fn test_smooth_fit_from_data() {
+        let (mut data, _) = linear_group(Method::Lm, Interval::Confidence);
+        let n = data.len();
+        data.add_column("group", vec!["a"; n]);
+        let (mut other, _) = linear_group(Method::Lm, Interval::Confidence);
+        other.add_column("group", vec!["b"; n]);
+        data.append(other).unwrap();
+
+        let fits = SmoothFit::from_data(&data);
+        assert_eq!(fits.len(), 2);
+        assert_eq!(
+            fits[1].group,
+            vec![("group".to_string(), DiscreteValue::Str(IStr::new("b".to_string())))]
+        );
+        let fit = &fits[0];
+        assert_eq!(fit.n, 8);
+        assert!((fit.coefficients[0] - 2.0).abs() < 0.5);
+        assert!((fit.coefficients[1] - 3.0).abs() < 0.5);
+        assert!(fit.r_squared > 0.99);
+        assert!(fit.residual_se > 0.0);
+    }
+
+    #[test]
+    fn test_smooth_fit_label() {
+        let fit = SmoothFit {
+            group: vec![],
+            coefficients: vec![2.0, -3.0],
+            r_squared: 0.95,
+            residual_se: 0.1,
+            n: 10,
+        };
+        assert_eq!(fit.label(true, true, 2), "y = 2.00 - 3.00x, R² = 0.95");
+        assert_eq!(fit.label(false, true, 3), "R² = 0.950");
+
+        let loess = SmoothFit {
+            coefficients: vec![],
+            ..fit
+        };
+        assert_eq!(loess.label(true, true, 2), "R² = 0.95");
+    }
 }
@@ -1,10 +1,14 @@
 // Statistical summary transformation
 
-use crate::aesthetics::{AesMap, Aesthetic, AestheticDomain};
-use crate::data::{ContinuousType, DiscreteType, VectorIter};
-use crate::error::Result;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::aesthetics::{AesMap, AesValue, Aesthetic, AestheticDomain, AestheticProperty};
+use crate::data::{ContinuousType, DataSource, DiscreteType, VectorIter, VectorValue};
+use crate::error::{PlotError, Result};
 use crate::stat::Stat;
-use crate::utils::data::Vectorable;
+use crate::utils::GroupByExt;
+use crate::utils::data::{ContinuousVectorVisitor, Vectorable, visit_c};
 use crate::utils::dataframe::DataFrame;
 
 /// Summary stat - computes scalar summary statistics for specified aesthetics
@@ -20,18 +24,26 @@ pub struct Summary {
 }
 
 impl Summary {
-    // Check that all the aesthetics have the same domain,
-    // and panic if they do not.
-    fn check_aesthetic_domains(&self) {
+    // Check that all the aesthetics have the same domain.
+    fn check_aesthetic_domains(&self) -> Result<()> {
         if self.aesthetics.len() == 0 {
-            return;
+            return Ok(());
         }
         let first_domain = self.aesthetics[0].domain();
         for aes in &self.aesthetics[1..] {
             if aes.domain() != first_domain {
-                panic!("All aesthetics for Summary stat must have the same domain");
+                return Err(PlotError::InvalidStatParameters {
+                    details: format!(
+                        "All aesthetics for Summary stat must have the same domain ({} is {:?}, {} is {:?})",
+                        self.aesthetics[0].to_str(),
+                        first_domain,
+                        aes.to_str(),
+                        aes.domain()
+                    ),
+                });
             }
         }
+        Ok(())
     }
 
     fn compute_group_inner_continuous<T: ContinuousType + Vectorable>(
@@ -126,19 +138,16 @@ impl Summary {
 
 impl From<Aesthetic> for Summary {
     fn from(aesthetic: Aesthetic) -> Self {
-        let result = Self {
+        Self {
             aesthetics: vec![aesthetic],
-        };
-        result.check_aesthetic_domains();
-        result
+        }
     }
 }
 
+/// Mixed aesthetic domains are reported as an error when the stat is computed.
 impl From<Vec<Aesthetic>> for Summary {
     fn from(aesthetics: Vec<Aesthetic>) -> Self {
-        let result = Self { aesthetics };
-        result.check_aesthetic_domains();
-        result
+        Self { aesthetics }
     }
 }
 
@@ -152,6 +161,16 @@ impl Stat for Summary {
         )
     }
 
+    fn compute_params(
+        &self,
+        _data: &dyn DataSource,
+        _mapping: &AesMap,
+        _aesthetics: &[Aesthetic],
+    ) -> Result<Option<Box<dyn std::any::Any>>> {
+        self.check_aesthetic_domains()?;
+        Ok(None)
+    }
+
     fn compute_group(
         &self,
         aesthetics: Vec<Aesthetic>,
@@ -198,6 +217,406 @@ fn mk_name(prefix: &Option<String>, base: &str) -> String {
     }
 }
 
+/// A function computing `(y, ymin, ymax)` from the y values at one x position
+pub type SummaryClosure = Arc<dyn Fn(&[f64]) -> (f64, f64, f64) + Send + Sync>;
+
+/// Summary function used by [`GroupSummary`]
+#[derive(Clone)]
+pub enum SummaryFunction {
+    /// Mean ± `mult` standard errors
+    MeanSe { mult: f64 },
+    /// Mean with a confidence interval from the t distribution
+    MeanClNormal { level: f64 },
+    /// Mean with a percentile bootstrap confidence interval
+    MeanClBoot {
+        level: f64,
+        replicates: usize,
+        seed: u64,
+    },
+    /// Median with the interquartile range
+    MedianIqr,
+    /// Arbitrary quantiles for `y`, `ymin` and `ymax`
+    Quantiles { y: f64, ymin: f64, ymax: f64 },
+    /// User supplied function returning `(y, ymin, ymax)`
+    Custom(SummaryClosure),
+}
+
+impl fmt::Debug for SummaryFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SummaryFunction::MeanSe { mult } => {
+                f.debug_struct("MeanSe").field("mult", mult).finish()
+            }
+            SummaryFunction::MeanClNormal { level } => {
+                f.debug_struct("MeanClNormal").field("level", level).finish()
+            }
+            SummaryFunction::MeanClBoot {
+                level,
+                replicates,
+                seed,
+            } => f
+                .debug_struct("MeanClBoot")
+                .field("level", level)
+                .field("replicates", replicates)
+                .field("seed", seed)
+                .finish(),
+            SummaryFunction::MedianIqr => f.write_str("MedianIqr"),
+            SummaryFunction::Quantiles { y, ymin, ymax } => f
+                .debug_struct("Quantiles")
+                .field("y", y)
+                .field("ymin", ymin)
+                .field("ymax", ymax)
+                .finish(),
+            SummaryFunction::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+impl SummaryFunction {
+    /// Apply the function to the (finite) values at one x position
+    pub fn apply(&self, values: &[f64]) -> (f64, f64, f64) {
+        if values.is_empty() {
+            return (f64::NAN, f64::NAN, f64::NAN);
+        }
+        match self {
+            SummaryFunction::MeanSe { mult } => {
+                let (mean, se) = mean_se(values);
+                (mean, mean - mult * se, mean + mult * se)
+            }
+            SummaryFunction::MeanClNormal { level } => {
+                let (mean, se) = mean_se(values);
+                if values.len() < 2 {
+                    return (mean, f64::NAN, f64::NAN);
+                }
+                let t = t_quantile(*level, values.len() - 1);
+                (mean, mean - t * se, mean + t * se)
+            }
+            SummaryFunction::MeanClBoot {
+                level,
+                replicates,
+                seed,
+            } => {
+                let n = values.len();
+                let mean = values.iter().sum::<f64>() / n as f64;
+                let mut rng = SplitMix64(*seed);
+                let mut means: Vec<f64> = (0..*replicates)
+                    .map(|_| {
+                        (0..n).map(|_| values[rng.next_index(n)]).sum::<f64>() / n as f64
+                    })
+                    .collect();
+                means.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                let alpha = (1.0 - level) / 2.0;
+                (mean, quantile(&means, alpha), quantile(&means, 1.0 - alpha))
+            }
+            SummaryFunction::MedianIqr => {
+                let sorted = sorted(values);
+                (
+                    quantile(&sorted, 0.5),
+                    quantile(&sorted, 0.25),
+                    quantile(&sorted, 0.75),
+                )
+            }
+            SummaryFunction::Quantiles { y, ymin, ymax } => {
+                let sorted = sorted(values);
+                (
+                    quantile(&sorted, *y),
+                    quantile(&sorted, *ymin),
+                    quantile(&sorted, *ymax),
+                )
+            }
+            SummaryFunction::Custom(f) => f(values),
+        }
+    }
+}
+
+/// Per-x summary stat - summarises the y values at each distinct x
+///
+/// Produces one row per x position (and group) with `y`, `ymin` and `ymax`
+/// columns, suitable for `geom_point`, `geom_errorbar` and similar geoms.
+///
+/// # Output columns
+/// - `x`: the distinct x values
+/// - `y`, `ymin`, `ymax`: the summary
+/// - `n`: number of finite y values summarised
+///
+/// # Example
+///
+/// ```rust,ignore
+/// plot(&data).aes(|a| {
+///     a.x_continuous("cyl");
+///     a.y_continuous("mpg");
+/// }) + geom_errorbar().stat(GroupSummary::mean_cl_normal())
+/// ```
+#[derive(Debug, Clone)]
+pub struct GroupSummary {
+    pub function: SummaryFunction,
+}
+
+impl GroupSummary {
+    /// Summarise with the given function
+    pub fn new(function: SummaryFunction) -> Self {
+        Self { function }
+    }
+
+    /// Mean ± one standard error
+    pub fn mean_se() -> Self {
+        Self::new(SummaryFunction::MeanSe { mult: 1.0 })
+    }
+
+    /// Mean with a 95% t-based confidence interval
+    pub fn mean_cl_normal() -> Self {
+        Self::new(SummaryFunction::MeanClNormal { level: 0.95 })
+    }
+
+    /// Mean with a 95% bootstrap confidence interval (1000 replicates, fixed seed)
+    pub fn mean_cl_boot() -> Self {
+        Self::new(SummaryFunction::MeanClBoot {
+            level: 0.95,
+            replicates: 1000,
+            seed: 1,
+        })
+    }
+
+    /// Median with the interquartile range
+    pub fn median_iqr() -> Self {
+        Self::new(SummaryFunction::MedianIqr)
+    }
+
+    /// Quantiles for `y`, `ymin` and `ymax` (each in 0..=1)
+    pub fn quantiles(y: f64, ymin: f64, ymax: f64) -> Self {
+        Self::new(SummaryFunction::Quantiles {
+            y: y.clamp(0.0, 1.0),
+            ymin: ymin.clamp(0.0, 1.0),
+            ymax: ymax.clamp(0.0, 1.0),
+        })
+    }
+
+    /// User supplied function returning `(y, ymin, ymax)`
+    pub fn custom(f: impl Fn(&[f64]) -> (f64, f64, f64) + Send + Sync + 'static) -> Self {
+        Self::new(SummaryFunction::Custom(Arc::new(f)))
+    }
+
+    /// Set the multiple of the standard error (`mean_se`) or the
+    /// confidence level (`mean_cl_normal`, `mean_cl_boot`)
+    pub fn level(mut self, value: f64) -> Self {
+        match &mut self.function {
+            SummaryFunction::MeanSe { mult } => *mult = value.max(0.0),
+            SummaryFunction::MeanClNormal { level } | SummaryFunction::MeanClBoot { level, .. } => {
+                *level = value.clamp(0.0, 1.0)
+            }
+            _ => {}
+        }
+        self
+    }
+
+    /// Set the number of bootstrap replicates (`mean_cl_boot` only)
+    pub fn replicates(mut self, n: usize) -> Self {
+        if let SummaryFunction::MeanClBoot { replicates, .. } = &mut self.function {
+            *replicates = n.max(1);
+        }
+        self
+    }
+
+    /// Set the bootstrap random seed (`mean_cl_boot` only)
+    pub fn seed(mut self, value: u64) -> Self {
+        if let SummaryFunction::MeanClBoot { seed, .. } = &mut self.function {
+            *seed = value;
+        }
+        self
+    }
+}
+
+impl Stat for GroupSummary {
+    fn aesthetic_requirements(&self) -> super::StatAestheticRequirements {
+        super::StatAestheticRequirements {
+            main: AestheticProperty::X,
+            secondary: Some(AestheticProperty::Y),
+            additional: vec![],
+        }
+    }
+
+    fn compute_group(
+        &self,
+        aesthetics: Vec<Aesthetic>,
+        iters: Vec<VectorIter<'_>>,
+        _params: Option<&dyn std::any::Any>,
+    ) -> Result<(DataFrame, AesMap)> {
+        let mut iters = iters.into_iter();
+        let (Some(x_iter), Some(y_iter)) = (iters.next(), iters.next()) else {
+            return Err(PlotError::MissingAesthetic {
+                aesthetic: Aesthetic::Y(AestheticDomain::Continuous),
+            });
+        };
+        let x_aesthetic = aesthetics
+            .first()
+            .copied()
+            .unwrap_or(Aesthetic::X(AestheticDomain::Continuous));
+
+        let x_values = x_iter.to_vector();
+        let y_values = visit_c(y_iter, &mut FloatCollector)?;
+
+        // Order the rows by x, dropping missing values
+        let mut indices: Vec<usize> = (0..y_values.len())
+            .filter(|&i| y_values[i].is_finite())
+            .filter(|&i| match &x_values {
+                VectorValue::Float(v) => v[i].is_finite(),
+                _ => true,
+            })
+            .collect();
+        indices.sort_by(|&i, &j| x_values.cmp_at_index(i, j));
+
+        let mut firsts = Vec::new();
+        let mut ys = Vec::new();
+        let mut ymins = Vec::new();
+        let mut ymaxs = Vec::new();
+        let mut ns = Vec::new();
+        for group in indices
+            .into_iter()
+            .group_by(|&i, &j| x_values.cmp_at_index(i, j))
+        {
+            let values: Vec<f64> = group.iter().map(|&i| y_values[i]).collect();
+            let (y, ymin, ymax) = self.function.apply(&values);
+            firsts.push(group[0]);
+            ys.push(y);
+            ymins.push(ymin);
+            ymaxs.push(ymax);
+            ns.push(values.len() as i64);
+        }
+
+        let mut data = DataFrame::new();
+        data.add_column("x", x_values.subset_iter(&firsts).to_vector());
+        data.add_column("y", ys);
+        data.add_column("ymin", ymins);
+        data.add_column("ymax", ymaxs);
+        data.add_column("n", ns);
+
+        let mut mapping = AesMap::new();
+        mapping.set(
+            Aesthetic::X(x_aesthetic.domain()),
+            AesValue::column("x"),
+        );
+        mapping.set(
+            Aesthetic::Y(AestheticDomain::Continuous),
+            AesValue::column("y"),
+        );
+        mapping.set(
+            Aesthetic::Ymin(AestheticDomain::Continuous),
+            AesValue::column("ymin"),
+        );
+        mapping.set(
+            Aesthetic::Ymax(AestheticDomain::Continuous),
+            AesValue::column("ymax"),
+        );
+
+        Ok((data, mapping))
+    }
+}
+
+pub(super) struct FloatCollector;
+
+impl ContinuousVectorVisitor for FloatCollector {
+    type Output = Vec<f64>;
+
+    fn visit<T: Vectorable + ContinuousType>(
+        &mut self,
+        values: impl Iterator<Item = T>,
+    ) -> std::result::Result<Self::Output, PlotError> {
+        Ok(values.map(|v| v.to_f64()).collect())
+    }
+}
+
+fn mean_se(values: &[f64]) -> (f64, f64) {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    if values.len() < 2 {
+        return (mean, 0.0);
+    }
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    (mean, (variance / n).sqrt())
+}
+
+pub(super) fn sorted(values: &[f64]) -> Vec<f64> {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    sorted
+}
+
+/// Quantile of sorted data using linear interpolation (R type 7)
+pub(super) fn quantile(sorted: &[f64], p: f64) -> f64 {
+    let n = sorted.len();
+    if n == 0 {
+        return f64::NAN;
+    }
+    let h = (n - 1) as f64 * p.clamp(0.0, 1.0);
+    let lower = h.floor() as usize;
+    let upper = h.ceil() as usize;
+    sorted[lower] + (h - lower as f64) * (sorted[upper] - sorted[lower])
+}
+
+/// Probability that |T| < t for Student's t with `df` degrees of freedom
+/// (Abramowitz & Stegun 26.7.3, exact for integer degrees of freedom)
+fn t_central_probability(t: f64, df: usize) -> f64 {
+    let theta = (t / (df as f64).sqrt()).atan();
+    let (sin, cos) = theta.sin_cos();
+    let cos2 = cos * cos;
+    if df % 2 == 1 {
+        let mut sum = 0.0;
+        let mut term = cos;
+        let mut k = 1;
+        while k + 2 <= df {
+            sum += term;
+            term *= cos2 * (k + 1) as f64 / (k + 2) as f64;
+            k += 2;
+        }
+        2.0 / std::f64::consts::PI * (theta + sin * sum)
+    } else {
+        let mut sum = 0.0;
+        let mut term = 1.0;
+        let mut k = 0;
+        while k + 2 <= df {
+            sum += term;
+            term *= cos2 * (k + 1) as f64 / (k + 2) as f64;
+            k += 2;
+        }
+        sin * sum
+    }
+}
+
+/// Critical value t such that P(|T| < t) = `level`
+pub(super) fn t_quantile(level: f64, df: usize) -> f64 {
+    let level = level.clamp(0.0, 0.999_999);
+    let (mut lo, mut hi) = (0.0, 1.0);
+    while t_central_probability(hi, df) < level {
+        hi *= 2.0;
+    }
+    for _ in 0..100 {
+        let mid = (lo + hi) / 2.0;
+        if t_central_probability(mid, df) < level {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    (lo + hi) / 2.0
+}
+
+/// Small seeded generator so bootstrap intervals are reproducible
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn next_index(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -278,4 +697,105 @@ mod tests {
             .unwrap();
         assert_eq!(n_unique, 3);
     }
+
+    fn summarise(stat: GroupSummary) -> DataFrame {
+        let mut df = DataFrame::new();
+        df.add_column("g", vec!["b", "a", "a", "b", "a", "b", "a"]);
+        df.add_column("v", vec![10.0, 1.0, 2.0, 12.0, 3.0, 14.0, f64::NAN]);
+        let df: Box<dyn DataSource> = Box::new(df);
+
+        let mut mapping = AesMap::new();
+        mapping.x("g", AestheticDomain::Discrete);
+        mapping.y("v", AestheticDomain::Continuous);
+
+        stat.compute(df.as_ref(), &mapping).unwrap().0
+    }
+
+    fn column(df: &DataFrame, name: &str) -> Vec<f64> {
+        df.get(name).unwrap().iter_float().unwrap().collect()
+    }
+
+    #[test]
+    fn test_summary_mixed_domains_is_error() {
+        let mut df = DataFrame::new();
+        df.add_column("x", vec![1.0, 2.0]);
+        df.add_column("g", vec!["a", "b"]);
+        let df: Box<dyn DataSource> = Box::new(df);
+
+        let mut mapping = AesMap::new();
+        mapping.x("x", AestheticDomain::Continuous);
+        mapping.y("g", AestheticDomain::Discrete);
+
+        let stat = Summary::from(vec![
+            Aesthetic::X(AestheticDomain::Continuous),
+            Aesthetic::Y(AestheticDomain::Discrete),
+        ]);
+        assert!(matches!(
+            stat.compute(df.as_ref(), &mapping),
+            Err(PlotError::InvalidStatParameters { .. })
+        ));
+    }
+
+    #[test]
+    fn test_group_summary_mean_se() {
+        let output = summarise(GroupSummary::mean_se());
+
+        let x: Vec<&str> = output.get("x").unwrap().iter_str().unwrap().collect();
+        assert_eq!(x, vec!["a", "b"]);
+        assert_eq!(column(&output, "y"), vec![2.0, 12.0]);
+        let n: Vec<i64> = output.get("n").unwrap().iter_int().unwrap().collect();
+        assert_eq!(n, vec![3, 3]);
+
+        // sd = 1 for group a, so se = 1/sqrt(3)
+        let se = 1.0 / 3.0_f64.sqrt();
+        assert!((column(&output, "ymin")[0] - (2.0 - se)).abs() < 1e-10);
+        assert!((column(&output, "ymax")[0] - (2.0 + se)).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_group_summary_mean_cl_normal() {
+        let output = summarise(GroupSummary::mean_cl_normal());
+        // t(0.975, 2) = 4.302653
+        let half = 4.302653 / 3.0_f64.sqrt();
+        assert!((column(&output, "ymax")[0] - (2.0 + half)).abs() < 1e-5);
+        assert!((column(&output, "ymin")[1] - (12.0 - 2.0 * half)).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_t_quantile() {
+        assert!((t_quantile(0.95, 1) - 12.706205).abs() < 1e-5);
+        assert!((t_quantile(0.95, 10) - 2.228139).abs() < 1e-5);
+        assert!((t_quantile(0.99, 29) - 2.756386).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_group_summary_mean_cl_boot_is_reproducible() {
+        let first = summarise(GroupSummary::mean_cl_boot().seed(42));
+        let second = summarise(GroupSummary::mean_cl_boot().seed(42));
+        assert_eq!(column(&first, "ymin"), column(&second, "ymin"));
+        assert_eq!(column(&first, "ymax"), column(&second, "ymax"));
+
+        let (ymin, ymax) = (column(&first, "ymin"), column(&first, "ymax"));
+        assert!(ymin[0] >= 1.0 && ymin[0] <= 2.0);
+        assert!(ymax[0] >= 2.0 && ymax[0] <= 3.0);
+    }
+
+    #[test]
+    fn test_group_summary_quantiles_and_custom() {
+        let output = summarise(GroupSummary::median_iqr());
+        assert_eq!(column(&output, "y"), vec![2.0, 12.0]);
+        assert_eq!(column(&output, "ymin"), vec![1.5, 11.0]);
+        assert_eq!(column(&output, "ymax"), vec![2.5, 13.0]);
+
+        let output = summarise(GroupSummary::quantiles(0.5, 0.0, 1.0));
+        assert_eq!(column(&output, "ymin"), vec![1.0, 10.0]);
+        assert_eq!(column(&output, "ymax"), vec![3.0, 14.0]);
+
+        let output = summarise(GroupSummary::custom(|v| {
+            let max = v.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+            (max, 0.0, max * 2.0)
+        }));
+        assert_eq!(column(&output, "y"), vec![3.0, 14.0]);
+        assert_eq!(column(&output, "ymax"), vec![6.0, 28.0]);
+    }
 }
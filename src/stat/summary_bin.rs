@@ -0,0 +1,492 @@
+//! Binned summary statistics
+//!
+//! Aggregates a third variable (`z`) over bins of `x`, or over a grid of
+//! `x` and `y` bins.
+
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::aesthetics::{AesMap, AesValue, Aesthetic, AestheticDomain, AestheticProperty};
+use crate::data::{DataSource, VectorIter};
+use crate::error::{PlotError, Result};
+use crate::stat::Stat;
+use crate::stat::bin::{BinStrategy, Binner, get_data_range};
+use crate::stat::summary::{FloatCollector, quantile, sorted};
+use crate::utils::data::visit_c;
+use crate::utils::dataframe::DataFrame;
+
+/// User supplied aggregation closure
+pub type AggregateClosure = Arc<dyn Fn(&[f64]) -> f64 + Send + Sync>;
+
+/// Aggregation applied to the `z` values falling in each bin
+#[derive(Clone, Default)]
+pub enum Aggregate {
+    #[default]
+    Mean,
+    Median,
+    Sum,
+    Count,
+    Custom(AggregateClosure),
+}
+
+impl fmt::Debug for Aggregate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Aggregate::Mean => write!(f, "Mean"),
+            Aggregate::Median => write!(f, "Median"),
+            Aggregate::Sum => write!(f, "Sum"),
+            Aggregate::Count => write!(f, "Count"),
+            Aggregate::Custom(_) => write!(f, "Custom(..)"),
+        }
+    }
+}
+
+impl Aggregate {
+    /// Create a custom aggregation from a closure
+    pub fn custom(f: impl Fn(&[f64]) -> f64 + Send + Sync + 'static) -> Self {
+        Aggregate::Custom(Arc::new(f))
+    }
+
+    /// Apply the aggregation to the values of one bin
+    pub fn apply(&self, values: &[f64]) -> f64 {
+        match self {
+            Aggregate::Mean => values.iter().sum::<f64>() / values.len() as f64,
+            Aggregate::Median => quantile(&sorted(values), 0.5),
+            Aggregate::Sum => values.iter().sum(),
+            Aggregate::Count => values.len() as f64,
+            Aggregate::Custom(f) => f(values),
+        }
+    }
+}
+
+/// Layer-wide data ranges, so that every group shares the same bins
+struct BinRanges {
+    x: (f64, f64),
+    y: Option<(f64, f64)>,
+}
+
+fn data_range(data: &dyn DataSource, mapping: &AesMap, aesthetic: &Aesthetic) -> Result<(f64, f64)> {
+    let iter = mapping
+        .get_vector_iter(aesthetic, data)
+        .ok_or(PlotError::MissingAesthetic {
+            aesthetic: *aesthetic,
+        })?;
+    let (min, max) = get_data_range(iter).ok_or(PlotError::InvalidAestheticType {
+        aesthetic: *aesthetic,
+        expected: crate::error::DataType::Continuous,
+        actual: crate::error::DataType::Discrete,
+    })?;
+    if min < max {
+        Ok((min, max))
+    } else {
+        // All values are identical; give the single bin some width
+        Ok((min - 0.5, max + 0.5))
+    }
+}
+
+fn bin_ranges<'a>(params: Option<&'a dyn Any>, stat: &str) -> Result<&'a BinRanges> {
+    params
+        .and_then(|p| p.downcast_ref::<BinRanges>())
+        .ok_or(PlotError::InvalidStatParameters {
+            details: format!("Missing or invalid parameters for {} stat", stat),
+        })
+}
+
+fn collect_floats(iter: VectorIter<'_>) -> Result<Vec<f64>> {
+    visit_c(iter, &mut FloatCollector)
+}
+
+/// Summary of `z` over bins of `x`
+///
+/// Bins `x` exactly as the [`Bin`](crate::stat::bin::Bin) stat does, and
+/// aggregates the `z` values falling in each bin. Empty bins are dropped.
+///
+/// # Output columns
+/// - `x`: bin center
+/// - `xmin`, `xmax`: bin bounds
+/// - `y`: aggregated `z`
+/// - `n`: number of observations in the bin
+///
+/// # Example
+///
+/// ```rust,ignore
+/// plot(&data).aes(|a| {
+///     a.x_continuous("wt");
+///     a.z_continuous("mpg");
+/// }) + geom_point().stat(SummaryBin::with_count(10))
+/// ```
+#[derive(Debug, Clone)]
+pub struct SummaryBin {
+    pub strategy: BinStrategy,
+    pub aggregate: Aggregate,
+}
+
+impl SummaryBin {
+    /// Create a new SummaryBin stat with the specified number of bins
+    pub fn with_count(bins: usize) -> Self {
+        Self {
+            strategy: BinStrategy::Count(bins),
+            aggregate: Aggregate::Mean,
+        }
+    }
+
+    /// Create a new SummaryBin stat with a specific bin width
+    pub fn with_width(binwidth: f64) -> Self {
+        Self {
+            strategy: BinStrategy::Width(binwidth),
+            aggregate: Aggregate::Mean,
+        }
+    }
+
+    /// Set the aggregation applied to each bin
+    pub fn aggregate(mut self, aggregate: Aggregate) -> Self {
+        self.aggregate = aggregate;
+        self
+    }
+}
+
+impl Default for SummaryBin {
+    fn default() -> Self {
+        Self::with_count(30)
+    }
+}
+
+impl Stat for SummaryBin {
+    fn aesthetic_requirements(&self) -> super::StatAestheticRequirements {
+        super::StatAestheticRequirements::from((AestheticProperty::X, AestheticProperty::Z))
+    }
+
+    fn compute_params(
+        &self,
+        data: &dyn DataSource,
+        mapping: &AesMap,
+        aesthetics: &[Aesthetic],
+    ) -> Result<Option<Box<dyn Any>>> {
+        let x = data_range(data, mapping, &aesthetics[0])?;
+        Ok(Some(Box::new(BinRanges { x, y: None })))
+    }
+
+    fn compute_group(
+        &self,
+        aesthetics: Vec<Aesthetic>,
+        iters: Vec<VectorIter<'_>>,
+        params: Option<&dyn Any>,
+    ) -> Result<(DataFrame, AesMap)> {
+        let ranges = bin_ranges(params, "SummaryBin")?;
+        if aesthetics.len() < 2 {
+            return Err(PlotError::MissingAesthetic {
+                aesthetic: Aesthetic::Z,
+            });
+        }
+
+        let mut iters = iters.into_iter();
+        let x_values = collect_floats(iters.next().unwrap())?;
+        let z_values = collect_floats(iters.next().unwrap())?;
+
+        let binner = Binner::new(ranges.x.0, ranges.x.1, &self.strategy);
+        let mut bins: Vec<Vec<f64>> = vec![Vec::new(); binner.len()];
+        for (x, z) in x_values.into_iter().zip(z_values) {
+            if x.is_finite() && z.is_finite() {
+                bins[binner.bin_of_value(x)].push(z);
+            }
+        }
+
+        let mut xs = Vec::new();
+        let mut xmins = Vec::new();
+        let mut xmaxs = Vec::new();
+        let mut ys = Vec::new();
+        let mut ns = Vec::new();
+        for (i, values) in bins.iter().enumerate() {
+            if values.is_empty() {
+                continue;
+            }
+            let (xmin, xmax) = binner.bin_bounds(i);
+            xs.push(binner.center_of_bin(i));
+            xmins.push(xmin);
+            xmaxs.push(xmax);
+            ys.push(self.aggregate.apply(values));
+            ns.push(values.len() as i64);
+        }
+
+        let mut data = DataFrame::new();
+        data.add_column("x", xs);
+        data.add_column("xmin", xmins);
+        data.add_column("xmax", xmaxs);
+        data.add_column("y", ys);
+        data.add_column("n", ns);
+
+        let mut mapping = AesMap::new();
+        mapping.set(
+            Aesthetic::X(AestheticDomain::Continuous),
+            AesValue::column("x"),
+        );
+        mapping.set(
+            Aesthetic::Y(AestheticDomain::Continuous),
+            AesValue::column("y"),
+        );
+
+        Ok((data, mapping))
+    }
+}
+
+/// Summary of `z` over a grid of `x` and `y` bins
+///
+/// Bins `x` and `y` independently and aggregates the `z` values falling
+/// in each cell, producing tiles suitable for a heatmap. Empty cells are
+/// dropped.
+///
+/// # Output columns
+/// - `x`, `y`: cell center
+/// - `xmin`, `xmax`, `ymin`, `ymax`: cell bounds
+/// - `value`: aggregated `z`, mapped to continuous fill
+/// - `n`: number of observations in the cell
+///
+/// # Example
+///
+/// ```rust,ignore
+/// plot(&data).aes(|a| {
+///     a.x_continuous("wt");
+///     a.y_continuous("hp");
+///     a.z_continuous("mpg");
+/// }) + geom_rect().stat(Summary2d::with_count(10, 10))
+/// ```
+#[derive(Debug, Clone)]
+pub struct Summary2d {
+    pub x_strategy: BinStrategy,
+    pub y_strategy: BinStrategy,
+    pub aggregate: Aggregate,
+}
+
+impl Summary2d {
+    /// Create a new Summary2d stat with the specified number of bins in each direction
+    pub fn with_count(x_bins: usize, y_bins: usize) -> Self {
+        Self {
+            x_strategy: BinStrategy::Count(x_bins),
+            y_strategy: BinStrategy::Count(y_bins),
+            aggregate: Aggregate::Mean,
+        }
+    }
+
+    /// Create a new Summary2d stat with specific bin widths
+    pub fn with_width(x_binwidth: f64, y_binwidth: f64) -> Self {
+        Self {
+            x_strategy: BinStrategy::Width(x_binwidth),
+            y_strategy: BinStrategy::Width(y_binwidth),
+            aggregate: Aggregate::Mean,
+        }
+    }
+
+    /// Set the aggregation applied to each cell
+    pub fn aggregate(mut self, aggregate: Aggregate) -> Self {
+        self.aggregate = aggregate;
+        self
+    }
+}
+
+impl Default for Summary2d {
+    fn default() -> Self {
+        Self::with_count(30, 30)
+    }
+}
+
+impl Stat for Summary2d {
+    fn aesthetic_requirements(&self) -> super::StatAestheticRequirements {
+        super::StatAestheticRequirements::from(vec![
+            AestheticProperty::X,
+            AestheticProperty::Y,
+            AestheticProperty::Z,
+        ])
+    }
+
+    fn compute_params(
+        &self,
+        data: &dyn DataSource,
+        mapping: &AesMap,
+        aesthetics: &[Aesthetic],
+    ) -> Result<Option<Box<dyn Any>>> {
+        if aesthetics.len() < 3 {
+            return Err(PlotError::InvalidStatParameters {
+                details: "Summary2d requires x, y and z aesthetics".to_string(),
+            });
+        }
+        let x = data_range(data, mapping, &aesthetics[0])?;
+        let y = data_range(data, mapping, &aesthetics[1])?;
+        Ok(Some(Box::new(BinRanges { x, y: Some(y) })))
+    }
+
+    fn compute_group(
+        &self,
+        _aesthetics: Vec<Aesthetic>,
+        iters: Vec<VectorIter<'_>>,
+        params: Option<&dyn Any>,
+    ) -> Result<(DataFrame, AesMap)> {
+        let ranges = bin_ranges(params, "Summary2d")?;
+        let y_range = ranges.y.ok_or(PlotError::InvalidStatParameters {
+            details: "Missing y range for Summary2d stat".to_string(),
+        })?;
+
+        let mut iters = iters.into_iter();
+        let x_values = collect_floats(iters.next().unwrap())?;
+        let y_values = collect_floats(iters.next().unwrap())?;
+        let z_values = collect_floats(iters.next().unwrap())?;
+
+        let x_binner = Binner::new(ranges.x.0, ranges.x.1, &self.x_strategy);
+        let y_binner = Binner::new(y_range.0, y_range.1, &self.y_strategy);
+        let mut cells: Vec<Vec<f64>> = vec![Vec::new(); x_binner.len() * y_binner.len()];
+        for ((x, y), z) in x_values.into_iter().zip(y_values).zip(z_values) {
+            if x.is_finite() && y.is_finite() && z.is_finite() {
+                let cell = y_binner.bin_of_value(y) * x_binner.len() + x_binner.bin_of_value(x);
+                cells[cell].push(z);
+            }
+        }
+
+        let mut xs = Vec::new();
+        let mut ys = Vec::new();
+        let mut xmins = Vec::new();
+        let mut xmaxs = Vec::new();
+        let mut ymins = Vec::new();
+        let mut ymaxs = Vec::new();
+        let mut values = Vec::new();
+        let mut ns = Vec::new();
+        for (cell, zs) in cells.iter().enumerate() {
+            if zs.is_empty() {
+                continue;
+            }
+            let (i, j) = (cell % x_binner.len(), cell / x_binner.len());
+            let (xmin, xmax) = x_binner.bin_bounds(i);
+            let (ymin, ymax) = y_binner.bin_bounds(j);
+            xs.push(x_binner.center_of_bin(i));
+            ys.push(y_binner.center_of_bin(j));
+            xmins.push(xmin);
+            xmaxs.push(xmax);
+            ymins.push(ymin);
+            ymaxs.push(ymax);
+            values.push(self.aggregate.apply(zs));
+            ns.push(zs.len() as i64);
+        }
+
+        let mut data = DataFrame::new();
+        data.add_column("x", xs);
+        data.add_column("y", ys);
+        data.add_column("xmin", xmins);
+        data.add_column("xmax", xmaxs);
+        data.add_column("ymin", ymins);
+        data.add_column("ymax", ymaxs);
+        data.add_column("value", values);
+        data.add_column("n", ns);
+
+        let mut mapping = AesMap::new();
+        mapping.set(
+            Aesthetic::Xmin(AestheticDomain::Continuous),
+            AesValue::column("xmin"),
+        );
+        mapping.set(
+            Aesthetic::Xmax(AestheticDomain::Continuous),
+            AesValue::column("xmax"),
+        );
+        mapping.set(
+            Aesthetic::Ymin(AestheticDomain::Continuous),
+            AesValue::column("ymin"),
+        );
+        mapping.set(
+            Aesthetic::Ymax(AestheticDomain::Continuous),
+            AesValue::column("ymax"),
+        );
+        mapping.set(
+            Aesthetic::Fill(AestheticDomain::Continuous),
+            AesValue::column("value"),
+        );
+
+        Ok((data, mapping))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapping_xz() -> AesMap {
+        let mut mapping = AesMap::new();
+        mapping.x("x", AestheticDomain::Continuous);
+        mapping.set(Aesthetic::Z, AesValue::column("z"));
+        mapping
+    }
+
+    #[test]
+    fn test_summary_bin_mean() {
+        let mut df = DataFrame::new();
+        df.add_column("x", vec![0.0, 0.5, 1.0, 3.5, 4.0]);
+        df.add_column("z", vec![1.0, 3.0, 5.0, 10.0, 20.0]);
+        let df: Box<dyn DataSource> = Box::new(df);
+
+        let (data, mapping) = SummaryBin::with_count(4)
+            .compute(df.as_ref(), &mapping_xz())
+            .unwrap();
+
+        assert_eq!(
+            mapping.get(&Aesthetic::Y(AestheticDomain::Continuous)),
+            Some(&AesValue::column("y"))
+        );
+
+        // Bins [0,1), [1,2), [2,3), [3,4]; the third bin is empty and dropped
+        let ys: Vec<f64> = data.get("y").unwrap().iter_float().unwrap().collect();
+        let ns: Vec<i64> = data.get("n").unwrap().iter_int().unwrap().collect();
+        assert_eq!(ys, vec![2.0, 5.0, 15.0]);
+        assert_eq!(ns, vec![2, 1, 2]);
+    }
+
+    #[test]
+    fn test_summary_bin_aggregates() {
+        let mut df = DataFrame::new();
+        df.add_column("x", vec![1.0, 1.0, 1.0, 2.0]);
+        df.add_column("z", vec![1.0, 2.0, 9.0, 4.0]);
+        let df: Box<dyn DataSource> = Box::new(df);
+
+        let run = |aggregate: Aggregate| -> Vec<f64> {
+            let (data, _) = SummaryBin::with_count(2)
+                .aggregate(aggregate)
+                .compute(df.as_ref(), &mapping_xz())
+                .unwrap();
+            data.get("y").unwrap().iter_float().unwrap().collect()
+        };
+
+        assert_eq!(run(Aggregate::Median), vec![2.0, 4.0]);
+        assert_eq!(run(Aggregate::Sum), vec![12.0, 4.0]);
+        assert_eq!(run(Aggregate::Count), vec![3.0, 1.0]);
+        assert_eq!(
+            run(Aggregate::custom(|v| v.iter().cloned().fold(f64::MIN, f64::max))),
+            vec![9.0, 4.0]
+        );
+    }
+
+    #[test]
+    fn test_summary_2d() {
+        let mut df = DataFrame::new();
+        df.add_column("x", vec![0.0, 0.1, 1.0, 0.0, 1.0]);
+        df.add_column("y", vec![0.0, 0.2, 0.0, 1.0, 1.0]);
+        df.add_column("z", vec![1.0, 3.0, 5.0, 7.0, 9.0]);
+        let df: Box<dyn DataSource> = Box::new(df);
+
+        let mut mapping = AesMap::new();
+        mapping.x("x", AestheticDomain::Continuous);
+        mapping.y("y", AestheticDomain::Continuous);
+        mapping.set(Aesthetic::Z, AesValue::column("z"));
+
+        let (data, mapping) = Summary2d::with_count(2, 2)
+            .compute(df.as_ref(), &mapping)
+            .unwrap();
+
+        assert_eq!(
+            mapping.get(&Aesthetic::Fill(AestheticDomain::Continuous)),
+            Some(&AesValue::column("value"))
+        );
+
+        let xs: Vec<f64> = data.get("x").unwrap().iter_float().unwrap().collect();
+        let ys: Vec<f64> = data.get("y").unwrap().iter_float().unwrap().collect();
+        let values: Vec<f64> = data.get("value").unwrap().iter_float().unwrap().collect();
+        assert_eq!(xs, vec![0.25, 0.75, 0.25, 0.75]);
+        assert_eq!(ys, vec![0.25, 0.25, 0.75, 0.75]);
+        assert_eq!(values, vec![2.0, 5.0, 7.0, 9.0]);
+    }
+}
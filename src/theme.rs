@@ -2,6 +2,7 @@
 
 use crate::{
     aesthetics::{Aesthetic, AestheticDomain, AestheticProperty},
+    error::PlotError,
     geom::properties::{Property, PropertyValue},
     visuals::{LineStyle, Shape},
 };
@@ -18,6 +19,75 @@ impl Color {
     }
 }
 
+impl std::str::FromStr for Color {
+    type Err = PlotError;
+
+    /// Parse a hex code (`#rgb`, `#rrggbb` or `#rrggbbaa`), `rgb(r, g, b)`,
+    /// `rgba(r, g, b, a)` with alpha in [0, 1], or one of the named colors
+    /// in [`color`], ignoring case
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let invalid = || PlotError::Other {
+            details: format!("invalid color: {}", s),
+        };
+        if let Some(hex) = s.strip_prefix('#') {
+            return parse_hex(hex).ok_or_else(invalid);
+        }
+
+        let lower = s.to_ascii_lowercase();
+        let functional = match lower.strip_prefix("rgb(") {
+            Some(rest) => Some((rest, false)),
+            None => lower.strip_prefix("rgba(").map(|rest| (rest, true)),
+        };
+        if let Some((rest, has_alpha)) = functional {
+            let args: Vec<&str> = rest
+                .strip_suffix(')')
+                .ok_or_else(invalid)?
+                .split(',')
+                .map(str::trim)
+                .collect();
+            if args.len() != if has_alpha { 4 } else { 3 } {
+                return Err(invalid());
+            }
+            let channel = |arg: &str| arg.parse::<u8>().map_err(|_| invalid());
+            let alpha = if has_alpha {
+                let alpha: f64 = args[3].parse().map_err(|_| invalid())?;
+                if !(0.0..=1.0).contains(&alpha) {
+                    return Err(invalid());
+                }
+                (alpha * 255.0).round() as u8
+            } else {
+                255
+            };
+            return Ok(Color(channel(args[0])?, channel(args[1])?, channel(args[2])?, alpha));
+        }
+
+        color::color_map()
+            .iter()
+            .find(|(name, _)| *name == lower)
+            .map(|(_, color)| *color)
+            .ok_or_else(invalid)
+    }
+}
+
+// The digits of a hex color code, without the leading '#'
+fn parse_hex(hex: &str) -> Option<Color> {
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let byte = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    match hex.len() {
+        3 => {
+            // Each digit is doubled, so "f80" is "ff8800"
+            let digit = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).ok().map(|d| d * 17);
+            Some(Color(digit(0)?, digit(1)?, digit(2)?, 255))
+        }
+        6 => Some(Color(byte(0)?, byte(2)?, byte(4)?, 255)),
+        8 => Some(Color(byte(0)?, byte(2)?, byte(4)?, byte(6)?)),
+        _ => None,
+    }
+}
+
 impl From<Color> for i64 {
     fn from(color: Color) -> i64 {
         ((color.0 as i64) << 24)
@@ -715,6 +785,9 @@ pub struct AxisLineTheme {
     pub line: LineElement,
     pub ticks: LineElement,
     pub tick_length: f32,
+    /// Ticks at the minor breaks of a continuous scale; not drawn if None
+    pub minor_ticks: Option<LineElement>,
+    pub minor_tick_length: f32,
 }
 
 impl Default for AxisLineTheme {
@@ -733,6 +806,8 @@ impl Default for AxisLineTheme {
                 linestyle: Some(LineStyle::Solid),
             },
             tick_length: 5.0,
+            minor_ticks: None,
+            minor_tick_length: 2.5,
         }
     }
 }
@@ -832,6 +907,8 @@ pub struct PanelTheme {
     pub border: Option<LineElement>,
     pub grid_major: Option<LineElement>,
     pub grid_minor: Option<LineElement>,
+    /// Gap between the panels of a faceted plot
+    pub spacing: f32,
 }
 
 impl Default for PanelTheme {
@@ -854,6 +931,42 @@ impl Default for PanelTheme {
                 alpha: Some(1.0),
                 linestyle: Some(LineStyle::Solid),
             }),
+            spacing: 8.0,
+        }
+    }
+}
+
+/// Theme for the strips labelling the panels of a faceted plot
+#[derive(Clone, Debug, PartialEq)]
+pub struct StripTheme {
+    pub background: Option<FillStyle>,
+    pub text: TextElement,
+    pub margin: Spacing,
+}
+
+impl Default for StripTheme {
+    fn default() -> Self {
+        StripTheme {
+            background: Some(FillStyle {
+                color: Color(217, 217, 217, 255),
+                opacity: 1.0,
+            }),
+            text: TextElement {
+                color: Some(Color(26, 26, 26, 255)),
+                size: Some(10.0),
+                alpha: Some(1.0),
+                family: Some("Sans".to_string()),
+                weight: Some(FontWeight::Normal),
+                style: Some(FontStyle::Normal),
+                hjust: None,
+                vjust: None,
+            },
+            margin: Spacing {
+                top: 4.0,
+                right: 4.0,
+                bottom: 4.0,
+                left: 4.0,
+            },
         }
     }
 }
@@ -893,6 +1006,7 @@ impl Default for PlotTitleTheme {
 pub struct Theme {
     pub background: Background,
     pub panel: PanelTheme,
+    pub strip: StripTheme,
     pub axis_x: AxisTheme,
     pub axis_y: AxisTheme,
     pub legend: LegendTheme,
@@ -908,6 +1022,7 @@ impl Default for Theme {
         Theme {
             background: Background::default(),
             panel: PanelTheme::default(),
+            strip: StripTheme::default(),
             axis_x: AxisTheme::default(),
             axis_y: AxisTheme::default(),
             legend: LegendTheme::default(),
@@ -965,6 +1080,11 @@ impl Theme {
         theme.axis_y.text.text.color = Some(light_gray);
         theme.axis_y.text.title.color = Some(light_gray);
         theme.plot_title.text.color = Some(light_gray);
+        theme.strip.text.color = Some(light_gray);
+        theme.strip.background = Some(FillStyle {
+            color: Color(70, 70, 70, 255),
+            opacity: 1.0,
+        });
         // Update axis lines
         theme.axis_x.line.line.color = Some(light_gray);
         theme.axis_x.line.ticks.color = Some(light_gray);
@@ -1045,6 +1165,19 @@ mod tests {
         utils::mtcars::mtcars,
     };
 
+    #[test]
+    fn color_from_str() {
+        assert_eq!("#ff0000".parse::<Color>().unwrap(), Color(255, 0, 0, 255));
+        assert_eq!("#F80".parse::<Color>().unwrap(), Color(255, 136, 0, 255));
+        assert_eq!("#00ff0080".parse::<Color>().unwrap(), Color(0, 255, 0, 128));
+        assert_eq!("rgb(10, 20, 30)".parse::<Color>().unwrap(), Color(10, 20, 30, 255));
+        assert_eq!("RGBA(10,20,30,0.5)".parse::<Color>().unwrap(), Color(10, 20, 30, 128));
+        assert_eq!("SteelBlue".parse::<Color>().unwrap(), color::STEELBLUE);
+        for bad in ["#ff00", "#gg0000", "rgb(1, 2)", "rgb(256, 0, 0)", "rgba(0, 0, 0, 2)", "notacolor"] {
+            assert!(bad.parse::<Color>().is_err(), "{} should not parse", bad);
+        }
+    }
+
     fn init_test_logging() {
         let _ = env_logger::builder()
             .is_test(true)
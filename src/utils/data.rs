@@ -606,6 +606,15 @@ fn visit3_ddc_inner2<
     }
 }
 
+/// Numeric values of a vector; other types have none
+pub(crate) fn numeric_values(iter: VectorIter<'_>) -> Vec<f64> {
+    match iter {
+        VectorIter::Int(iter) => iter.map(|v| v as f64).collect(),
+        VectorIter::Float(iter) => iter.collect(),
+        _ => Vec::new(),
+    }
+}
+
 pub fn make_color_iter<'a>(
     iter: VectorIter<'a>,
 ) -> impl Iterator<Item = Color> + 'a {
@@ -1,4 +1,5 @@
-use crate::data::{DataSource, GenericVector, StrVector, VectorIter, VectorType, VectorValue};
+use crate::data::{DataSource, GenericVector, StrVector, TemporalType, VectorIter, VectorType, VectorValue};
+use crate::error::PlotError;
 use std::collections::HashMap;
 
 // Concrete vector implementations
@@ -139,10 +140,88 @@ impl GenericVector for BoolVec {
 /// ```
 #[derive(Debug)]
 pub struct DataFrame {
-    columns: HashMap<String, VectorValue>,
+    columns: HashMap<String, Column>,
     len: usize,
 }
 
+/// A column of a DataFrame and, if any of its values are missing, a
+/// validity bitmap (false where the value is missing)
+#[derive(Debug)]
+struct Column {
+    values: VectorValue,
+    validity: Option<Vec<bool>>,
+}
+
+impl Column {
+    fn new(values: VectorValue, validity: Option<Vec<bool>>) -> Self {
+        let mut values = values;
+        let mut validity = validity;
+        // Missing categorical values are missing rows
+        if values.null_count() > 0 {
+            let validity = validity.get_or_insert_with(|| vec![true; values.len()]);
+            for (i, valid) in validity.iter_mut().enumerate() {
+                *valid &= !values.is_null(i);
+            }
+        }
+        let validity = validity.filter(|validity| validity.contains(&false));
+        // Missing floats and date-times read as NaN
+        if let (VectorValue::Float(floats) | VectorValue::DateTime(floats, _), Some(validity)) =
+            (&mut values, &validity)
+        {
+            for (value, _) in floats.iter_mut().zip(validity).filter(|(_, valid)| !**valid) {
+                *value = f64::NAN;
+            }
+        }
+        Self { values, validity }
+    }
+
+    fn append(&mut self, other: &Column) -> Result<(), PlotError> {
+        let len = self.values.len();
+        self.values.append(&other.values)?;
+        if self.validity.is_some() || other.validity.is_some() {
+            let mut validity = self.validity.take().unwrap_or_else(|| vec![true; len]);
+            match &other.validity {
+                Some(other_validity) => validity.extend_from_slice(other_validity),
+                None => validity.resize(len + other.values.len(), true),
+            }
+            self.validity = Some(validity);
+        }
+        Ok(())
+    }
+}
+
+impl GenericVector for Column {
+    fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    fn vtype(&self) -> VectorType {
+        self.values.vtype()
+    }
+
+    fn iter(&self) -> VectorIter<'_> {
+        GenericVector::iter(&self.values)
+    }
+
+    fn is_null(&self, i: usize) -> bool {
+        self.validity.as_ref().is_some_and(|validity| !validity[i])
+    }
+
+    fn null_count(&self) -> usize {
+        self.validity
+            .as_ref()
+            .map_or(0, |validity| validity.iter().filter(|valid| !**valid).count())
+    }
+
+    fn temporal(&self) -> Option<TemporalType> {
+        self.values.temporal()
+    }
+
+    fn levels(&self) -> Option<Vec<String>> {
+        self.values.levels()
+    }
+}
+
 impl DataFrame {
     /// Create a new empty DataFrame
     pub fn new() -> Self {
@@ -163,8 +242,49 @@ impl DataFrame {
     ///
     /// Panics if the column length doesn't match existing columns
     pub fn add_column(&mut self, name: impl Into<String>, column: impl Into<VectorValue>) {
+        self.insert_column(name.into(), Column::new(column.into(), None));
+    }
+
+    /// Add a column with missing values; `validity` is false where the
+    /// value is missing. Missing floats are stored as NaN.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the validity or column length doesn't match existing columns
+    pub fn add_column_with_validity(
+        &mut self,
+        name: impl Into<String>,
+        column: impl Into<VectorValue>,
+        validity: Vec<bool>,
+    ) {
         let name = name.into();
         let column = column.into();
+        if validity.len() != column.len() {
+            panic!(
+                "Column '{}' has length {} but its validity has length {}",
+                name,
+                column.len(),
+                validity.len()
+            );
+        }
+        self.insert_column(name, Column::new(column, Some(validity)));
+    }
+
+    /// Add a column from optional values, `None` marking missing values
+    ///
+    /// # Panics
+    ///
+    /// Panics if the column length doesn't match existing columns
+    pub fn add_optional_column<T: Default>(&mut self, name: impl Into<String>, values: Vec<Option<T>>)
+    where
+        Vec<T>: Into<VectorValue>,
+    {
+        let validity = values.iter().map(Option::is_some).collect();
+        let values: Vec<T> = values.into_iter().map(Option::unwrap_or_default).collect();
+        self.add_column_with_validity(name, values, validity);
+    }
+
+    fn insert_column(&mut self, name: String, column: Column) {
         let col_len = column.len();
 
         if self.columns.is_empty() {
@@ -240,30 +360,46 @@ impl DataFrame {
     pub fn column_data_type(&self, name: &str) -> Option<crate::data::ColumnDataType> {
         self.columns
             .get(name)
-            .map(|col| col.vtype().to_column_data_type())
+            .map(|col| col.values.vtype().to_column_data_type())
     }
 
     /// Append rows from another DataFrame to this one.
     /// Both DataFrames must have the same columns and types.
     /// # Panics
     ///
-    /// Panics if the columns or types don't match.
-    pub fn append(&mut self, other: DataFrame) {
+    /// Panics if the columns don't match. Returns an error if their types
+    /// or time zones don't.
+    pub fn append(&mut self, other: DataFrame) -> Result<(), PlotError> {
         for (name, other_col) in other.columns {
             let col = self
                 .columns
                 .get_mut(&name)
                 .expect(&format!("Column '{}' not found in DataFrame", name));
-            col.append(&other_col);
+            col.append(&other_col)?;
         }
         self.len += other.len;
+        Ok(())
+    }
+
+    /// The given rows of every column, keeping missing values missing
+    pub fn subset(&self, rows: &[usize]) -> DataFrame {
+        let mut subset = DataFrame::new();
+        for (name, column) in &self.columns {
+            let validity = column
+                .validity
+                .as_ref()
+                .map(|validity| rows.iter().map(|&i| validity[i]).collect());
+            subset.insert_column(name.clone(), Column::new(column.values.subset(rows), validity));
+        }
+        subset.len = rows.len();
+        subset
     }
 
     /// Add the columns and data from another DataFrame to this one.
     /// If a column already exists, the implementation will panic.
     pub fn extend(&mut self, other: DataFrame) {
         for (name, other_col) in other.columns.into_iter() {
-            self.add_column(name, other_col);
+            self.insert_column(name, other_col);
         }
     }
 }
@@ -273,8 +409,9 @@ impl From<&dyn DataSource> for DataFrame {
         let mut df = DataFrame::new();
         for col_name in data_source.column_names() {
             if let Some(col) = data_source.get(&col_name) {
-                let new_col = col.iter().to_vector();
-                df.add_column(col_name, new_col);
+                let new_col = col.to_vector();
+                let validity = (col.null_count() > 0).then(|| (0..col.len()).map(|i| !col.is_null(i)).collect());
+                df.insert_column(col_name, Column::new(new_col, validity));
             }
         }
         df
@@ -514,4 +651,133 @@ mod tests {
             _ => panic!("Expected Bool variant"),
         }
     }
+
+    #[test]
+    fn test_dataframe_missing_values() {
+        let mut df = DataFrame::new();
+        df.add_optional_column("x", vec![Some(1i64), None, Some(3)]);
+        df.add_optional_column("y", vec![Some(1.5), Some(2.5), None]);
+        df.add_column("label", vec!["a", "b", "c"]);
+
+        let x = df.get("x").unwrap();
+        assert_eq!(x.null_count(), 1);
+        assert!(x.is_null(1));
+        assert_eq!(x.missing(), Some(vec![false, true, false]));
+
+        let y: Vec<f64> = df.get("y").unwrap().iter_float().unwrap().collect();
+        assert!(y[2].is_nan());
+        assert_eq!(df.get("label").unwrap().missing(), None);
+
+        // Copies and appends keep track of the missing values
+        let mut copy = DataFrame::from(&df as &dyn DataSource);
+        assert_eq!(copy.get("x").unwrap().missing(), Some(vec![false, true, false]));
+        let mut more = DataFrame::new();
+        more.add_column("x", vec![4i64]);
+        more.add_column("y", vec![f64::NAN]);
+        more.add_column("label", vec!["d"]);
+        copy.append(more).unwrap();
+        assert_eq!(copy.get("x").unwrap().missing(), Some(vec![false, true, false, false]));
+        assert_eq!(copy.get("y").unwrap().missing(), Some(vec![false, false, true, true]));
+    }
+
+    #[test]
+    fn test_dataframe_categorical_column() {
+        use crate::data::Categorical;
+
+        let mut df = DataFrame::new();
+        df.add_column("size", Categorical::new(["large", "small", "large"], ["small", "medium", "large"]));
+
+        let size = df.get("size").unwrap();
+        assert_eq!(size.vtype(), VectorType::Str);
+        assert_eq!(size.levels(), Some(vec!["small".into(), "medium".into(), "large".into()]));
+        let labels: Vec<&str> = size.iter_str().unwrap().collect();
+        assert_eq!(labels, vec!["large", "small", "large"]);
+
+        // Subsets, copies and appends keep the levels
+        let VectorValue::Categorical(subset) = size.to_vector().subset(&[1]) else {
+            panic!("subset lost the levels");
+        };
+        assert_eq!(subset.labels().collect::<Vec<_>>(), vec!["small"]);
+        assert_eq!(subset.levels().len(), 3);
+
+        let mut copy = DataFrame::from(&df as &dyn DataSource);
+        let mut more = DataFrame::new();
+        more.add_column("size", Categorical::from_values(["huge", "medium"]));
+        copy.append(more).unwrap();
+        let size = copy.get("size").unwrap();
+        assert_eq!(
+            size.levels(),
+            Some(vec!["small".into(), "medium".into(), "large".into(), "huge".into()])
+        );
+        let labels: Vec<&str> = size.iter_str().unwrap().collect();
+        assert_eq!(labels, vec!["large", "small", "large", "huge", "medium"]);
+    }
+
+    #[test]
+    fn test_dataframe_categorical_missing_values() {
+        use crate::data::{Categorical, DiscreteValue, NA_LEVEL};
+
+        let size = Categorical::with_missing([Some("large"), None, Some("small")], ["small", "large"]);
+        let mut df = DataFrame::new();
+        df.add_column("size", size);
+
+        // Missing values are not a level, but missing rows
+        let size = df.get("size").unwrap();
+        assert_eq!(size.levels(), Some(vec!["small".into(), "large".into()]));
+        assert_eq!(size.missing(), Some(vec![false, true, false]));
+        let labels: Vec<&str> = size.iter_str().unwrap().collect();
+        assert_eq!(labels, vec!["large", NA_LEVEL, "small"]);
+        let values: Vec<DiscreteValue> = size.iter_discrete().unwrap().collect();
+        assert_eq!(values, vec![DiscreteValue::from("large"), DiscreteValue::Na, DiscreteValue::from("small")]);
+
+        // Copies, subsets and appends keep them missing
+        let VectorValue::Categorical(subset) = size.to_vector().subset(&[1, 2]) else {
+            panic!("subset lost the levels");
+        };
+        assert!(subset.is_missing(0) && !subset.is_missing(1));
+        let mut copy = DataFrame::from(&df as &dyn DataSource);
+        let mut more = DataFrame::new();
+        more.add_column("size", Categorical::with_missing([None, Some("huge")], Vec::<String>::new()));
+        copy.append(more).unwrap();
+        let size = copy.get("size").unwrap();
+        assert_eq!(size.levels(), Some(vec!["small".into(), "large".into(), "huge".into()]));
+        assert_eq!(size.missing(), Some(vec![false, true, false, true, false]));
+    }
+
+    #[test]
+    fn test_dataframe_temporal_columns() {
+        use crate::data::TemporalType;
+        use jiff::civil::date;
+
+        let mut df = DataFrame::new();
+        df.add_column("day", vec![date(1970, 1, 2), date(2024, 2, 29), date(1969, 12, 31)]);
+        let at: Vec<jiff::Zoned> = ["2024-03-01T12:00:00[Asia/Tokyo]", "2024-03-02T12:00:00[Asia/Tokyo]"]
+            .iter()
+            .map(|z| z.parse().unwrap())
+            .collect();
+        let mut times = DataFrame::new();
+        times.add_column("at", at);
+
+        let day = df.get("day").unwrap();
+        assert_eq!(day.vtype(), VectorType::Date);
+        assert_eq!(day.temporal(), Some(TemporalType::Date));
+        assert_eq!(day.iter_date().unwrap().collect::<Vec<_>>(), vec![1, 19_782, -1]);
+        assert_eq!(day.iter_int().unwrap().collect::<Vec<_>>(), vec![1, 19_782, -1]);
+
+        let at = times.get("at").unwrap();
+        assert_eq!(at.vtype(), VectorType::DateTime);
+        assert_eq!(at.temporal(), Some(TemporalType::DateTime { tz: Some("Asia/Tokyo".to_string()) }));
+        assert_eq!(at.iter_datetime().unwrap().collect::<Vec<_>>(), vec![1_709_262_000.0, 1_709_348_400.0]);
+
+        // Subsets and copies stay temporal
+        assert_eq!(df.get("day").unwrap().to_vector().subset(&[2]), VectorValue::Date(vec![-1]));
+        let copy = DataFrame::from(&times as &dyn DataSource);
+        assert_eq!(copy.get("at").unwrap().temporal(), at.temporal());
+
+        // Date-times in another time zone can't be appended
+        let mut copy = DataFrame::from(&times as &dyn DataSource);
+        let mut utc = DataFrame::new();
+        utc.add_column("at", vec![jiff::Timestamp::UNIX_EPOCH]);
+        assert!(matches!(copy.append(utc), Err(crate::error::PlotError::IncompatibleVectors { .. })));
+    }
 }
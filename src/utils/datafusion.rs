@@ -25,16 +25,36 @@
 //!     .geom_point();
 //! ```
 
-use crate::data::{GenericVector, StrVector, VectorIter};
+use crate::data::{GenericVector, StrVector, TemporalType, VectorIter};
 use arrow::array::{
-    Array, BooleanArray, DictionaryArray, Float32Array, Float64Array, Int8Array, Int16Array,
-    Int32Array, Int64Array, LargeStringArray, StringArray, StringViewArray, UInt8Array,
+    Array, ArrayIter, BooleanArray, Date32Array, Date64Array, DictionaryArray,
+    DurationMicrosecondArray, DurationMillisecondArray, DurationNanosecondArray,
+    DurationSecondArray, Float32Array, Float64Array, Int8Array, Int16Array, Int32Array,
+    Int64Array, LargeStringArray, StringArray, StringViewArray, TimestampMicrosecondArray,
+    TimestampMillisecondArray, TimestampNanosecondArray, TimestampSecondArray, UInt8Array,
     UInt16Array, UInt32Array, UInt64Array,
 };
-use arrow::datatypes::{DataType, Int32Type};
+use arrow::datatypes::{DataType, Int32Type, TimeUnit};
 use arrow::record_batch::RecordBatch;
+use std::collections::HashSet;
+
+
+/// Report nulls from the array's validity bitmap
+macro_rules! array_nulls {
+    () => {
+        fn is_null(&self, i: usize) -> bool {
+            arrow::array::Array::is_null(self, i)
+        }
+
+        fn null_count(&self) -> usize {
+            arrow::array::Array::null_count(self)
+        }
+    };
+}
 
 impl GenericVector for BooleanArray {
+    array_nulls!();
+
     fn len(&self) -> usize {
         arrow::array::Array::len(self)
     }
@@ -53,6 +73,8 @@ impl GenericVector for BooleanArray {
 }
 
 impl GenericVector for Int64Array {
+    array_nulls!();
+
     fn len(&self) -> usize {
         arrow::array::Array::len(self)
     }
@@ -76,6 +98,8 @@ impl GenericVector for Int64Array {
 // by value (with casting). Similarly for Float32Array with FloatVector.
 
 impl GenericVector for Int8Array {
+    array_nulls!();
+
     fn len(&self) -> usize {
         arrow::array::Array::len(self)
     }
@@ -94,6 +118,8 @@ impl GenericVector for Int8Array {
 }
 
 impl GenericVector for Int16Array {
+    array_nulls!();
+
     fn len(&self) -> usize {
         arrow::array::Array::len(self)
     }
@@ -112,6 +138,8 @@ impl GenericVector for Int16Array {
 }
 
 impl GenericVector for Int32Array {
+    array_nulls!();
+
     fn len(&self) -> usize {
         arrow::array::Array::len(self)
     }
@@ -130,6 +158,8 @@ impl GenericVector for Int32Array {
 }
 
 impl GenericVector for UInt8Array {
+    array_nulls!();
+
     fn len(&self) -> usize {
         arrow::array::Array::len(self)
     }
@@ -148,6 +178,8 @@ impl GenericVector for UInt8Array {
 }
 
 impl GenericVector for UInt16Array {
+    array_nulls!();
+
     fn len(&self) -> usize {
         arrow::array::Array::len(self)
     }
@@ -166,6 +198,8 @@ impl GenericVector for UInt16Array {
 }
 
 impl GenericVector for UInt32Array {
+    array_nulls!();
+
     fn len(&self) -> usize {
         arrow::array::Array::len(self)
     }
@@ -184,6 +218,8 @@ impl GenericVector for UInt32Array {
 }
 
 impl GenericVector for UInt64Array {
+    array_nulls!();
+
     fn len(&self) -> usize {
         arrow::array::Array::len(self)
     }
@@ -204,6 +240,8 @@ impl GenericVector for UInt64Array {
 }
 
 impl GenericVector for Float32Array {
+    array_nulls!();
+
     fn len(&self) -> usize {
         arrow::array::Array::len(self)
     }
@@ -213,15 +251,17 @@ impl GenericVector for Float32Array {
     }
 
     fn iter(&self) -> VectorIter<'_> {
-        VectorIter::Float(Box::new(self.values().iter().map(|&v| v as f64)))
+        VectorIter::Float(Box::new(ArrayIter::new(self).map(|v| v.map_or(f64::NAN, |v| v as f64))))
     }
 
     fn iter_float(&self) -> Option<Box<dyn Iterator<Item = f64> + '_>> {
-        Some(Box::new(self.values().iter().map(|&v| v as f64)))
+        Some(Box::new(ArrayIter::new(self).map(|v| v.map_or(f64::NAN, |v| v as f64))))
     }
 }
 
 impl GenericVector for Float64Array {
+    array_nulls!();
+
     fn len(&self) -> usize {
         arrow::array::Array::len(self)
     }
@@ -231,15 +271,125 @@ impl GenericVector for Float64Array {
     }
 
     fn iter(&self) -> VectorIter<'_> {
-        VectorIter::Float(Box::new(self.values().iter().copied()))
+        VectorIter::Float(Box::new(ArrayIter::new(self).map(|v| v.unwrap_or(f64::NAN))))
     }
 
     fn iter_float(&self) -> Option<Box<dyn Iterator<Item = f64> + '_>> {
+        Some(Box::new(ArrayIter::new(self).map(|v| v.unwrap_or(f64::NAN))))
+    }
+}
+
+/// Date32 holds days since the epoch, which is what date scales expect
+impl GenericVector for Date32Array {
+    array_nulls!();
+
+    fn len(&self) -> usize {
+        arrow::array::Array::len(self)
+    }
+
+    fn vtype(&self) -> crate::data::VectorType {
+        crate::data::VectorType::Date
+    }
+
+    fn iter(&self) -> VectorIter<'_> {
+        VectorIter::Int(Box::new(self.values().iter().map(|&v| v as i64)))
+    }
+
+    fn iter_int(&self) -> Option<Box<dyn Iterator<Item = i64> + '_>> {
+        Some(Box::new(self.values().iter().map(|&v| v as i64)))
+    }
+
+    fn iter_date(&self) -> Option<Box<dyn Iterator<Item = i32> + '_>> {
         Some(Box::new(self.values().iter().copied()))
     }
 }
 
+const MILLIS_PER_DAY: i64 = 86_400_000;
+
+/// Date64 holds milliseconds since the epoch, read as whole days
+impl GenericVector for Date64Array {
+    array_nulls!();
+
+    fn len(&self) -> usize {
+        arrow::array::Array::len(self)
+    }
+
+    fn vtype(&self) -> crate::data::VectorType {
+        crate::data::VectorType::Date
+    }
+
+    fn iter(&self) -> VectorIter<'_> {
+        VectorIter::Int(Box::new(self.values().iter().map(|&v| v.div_euclid(MILLIS_PER_DAY))))
+    }
+
+    fn iter_int(&self) -> Option<Box<dyn Iterator<Item = i64> + '_>> {
+        Some(Box::new(self.values().iter().map(|&v| v.div_euclid(MILLIS_PER_DAY))))
+    }
+}
+
+/// Time zone of a timestamp column, if it carries one
+fn timestamp_tz(data_type: &DataType) -> Option<String> {
+    match data_type {
+        DataType::Timestamp(_, tz) => tz.as_ref().map(|tz| tz.to_string()),
+        _ => None,
+    }
+}
+
+/// Timestamps and durations of any unit are exposed as float seconds
+macro_rules! impl_seconds_vector {
+    ($array:ty, $per_second:expr, $vtype:ident, $temporal:expr) => {
+        impl GenericVector for $array {
+            array_nulls!();
+
+            fn len(&self) -> usize {
+                arrow::array::Array::len(self)
+            }
+
+            fn vtype(&self) -> crate::data::VectorType {
+                crate::data::VectorType::$vtype
+            }
+
+            fn iter(&self) -> VectorIter<'_> {
+                VectorIter::Float(Box::new(
+                    ArrayIter::new(self).map(|v| v.map_or(f64::NAN, |v| v as f64 / $per_second)),
+                ))
+            }
+
+            fn iter_float(&self) -> Option<Box<dyn Iterator<Item = f64> + '_>> {
+                Some(Box::new(
+                    ArrayIter::new(self).map(|v| v.map_or(f64::NAN, |v| v as f64 / $per_second)),
+                ))
+            }
+
+            fn temporal(&self) -> Option<TemporalType> {
+                Some($temporal(self.data_type()))
+            }
+        }
+    };
+}
+
+fn datetime(data_type: &DataType) -> TemporalType {
+    TemporalType::DateTime {
+        tz: timestamp_tz(data_type),
+    }
+}
+
+fn duration(_: &DataType) -> TemporalType {
+    TemporalType::Duration
+}
+
+impl_seconds_vector!(TimestampSecondArray, 1.0, DateTime, datetime);
+impl_seconds_vector!(TimestampMillisecondArray, 1e3, DateTime, datetime);
+impl_seconds_vector!(TimestampMicrosecondArray, 1e6, DateTime, datetime);
+impl_seconds_vector!(TimestampNanosecondArray, 1e9, DateTime, datetime);
+impl_seconds_vector!(DurationSecondArray, 1.0, Float, duration);
+impl_seconds_vector!(DurationMillisecondArray, 1e3, Float, duration);
+impl_seconds_vector!(DurationMicrosecondArray, 1e6, Float, duration);
+impl_seconds_vector!(DurationNanosecondArray, 1e9, Float, duration);
+
 impl GenericVector for StringArray {
+    array_nulls!();
+
     fn len(&self) -> usize {
         arrow::array::Array::len(self)
     }
@@ -309,6 +459,8 @@ impl StrVector for StringArray {
 }
 
 impl GenericVector for LargeStringArray {
+    array_nulls!();
+
     fn len(&self) -> usize {
         arrow::array::Array::len(self)
     }
@@ -378,6 +530,8 @@ impl StrVector for LargeStringArray {
 }
 
 impl GenericVector for StringViewArray {
+    array_nulls!();
+
     fn len(&self) -> usize {
         arrow::array::Array::len(self)
     }
@@ -448,10 +602,35 @@ impl StrVector for StringViewArray {
 
 // Dictionary array implementations for Utf8 values
 impl GenericVector for DictionaryArray<Int32Type> {
+    array_nulls!();
+
     fn len(&self) -> usize {
         arrow::array::Array::len(self)
     }
 
+    /// The dictionary values are the levels, in order
+    fn levels(&self) -> Option<Vec<String>> {
+        let values = self.values().as_any();
+        let levels: Vec<Option<&str>> = if let Some(values) = values.downcast_ref::<StringArray>() {
+            ArrayIter::new(values).collect()
+        } else if let Some(values) = values.downcast_ref::<LargeStringArray>() {
+            ArrayIter::new(values).collect()
+        } else if let Some(values) = values.downcast_ref::<StringViewArray>() {
+            ArrayIter::new(values).collect()
+        } else {
+            return None;
+        };
+        // Dictionaries may repeat a value; each level is listed once
+        let mut seen = HashSet::new();
+        Some(
+            levels
+                .into_iter()
+                .map(|level| level.unwrap_or("").to_string())
+                .filter(|level| seen.insert(level.clone()))
+                .collect(),
+        )
+    }
+
     fn vtype(&self) -> crate::data::VectorType {
         // Dictionary arrays with string values are treated as strings
         crate::data::VectorType::Str
@@ -525,9 +704,10 @@ impl<'a> Iterator for DictUtf8ArrayIter<'a> {
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.index < arrow::array::Array::len(self.dict_array) {
-            let key = self.dict_array.key(self.index)?;
+            // Null keys read as "" so rows stay aligned
+            let key = self.dict_array.key(self.index);
             self.index += 1;
-            Some(self.values_array.value(key as usize))
+            Some(key.map_or("", |key| self.values_array.value(key)))
         } else {
             None
         }
@@ -557,9 +737,10 @@ impl<'a> Iterator for DictLargeUtf8ArrayIter<'a> {
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.index < arrow::array::Array::len(self.dict_array) {
-            let key = self.dict_array.key(self.index)?;
+            // Null keys read as "" so rows stay aligned
+            let key = self.dict_array.key(self.index);
             self.index += 1;
-            Some(self.values_array.value(key as usize))
+            Some(key.map_or("", |key| self.values_array.value(key)))
         } else {
             None
         }
@@ -589,9 +770,10 @@ impl<'a> Iterator for DictUtf8ViewArrayIter<'a> {
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.index < arrow::array::Array::len(self.dict_array) {
-            let key = self.dict_array.key(self.index)?;
+            // Null keys read as "" so rows stay aligned
+            let key = self.dict_array.key(self.index);
             self.index += 1;
-            Some(self.values_array.value(key as usize))
+            Some(key.map_or("", |key| self.values_array.value(key)))
         } else {
             None
         }
@@ -750,6 +932,46 @@ impl crate::data::DataSource for RecordBatch {
                 .as_any()
                 .downcast_ref::<StringViewArray>()
                 .map(|arr| arr as &dyn crate::data::GenericVector),
+            DataType::Date32 => column
+                .as_any()
+                .downcast_ref::<Date32Array>()
+                .map(|arr| arr as &dyn crate::data::GenericVector),
+            DataType::Date64 => column
+                .as_any()
+                .downcast_ref::<Date64Array>()
+                .map(|arr| arr as &dyn crate::data::GenericVector),
+            DataType::Timestamp(TimeUnit::Second, _) => column
+                .as_any()
+                .downcast_ref::<TimestampSecondArray>()
+                .map(|arr| arr as &dyn crate::data::GenericVector),
+            DataType::Timestamp(TimeUnit::Millisecond, _) => column
+                .as_any()
+                .downcast_ref::<TimestampMillisecondArray>()
+                .map(|arr| arr as &dyn crate::data::GenericVector),
+            DataType::Timestamp(TimeUnit::Microsecond, _) => column
+                .as_any()
+                .downcast_ref::<TimestampMicrosecondArray>()
+                .map(|arr| arr as &dyn crate::data::GenericVector),
+            DataType::Timestamp(TimeUnit::Nanosecond, _) => column
+                .as_any()
+                .downcast_ref::<TimestampNanosecondArray>()
+                .map(|arr| arr as &dyn crate::data::GenericVector),
+            DataType::Duration(TimeUnit::Second) => column
+                .as_any()
+                .downcast_ref::<DurationSecondArray>()
+                .map(|arr| arr as &dyn crate::data::GenericVector),
+            DataType::Duration(TimeUnit::Millisecond) => column
+                .as_any()
+                .downcast_ref::<DurationMillisecondArray>()
+                .map(|arr| arr as &dyn crate::data::GenericVector),
+            DataType::Duration(TimeUnit::Microsecond) => column
+                .as_any()
+                .downcast_ref::<DurationMicrosecondArray>()
+                .map(|arr| arr as &dyn crate::data::GenericVector),
+            DataType::Duration(TimeUnit::Nanosecond) => column
+                .as_any()
+                .downcast_ref::<DurationNanosecondArray>()
+                .map(|arr| arr as &dyn crate::data::GenericVector),
             DataType::Dictionary(key_type, _value_type) => {
                 if matches!(key_type.as_ref(), DataType::Int32) {
                     column
@@ -1159,6 +1381,63 @@ mod tests {
         assert_eq!(values_vec, vec!["red", "green", "red", "blue", "green"]);
     }
 
+    #[test]
+    fn test_dictionary_levels() {
+        use crate::data::VectorValue;
+        use arrow::array::Int32Array;
+
+        // Dictionary values keep their order as levels, used or not, and
+        // null keys read as NA
+        let keys = Int32Array::from(vec![Some(2), Some(0), None, Some(2)]);
+        let values = StringArray::from(vec!["low", "mid", "high"]);
+        let dict_array = DictionaryArray::<Int32Type>::try_new(keys, Arc::new(values)).unwrap();
+
+        assert_eq!(dict_array.levels(), Some(vec!["low".into(), "mid".into(), "high".into()]));
+        let VectorValue::Categorical(categorical) = dict_array.to_vector() else {
+            panic!("dictionary should convert to a categorical");
+        };
+        assert_eq!(categorical.levels(), ["low", "mid", "high", "NA"]);
+        assert_eq!(categorical.labels().collect::<Vec<_>>(), vec!["high", "low", "NA", "high"]);
+    }
+
+    #[test]
+    fn test_temporal_columns() {
+        use crate::data::{TemporalType, VectorType, VectorValue};
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("day", DataType::Date32, false),
+            Field::new("day64", DataType::Date64, false),
+            Field::new("at", DataType::Timestamp(TimeUnit::Second, Some("Europe/Paris".into())), false),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(Date32Array::from(vec![0, 19_000])) as ArrayRef,
+                Arc::new(Date64Array::from(vec![-1, 19_000 * 86_400_000 + 1])) as ArrayRef,
+                Arc::new(TimestampSecondArray::from(vec![0, 3_600]).with_timezone("Europe/Paris")) as ArrayRef,
+            ],
+        )
+        .unwrap();
+
+        let day = batch.get("day").unwrap();
+        assert_eq!(day.vtype(), VectorType::Date);
+        assert_eq!(day.temporal(), Some(TemporalType::Date));
+        assert_eq!(day.iter_date().unwrap().collect::<Vec<_>>(), vec![0, 19_000]);
+
+        // Date64 milliseconds round down to whole days
+        let day64 = batch.get("day64").unwrap();
+        assert_eq!(day64.iter_date().unwrap().collect::<Vec<_>>(), vec![-1, 19_000]);
+
+        // Time stamps keep their zone when copied
+        let at = batch.get("at").unwrap();
+        assert_eq!(at.vtype(), VectorType::DateTime);
+        assert_eq!(at.iter_datetime().unwrap().collect::<Vec<_>>(), vec![0.0, 3_600.0]);
+        assert_eq!(
+            at.to_vector(),
+            VectorValue::DateTime(vec![0.0, 3_600.0], Some("Europe/Paris".to_string()))
+        );
+    }
+
     #[test]
     fn test_convert_dictionary_large_utf8_column() {
         use arrow::array::Int32Array;
@@ -1378,4 +1657,42 @@ mod tests {
         let bool_values: Vec<bool> = bool_col.iter_bool().unwrap().collect();
         assert_eq!(bool_values, vec![true, false, true]);
     }
+
+    #[test]
+    fn test_null_values() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("x", DataType::Int64, true),
+            Field::new("y", DataType::Float64, true),
+            Field::new("label", DataType::Utf8, true),
+        ]));
+
+        let int_array = Int64Array::from(vec![Some(1), None, Some(3)]);
+        let float_array = Float64Array::from(vec![Some(1.5), Some(2.5), None]);
+        let string_array = StringArray::from(vec![None, Some("b"), Some("c")]);
+
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(int_array) as ArrayRef,
+                Arc::new(float_array) as ArrayRef,
+                Arc::new(string_array) as ArrayRef,
+            ],
+        )
+        .unwrap();
+
+        let x = batch.get("x").unwrap();
+        assert_eq!(x.null_count(), 1);
+        assert!(x.is_null(1));
+        assert_eq!(x.missing(), Some(vec![false, true, false]));
+
+        let y = batch.get("y").unwrap();
+        let y_values: Vec<f64> = y.iter_float().unwrap().collect();
+        assert_eq!(&y_values[..2], &[1.5, 2.5]);
+        assert!(y_values[2].is_nan());
+        assert_eq!(y.missing(), Some(vec![false, false, true]));
+
+        let label = batch.get("label").unwrap();
+        assert_eq!(label.iter_str().unwrap().count(), 3);
+        assert_eq!(label.missing(), Some(vec![true, false, false]));
+    }
 }
@@ -24,7 +24,7 @@
 //!     .geom_point();
 //! ```
 
-use crate::data::{DataSource, GenericVector, VectorIter};
+use crate::data::{DataSource, GenericVector, TemporalType, VectorIter};
 use polars::prelude::*;
 
 impl DataSource for DataFrame {
@@ -64,6 +64,27 @@ impl GenericVector for Series {
         self.chunk_lengths().sum()
     }
 
+    // Null entries read as 0, NaN, "" or false; these report where they are
+    fn is_null(&self, i: usize) -> bool {
+        matches!(self.get(i), Ok(AnyValue::Null))
+    }
+
+    fn null_count(&self) -> usize {
+        // Through the series itself, not this trait
+        (**self).null_count()
+    }
+
+    // Enums list their categories in order; plain categoricals compare
+    // lexically in polars, which is how discrete scales sort anyway
+    fn levels(&self) -> Option<Vec<String>> {
+        match self.dtype() {
+            DataType::Enum(categories, _) => {
+                Some(categories.categories().values_iter().map(|level| level.to_string()).collect())
+            }
+            _ => None,
+        }
+    }
+
     fn vtype(&self) -> crate::data::VectorType {
         use polars::datatypes::DataType as PolarsDataType;
         match self.dtype() {
@@ -72,7 +93,12 @@ impl GenericVector for Series {
                 crate::data::VectorType::Int
             }
             PolarsDataType::Float32 | PolarsDataType::Float64 => crate::data::VectorType::Float,
-            PolarsDataType::String => crate::data::VectorType::Str,
+            PolarsDataType::Date => crate::data::VectorType::Date,
+            PolarsDataType::Datetime(_, _) => crate::data::VectorType::DateTime,
+            PolarsDataType::Duration(_) => crate::data::VectorType::Float,
+            PolarsDataType::String | PolarsDataType::Categorical(..) | PolarsDataType::Enum(..) => {
+                crate::data::VectorType::Str
+            }
             PolarsDataType::Boolean => crate::data::VectorType::Bool,
             _ => {
                 // Default to string for unsupported types
@@ -144,14 +170,52 @@ impl GenericVector for Series {
             // Float types
             PolarsDataType::Float64 => {
                 if let Ok(ca) = self.f64() {
-                    VectorIter::Float(Box::new(ca.into_iter().map(|opt| opt.unwrap_or(0.0))))
+                    VectorIter::Float(Box::new(ca.into_iter().map(|opt| opt.unwrap_or(f64::NAN))))
                 } else {
                     VectorIter::Float(Box::new(std::iter::empty()))
                 }
             }
             PolarsDataType::Float32 => {
                 if let Ok(ca) = self.f32() {
-                    VectorIter::Float(Box::new(ca.into_iter().map(|opt| opt.unwrap_or(0.0) as f64)))
+                    VectorIter::Float(Box::new(ca.into_iter().map(|opt| opt.map_or(f64::NAN, |v| v as f64))))
+                } else {
+                    VectorIter::Float(Box::new(std::iter::empty()))
+                }
+            }
+            // Dates are days since the epoch
+            PolarsDataType::Date => {
+                if let Ok(ca) = self.date() {
+                    VectorIter::Int(Box::new(ca.physical().into_iter().map(|opt| opt.unwrap_or(0) as i64)))
+                } else {
+                    VectorIter::Int(Box::new(std::iter::empty()))
+                }
+            }
+            // Date-times are normalised to seconds since the epoch
+            PolarsDataType::Datetime(unit, _) => {
+                let per_second = match unit {
+                    TimeUnit::Milliseconds => 1e3,
+                    TimeUnit::Microseconds => 1e6,
+                    TimeUnit::Nanoseconds => 1e9,
+                };
+                if let Ok(ca) = self.datetime() {
+                    VectorIter::Float(Box::new(
+                        ca.physical().into_iter().map(move |opt| opt.map_or(f64::NAN, |v| v as f64 / per_second)),
+                    ))
+                } else {
+                    VectorIter::Float(Box::new(std::iter::empty()))
+                }
+            }
+            // Durations are normalised to seconds
+            PolarsDataType::Duration(unit) => {
+                let per_second = match unit {
+                    TimeUnit::Milliseconds => 1e3,
+                    TimeUnit::Microseconds => 1e6,
+                    TimeUnit::Nanoseconds => 1e9,
+                };
+                if let Ok(ca) = self.duration() {
+                    VectorIter::Float(Box::new(
+                        ca.physical().into_iter().map(move |opt| opt.map_or(f64::NAN, |v| v as f64 / per_second)),
+                    ))
                 } else {
                     VectorIter::Float(Box::new(std::iter::empty()))
                 }
@@ -164,6 +228,8 @@ impl GenericVector for Series {
                     VectorIter::Str(Box::new(std::iter::empty()))
                 }
             }
+            // Categoricals and enums read as their labels
+            PolarsDataType::Categorical(..) | PolarsDataType::Enum(..) => VectorIter::Str(Box::new(category_labels(self))),
             // Boolean type
             PolarsDataType::Boolean => {
                 if let Ok(ca) = self.bool() {
@@ -212,6 +278,10 @@ impl GenericVector for Series {
                 let ca = self.u8().ok()?;
                 Some(Box::new(ca.into_iter().map(|opt| opt.unwrap_or(0) as i64)))
             }
+            PolarsDataType::Date => match GenericVector::iter(self) {
+                VectorIter::Int(iter) => Some(iter),
+                _ => None,
+            },
             _ => None,
         }
     }
@@ -221,12 +291,16 @@ impl GenericVector for Series {
         match self.dtype() {
             PolarsDataType::Float64 => {
                 let ca = self.f64().ok()?;
-                Some(Box::new(ca.into_iter().map(|opt| opt.unwrap_or(0.0))))
+                Some(Box::new(ca.into_iter().map(|opt| opt.unwrap_or(f64::NAN))))
             }
             PolarsDataType::Float32 => {
                 let ca = self.f32().ok()?;
-                Some(Box::new(ca.into_iter().map(|opt| opt.unwrap_or(0.0) as f64)))
+                Some(Box::new(ca.into_iter().map(|opt| opt.map_or(f64::NAN, |v| v as f64))))
             }
+            PolarsDataType::Datetime(_, _) | PolarsDataType::Duration(_) => match GenericVector::iter(self) {
+                VectorIter::Float(iter) => Some(iter),
+                _ => None,
+            },
             _ => None,
         }
     }
@@ -238,6 +312,7 @@ impl GenericVector for Series {
                 let ca = self.str().ok()?;
                 Some(Box::new(ca.into_iter().map(|opt| opt.unwrap_or(""))))
             }
+            PolarsDataType::Categorical(..) | PolarsDataType::Enum(..) => Some(Box::new(category_labels(self))),
             _ => None,
         }
     }
@@ -252,6 +327,27 @@ impl GenericVector for Series {
             _ => None,
         }
     }
+    fn temporal(&self) -> Option<TemporalType> {
+        use polars::datatypes::DataType as PolarsDataType;
+        match self.dtype() {
+            PolarsDataType::Date => Some(TemporalType::Date),
+            PolarsDataType::Datetime(_, tz) => Some(TemporalType::DateTime {
+                tz: tz.as_ref().map(|tz| tz.to_string()),
+            }),
+            PolarsDataType::Duration(_) => Some(TemporalType::Duration),
+            _ => None,
+        }
+    }
+}
+
+/// Label of each value of a categorical or enum series ("" for nulls)
+fn category_labels(series: &Series) -> impl Iterator<Item = &str> + '_ {
+    (0..GenericVector::len(series)).map(move |i| match series.get(i) {
+        Ok(AnyValue::Categorical(cat, mapping)) | Ok(AnyValue::Enum(cat, mapping)) => {
+            mapping.cat_to_str(cat).unwrap_or("")
+        }
+        _ => "",
+    })
 }
 
 #[cfg(test)]
@@ -1,10 +1,39 @@
+use std::collections::HashSet;
+
 use crate::data::{DiscreteType, DiscreteValue, IStr};
 
+/// How a [`DiscreteSet`] orders its levels
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum LevelOrder {
+    /// Integers, then strings, then booleans, each sorted ascending
+    #[default]
+    Sorted,
+    /// The order in which levels first appear in the data
+    Appearance,
+    /// Ascending by a key per level; levels without a key (or with a NaN
+    /// key) follow in sorted order
+    ByKey(Vec<(DiscreteValue, f64)>),
+}
+
 #[derive(Debug, Clone)]
 pub struct DiscreteSet {
+    // Sorted, deduplicated storage used for lookups
     ints: Vec<i64>,
     strings: Vec<IStr>,
     bools: Vec<bool>,
+    // Whether the level for missing values is present; it is stored last
+    na: bool,
+    // Levels in the order they were added, deduplicated on build
+    seen: Vec<DiscreteValue>,
+    limits: Option<Vec<DiscreteValue>>,
+    order: LevelOrder,
+    reverse: bool,
+    labels: Option<Vec<String>>,
+    relabel: Vec<(DiscreteValue, String)>,
+    // Display position -> storage index, and its inverse; empty when the
+    // display order is the storage order
+    display: Vec<usize>,
+    rank: Vec<usize>,
 }
 
 impl DiscreteSet {
@@ -13,82 +42,227 @@ impl DiscreteSet {
             ints: Vec::new(),
             strings: Vec::new(),
             bools: Vec::new(),
+            na: false,
+            seen: Vec::new(),
+            limits: None,
+            order: LevelOrder::default(),
+            reverse: false,
+            labels: None,
+            relabel: Vec::new(),
+            display: Vec::new(),
+            rank: Vec::new(),
         }
     }
 
     pub fn len(&self) -> usize {
-        self.ints.len() + self.strings.len() + self.bools.len()
+        self.ints.len() + self.strings.len() + self.bools.len() + usize::from(self.na)
     }
 
     pub fn add<T: DiscreteType>(&mut self, value: &T) {
-        let value = DiscreteValue::from(value.to_primitive());
-        match value {
+        if let Some(value) = value.to_discrete() {
+            self.push(value);
+        }
+    }
+
+    fn push(&mut self, value: DiscreteValue) {
+        match &value {
             DiscreteValue::Int(v) => {
-                self.ints.push(v);
+                self.ints.push(*v);
             }
             DiscreteValue::Str(v) => {
-                self.strings.push(v);
+                self.strings.push(*v);
             }
             DiscreteValue::Bool(v) => {
-                self.bools.push(v);
+                self.bools.push(*v);
+            }
+            DiscreteValue::Na => {
+                self.na = true;
             }
         }
+        self.seen.push(value);
     }
 
     pub fn build(&mut self) {
+        if let Some(limits) = &self.limits {
+            // Limits fix the levels regardless of the data
+            self.ints.clear();
+            self.strings.clear();
+            self.bools.clear();
+            self.na = false;
+            for level in limits.clone() {
+                match level {
+                    DiscreteValue::Int(v) => self.ints.push(v),
+                    DiscreteValue::Str(v) => self.strings.push(v),
+                    DiscreteValue::Bool(v) => self.bools.push(v),
+                    DiscreteValue::Na => self.na = true,
+                }
+            }
+        }
         self.ints.sort();
         self.ints.dedup();
         self.strings.sort();
         self.strings.dedup();
         self.bools.sort();
         self.bools.dedup();
+
+        let mut unique = HashSet::new();
+        self.seen.retain(|level| unique.insert(level.clone()));
+
+        self.build_order();
     }
 
-    pub fn contains<T: DiscreteType>(&self, value: &T) -> bool {
-        let value = DiscreteValue::from(value.to_primitive());
-        match value {
-            DiscreteValue::Int(v) => {
-                let index = Self::lower_bound(&self.ints, &v);
-                index < self.ints.len() && self.ints[index] == v
+    fn build_order(&mut self) {
+        let mut levels: Vec<usize> = match (&self.limits, &self.order) {
+            (Some(limits), _) => {
+                let mut unique = HashSet::new();
+                limits
+                    .iter()
+                    .filter(|level| unique.insert(*level))
+                    .filter_map(|level| self.index_of(level))
+                    .collect()
             }
-            DiscreteValue::Str(v) => {
-                let index = Self::lower_bound(&self.strings, &v);
-                index < self.strings.len() && self.strings[index] == v
+            (None, LevelOrder::Sorted) => (0..self.len()).collect(),
+            (None, LevelOrder::Appearance) => {
+                self.seen.iter().filter_map(|level| self.index_of(level)).collect()
             }
-            DiscreteValue::Bool(v) => {
-                let index = Self::lower_bound(&self.bools, &v);
-                index < self.bools.len() && self.bools[index] == v
+            (None, LevelOrder::ByKey(keys)) => {
+                let key = |index: usize| {
+                    let level = self.stored_at(index)?;
+                    keys.iter()
+                        .find(|(keyed, _)| *keyed == level)
+                        .map(|(_, key)| *key)
+                        .filter(|key| !key.is_nan())
+                };
+                let mut levels: Vec<usize> = (0..self.len()).collect();
+                levels.sort_by(|&a, &b| match (key(a), key(b)) {
+                    (Some(a), Some(b)) => a.total_cmp(&b),
+                    (Some(_), None) => std::cmp::Ordering::Less,
+                    (None, Some(_)) => std::cmp::Ordering::Greater,
+                    (None, None) => a.cmp(&b),
+                });
+                levels
             }
+        };
+        if self.reverse {
+            levels.reverse();
+        }
+        // The level for missing values goes last, unless the limits place it
+        if self.limits.is_none()
+            && let Some(na) = self.index_of(&DiscreteValue::na())
+        {
+            levels.retain(|&index| index != na);
+            levels.push(na);
+        }
+
+        if levels.iter().enumerate().all(|(i, &index)| i == index) {
+            self.display.clear();
+            self.rank.clear();
+            return;
+        }
+        let mut rank = vec![0; levels.len()];
+        for (position, &index) in levels.iter().enumerate() {
+            rank[index] = position;
         }
+        self.display = levels;
+        self.rank = rank;
+    }
+
+    /// Keep only these levels, in this order, whatever the data contains
+    pub fn set_limits(&mut self, limits: Vec<DiscreteValue>) {
+        self.limits = Some(limits);
+        self.build();
+    }
+
+    pub fn limits(&self) -> Option<&[DiscreteValue]> {
+        self.limits.as_deref()
+    }
+
+    /// Set how levels are ordered (ignored when limits are set)
+    pub fn set_order(&mut self, order: LevelOrder) {
+        self.order = order;
+        self.build();
+    }
+
+    /// Reverse the level order
+    pub fn set_reverse(&mut self, reverse: bool) {
+        self.reverse = reverse;
+        self.build();
+    }
+
+    /// Label the levels in display order
+    pub fn set_labels(&mut self, labels: Vec<String>) {
+        self.labels = Some(labels);
+    }
+
+    /// Label one level, overriding the positional labels
+    pub fn relabel(&mut self, level: DiscreteValue, label: String) {
+        self.relabel.retain(|(other, _)| *other != level);
+        self.relabel.push((level, label));
+    }
+
+    /// Number of positional labels, if any were set
+    pub fn labels_len(&self) -> Option<usize> {
+        self.labels.as_ref().map(Vec::len)
+    }
+
+    /// Get the label of the level at a display position
+    pub fn label_at(&self, index: usize) -> Option<String> {
+        let value = self.get_at(index)?;
+        if let Some((_, label)) = self.relabel.iter().find(|(level, _)| *level == value) {
+            return Some(label.clone());
+        }
+        match self.labels.as_ref().and_then(|labels| labels.get(index)) {
+            Some(label) => Some(label.clone()),
+            None => Some(value.to_string()),
+        }
+    }
+
+    pub fn contains<T: DiscreteType>(&self, value: &T) -> bool {
+        value.to_discrete().is_some_and(|value| self.index_of(&value).is_some())
+    }
+
+    /// Whether a level is in the set
+    pub fn contains_value(&self, value: &DiscreteValue) -> bool {
+        self.index_of(value).is_some()
     }
 
     pub fn ordinal<T: DiscreteType>(&self, value: &T) -> Option<usize> {
-        let value = DiscreteValue::from(value.to_primitive());
+        let index = self.index_of(&value.to_discrete()?)?;
+        if self.rank.is_empty() {
+            Some(index)
+        } else {
+            self.rank.get(index).copied()
+        }
+    }
+
+    // Position of a level in the sorted storage
+    fn index_of(&self, value: &DiscreteValue) -> Option<usize> {
         match value {
             DiscreteValue::Int(v) => {
-                let index = Self::lower_bound(&self.ints, &v);
-                if index < self.ints.len() && self.ints[index] == v {
+                let index = Self::lower_bound(&self.ints, v);
+                if index < self.ints.len() && self.ints[index] == *v {
                     Some(index)
                 } else {
                     None
                 }
             }
             DiscreteValue::Str(v) => {
-                let index = Self::lower_bound(&self.strings, &v);
-                if index < self.strings.len() && self.strings[index] == v {
+                let index = Self::lower_bound(&self.strings, v);
+                if index < self.strings.len() && self.strings[index] == *v {
                     Some(self.ints.len() + index)
                 } else {
                     None
                 }
             }
             DiscreteValue::Bool(v) => {
-                let index = Self::lower_bound(&self.bools, &v);
-                if index < self.bools.len() && self.bools[index] == v {
+                let index = Self::lower_bound(&self.bools, v);
+                if index < self.bools.len() && self.bools[index] == *v {
                     Some(self.ints.len() + self.strings.len() + index)
                 } else {
                     None
                 }
             }
+            DiscreteValue::Na => self.na.then(|| self.len() - 1),
         }
     }
 
@@ -108,25 +282,34 @@ impl DiscreteSet {
                 self.bools.push(*v);
             }
         }
+        self.na |= other.na;
+        self.seen.extend(other.seen.iter().cloned());
         self.build();
     }
+
+    /// Iterate over the levels in display order
     pub fn iter(&self) -> impl Iterator<Item = DiscreteValue> + '_ {
-        self.ints
-            .iter()
-            .cloned()
-            .map(DiscreteValue::Int)
-            .chain(self.strings.iter().cloned().map(DiscreteValue::Str))
-            .chain(self.bools.iter().cloned().map(DiscreteValue::Bool))
+        (0..self.len()).filter_map(|index| self.get_at(index))
     }
 
     /// Get the value at a specific ordinal position (0-indexed)
     pub fn get_at(&self, index: usize) -> Option<DiscreteValue> {
+        if self.display.is_empty() {
+            self.stored_at(index)
+        } else {
+            self.stored_at(*self.display.get(index)?)
+        }
+    }
+
+    fn stored_at(&self, index: usize) -> Option<DiscreteValue> {
         if index < self.ints.len() {
             Some(DiscreteValue::Int(self.ints[index]))
         } else if index < self.ints.len() + self.strings.len() {
             Some(DiscreteValue::Str(self.strings[index - self.ints.len()].clone()))
-        } else if index < self.len() {
+        } else if index < self.ints.len() + self.strings.len() + self.bools.len() {
             Some(DiscreteValue::Bool(self.bools[index - self.ints.len() - self.strings.len()]))
+        } else if index < self.len() {
+            Some(DiscreteValue::Na)
         } else {
             None
         }
@@ -33,13 +33,14 @@ use crate::utils::dataframe::DataFrame;
 /// use gogplot::utils::sp500::sp500;
 /// use gogplot::prelude::*;
 ///
+/// use gogplot::scale::scale_x_date;
+///
 /// let data = sp500();
-/// let plot = Plot::new(Some(data))
-///     .aes(|a| {
-///         a.x("date");
-///         a.y("close");
-///     })
-///     .geom_line();
+/// let plot = plot(&data).aes(|a| {
+///     a.x_continuous("date");
+///     a.y_continuous("close");
+/// }) + geom_line()
+///     + scale_x_date().date_breaks("1 month").date_labels("%b");
 /// ```
 pub fn sp500() -> Box<dyn DataSource> {
     let mut df = DataFrame::new();
@@ -2,6 +2,7 @@
 
 use cairo::Context;
 
+pub mod colorspace;
 pub mod palette;
 
 /// Line style patterns for line geoms
@@ -0,0 +1,248 @@
+use crate::theme::Color;
+
+/// Color space in which gradients are interpolated
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorSpace {
+    /// Straight blend of the sRGB channels
+    #[default]
+    Rgb,
+    /// CIE L*a*b* (D65 white point)
+    Lab,
+    /// OKLab, a perceptual space with more even hue and lightness steps
+    Oklab,
+    /// Polar CIE L*u*v* (hue, chroma, luminance); hue takes the shorter way
+    /// round the color wheel
+    Hcl,
+}
+
+impl ColorSpace {
+    /// Blend two colors, `t` = 0 giving `from` and `t` = 1 giving `to`
+    ///
+    /// Alpha is always blended linearly. Results that fall outside the sRGB
+    /// gamut are clamped.
+    pub fn interpolate(self, from: Color, to: Color, t: f64) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        let lerp = |a: f64, b: f64| a + t * (b - a);
+        let alpha = lerp(from.3 as f64, to.3 as f64);
+
+        if self == ColorSpace::Rgb {
+            let channel = |a: u8, b: u8| lerp(a as f64, b as f64) as u8;
+            return Color(channel(from.0, to.0), channel(from.1, to.1), channel(from.2, to.2), alpha as u8);
+        }
+
+        let (a, b) = (to_linear(from), to_linear(to));
+        let rgb = match self {
+            ColorSpace::Rgb => unreachable!(),
+            ColorSpace::Lab => {
+                let (a, b) = (xyz_to_lab(linear_to_xyz(a)), xyz_to_lab(linear_to_xyz(b)));
+                xyz_to_linear(lab_to_xyz((lerp(a.0, b.0), lerp(a.1, b.1), lerp(a.2, b.2))))
+            }
+            ColorSpace::Oklab => {
+                let (a, b) = (linear_to_oklab(a), linear_to_oklab(b));
+                oklab_to_linear((lerp(a.0, b.0), lerp(a.1, b.1), lerp(a.2, b.2)))
+            }
+            ColorSpace::Hcl => {
+                let (a, b) = (luv_to_hcl(xyz_to_luv(linear_to_xyz(a))), luv_to_hcl(xyz_to_luv(linear_to_xyz(b))));
+                xyz_to_linear(luv_to_xyz(hcl_to_luv(interpolate_hcl(a, b, t))))
+            }
+        };
+        from_linear(rgb, alpha.round() as u8)
+    }
+}
+
+type Triple = (f64, f64, f64);
+
+// D65 reference white
+const WHITE: Triple = (0.95047, 1.0, 1.08883);
+const EPSILON: f64 = 216.0 / 24389.0;
+const KAPPA: f64 = 24389.0 / 27.0;
+
+fn to_linear(color: Color) -> Triple {
+    let channel = |c: u8| {
+        let c = c as f64 / 255.0;
+        if c <= 0.04045 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    (channel(color.0), channel(color.1), channel(color.2))
+}
+
+fn from_linear((r, g, b): Triple, alpha: u8) -> Color {
+    let channel = |c: f64| {
+        let c = if c <= 0.0031308 {
+            12.92 * c
+        } else {
+            1.055 * c.powf(1.0 / 2.4) - 0.055
+        };
+        (c.clamp(0.0, 1.0) * 255.0).round() as u8
+    };
+    Color(channel(r), channel(g), channel(b), alpha)
+}
+
+fn linear_to_xyz((r, g, b): Triple) -> Triple {
+    (
+        0.4124564 * r + 0.3575761 * g + 0.1804375 * b,
+        0.2126729 * r + 0.7151522 * g + 0.0721750 * b,
+        0.0193339 * r + 0.1191920 * g + 0.9503041 * b,
+    )
+}
+
+fn xyz_to_linear((x, y, z): Triple) -> Triple {
+    (
+        3.2404542 * x - 1.5371385 * y - 0.4985314 * z,
+        -0.9692660 * x + 1.8760108 * y + 0.0415560 * z,
+        0.0556434 * x - 0.2040259 * y + 1.0572252 * z,
+    )
+}
+
+fn lab_f(t: f64) -> f64 {
+    if t > EPSILON {
+        t.cbrt()
+    } else {
+        (KAPPA * t + 16.0) / 116.0
+    }
+}
+
+fn lab_f_inverse(f: f64) -> f64 {
+    let t = f * f * f;
+    if t > EPSILON {
+        t
+    } else {
+        (116.0 * f - 16.0) / KAPPA
+    }
+}
+
+fn xyz_to_lab((x, y, z): Triple) -> Triple {
+    let (fx, fy, fz) = (lab_f(x / WHITE.0), lab_f(y / WHITE.1), lab_f(z / WHITE.2));
+    (116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz))
+}
+
+fn lab_to_xyz((l, a, b): Triple) -> Triple {
+    let fy = (l + 16.0) / 116.0;
+    let fx = fy + a / 500.0;
+    let fz = fy - b / 200.0;
+    (
+        WHITE.0 * lab_f_inverse(fx),
+        WHITE.1 * lab_f_inverse(fy),
+        WHITE.2 * lab_f_inverse(fz),
+    )
+}
+
+// Chromaticity coordinates (u', v') of an XYZ color
+fn chromaticity((x, y, z): Triple) -> (f64, f64) {
+    let denominator = x + 15.0 * y + 3.0 * z;
+    if denominator == 0.0 {
+        return chromaticity(WHITE);
+    }
+    (4.0 * x / denominator, 9.0 * y / denominator)
+}
+
+fn xyz_to_luv(xyz: Triple) -> Triple {
+    let (u, v) = chromaticity(xyz);
+    let (un, vn) = chromaticity(WHITE);
+    let l = 116.0 * lab_f(xyz.1 / WHITE.1) - 16.0;
+    (l, 13.0 * l * (u - un), 13.0 * l * (v - vn))
+}
+
+fn luv_to_xyz((l, u, v): Triple) -> Triple {
+    if l <= 0.0 {
+        return (0.0, 0.0, 0.0);
+    }
+    let (un, vn) = chromaticity(WHITE);
+    let u = u / (13.0 * l) + un;
+    let v = v / (13.0 * l) + vn;
+    let y = WHITE.1 * lab_f_inverse((l + 16.0) / 116.0);
+    (y * 9.0 * u / (4.0 * v), y, y * (12.0 - 3.0 * u - 20.0 * v) / (4.0 * v))
+}
+
+// (hue in degrees, chroma, luminance)
+fn luv_to_hcl((l, u, v): Triple) -> Triple {
+    (v.atan2(u).to_degrees().rem_euclid(360.0), u.hypot(v), l)
+}
+
+fn hcl_to_luv((h, c, l): Triple) -> Triple {
+    let h = h.to_radians();
+    (l, c * h.cos(), c * h.sin())
+}
+
+fn interpolate_hcl(a: Triple, b: Triple, t: f64) -> Triple {
+    // A grey has no meaningful hue, so it borrows the other end's
+    const ACHROMATIC: f64 = 1e-6;
+    let (ha, hb) = match (a.1 < ACHROMATIC, b.1 < ACHROMATIC) {
+        (true, false) => (b.0, b.0),
+        (false, true) => (a.0, a.0),
+        _ => (a.0, b.0),
+    };
+    let mut delta = hb - ha;
+    if delta > 180.0 {
+        delta -= 360.0;
+    } else if delta < -180.0 {
+        delta += 360.0;
+    }
+    (
+        (ha + t * delta).rem_euclid(360.0),
+        a.1 + t * (b.1 - a.1),
+        a.2 + t * (b.2 - a.2),
+    )
+}
+
+fn linear_to_oklab((r, g, b): Triple) -> Triple {
+    let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+    let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+    let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+    (
+        0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+        1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+        0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+    )
+}
+
+fn oklab_to_linear((l, a, b): Triple) -> Triple {
+    let l_ = l + 0.3963377774 * a + 0.2158037573 * b;
+    let m_ = l - 0.1055613458 * a - 0.0638541728 * b;
+    let s_ = l - 0.0894841775 * a - 1.2914855480 * b;
+    let (l, m, s) = (l_ * l_ * l_, m_ * m_ * m_, s_ * s_ * s_);
+    (
+        4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s,
+        -1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s,
+        -0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPACES: [ColorSpace; 4] = [ColorSpace::Rgb, ColorSpace::Lab, ColorSpace::Oklab, ColorSpace::Hcl];
+
+    #[test]
+    fn test_endpoints_round_trip() {
+        let from = Color(30, 144, 255, 255);
+        let to = Color(220, 20, 60, 128);
+        for space in SPACES {
+            assert_eq!(space.interpolate(from, to, 0.0), from, "{:?}", space);
+            let end = space.interpolate(from, to, 1.0);
+            let close = |a: u8, b: u8| (a as i32 - b as i32).abs() <= 1;
+            assert!(close(end.0, to.0) && close(end.1, to.1) && close(end.2, to.2), "{:?}: {:?}", space, end);
+            assert_eq!(end.3, to.3);
+        }
+    }
+
+    #[test]
+    fn test_lab_midpoint_of_black_and_white() {
+        // L* = 50 is a mid grey, lighter than the sRGB channel midpoint
+        let grey = ColorSpace::Lab.interpolate(Color::rgb(0, 0, 0), Color::rgb(255, 255, 255), 0.5);
+        assert_eq!((grey.0, grey.1, grey.2), (119, 119, 119));
+        let grey = ColorSpace::Rgb.interpolate(Color::rgb(0, 0, 0), Color::rgb(255, 255, 255), 0.5);
+        assert_eq!((grey.0, grey.1, grey.2), (127, 127, 127));
+    }
+
+    #[test]
+    fn test_hcl_takes_short_way_round() {
+        // Red to magenta passes through pink, not through green
+        let mid = ColorSpace::Hcl.interpolate(Color::rgb(255, 0, 0), Color::rgb(255, 0, 255), 0.5);
+        assert!(mid.0 > mid.1 && mid.2 > mid.1, "{:?}", mid);
+    }
+}
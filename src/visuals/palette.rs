@@ -85,6 +85,92 @@ pub fn plasma_palette() -> Vec<Color> {
     ]
 }
 
+pub fn magma_palette() -> Vec<Color> {
+    vec![
+        Color::rgb(0, 0, 4),
+        Color::rgb(29, 17, 71),
+        Color::rgb(81, 18, 124),
+        Color::rgb(130, 38, 129),
+        Color::rgb(182, 54, 121),
+        Color::rgb(230, 81, 100),
+        Color::rgb(251, 136, 97),
+        Color::rgb(254, 194, 135),
+        Color::rgb(252, 253, 191),
+    ]
+}
+
+pub fn inferno_palette() -> Vec<Color> {
+    vec![
+        Color::rgb(0, 0, 4),
+        Color::rgb(27, 12, 66),
+        Color::rgb(75, 12, 107),
+        Color::rgb(120, 28, 109),
+        Color::rgb(165, 44, 96),
+        Color::rgb(207, 68, 70),
+        Color::rgb(237, 105, 37),
+        Color::rgb(251, 154, 6),
+        Color::rgb(252, 255, 164),
+    ]
+}
+
+pub fn cividis_palette() -> Vec<Color> {
+    vec![
+        Color::rgb(0, 32, 77),
+        Color::rgb(0, 51, 111),
+        Color::rgb(57, 72, 107),
+        Color::rgb(87, 92, 109),
+        Color::rgb(112, 113, 115),
+        Color::rgb(138, 135, 121),
+        Color::rgb(166, 157, 117),
+        Color::rgb(196, 181, 108),
+        Color::rgb(228, 207, 91),
+        Color::rgb(255, 234, 70),
+    ]
+}
+
+pub fn turbo_palette() -> Vec<Color> {
+    vec![
+        Color::rgb(48, 18, 59),
+        Color::rgb(70, 98, 215),
+        Color::rgb(54, 170, 249),
+        Color::rgb(26, 228, 182),
+        Color::rgb(114, 254, 94),
+        Color::rgb(199, 239, 52),
+        Color::rgb(250, 186, 57),
+        Color::rgb(246, 107, 25),
+        Color::rgb(203, 42, 4),
+        Color::rgb(122, 4, 3),
+    ]
+}
+
+/// The viridis family of perceptually uniform colormaps
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ViridisOption {
+    #[default]
+    Viridis,
+    Magma,
+    Inferno,
+    Plasma,
+    /// Tuned for color vision deficiency
+    Cividis,
+    /// A rainbow map; not perceptually uniform in lightness
+    Turbo,
+}
+
+impl ViridisOption {
+    /// Evenly spaced stops along the colormap, darkest (or bluest) first
+    pub fn colors(self) -> Vec<Color> {
+        match self {
+            ViridisOption::Viridis => viridis_palette(),
+            ViridisOption::Magma => magma_palette(),
+            ViridisOption::Inferno => inferno_palette(),
+            ViridisOption::Plasma => plasma_palette(),
+            ViridisOption::Cividis => cividis_palette(),
+            ViridisOption::Turbo => turbo_palette(),
+        }
+    }
+}
+
 pub fn discrete_palette(n: usize) -> Vec<Color> {
     if n <= 8 {
         return okabe_ito_palette()[0..n].to_vec();